@@ -0,0 +1,76 @@
+#!/usr/bin/env node
+
+import { execSync } from 'child_process';
+import { copyFileSync, existsSync, mkdirSync } from 'fs';
+import { join, dirname } from 'path';
+import { fileURLToPath } from 'url';
+
+const __filename = fileURLToPath(import.meta.url);
+const __dirname = dirname(__filename);
+
+// NOTE: This script builds Whisper.cpp's command-line tool and places it where
+// Tauri expects a sidecar (bundle.externalBin), so release builds ship with a
+// transcription engine and first run only has to download the starter model.
+// Requires git, CMake and a C++ compiler.
+
+// Whisper.cpp release the app is built and tested against
+const WHISPER_TAG = 'v1.7.6';
+
+const tauriDir = join(__dirname, '..', 'src-tauri');
+const sourceDir = join(tauriDir, 'target', `whisper.cpp-${WHISPER_TAG}`);
+const binariesDir = join(tauriDir, 'binaries');
+
+/**
+ * Target triple Rust builds for, which Tauri appends to sidecar names
+ * @returns {string} e.g. "aarch64-apple-darwin"
+ */
+function getTargetTriple() {
+  const output = execSync('rustc -vV', { encoding: 'utf-8' });
+  const host = output.split('\n').find((line) => line.startsWith('host:'));
+  if (!host) {
+    throw new Error('Could not determine the Rust target triple');
+  }
+  return host.replace('host:', '').trim();
+}
+
+const exeSuffix = process.platform === 'win32' ? '.exe' : '';
+const sidecarPath = join(binariesDir, `whisper-cli-${getTargetTriple()}${exeSuffix}`);
+
+if (existsSync(sidecarPath)) {
+  console.log(`✓ Whisper.cpp already built: ${sidecarPath}`);
+  process.exit(0);
+}
+
+if (!existsSync(sourceDir)) {
+  console.log(`Fetching Whisper.cpp ${WHISPER_TAG}...`);
+  execSync(
+    `git clone --depth 1 --branch ${WHISPER_TAG} https://github.com/ggml-org/whisper.cpp.git "${sourceDir}"`,
+    { stdio: 'inherit' }
+  );
+}
+
+console.log('Building whisper-cli...');
+// Static libraries, so the sidecar is a single self-contained file
+execSync(
+  'cmake -B build -DCMAKE_BUILD_TYPE=Release -DBUILD_SHARED_LIBS=OFF -DWHISPER_BUILD_TESTS=OFF',
+  { cwd: sourceDir, stdio: 'inherit' }
+);
+execSync('cmake --build build --config Release --target whisper-cli -j', {
+  cwd: sourceDir,
+  stdio: 'inherit'
+});
+
+// Multi-config generators (Visual Studio) put the binary in a Release folder
+const builtPath = [
+  join(sourceDir, 'build', 'bin', `whisper-cli${exeSuffix}`),
+  join(sourceDir, 'build', 'bin', 'Release', `whisper-cli${exeSuffix}`)
+].find((path) => existsSync(path));
+
+if (!builtPath) {
+  console.error('Error: whisper-cli was not found after building Whisper.cpp');
+  process.exit(1);
+}
+
+mkdirSync(binariesDir, { recursive: true });
+copyFileSync(builtPath, sidecarPath);
+console.log(`✓ Built Whisper.cpp ${WHISPER_TAG}: ${sidecarPath}`);
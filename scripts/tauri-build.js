@@ -21,6 +21,9 @@ const version = execSync(`node scripts/inject-git-version.js ${mode}`, { encodin
 
 console.log(`Running Tauri build (${mode} mode) with version: ${version}`);
 
+// Build the Whisper.cpp sidecar bundled for zero-config first run
+execSync('node scripts/build-whisper.js', { stdio: 'inherit' });
+
 const configPath = join(__dirname, '..', 'src-tauri', 'tauri.conf.json');
 const cargoPath = join(__dirname, '..', 'src-tauri', 'Cargo.toml');
 
@@ -33,6 +36,8 @@ try {
   // Modify tauri.conf.json with version
   const config = JSON.parse(originalConfig);
   config.version = version;
+  // Only release builds bundle Whisper.cpp, so dev builds don't need it
+  config.bundle.externalBin = ['binaries/whisper-cli'];
   writeFileSync(configPath, JSON.stringify(config, null, 2) + '\n', 'utf-8');
 
   // Modify Cargo.toml with version (regex replace the version line)
@@ -42,7 +47,7 @@ try {
   );
   writeFileSync(cargoPath, updatedCargo, 'utf-8');
 
-  console.log(`✓ Temporarily set version to: ${version}`);
+  console.log(`✓ Temporarily set version to: ${version} and bundled Whisper.cpp`);
 
   // Run build
   execSync('npx tauri build', {
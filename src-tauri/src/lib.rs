@@ -1,23 +1,70 @@
 mod recording;
 
 use recording::{
-    estimate_transcription_time, extract_transcription_stats, RecordingState, RecordingStatus,
-    Session, SessionIndex, SharedRecordingState, TranscriptionCompleteEvent,
-    TranscriptionErrorEvent, TranscriptionEstimate, TranscriptionResult, WhisperConfig,
+    AppConfig, AudioDeviceList, AutoPauseEvent, BenchmarkResult, BulkOperationSummary, ClipExport,
+    ClipFormat, ClipboardMechanism, DayActivity, DecodingConfig, DragExportBundle, EmailDelivery,
+    EstimateAccuracy, HealthCheck, HotkeyConfig, ImportSummary, InputDeviceSettings, IntegrityFix,
+    JournaledOperation, MicrophonePermissionAction, MicrophonePermissionStatus, MobileSyncEvent,
+    ModelComparison, ModelStats, PairedDevice, PairingCode, PausedTooLongEvent, QualityFlag,
+    RecordingAutoPausedEvent, RecordingState, RecordingStatus, RecordingStatusChangedEvent,
+    RemoteCommand, RetentionCandidate, SavedTranscriptVersion, SchedulerEvent, SchedulerState,
+    Session, SessionAccess, SessionCache, SessionDayGroup, SessionFilter, SessionIndex,
+    SessionSort, SetupStatus, ShareTarget, SharedRecordingState, SharedSchedulerState,
+    SharedSessionCache, StorageIntegrityReport, TaskProvider, ThroughputSample, TranscriptDiff,
+    TranscriptRefinedEvent, TranscriptSegment, TranscriptVersion, TranscriptionCompleteEvent,
+    TranscriptionDeferredEvent, TranscriptionErrorEvent, TranscriptionEstimate,
+    TranscriptionResult, TranscriptionStats, WaveformStyle, WeeklyReport, WhisperBinaryInfo,
 };
 use std::sync::{Arc, Mutex};
-use tauri::{Emitter, State};
+use std::time::Duration;
+use tauri::{Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+/// How long quitting waits for a running transcription to finish
+const EXIT_TRANSCRIPTION_WAIT: Duration = Duration::from_secs(5);
 
 struct AppState {
     recording: SharedRecordingState,
+    scheduler: SharedSchedulerState,
+    sessions: SharedSessionCache,
 }
 
 #[tauri::command]
-fn start_recording(state: State<AppState>) -> Result<(), String> {
+fn start_recording(state: State<AppState>, app: tauri::AppHandle) -> Result<(), String> {
     let recording_state = Arc::clone(&state.inner().recording);
+    reject_if_recording(&app, &recording_state)?;
     recording::start_recording(recording_state)
 }
 
+/// Stop the active recording (transcribing it in the background) and start
+/// a new one right away; returns the saved session, if any
+#[tauri::command]
+fn restart_recording(
+    state: State<AppState>,
+    app: tauri::AppHandle,
+) -> Result<Option<Session>, String> {
+    let recording_state = Arc::clone(&state.inner().recording);
+    recording::restart_recording(recording_state, move |result| {
+        emit_transcription_result(&app, result)
+    })
+}
+
+/// Refuse to start over an active recording
+///
+/// Emits `recording-already-active` with the recording's status and elapsed
+/// time, so the frontend can offer `restart_recording` instead.
+fn reject_if_recording(
+    app: &tauri::AppHandle,
+    recording_state: &SharedRecordingState,
+) -> Result<(), String> {
+    let Some(active) = recording_state.lock().unwrap().already_recording() else {
+        return Ok(());
+    };
+    let _ = app.emit("recording-already-active", active);
+    Err(active.into())
+}
+
 #[tauri::command]
 fn pause_recording(state: State<AppState>) -> Result<(), String> {
     let recording_state = Arc::clone(&state.inner().recording);
@@ -36,6 +83,22 @@ fn cancel_recording(state: State<AppState>) -> Result<(), String> {
     recording::cancel_recording(recording_state)
 }
 
+/// Start recording a calendar meeting (e.g. accepting a meeting-starting prompt)
+///
+/// The session is titled after the event and stores its metadata
+#[tauri::command]
+fn start_meeting_recording(state: State<AppState>, event_uid: String) -> Result<(), String> {
+    let meeting = recording::find_calendar_event(&event_uid)?;
+    let recording_state = Arc::clone(&state.inner().recording);
+    recording::start_meeting_recording(recording_state, meeting)
+}
+
+/// Cancel a scheduled recording during its grace period
+#[tauri::command]
+fn cancel_scheduled_recording(state: State<AppState>) -> Result<(), String> {
+    recording::cancel_scheduled_recording(Arc::clone(&state.inner().scheduler))
+}
+
 #[tauri::command]
 fn stop_recording(state: State<AppState>, app: tauri::AppHandle) -> Result<Session, String> {
     let recording_state = Arc::clone(&state.inner().recording);
@@ -49,33 +112,114 @@ fn stop_recording(state: State<AppState>, app: tauri::AppHandle) -> Result<Sessi
 
     // Orchestrate async transcription with event emission callback
     recording::orchestrate_async_transcription(
-        recording_state,
+        recording_state.clone(),
         session_id,
         audio_path,
-        move |result| match result {
-            TranscriptionResult::Success(updated_session) => {
-                let _ = app.emit(
-                    "transcription-complete",
-                    TranscriptionCompleteEvent {
-                        session: updated_session,
-                    },
-                );
-            }
-            TranscriptionResult::Error { session_id, error } => {
-                let _ = app.emit(
-                    "transcription-error",
-                    TranscriptionErrorEvent { session_id, error },
-                );
-            }
-        },
+        move |result| emit_transcription_result(&app, result),
     );
+    recording::continue_rapid_capture(recording_state);
 
     Ok(session)
 }
 
+/// Emit the Tauri event for a transcription result
+fn emit_transcription_result(app: &tauri::AppHandle, result: TranscriptionResult) {
+    match result {
+        TranscriptionResult::Success(updated_session) => {
+            let _ = app.emit(
+                "transcription-complete",
+                TranscriptionCompleteEvent {
+                    session: updated_session,
+                },
+            );
+        }
+        TranscriptionResult::Refined(refined_session) => {
+            let _ = app.emit(
+                "transcript-refined",
+                TranscriptRefinedEvent {
+                    session: refined_session,
+                },
+            );
+        }
+        TranscriptionResult::Error { session_id, error } => {
+            let _ = app.emit(
+                "transcription-error",
+                TranscriptionErrorEvent { session_id, error },
+            );
+        }
+        TranscriptionResult::Deferred { session_id, reason } => {
+            let _ = app.emit(
+                "transcription-deferred",
+                TranscriptionDeferredEvent { session_id, reason },
+            );
+        }
+    }
+}
+
+/// Load the session index, keeping only sessions matching the filter
+/// (rating bounds and whether a quality flag is set), newest first unless
+/// another order is given
+#[tauri::command]
+fn get_sessions(
+    filter: Option<SessionFilter>,
+    sort: Option<SessionSort>,
+) -> Result<SessionIndex, String> {
+    let mut session_index = recording::load_sessions()?;
+    if let Some(filter) = filter {
+        session_index.sessions = recording::filter_sessions(session_index.sessions, &filter);
+    }
+    if let Some(sort) = sort {
+        recording::sort_sessions(&mut session_index.sessions, sort)?;
+    }
+    Ok(session_index)
+}
+
+/// Recordings and minutes recorded per local day of `year`, for an
+/// activity heatmap
+///
+/// `local_tz` is used like in `get_sessions_grouped_by_day`
+#[tauri::command]
+fn get_recording_activity(year: i32, local_tz: Option<i32>) -> Result<Vec<DayActivity>, String> {
+    let session_index = recording::load_sessions()?;
+    let fallback_offset =
+        local_tz.unwrap_or_else(|| chrono::Local::now().offset().local_minus_utc() / 60);
+    Ok(recording::recording_activity(
+        &session_index.sessions,
+        year,
+        fallback_offset,
+    ))
+}
+
+/// Group sessions by local calendar day
+///
+/// `local_tz` is the viewer's UTC offset in minutes east of UTC, used for
+/// sessions recorded before offsets were stored (defaults to the system offset)
 #[tauri::command]
-fn get_sessions() -> Result<SessionIndex, String> {
-    recording::load_sessions()
+fn get_sessions_grouped_by_day(local_tz: Option<i32>) -> Result<Vec<SessionDayGroup>, String> {
+    let session_index = recording::load_sessions()?;
+    let fallback_offset =
+        local_tz.unwrap_or_else(|| chrono::Local::now().offset().local_minus_utc() / 60);
+    Ok(recording::group_sessions_by_day(
+        &session_index.sessions,
+        fallback_offset,
+    ))
+}
+
+#[tauri::command]
+fn import_from_directory(path: String) -> Result<ImportSummary, String> {
+    recording::import_from_directory(std::path::Path::new(&path))
+}
+
+#[tauri::command]
+fn transcribe_file(path: String) -> Result<String, String> {
+    recording::transcribe_standalone_file(std::path::Path::new(&path))
+}
+
+/// Download the model at a missing configured path into the models folder
+/// and point the config at it, returning the new path
+#[tauri::command]
+fn download_model(model_path: String) -> Result<String, String> {
+    recording::download_model(&model_path).map(|path| path.to_string_lossy().to_string())
 }
 
 #[tauri::command]
@@ -86,30 +230,73 @@ fn get_recording_duration(state: State<AppState>) -> Result<f64, String> {
         return Ok(0.0);
     }
 
-    if let Some(start_time) = recording_state.start_time {
-        let now = chrono::Utc::now();
-        let total_elapsed_ms = (now - start_time).num_milliseconds();
-
-        // Calculate total paused duration including current pause if active
-        let mut total_paused_ms = recording_state.total_paused_duration_ms;
-        if recording_state.status == RecordingStatus::Paused {
-            if let Some(pause_start) = recording_state.pause_start_time {
-                let current_pause_duration = (now - pause_start).num_milliseconds();
-                total_paused_ms += current_pause_duration;
-            }
-        }
-
-        let active_duration_ms = total_elapsed_ms - total_paused_ms;
-        Ok(active_duration_ms as f64 / 1000.0)
-    } else {
-        Ok(0.0)
-    }
+    Ok(recording_state.timer.elapsed_seconds())
 }
 
 #[tauri::command]
 fn get_recording_status(state: State<AppState>) -> Result<RecordingStatus, String> {
     let recording_state = state.inner().recording.lock().unwrap();
-    Ok(recording_state.status)
+    Ok(recording_state.status())
+}
+
+/// Enable or disable the pre-roll buffer (audio kept from before "start")
+#[tauri::command]
+fn set_pre_roll_enabled(state: State<AppState>, enabled: bool) -> Result<(), String> {
+    let recording_state = Arc::clone(&state.inner().recording);
+    recording::set_pre_roll_enabled(recording_state, enabled)
+}
+
+/// Audio input and output devices currently available
+#[tauri::command]
+fn list_audio_devices() -> AudioDeviceList {
+    recording::list_audio_devices()
+}
+
+/// OS microphone permission, optionally prompting for it or opening the
+/// settings pane where it is granted
+#[tauri::command]
+fn check_microphone_permission(
+    action: Option<MicrophonePermissionAction>,
+) -> Result<MicrophonePermissionStatus, String> {
+    recording::check_microphone_permission(action)
+}
+
+/// Change the monitoring (listen-through) volume, including while recording
+#[tauri::command]
+fn set_monitor_volume(volume: f32) {
+    recording::set_monitor_volume(volume)
+}
+
+/// Whether the microphone is open for pre-roll while idle (for a privacy indicator)
+#[tauri::command]
+fn get_pre_roll_active(state: State<AppState>) -> bool {
+    recording::is_pre_roll_active(Arc::clone(&state.inner().recording))
+}
+
+/// Names of the recording profiles defined in config.json
+#[tauri::command]
+fn list_profiles() -> Result<Vec<String>, String> {
+    let config = recording::load_base_config()?;
+    Ok(config.profiles.into_keys().collect())
+}
+
+#[tauri::command]
+fn get_active_profile() -> Result<Option<String>, String> {
+    let config = recording::load_base_config()?;
+    Ok(recording::active_profile_name(&config))
+}
+
+/// Switch recording profile (device, model, clipboard and storage settings) in one action
+#[tauri::command]
+fn set_active_profile(
+    state: State<AppState>,
+    app: tauri::AppHandle,
+    name: Option<String>,
+) -> Result<(), String> {
+    let config = recording::load_base_config()?;
+    recording::select_profile(&config, name)?;
+    apply_config_change(&state, &app);
+    Ok(())
 }
 
 #[tauri::command]
@@ -127,32 +314,629 @@ fn get_audio_levels(state: State<AppState>) -> Result<Vec<f32>, String> {
     Ok(recording::get_audio_levels(samples))
 }
 
+/// Load the settings for editing: config.json (migrated to the current
+/// version) with machine-local overrides, without a recording profile applied
+#[tauri::command]
+fn load_app_config() -> Result<AppConfig, String> {
+    recording::load_base_config()
+}
+
+/// Save settings to config.json and apply them without restarting
+///
+/// Expects the base settings as returned by `load_app_config`. Settings
+/// pointing at a damaged model are refused.
+#[tauri::command]
+fn save_app_config(
+    state: State<AppState>,
+    app: tauri::AppHandle,
+    config: AppConfig,
+) -> Result<(), String> {
+    recording::verify_models(&config.transcription)?;
+    recording::save_config(&config)?;
+    check_whisper_binary(&app, &config);
+    apply_config_change(&state, &app);
+    Ok(())
+}
+
+/// Record the Whisper.cpp version and warn the UI about configured flags
+/// the binary doesn't accept
+fn check_whisper_binary(app: &tauri::AppHandle, config: &AppConfig) {
+    match recording::check_whisper_compatibility(&config.transcription) {
+        Ok(info) if !info.unsupported_flags.is_empty() => {
+            let _ = app.emit("whisper-compatibility-warning", info);
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Whisper.cpp check failed: {}", e),
+    }
+}
+
+#[tauri::command]
+fn get_setup_status() -> SetupStatus {
+    recording::setup_status()
+}
+
 #[tauri::command]
-fn load_config() -> Result<WhisperConfig, String> {
-    recording::load_config()
+fn get_whisper_binary_info() -> Option<WhisperBinaryInfo> {
+    recording::load_whisper_binary_info()
 }
 
+/// Remember gain, channel and sample rate for an input device, applied
+/// whenever that device is used
+#[tauri::command]
+fn save_input_device_settings(
+    state: State<AppState>,
+    app: tauri::AppHandle,
+    device_name: String,
+    settings: InputDeviceSettings,
+) -> Result<(), String> {
+    recording::save_input_device_settings(&device_name, settings)?;
+    apply_config_change(&state, &app);
+    Ok(())
+}
+
+/// Hot-reload changed settings and notify the frontend
+fn apply_config_change(state: &State<AppState>, app: &tauri::AppHandle) {
+    // Sessions are read again, e.g. from a newly chosen storage folder
+    state.inner().sessions.invalidate();
+    if let Some(config) = recording::reload_config(Arc::clone(&state.inner().recording)) {
+        register_hotkeys(app, &config.hotkeys);
+        let _ = app.emit("config-changed", config);
+    }
+}
+
+/// Replace the registered global shortcuts with the configured ones
+fn register_hotkeys(app: &tauri::AppHandle, hotkeys: &HotkeyConfig) {
+    let shortcuts = app.global_shortcut();
+
+    if let Err(e) = shortcuts.unregister_all() {
+        log::warn!("Failed to unregister hotkeys: {}", e);
+    }
+
+    let copy_hotkey = recording::copy_transcript_hotkey(hotkeys);
+    for hotkey in recording::recording_hotkeys(hotkeys).into_iter().chain(copy_hotkey) {
+        if let Err(e) = shortcuts.register(hotkey.as_str()) {
+            log::warn!("Failed to register hotkey {}: {}", hotkey, e);
+        }
+    }
+}
+
+/// Carry out what a pressed global shortcut is bound to
+fn handle_hotkey(app: &tauri::AppHandle, shortcut: &Shortcut) {
+    let is_copy_hotkey = recording::load_config()
+        .ok()
+        .and_then(|config| recording::copy_transcript_hotkey(&config.hotkeys))
+        .and_then(|accelerator| accelerator.parse::<Shortcut>().ok())
+        .is_some_and(|copy| copy.id() == shortcut.id());
+
+    if is_copy_hotkey {
+        match recording::copy_last_transcript() {
+            Ok(session) => {
+                let _ = app.emit("last-transcript-copied", session);
+            }
+            Err(e) => log::warn!("Hotkey could not copy the last transcript: {}", e),
+        }
+    } else {
+        handle_recording_hotkey(app);
+    }
+}
+
+/// Start or stop recording from a global shortcut or media key
+fn handle_recording_hotkey(app: &tauri::AppHandle) {
+    if let Err(e) = toggle_recording_from(app, "hotkey") {
+        log::warn!("Hotkey could not toggle recording: {}", e);
+    }
+}
+
+/// Carry out a command from a remote controller (e.g. a Stream Deck plugin)
+fn handle_remote_command(app: &tauri::AppHandle, command: RemoteCommand) -> Result<(), String> {
+    let recording_state = Arc::clone(&app.state::<AppState>().recording);
+    let is_active = recording_state.lock().unwrap().is_active();
+
+    match command {
+        RemoteCommand::Toggle => toggle_recording_from(app, "remote"),
+        RemoteCommand::Start if is_active => reject_if_recording(app, &recording_state),
+        RemoteCommand::Stop if !is_active => Err("No active recording".to_string()),
+        RemoteCommand::Start | RemoteCommand::Stop => toggle_recording_from(app, "remote"),
+        RemoteCommand::Pause => recording::pause_recording(recording_state),
+        RemoteCommand::Resume => recording::resume_recording(recording_state),
+    }
+}
+
+/// Start recording, or stop and transcribe, notifying the frontend
+///
+/// Emits `{source}-recording-started` or `{source}-recording-stopped`
+fn toggle_recording_from(app: &tauri::AppHandle, source: &str) -> Result<(), String> {
+    let recording_state = Arc::clone(&app.state::<AppState>().recording);
+    let transcription_app = app.clone();
+
+    let toggled = recording::toggle_recording(recording_state, move |result| {
+        emit_transcription_result(&transcription_app, result)
+    })?;
+
+    match toggled {
+        Some(session) => {
+            let _ = app.emit(&format!("{}-recording-stopped", source), session);
+        }
+        None => {
+            let _ = app.emit(&format!("{}-recording-started", source), ());
+        }
+    }
+    Ok(())
+}
+
+/// A session's transcript, counted as the session being viewed
 #[tauri::command]
 fn load_transcript(session_id: String) -> Result<String, String> {
-    recording::load_transcript(&session_id)
+    let transcript = recording::load_transcript(&session_id)?;
+    if let Err(e) = recording::record_session_access(&session_id) {
+        log::warn!("{}", e);
+    }
+    Ok(transcript)
+}
+
+/// A session's transcript with Cyrillic, Greek and Japanese kana romanized
+#[tauri::command]
+fn load_romanized_transcript(session_id: String) -> Result<String, String> {
+    recording::load_romanized_transcript(&session_id)
+}
+
+/// Sessions whose transcript was opened most recently (10 by default)
+#[tauri::command]
+fn get_recently_viewed(limit: Option<usize>) -> Result<Vec<Session>, String> {
+    recording::get_recently_viewed(limit.unwrap_or(10))
+}
+
+/// Sessions whose transcript is opened most, favoring recent openings
+/// (10 by default)
+#[tauri::command]
+fn get_frequently_viewed(limit: Option<usize>) -> Result<Vec<Session>, String> {
+    recording::get_frequently_viewed(limit.unwrap_or(10))
+}
+
+/// When and how often each session was viewed, keyed by session id
+#[tauri::command]
+fn get_session_access() -> std::collections::BTreeMap<String, SessionAccess> {
+    recording::get_session_access()
 }
 
+/// Copy a session's transcript, optionally romanized, to the clipboard
 #[tauri::command]
-fn copy_transcript_to_clipboard(session_id: String) -> Result<(), String> {
+fn copy_transcript_to_clipboard(
+    session_id: String,
+    romanized: Option<bool>,
+) -> Result<ClipboardMechanism, String> {
     // Load transcript from file
-    let transcript = recording::load_transcript(&session_id)?;
+    let transcript = if romanized.unwrap_or(false) {
+        recording::load_romanized_transcript(&session_id)?
+    } else {
+        recording::load_transcript(&session_id)?
+    };
 
     // Copy transcript to clipboard
     if transcript.is_empty() {
         return Err("No transcript available for this session".to_string());
     }
 
-    recording::copy_to_clipboard(&transcript)
+    recording::copy_to_clipboard(&recording::redact_outgoing(&transcript))
+}
+
+/// Most recently recorded session, without loading the session list
+#[tauri::command]
+fn get_last_session() -> Result<Option<Session>, String> {
+    recording::get_last_session()
+}
+
+/// Copy the latest transcript to the clipboard, returning its session
+#[tauri::command]
+fn copy_last_transcript() -> Result<Session, String> {
+    recording::copy_last_transcript()
+}
+
+/// Attach freeform notes to a session (empty notes clear them)
+#[tauri::command]
+fn update_session_notes(session_id: String, notes: Option<String>) -> Result<Session, String> {
+    recording::update_session_notes(&session_id, notes.as_deref())
+}
+
+/// Rate a session from 1 to 5 stars (no rating clears it)
+#[tauri::command]
+fn set_session_rating(session_id: String, rating: Option<u8>) -> Result<Session, String> {
+    recording::set_session_rating(&session_id, rating)
+}
+
+/// Flag a session for review, or clear its flag
+#[tauri::command]
+fn set_session_quality_flag(
+    session_id: String,
+    flag: Option<QualityFlag>,
+) -> Result<Session, String> {
+    recording::set_session_quality_flag(&session_id, flag)
 }
 
 #[tauri::command]
-fn retranscribe_session(session_id: String) -> Result<String, String> {
-    recording::retranscribe_session(&session_id)
+fn reveal_session_audio(session_id: String) -> Result<(), String> {
+    recording::reveal_session_audio(&session_id)
+}
+
+#[tauri::command]
+fn open_transcript_in_editor(session_id: String) -> Result<(), String> {
+    recording::open_transcript_in_editor(&session_id)
+}
+
+#[tauri::command]
+fn prepare_drag_export(
+    session_id: String,
+    romanized: Option<bool>,
+) -> Result<DragExportBundle, String> {
+    recording::prepare_drag_export(&session_id, romanized.unwrap_or(false))
+}
+
+#[tauri::command]
+fn cleanup_drag_exports() -> Result<(), String> {
+    recording::cleanup_drag_exports()
+}
+
+/// Share a session's transcript with another app; the share sheet opens
+/// over the window that asked
+#[tauri::command]
+fn share_session(
+    window: tauri::WebviewWindow,
+    session_id: String,
+    target: ShareTarget,
+) -> Result<(), String> {
+    recording::share_session(&session_id, target, native_window(&window))
+}
+
+/// The window's native view (NSView on macOS, HWND on Windows), if it has
+/// one the share sheet can open over
+fn native_window(window: &tauri::WebviewWindow) -> Option<recording::NativeWindow> {
+    #[cfg(target_os = "macos")]
+    return window.ns_view().ok();
+
+    #[cfg(target_os = "windows")]
+    return window
+        .hwnd()
+        .ok()
+        .map(|hwnd| hwnd.0 as recording::NativeWindow);
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = window;
+        None
+    }
+}
+
+/// E-mail a session's transcript, optionally with the audio attached
+#[tauri::command]
+fn email_session(
+    session_id: String,
+    to: Vec<String>,
+    attach_audio: Option<bool>,
+) -> Result<EmailDelivery, String> {
+    recording::email_session(&session_id, &to, attach_audio.unwrap_or(false))
+}
+
+/// Publish a session's transcript as a Notion page, returning its URL
+#[tauri::command]
+fn publish_to_notion(session_id: String) -> Result<String, String> {
+    recording::publish_to_notion(&session_id)
+}
+
+/// Action items spoken in a session
+#[tauri::command]
+fn get_action_items(session_id: String) -> Result<Vec<String>, String> {
+    recording::get_action_items(&session_id)
+}
+
+/// Send a session's action items to the configured task manager, returning
+/// how many tasks were created
+#[tauri::command]
+fn create_tasks(session_id: String) -> Result<usize, String> {
+    recording::create_tasks(&session_id)
+}
+
+/// Store a task manager's API token in the system keychain, or remove it
+/// when `token` is null
+#[tauri::command]
+fn set_integration_token(provider: TaskProvider, token: Option<String>) -> Result<(), String> {
+    recording::set_integration_token(provider, token.as_deref())
+}
+
+/// Saved versions of a session's transcript, newest first
+#[tauri::command]
+fn get_transcript_history(session_id: String) -> Result<Vec<TranscriptVersion>, String> {
+    recording::get_transcript_history(&session_id)
+}
+
+/// Earlier transcripts of a session kept when it was retranscribed, newest
+/// first
+#[tauri::command]
+fn list_transcript_versions(session_id: String) -> Result<Vec<SavedTranscriptVersion>, String> {
+    recording::list_transcript_versions(&session_id)
+}
+
+/// Make an earlier transcript version current again, keeping the replaced
+/// transcript as a new version
+#[tauri::command]
+fn restore_transcript_version(session_id: String, version: u32) -> Result<Session, String> {
+    recording::restore_transcript_version(&session_id, version)
+}
+
+/// Word-level diff between two transcripts of a session, e.g. before and after
+/// retranscribing with a bigger model
+///
+/// Versions are numbers from `list_transcript_versions`; no version means the
+/// current transcript
+#[tauri::command]
+fn diff_transcripts(
+    session_id: String,
+    version_a: Option<u32>,
+    version_b: Option<u32>,
+) -> Result<TranscriptDiff, String> {
+    recording::diff_transcripts(&session_id, version_a, version_b)
+}
+
+/// What a saved version changed in a session's transcript, as a unified diff
+#[tauri::command]
+fn get_transcript_diff(session_id: String, commit_id: String) -> Result<String, String> {
+    recording::get_transcript_diff(&session_id, &commit_id)
+}
+
+/// Render a session's waveform (or a snippet of it) as a PNG or SVG image,
+/// returning the image path
+#[tauri::command]
+fn export_waveform_image(
+    session_id: String,
+    style: Option<WaveformStyle>,
+) -> Result<String, String> {
+    recording::export_waveform_image(&session_id, &style.unwrap_or_default())
+}
+
+/// Cut a time range of a session into a standalone audio clip and the
+/// transcript spoken in it
+#[tauri::command]
+fn export_clip(
+    session_id: String,
+    start: f64,
+    end: f64,
+    format: Option<ClipFormat>,
+) -> Result<ClipExport, String> {
+    recording::export_clip(&session_id, start, end, format.unwrap_or_default())
+}
+
+/// Show a code for pairing a phone with the mobile sync endpoint
+#[tauri::command]
+fn start_mobile_pairing() -> Result<PairingCode, String> {
+    recording::start_pairing()
+}
+
+#[tauri::command]
+fn list_paired_devices() -> Result<Vec<PairedDevice>, String> {
+    recording::list_paired_devices()
+}
+
+/// Token hardware controllers connect to the remote control server with
+#[tauri::command]
+fn get_remote_control_token() -> Result<String, String> {
+    recording::get_remote_control_token()
+}
+
+/// Revoke a phone's access to the mobile sync endpoint
+#[tauri::command]
+fn unpair_device(device_id: String) -> Result<(), String> {
+    recording::unpair_device(&device_id)
+}
+
+#[tauri::command]
+fn copy_audio_to_clipboard(session_id: String) -> Result<ClipboardMechanism, String> {
+    recording::copy_session_audio_to_clipboard(&session_id)
+}
+
+/// Rebuild all session previews from their transcripts
+///
+/// Returns the number of previews that changed
+#[tauri::command]
+fn regenerate_previews() -> Result<usize, String> {
+    recording::regenerate_previews()
+}
+
+/// Markdown report of a week's sessions, summaries and action items
+///
+/// `week` is an ISO week such as `2024-W45`; the current week when omitted
+#[tauri::command]
+fn generate_weekly_report(week: Option<String>) -> Result<WeeklyReport, String> {
+    recording::generate_weekly_report(week.as_deref())
+}
+
+/// Audio and sessions the retention policy would delete now, as a preview
+#[tauri::command]
+fn get_retention_candidates() -> Result<Vec<RetentionCandidate>, String> {
+    recording::get_retention_candidates()
+}
+
+/// Confirm the retention policy and apply it now, returning what was removed
+///
+/// The background task applies only a confirmed policy.
+#[tauri::command]
+fn apply_retention_policy() -> Result<Vec<RetentionCandidate>, String> {
+    recording::apply_retention_policy()
+}
+
+/// Check sessions against the files in the storage folder, optionally
+/// repairing, re-indexing or deleting what doesn't match
+///
+/// Fixes are refused while recording, since the recording's audio file has
+/// no session yet.
+#[tauri::command]
+fn scan_storage_integrity(
+    state: State<AppState>,
+    fix: Option<IntegrityFix>,
+) -> Result<StorageIntegrityReport, String> {
+    if fix.is_some() && state.inner().recording.lock().unwrap().is_active() {
+        return Err("Finish the current recording before fixing storage".to_string());
+    }
+    recording::scan_storage_integrity(fix)
+}
+
+/// Generate titles again for sessions whose title wasn't given by a meeting
+/// or phone
+///
+/// Returns the number of titles that changed
+#[tauri::command]
+fn regenerate_titles() -> Result<usize, String> {
+    recording::regenerate_titles()
+}
+
+/// Sessions whose transcript, title or notes contain every word of the query,
+/// optionally only those in the given language
+#[tauri::command]
+fn search_sessions(query: String, language: Option<String>) -> Result<Vec<Session>, String> {
+    recording::search_sessions(&query, language.as_deref())
+}
+
+#[tauri::command]
+fn retranscribe_session(
+    session_id: String,
+    decoding: Option<DecodingConfig>,
+) -> Result<String, String> {
+    recording::retranscribe_session(&session_id, decoding)
+}
+
+/// Transcribe a session's audio with two models and return both transcripts,
+/// their timings and a word diff, without changing the session
+#[tauri::command]
+fn compare_models(
+    session_id: String,
+    model_a: String,
+    model_b: String,
+) -> Result<ModelComparison, String> {
+    recording::compare_models(&session_id, &model_a, &model_b)
+}
+
+/// Transcribe sessions in parallel (all untranscribed sessions when no ids are
+/// given), emitting `batch-progress` per session
+///
+/// Returns the number of sessions queued
+#[tauri::command]
+fn transcribe_sessions(
+    app: tauri::AppHandle,
+    session_ids: Option<Vec<String>>,
+) -> Result<usize, String> {
+    recording::start_batch_transcription(session_ids, move |progress| {
+        let _ = app.emit("batch-progress", progress);
+    })
+}
+
+/// Delete the selected sessions and their files, all or none, emitting
+/// `bulk-operation-complete`
+#[tauri::command]
+fn delete_sessions(
+    app: tauri::AppHandle,
+    session_ids: Vec<String>,
+) -> Result<BulkOperationSummary, String> {
+    let summary = recording::delete_sessions(&session_ids)?;
+    let _ = app.emit("bulk-operation-complete", &summary);
+    Ok(summary)
+}
+
+/// Add and remove tags on the selected sessions, emitting
+/// `bulk-operation-complete`
+#[tauri::command]
+fn tag_sessions(
+    app: tauri::AppHandle,
+    session_ids: Vec<String>,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<BulkOperationSummary, String> {
+    let summary = recording::tag_sessions(
+        &session_ids,
+        &add.unwrap_or_default(),
+        &remove.unwrap_or_default(),
+    )?;
+    let _ = app.emit("bulk-operation-complete", &summary);
+    Ok(summary)
+}
+
+/// Export the selected sessions into one folder, emitting
+/// `bulk-operation-complete`
+#[tauri::command]
+fn export_sessions(
+    app: tauri::AppHandle,
+    session_ids: Vec<String>,
+) -> Result<BulkOperationSummary, String> {
+    let summary = recording::export_sessions(&session_ids)?;
+    let _ = app.emit("bulk-operation-complete", &summary);
+    Ok(summary)
+}
+
+/// Re-transcribe the selected sessions, emitting `bulk-operation-complete`
+/// once all of them finished
+///
+/// Returns the number of sessions queued
+#[tauri::command]
+fn retranscribe_sessions(app: tauri::AppHandle, session_ids: Vec<String>) -> Result<usize, String> {
+    recording::retranscribe_sessions(&session_ids, move |summary| {
+        let _ = app.emit("bulk-operation-complete", summary);
+    })
+}
+
+/// Revert the last delete, retranscription or bulk tag
+#[tauri::command]
+fn undo_last_operation() -> Result<JournaledOperation, String> {
+    recording::undo_last_operation()
+}
+
+/// Reapply the last undone operation
+#[tauri::command]
+fn redo_last_operation() -> Result<JournaledOperation, String> {
+    recording::redo_last_operation()
+}
+
+/// Transcript segments whose confidence is below `threshold` (0.0-1.0)
+///
+/// Lets the UI highlight passages worth proofreading
+#[tauri::command]
+fn get_low_confidence_segments(
+    session_id: String,
+    threshold: Option<f64>,
+) -> Result<Vec<TranscriptSegment>, String> {
+    let transcript = recording::load_structured_transcript(&session_id)?;
+    Ok(recording::low_confidence_segments(
+        &transcript,
+        threshold.unwrap_or(recording::DEFAULT_LOW_CONFIDENCE_THRESHOLD),
+    ))
+}
+
+/// Whisper.cpp output (stdout, stderr, timing) of a session's last transcription
+#[tauri::command]
+fn get_transcription_log(session_id: String) -> Result<String, String> {
+    recording::load_transcription_log(&session_id)
+}
+
+/// Stop a session's running transcription, ending its Whisper.cpp process
+#[tauri::command]
+fn cancel_transcription(session_id: String) -> Result<(), String> {
+    recording::cancel_transcription(&session_id)
+}
+
+/// Transcribe queued sessions now, even if power settings would defer them
+///
+/// Returns the number of sessions in the queue
+#[tauri::command]
+fn process_pending_now() -> Result<usize, String> {
+    recording::process_pending_now()
+}
+
+/// Environment self-check for the settings page (mic, Whisper, model, storage, clipboard)
+#[tauri::command]
+fn run_health_check() -> Vec<HealthCheck> {
+    recording::run_health_check()
+}
+
+/// Last lines of the log file, for attaching diagnostics to bug reports
+#[tauri::command]
+fn get_recent_logs(lines: Option<usize>) -> Result<Vec<String>, String> {
+    recording::read_recent_logs(lines.unwrap_or(recording::DEFAULT_LOG_LINES))
 }
 
 #[tauri::command]
@@ -160,53 +944,416 @@ fn get_app_version(app: tauri::AppHandle) -> String {
     app.package_info().version.to_string()
 }
 
+/// Estimated transcription time; the speech is measured in the recording
+/// being transcribed when not given
 #[tauri::command]
-fn get_transcription_estimate(audio_duration_seconds: f64) -> Result<Option<TranscriptionEstimate>, String> {
-    // Load sessions and extract transcription statistics
-    let session_index = recording::load_sessions()?;
-    let stats = extract_transcription_stats(&session_index.sessions);
-    Ok(estimate_transcription_time(&stats, audio_duration_seconds))
+fn get_transcription_estimate(
+    state: State<AppState>,
+    audio_duration_seconds: f64,
+    speech_seconds: Option<f64>,
+) -> Result<Option<TranscriptionEstimate>, String> {
+    let speech_seconds =
+        speech_seconds.or_else(|| recording::processing_speech_seconds(&state.inner().recording));
+    recording::estimate_for_duration(audio_duration_seconds, speech_seconds)
+}
+
+/// Run a synthetic sample of the given length (30 seconds by default)
+/// through the configured model
+///
+/// Reports timing, realtime factor and the compute backend so users can
+/// verify acceleration settings are effective; the result is recorded and
+/// used as the estimator's baseline until enough transcription history exists
+#[tauri::command]
+fn benchmark_model(sample_seconds: Option<f64>) -> Result<BenchmarkResult, String> {
+    recording::benchmark_model(sample_seconds.unwrap_or(recording::DEFAULT_BENCHMARK_SECONDS))
+}
+
+/// Calibrate transcription estimates on a fresh install, once the user
+/// agrees to a 30 second benchmark run (None when already calibrated)
+#[tauri::command]
+fn calibrate_transcription_estimates() -> Result<Option<BenchmarkResult>, String> {
+    recording::ensure_benchmark_baseline()
+}
+
+/// How far transcription estimates have been off (mean absolute percentage
+/// error) and the confidence thresholds that results in
+#[tauri::command]
+fn get_estimate_accuracy() -> Result<EstimateAccuracy, String> {
+    recording::get_estimate_accuracy()
+}
+
+/// Failure rate and average transcript confidence of each model used
+#[tauri::command]
+fn get_model_stats() -> Result<Vec<ModelStats>, String> {
+    recording::get_model_stats()
+}
+
+/// Audio length and transcription time of every transcribed session, for
+/// plotting how the ratio changes over time
+#[tauri::command]
+fn get_transcription_stats() -> Result<TranscriptionStats, String> {
+    recording::get_transcription_stats()
+}
+
+/// Realtime factor of recent transcriptions on this machine, oldest first
+#[tauri::command]
+fn get_throughput_history() -> Result<Vec<ThroughputSample>, String> {
+    recording::get_throughput_history()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+  let session_cache: SharedSessionCache = Arc::new(SessionCache::default());
+  recording::use_session_cache(Arc::clone(&session_cache));
+
+  // Headless transcription server for other devices, without the app window
+  if std::env::args().any(|arg| arg == "--serve") {
+      if let Err(e) = recording::run_transcription_server() {
+          eprintln!("{}", e);
+          std::process::exit(1);
+      }
+      return;
+  }
+
   let app_state = AppState {
       recording: Arc::new(Mutex::new(RecordingState::new())),
+      scheduler: Arc::new(Mutex::new(SchedulerState::default())),
+      sessions: session_cache,
   };
+  let pre_roll_state = Arc::clone(&app_state.recording);
+  let scheduler_recording_state = Arc::clone(&app_state.recording);
+  let scheduler_state = Arc::clone(&app_state.scheduler);
+  let segment_state = Arc::clone(&app_state.recording);
+  let config_watch_state = Arc::clone(&app_state.recording);
+  let queue_state = Arc::clone(&app_state.recording);
+  let sleep_guard_state = Arc::clone(&app_state.recording);
+  let auto_pause_state = Arc::clone(&app_state.recording);
+  let pause_reminder_state = Arc::clone(&app_state.recording);
+  let remote_control_state = Arc::clone(&app_state.recording);
+  let mobile_sync_state = Arc::clone(&app_state.recording);
+  let status_state = Arc::clone(&app_state.recording);
 
   tauri::Builder::default()
     .manage(app_state)
-    .setup(|app| {
-      if cfg!(debug_assertions) {
-        app.handle().plugin(
-          tauri_plugin_log::Builder::default()
-            .level(log::LevelFilter::Info)
-            .build(),
-        )?;
-      }
-
+    .setup(move |app| {
       // Initialize storage directory
       recording::get_storage_dir()?;
 
+      // Log to the console and a rotating file in the local state directory
+      app.handle().plugin(
+        tauri_plugin_log::Builder::default()
+          .level(log::LevelFilter::Info)
+          .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::Folder {
+              path: recording::get_log_dir()?,
+              file_name: Some(recording::LOG_FILE_NAME.to_string()),
+            }),
+          ])
+          .max_file_size(recording::MAX_LOG_FILE_BYTES)
+          .rotation_strategy(RotationStrategy::KeepSome(recording::KEPT_LOG_FILES))
+          .build(),
+      )?;
+
+      // Transcribe out of the box: on first run, set up the bundled
+      // Whisper.cpp with a small starter model in the background
+      let setup_app = app.handle().clone();
+      std::thread::spawn(move || match recording::ensure_starter_setup() {
+          Ok(true) => {
+              let _ = setup_app.emit("setup-status", recording::setup_status());
+          }
+          Ok(false) => {}
+          Err(e) => log::warn!("Starter setup failed: {}", e),
+      });
+
+      // Start/stop recording or copy the last transcript from global
+      // shortcuts and media keys
+      app.handle().plugin(
+        tauri_plugin_global_shortcut::Builder::new()
+          .with_handler(|app, shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+              handle_hotkey(app, shortcut);
+            }
+          })
+          .build(),
+      )?;
+      if let Ok(config) = recording::load_config() {
+          register_hotkeys(app.handle(), &config.hotkeys);
+      }
+
+      // Remove drag-export bundles left over from a previous run
+      if let Err(e) = recording::cleanup_drag_exports() {
+          log::warn!("{}", e);
+      }
+
+      // Start the opt-in pre-roll buffer
+      if let Err(e) = recording::start_configured_pre_roll(pre_roll_state) {
+          log::warn!("Pre-roll not started: {}", e);
+      }
+
+      // Start scheduled recordings, announcing each start before it happens
+      let app_handle = app.handle().clone();
+      recording::start_scheduler(
+          scheduler_recording_state,
+          scheduler_state,
+          move |event| match event {
+              SchedulerEvent::Pending(payload) => {
+                  let _ = app_handle.emit("scheduled-recording-pending", payload);
+              }
+              SchedulerEvent::Started(payload) => {
+                  let _ = app_handle.emit("scheduled-recording-started", payload);
+              }
+              SchedulerEvent::Cancelled(payload) => {
+                  let _ = app_handle.emit("scheduled-recording-cancelled", payload);
+              }
+              SchedulerEvent::MeetingStarting(meeting) => {
+                  let _ = app_handle.emit("meeting-starting", meeting);
+              }
+          },
+      );
+
+      // Split long recordings into linked, incrementally transcribed sessions
+      let segment_app = app.handle().clone();
+      recording::start_segment_timer(segment_state, move |result| {
+          emit_transcription_result(&segment_app, result)
+      });
+
+      // Report every status transition instead of having listeners poll
+      let status_app = app.handle().clone();
+      let fallback_app = app.handle().clone();
+      {
+          let mut status_guard = status_state.lock().unwrap();
+          status_guard.set_status_listener(Box::new(move |event: RecordingStatusChangedEvent| {
+              let _ = status_app.emit("recording-status-changed", event);
+          }));
+          // Warn when a busy microphone was swapped for the default one
+          status_guard.set_input_fallback_listener(Box::new(move |event| {
+              let _ = fallback_app.emit("input-device-fallback", event);
+          }));
+      }
+
+      // Pause recording while the microphone is muted or taken by another app
+      let auto_pause_app = app.handle().clone();
+      recording::start_auto_pause_monitor(auto_pause_state, move |event| match event {
+          AutoPauseEvent::Paused { reason } => {
+              let _ = auto_pause_app.emit(
+                  "recording-auto-paused",
+                  RecordingAutoPausedEvent { reason },
+              );
+          }
+          AutoPauseEvent::Resumed => {
+              let _ = auto_pause_app.emit("recording-auto-resumed", ());
+          }
+      });
+
+      // Remind about a recording left paused
+      let pause_reminder_app = app.handle().clone();
+      recording::start_pause_reminder(pause_reminder_state, move |event: PausedTooLongEvent| {
+          let _ = pause_reminder_app.emit("paused-too-long", event);
+      });
+
+      // Report status to and take commands from hardware controllers
+      if let Ok(config) = recording::load_config() {
+          if config.integrations.remote_control.enabled {
+              let remote_app = app.handle().clone();
+              if let Err(e) = recording::start_remote_control(
+                  remote_control_state,
+                  &config.integrations.remote_control,
+                  move |command| handle_remote_command(&remote_app, command),
+              ) {
+                  log::warn!("Remote control not started: {}", e);
+              }
+          }
+      }
+
+      // Receive recordings from paired phones
+      if let Ok(config) = recording::load_config() {
+          if config.integrations.mobile_sync.enabled {
+              let mobile_app = app.handle().clone();
+              if let Err(e) = recording::start_mobile_sync(
+                  mobile_sync_state,
+                  &config.integrations.mobile_sync,
+                  move |event| match event {
+                      MobileSyncEvent::Paired(device) => {
+                          let _ = mobile_app.emit("mobile-device-paired", device);
+                      }
+                      MobileSyncEvent::Received(session) => {
+                          let _ = mobile_app.emit("mobile-recording-received", session);
+                      }
+                      MobileSyncEvent::Transcription(result) => {
+                          emit_transcription_result(&mobile_app, result)
+                      }
+                  },
+              ) {
+                  log::warn!("Mobile sync not started: {}", e);
+              }
+          }
+      }
+
+      // Keep the system awake while recording or transcribing
+      recording::start_sleep_guard(sleep_guard_state);
+
+      // Normalize sessions from older versions before any are transcribed,
+      // since stray Whisper.cpp output is removed from the audio folder
+      if let Err(e) = recording::migrate_legacy_sessions() {
+          log::warn!("Legacy session migration failed: {}", e);
+      }
+
+      // Save the audio of a recording that was paused when the app quit
+      if let Err(e) = recording::recover_checkpoint() {
+          log::warn!("Recording checkpoint recovery failed: {}", e);
+      }
+
+      // Finish transcriptions interrupted when the app last quit or deferred
+      // by the power settings
+      let queue_app = app.handle().clone();
+      recording::start_transcription_queue(queue_state, move |result| {
+          emit_transcription_result(&queue_app, result)
+      });
+
+      // Commit transcript edits when versioning is on
+      recording::start_transcript_versioning();
+
+      // Write (and e-mail) the weekly report when scheduled
+      recording::start_report_scheduler();
+
+      // Delete old audio and sessions as set in the confirmed retention
+      // policy, asking for confirmation of a new one
+      let retention_app = app.handle().clone();
+      let confirm_app = app.handle().clone();
+      recording::start_retention_task(
+          move |removed| {
+              let _ = retention_app.emit("retention-applied", removed);
+          },
+          move |candidates| {
+              let _ = confirm_app.emit("retention-confirmation-needed", candidates);
+          },
+      );
+
+      // Refresh device pickers when audio devices are plugged in or removed
+      let devices_app = app.handle().clone();
+      recording::start_device_watcher(move |devices| {
+          let _ = devices_app.emit("audio-devices-changed", devices);
+      });
+
+      // Hot-reload config.json edits made outside the app
+      let config_app = app.handle().clone();
+      recording::start_config_watcher(config_watch_state, move |config| {
+          register_hotkeys(&config_app, &config.hotkeys);
+          let _ = config_app.emit("config-changed", config);
+      });
+
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
         start_recording,
+        restart_recording,
         pause_recording,
         resume_recording,
         cancel_recording,
+        cancel_scheduled_recording,
+        start_meeting_recording,
         stop_recording,
         get_sessions,
+        get_sessions_grouped_by_day,
+        get_recording_activity,
+        import_from_directory,
         get_recording_duration,
         get_recording_status,
         get_audio_levels,
-        load_config,
+        set_pre_roll_enabled,
+        get_pre_roll_active,
+        set_monitor_volume,
+        list_audio_devices,
+        check_microphone_permission,
+        list_profiles,
+        get_active_profile,
+        set_active_profile,
+        load_app_config,
+        save_app_config,
+        save_input_device_settings,
         load_transcript,
+        load_romanized_transcript,
+        get_recently_viewed,
+        get_frequently_viewed,
+        get_session_access,
+        update_session_notes,
+        set_session_rating,
+        set_session_quality_flag,
+        delete_sessions,
+        tag_sessions,
+        export_sessions,
+        retranscribe_sessions,
+        compare_models,
+        undo_last_operation,
+        redo_last_operation,
         copy_transcript_to_clipboard,
+        get_last_session,
+        copy_last_transcript,
+        copy_audio_to_clipboard,
+        reveal_session_audio,
+        open_transcript_in_editor,
+        prepare_drag_export,
+        cleanup_drag_exports,
+        share_session,
+        email_session,
+        publish_to_notion,
+        get_action_items,
+        create_tasks,
+        set_integration_token,
+        get_transcript_history,
+        get_transcript_diff,
+        list_transcript_versions,
+        restore_transcript_version,
+        diff_transcripts,
+        export_waveform_image,
+        export_clip,
+        start_mobile_pairing,
+        list_paired_devices,
+        get_remote_control_token,
+        unpair_device,
+        search_sessions,
+        regenerate_previews,
+        regenerate_titles,
+        generate_weekly_report,
+        get_retention_candidates,
+        apply_retention_policy,
+        scan_storage_integrity,
+        transcribe_file,
+        download_model,
+        get_whisper_binary_info,
+        get_setup_status,
         retranscribe_session,
+        transcribe_sessions,
+        get_low_confidence_segments,
+        get_transcription_log,
+        cancel_transcription,
+        process_pending_now,
         get_app_version,
-        get_transcription_estimate
+        get_recent_logs,
+        run_health_check,
+        get_transcription_estimate,
+        benchmark_model,
+        calibrate_transcription_estimates,
+        get_model_stats,
+        get_estimate_accuracy,
+        get_transcription_stats,
+        get_throughput_history
     ])
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app, event| {
+      // Save an in-progress recording instead of losing it on quit; it is
+      // transcribed on the next launch, as are transcriptions that don't
+      // finish in time
+      if let tauri::RunEvent::ExitRequested { .. } = event {
+          let state = app.state::<AppState>();
+          recording::finalize_for_exit(Arc::clone(&state.inner().recording));
+
+          let unfinished = recording::wait_for_transcriptions(EXIT_TRANSCRIPTION_WAIT);
+          if !unfinished.is_empty() {
+              log::info!("Quitting during transcription of {}", unfinished.join(", "));
+          }
+      }
+    });
 }
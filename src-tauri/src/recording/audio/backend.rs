@@ -0,0 +1,317 @@
+use cpal::traits::StreamTrait;
+use std::f32::consts::TAU;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::recording::audio::capture::{cpal_input_name, open_cpal_stream, CpalInput};
+use crate::recording::audio::permission::require_microphone_permission;
+use crate::recording::audio::writer::{read_wav_samples, AudioFormat, WAV_SAMPLE_RATE};
+use crate::recording::config::load_config;
+use crate::recording::models::{InputDeviceFallbackEvent, VirtualInput};
+
+/// Receives each captured buffer, converted to f32
+pub type SampleCallback = Box<dyn FnMut(&[f32]) + Send>;
+
+/// How often the virtual microphone delivers a buffer
+const VIRTUAL_BUFFER_INTERVAL: Duration = Duration::from_millis(20);
+
+/// An open input stream; capture stops when it is dropped
+pub trait InputStream {
+    fn play(&self) -> Result<(), String>;
+
+    /// Name of the device captured from, when it is a real device
+    fn device_name(&self) -> Option<String> {
+        None
+    }
+
+    /// Set when the selected device was busy and another one was opened
+    fn fallback(&self) -> Option<InputDeviceFallbackEvent> {
+        None
+    }
+}
+
+/// Source of microphone audio
+pub trait AudioBackend {
+    /// Open an input stream passing each buffer to `on_samples`
+    ///
+    /// The stream is returned paused along with the format of the samples
+    /// it delivers.
+    fn open_input(
+        &self,
+        on_samples: SampleCallback,
+    ) -> Result<(Box<dyn InputStream>, AudioFormat), String>;
+
+    /// Name shown for the input, e.g. in the health check
+    fn input_name(&self) -> Result<String, String>;
+
+    /// Fail when the OS won't let the app record from the input
+    fn check_permission(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// The configured input device, through CPAL
+pub struct CpalBackend;
+
+impl AudioBackend for CpalBackend {
+    fn open_input(
+        &self,
+        on_samples: SampleCallback,
+    ) -> Result<(Box<dyn InputStream>, AudioFormat), String> {
+        let (input, format) = open_cpal_stream(on_samples)?;
+        Ok((Box::new(input), format))
+    }
+
+    fn input_name(&self) -> Result<String, String> {
+        require_microphone_permission()?;
+        cpal_input_name()
+    }
+
+    fn check_permission(&self) -> Result<(), String> {
+        require_microphone_permission()
+    }
+}
+
+impl InputStream for CpalInput {
+    fn play(&self) -> Result<(), String> {
+        StreamTrait::play(&self.stream).map_err(|e| e.to_string())
+    }
+
+    fn device_name(&self) -> Option<String> {
+        self.device_name.clone()
+    }
+
+    fn fallback(&self) -> Option<InputDeviceFallbackEvent> {
+        self.fallback.clone()
+    }
+}
+
+/// Virtual microphone producing a tone, silence or a WAV file in real time
+///
+/// Delivers mono audio at `WAV_SAMPLE_RATE`, so recordings, pre-roll and
+/// the rest of the pipeline run without audio hardware.
+pub struct SyntheticBackend {
+    input: VirtualInput,
+}
+
+impl SyntheticBackend {
+    pub fn new(input: VirtualInput) -> Self {
+        SyntheticBackend { input }
+    }
+}
+
+impl AudioBackend for SyntheticBackend {
+    fn open_input(
+        &self,
+        on_samples: SampleCallback,
+    ) -> Result<(Box<dyn InputStream>, AudioFormat), String> {
+        let source = SyntheticSource::new(&self.input)?;
+        let stream = SyntheticStream {
+            pending: Mutex::new(Some((source, on_samples))),
+            stopped: Arc::new(AtomicBool::new(false)),
+        };
+        Ok((Box::new(stream), AudioFormat::default()))
+    }
+
+    fn input_name(&self) -> Result<String, String> {
+        Ok(match &self.input {
+            VirtualInput::Tone { frequency, .. } => {
+                format!("virtual microphone ({} Hz tone)", frequency)
+            }
+            VirtualInput::Silence => "virtual microphone (silence)".to_string(),
+            VirtualInput::Wav { path } => format!("virtual microphone ({})", path),
+        })
+    }
+}
+
+/// The virtual microphone when configured, otherwise the input device
+pub fn audio_backend() -> Box<dyn AudioBackend> {
+    match load_config()
+        .ok()
+        .and_then(|config| config.audio.virtual_input)
+    {
+        Some(input) => Box::new(SyntheticBackend::new(input)),
+        None => Box::new(CpalBackend),
+    }
+}
+
+/// Stream of generated audio, delivered from its own thread once playing
+struct SyntheticStream {
+    pending: Mutex<Option<(SyntheticSource, SampleCallback)>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl InputStream for SyntheticStream {
+    fn play(&self) -> Result<(), String> {
+        let Some((mut source, mut on_samples)) = self.pending.lock().unwrap().take() else {
+            return Ok(());
+        };
+        let stopped = Arc::clone(&self.stopped);
+
+        thread::spawn(move || {
+            let started = Instant::now();
+            let mut delivered = 0usize;
+
+            loop {
+                thread::sleep(VIRTUAL_BUFFER_INTERVAL);
+                if stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                // Catch up with the wall clock, so oversleeping loses nothing
+                let due = (started.elapsed().as_secs_f64() * WAV_SAMPLE_RATE as f64) as usize;
+                if due > delivered {
+                    on_samples(&source.next(due - delivered));
+                    delivered = due;
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+impl Drop for SyntheticStream {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Generator behind the virtual microphone
+enum SyntheticSource {
+    Tone {
+        amplitude: f32,
+        step: f32,
+        phase: f32,
+    },
+    Silence,
+    Wav {
+        samples: Vec<f32>,
+        position: usize,
+    },
+}
+
+impl SyntheticSource {
+    fn new(input: &VirtualInput) -> Result<Self, String> {
+        Ok(match input {
+            VirtualInput::Tone {
+                frequency,
+                amplitude,
+            } => SyntheticSource::Tone {
+                amplitude: amplitude.clamp(0.0, 1.0),
+                step: TAU * frequency / WAV_SAMPLE_RATE as f32,
+                phase: 0.0,
+            },
+            VirtualInput::Silence => SyntheticSource::Silence,
+            VirtualInput::Wav { path } => {
+                let (samples, samples_per_second) = read_wav_samples(Path::new(path))?;
+                if samples_per_second != WAV_SAMPLE_RATE as usize {
+                    log::warn!(
+                        "Virtual microphone file {} is not {} Hz mono and will play at the wrong speed",
+                        path,
+                        WAV_SAMPLE_RATE
+                    );
+                }
+                SyntheticSource::Wav {
+                    samples,
+                    position: 0,
+                }
+            }
+        })
+    }
+
+    /// The next `count` samples
+    fn next(&mut self, count: usize) -> Vec<f32> {
+        match self {
+            SyntheticSource::Tone {
+                amplitude,
+                step,
+                phase,
+            } => (0..count)
+                .map(|_| {
+                    let sample = *amplitude * phase.sin();
+                    *phase = (*phase + *step) % TAU;
+                    sample
+                })
+                .collect(),
+            SyntheticSource::Silence => vec![0.0; count],
+            SyntheticSource::Wav { samples, position } => {
+                let end = (*position + count).min(samples.len());
+                let mut buffer = samples[*position..end].to_vec();
+                buffer.resize(count, 0.0);
+                *position = end;
+                buffer
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::audio::writer::write_wav_file;
+    use crate::recording::fixtures;
+
+    #[test]
+    fn test_tone_has_requested_pitch_and_level() {
+        let mut source = SyntheticSource::new(&VirtualInput::Tone {
+            frequency: 441.0,
+            amplitude: 0.5,
+        })
+        .unwrap();
+        let samples = source.next(WAV_SAMPLE_RATE as usize / 10);
+
+        let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        assert!((peak - 0.5).abs() < 0.01);
+
+        // 441 Hz for 0.1 s: 44.1 cycles, one rising zero crossing each
+        let crossings = samples
+            .windows(2)
+            .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+            .count();
+        assert_eq!(crossings, 44);
+    }
+
+    #[test]
+    fn test_wav_plays_once_then_silence() {
+        let dir = fixtures::temp_dir("virtual-mic");
+        let path = dir.join("input.wav");
+        write_wav_file(&[0.5; 4], AudioFormat::default(), &path).unwrap();
+
+        let mut source = SyntheticSource::new(&VirtualInput::Wav {
+            path: path.to_string_lossy().to_string(),
+        })
+        .unwrap();
+        let first = source.next(3);
+        let second = source.next(3);
+        assert!(first.iter().all(|s| (s - 0.5).abs() < 0.001));
+        assert!((second[0] - 0.5).abs() < 0.001);
+        assert_eq!(&second[1..], &[0.0, 0.0]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_stream_delivers_audio_until_dropped() {
+        let received = Arc::new(Mutex::new(0usize));
+        let counter = Arc::clone(&received);
+        let backend = SyntheticBackend::new(VirtualInput::Silence);
+
+        let (stream, format) = backend
+            .open_input(Box::new(move |data| *counter.lock().unwrap() += data.len()))
+            .unwrap();
+        assert_eq!(format, AudioFormat::default());
+        stream.play().unwrap();
+        thread::sleep(Duration::from_millis(150));
+        drop(stream);
+        thread::sleep(Duration::from_millis(50));
+
+        let total = *received.lock().unwrap();
+        assert!(total > 0);
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(*received.lock().unwrap(), total);
+    }
+}
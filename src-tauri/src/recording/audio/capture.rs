@@ -1,43 +1,66 @@
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::Sample;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 
+use crate::recording::audio::backend::{audio_backend, InputStream};
+use crate::recording::audio::monitor::{start_monitor, MonitorBuffer};
+use crate::recording::audio::pre_roll::take_pre_roll;
+use crate::recording::audio::writer::AudioFormat;
+use crate::recording::config::{load_base_config, load_config, save_config};
+use crate::recording::models::{AudioConfig, InputDeviceFallbackEvent, InputDeviceSettings};
 use crate::recording::state::{RecordingStatus, SharedRecordingState};
 
 /// Start capturing audio from the default microphone
 ///
-/// Spawns a background thread that:
+/// Seeds the recording with the pre-roll buffer when enabled, then spawns a
+/// background thread that:
 /// 1. Initializes CPAL audio input stream
 /// 2. Captures audio samples to the shared buffer when recording
 /// 3. Continues running through pause/resume cycles
 /// 4. Runs until status is set to Idle
 pub fn start_capture(state: SharedRecordingState) -> Result<(), String> {
+    // Report a blocked microphone here; the capture thread can only log it
+    audio_backend().check_permission()?;
+
     let mut state_guard = state.lock().unwrap();
 
-    if state_guard.is_active() {
-        return Err("Recording is already in progress.".to_string());
+    if let Some(active) = state_guard.already_recording() {
+        return Err(active.into());
     }
+    state_guard.transition(RecordingStatus::Recording)?;
 
-    // Clear previous samples
+    // Start from the pre-roll audio (if enabled) instead of an empty buffer
+    let pre_roll = take_pre_roll(&state_guard);
+    let pre_roll_format = pre_roll.format;
+    let pre_roll_ms = (pre_roll.duration_seconds * 1000.0) as i64;
     {
         let mut samples = state_guard.samples.lock().unwrap();
         samples.clear();
+        samples.extend(pre_roll.samples);
     }
-    state_guard.start_time = Some(chrono::Utc::now());
-    state_guard.pause_start_time = None;
-    state_guard.total_paused_duration_ms = 0;
-    state_guard.status = RecordingStatus::Recording;
+    state_guard.checkpointed_samples = 0;
+    state_guard
+        .timer
+        .start(chrono::Duration::milliseconds(pre_roll_ms));
+    state_guard.meeting = None;
+    state_guard.previous_segment_id = None;
+    state_guard.input_device = None;
+    state_guard.reset_input_health();
 
     // Clone references for the recording thread
     let samples_clone = Arc::clone(&state_guard.samples);
     let state_clone = Arc::clone(&state);
+    let (stopped_tx, stopped_rx) = mpsc::channel();
+    state_guard.capture_stopped = Some(stopped_rx);
 
     // Spawn a thread to handle audio recording
     thread::spawn(move || {
-        if let Err(e) = run_audio_capture_loop(samples_clone, state_clone) {
-            eprintln!("Audio capture error: {}", e);
+        if let Err(e) = run_audio_capture_loop(samples_clone, state_clone, pre_roll_format) {
+            log::error!("Audio capture error: {}", e);
         }
+        // The stream is closed, so the recorded samples are final
+        let _ = stopped_tx.send(());
     });
 
     Ok(())
@@ -47,47 +70,64 @@ pub fn start_capture(state: SharedRecordingState) -> Result<(), String> {
 ///
 /// Continues running while status is Recording or Paused.
 /// Only stops when status transitions to Idle.
+///
+/// `pre_roll_format` is the format of the pre-roll audio the recording
+/// starts with, if any; that audio is dropped when the stream's differs.
 fn run_audio_capture_loop(
     samples: Arc<Mutex<Vec<f32>>>,
     state: SharedRecordingState,
+    pre_roll_format: Option<AudioFormat>,
 ) -> Result<(), String> {
-    // Get the default audio host
-    let host = cpal::default_host();
-
-    // Get the default input device
-    let device = host
-        .default_input_device()
-        .ok_or(
-            "No microphone access. Please grant microphone permission in \
-             System Settings → Privacy & Security → Microphone → ThoughtCast"
-        )?;
+    let state_for_stream = Arc::clone(&state);
 
-    // Get the default input config
-    let config = device
-        .default_input_config()
-        .map_err(|e| format!("Failed to get default input config: {}", e))?;
+    let monitor_config = load_config()
+        .map(|config| config.audio.monitor)
+        .unwrap_or_default();
+    let monitor = monitor_config
+        .enabled
+        .then(|| Arc::new(Mutex::new(MonitorBuffer::default())));
+    let monitor_for_stream = monitor.clone();
 
+    // Only collect (and monitor) samples if actively recording (not paused)
     let samples_for_stream = Arc::clone(&samples);
-    let state_for_stream = Arc::clone(&state);
-
-    // Build the input stream based on sample format
-    let stream = match config.sample_format() {
-        cpal::SampleFormat::F32 => {
-            build_input_stream::<f32>(&device, &config.into(), samples_for_stream, state_for_stream)
+    let (stream, format) = open_input_stream(move |data| {
+        if let Ok(mut state_guard) = state_for_stream.lock() {
+            state_guard.record_input(data);
+            if state_guard.is_recording() {
+                if let Ok(mut samples_guard) = samples_for_stream.lock() {
+                    samples_guard.extend_from_slice(data);
+                }
+                if let Some(Ok(mut monitor_guard)) = monitor_for_stream.as_ref().map(|m| m.lock()) {
+                    monitor_guard.push(data);
+                }
+            }
         }
-        cpal::SampleFormat::I16 => {
-            build_input_stream::<i16>(&device, &config.into(), samples_for_stream, state_for_stream)
+    })?;
+
+    if let Ok(mut state_guard) = state.lock() {
+        if pre_roll_format.is_some_and(|pre_roll_format| pre_roll_format != format) {
+            log::warn!("Input format changed since pre-roll started, recording without pre-roll");
+            samples.lock().unwrap().clear();
+            state_guard.timer.start(chrono::Duration::zero());
         }
-        cpal::SampleFormat::U16 => {
-            build_input_stream::<u16>(&device, &config.into(), samples_for_stream, state_for_stream)
+        state_guard.audio_format = format;
+        state_guard.input_device = stream.device_name();
+        if let Some(fallback) = stream.fallback() {
+            state_guard.report_input_fallback(fallback);
         }
-        _ => return Err("Unsupported sample format".to_string()),
-    }?;
+    }
 
     stream
         .play()
         .map_err(|e| format!("Failed to start recording: {}", e))?;
 
+    // Recording goes on without monitoring if the output can't be opened
+    let _monitor_stream = monitor.and_then(|buffer| {
+        start_monitor(buffer, &monitor_config, format.samples_per_second())
+            .map_err(|e| log::warn!("Monitoring not started: {}", e))
+            .ok()
+    });
+
     // Keep the stream alive while recording session is active
     loop {
         thread::sleep(std::time::Duration::from_millis(100));
@@ -104,39 +144,327 @@ fn run_audio_capture_loop(
     Ok(())
 }
 
+/// Open an input stream on the microphone, or the virtual microphone when
+/// one is configured
+///
+/// `on_samples` receives each buffer converted to f32. The stream is
+/// returned paused along with the format of those samples.
+pub fn open_input_stream<F>(on_samples: F) -> Result<(Box<dyn InputStream>, AudioFormat), String>
+where
+    F: FnMut(&[f32]) + Send + 'static,
+{
+    audio_backend().open_input(Box::new(on_samples))
+}
+
+/// An open CPAL input stream and the device it captures from
+pub(super) struct CpalInput {
+    pub stream: cpal::Stream,
+    pub device_name: Option<String>,
+    /// Set when the selected device was busy and the default one is used
+    pub fallback: Option<InputDeviceFallbackEvent>,
+}
+
+/// Open a CPAL input stream on the configured (or default) input device
+///
+/// When the configured device is held by another app (e.g. in exclusive
+/// mode), the default input device is used instead.
+pub(super) fn open_cpal_stream<F>(on_samples: F) -> Result<(CpalInput, AudioFormat), String>
+where
+    F: FnMut(&[f32]) + Send + 'static,
+{
+    // Get the default audio host
+    let host = cpal::default_host();
+
+    // Use the configured input device, falling back to the default one
+    let audio_config = load_config().map(|config| config.audio).unwrap_or_default();
+    let device = select_input_device(&host, audio_config.input_device.as_deref())?;
+    let requested = device.name().ok();
+
+    // Shared, so the callback is still there to retry with after a failure
+    let on_samples = Arc::new(Mutex::new(on_samples));
+    let forward = |on_samples: &Arc<Mutex<F>>| {
+        let on_samples = Arc::clone(on_samples);
+        move |data: &[f32]| {
+            if let Ok(mut on_samples) = on_samples.lock() {
+                on_samples(data);
+            }
+        }
+    };
+
+    let error = match open_device_stream(&device, &audio_config, forward(&on_samples)) {
+        Ok((stream, format)) => {
+            let input = CpalInput {
+                stream,
+                device_name: requested,
+                fallback: None,
+            };
+            return Ok((input, format));
+        }
+        Err(error) if is_device_busy(&error) => error,
+        Err(error) => return Err(error),
+    };
+
+    // Nothing to fall back to when the busy device is the default one
+    let default = host
+        .default_input_device()
+        .filter(|default| default.name().ok() != requested)
+        .ok_or_else(|| error.clone())?;
+    let used = default.name().unwrap_or_default();
+    log::warn!(
+        "Input device '{}' is in use by another app, recording from '{}' instead",
+        requested.as_deref().unwrap_or_default(),
+        used
+    );
+
+    let (stream, format) = open_device_stream(&default, &audio_config, forward(&on_samples))?;
+    let input = CpalInput {
+        stream,
+        device_name: Some(used.clone()),
+        fallback: Some(InputDeviceFallbackEvent {
+            requested: requested.unwrap_or_default(),
+            used,
+            reason: error,
+        }),
+    };
+    Ok((input, format))
+}
+
+/// Whether an error opening a device means another app holds it, e.g.
+/// WASAPI exclusive mode, an ALSA device without dmix or CoreAudio hog mode
+fn is_device_busy(error: &str) -> bool {
+    const BUSY_MESSAGES: &[&str] = &[
+        "device or resource busy",
+        "audclnt_e_device_in_use",
+        "0x8889000a",
+        "exclusive mode",
+        "hog mode",
+        "in use by another",
+    ];
+
+    let error = error.to_lowercase();
+    BUSY_MESSAGES.iter().any(|message| error.contains(message))
+}
+
+/// Open a CPAL input stream on a device, with its remembered settings
+fn open_device_stream<F>(
+    device: &cpal::Device,
+    audio_config: &AudioConfig,
+    on_samples: F,
+) -> Result<(cpal::Stream, AudioFormat), String>
+where
+    F: FnMut(&[f32]) + Send + 'static,
+{
+    // Apply the settings remembered for this device
+    let settings = device
+        .name()
+        .ok()
+        .and_then(|name| audio_config.input_devices.get(&name).cloned())
+        .unwrap_or_default();
+    let config = input_stream_config(device, settings.sample_rate)?;
+    let processing = InputProcessing::new(&settings, config.channels() as usize);
+
+    // What is saved, after channel selection
+    let format = AudioFormat {
+        sample_rate: config.sample_rate().0,
+        channels: processing.output_channels() as u16,
+    };
+
+    // Build the input stream based on sample format
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            build_input_stream::<f32, F>(device, &config.into(), processing, on_samples)
+        }
+        cpal::SampleFormat::I16 => {
+            build_input_stream::<i16, F>(device, &config.into(), processing, on_samples)
+        }
+        cpal::SampleFormat::U16 => {
+            build_input_stream::<u16, F>(device, &config.into(), processing, on_samples)
+        }
+        _ => return Err("Unsupported sample format".to_string()),
+    }?;
+
+    Ok((stream, format))
+}
+
+/// Remember gain, channel and sample rate for an input device
+///
+/// Saved to config.json; the settings apply whenever the device is used,
+/// from the next recording on.
+pub fn save_input_device_settings(
+    device_name: &str,
+    settings: InputDeviceSettings,
+) -> Result<(), String> {
+    let mut config = load_base_config()?;
+    config
+        .audio
+        .input_devices
+        .insert(device_name.to_string(), settings);
+    save_config(&config)
+}
+
+/// Check that the configured (or default) microphone can be opened
+///
+/// Returns the device name
+pub fn check_input_device() -> Result<String, String> {
+    audio_backend().input_name()
+}
+
+/// Name of the configured (or default) input device, if it can be opened
+pub(super) fn cpal_input_name() -> Result<String, String> {
+    let host = cpal::default_host();
+    let device_name = load_config().ok().and_then(|config| config.audio.input_device);
+    let device = select_input_device(&host, device_name.as_deref())?;
+
+    device
+        .default_input_config()
+        .map_err(|e| format!("Failed to get default input config: {}", e))?;
+
+    device
+        .name()
+        .map_err(|e| format!("Failed to read input device name: {}", e))
+}
+
+/// Find an input device by name, or the default input device
+fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::Device, String> {
+    if let Some(name) = name {
+        let named = host
+            .input_devices()
+            .ok()
+            .and_then(|mut devices| devices.find(|d| d.name().is_ok_and(|n| n == name)));
+
+        match named {
+            Some(device) => return Ok(device),
+            None => log::warn!("Input device '{}' not found, using default", name),
+        }
+    }
+
+    host.default_input_device().ok_or_else(|| {
+        "No microphone access. Please grant microphone permission in \
+         System Settings → Privacy & Security → Microphone → ThoughtCast"
+            .to_string()
+    })
+}
+
+/// Device's default input config, at `sample_rate` if given and supported
+fn input_stream_config(
+    device: &cpal::Device,
+    sample_rate: Option<u32>,
+) -> Result<cpal::SupportedStreamConfig, String> {
+    let default = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to get default input config: {}", e))?;
+
+    let Some(rate) = sample_rate else {
+        return Ok(default);
+    };
+    if rate == default.sample_rate().0 {
+        return Ok(default);
+    }
+
+    let supported = device
+        .supported_input_configs()
+        .ok()
+        .and_then(|mut ranges| {
+            ranges.find(|range| {
+                range.channels() == default.channels()
+                    && range.sample_format() == default.sample_format()
+                    && (range.min_sample_rate().0..=range.max_sample_rate().0).contains(&rate)
+            })
+        });
+
+    match supported {
+        Some(range) => Ok(range.with_sample_rate(cpal::SampleRate(rate))),
+        None => {
+            log::warn!(
+                "Input device does not support {} Hz, using {} Hz",
+                rate,
+                default.sample_rate().0
+            );
+            Ok(default)
+        }
+    }
+}
+
+/// Gain and channel selection applied to captured audio
+struct InputProcessing {
+    gain: f32,
+    channels: usize,
+    /// Channel kept from each interleaved frame; all are kept when unset
+    channel: Option<usize>,
+}
+
+impl InputProcessing {
+    fn new(settings: &InputDeviceSettings, channels: usize) -> Self {
+        let channel = settings.channel.map(usize::from);
+        if channel.is_some_and(|channel| channel >= channels) {
+            log::warn!(
+                "Input device has {} channel(s), recording all of them",
+                channels
+            );
+        }
+
+        InputProcessing {
+            gain: settings.gain,
+            channels,
+            channel: channel.filter(|&channel| channel < channels && channels > 1),
+        }
+    }
+
+    /// Channels in the processed audio
+    fn output_channels(&self) -> usize {
+        match self.channel {
+            Some(_) => 1,
+            None => self.channels,
+        }
+    }
+
+    fn apply(&self, data: &[f32]) -> Vec<f32> {
+        let selected: Vec<f32> = match self.channel {
+            Some(channel) => data
+                .iter()
+                .skip(channel)
+                .step_by(self.channels)
+                .copied()
+                .collect(),
+            None => data.to_vec(),
+        };
+
+        if self.gain == 1.0 {
+            return selected;
+        }
+        selected
+            .into_iter()
+            .map(|sample| (sample * self.gain).clamp(-1.0, 1.0))
+            .collect()
+    }
+}
+
 /// Build a CPAL input stream for a specific sample format
 ///
-/// Handles conversion from various sample formats (F32, I16, U16) to F32
-/// and stores samples in the shared buffer only when status is Recording.
-/// When paused, the callback runs but samples are not collected.
-fn build_input_stream<T>(
+/// Handles conversion from various sample formats (F32, I16, U16) to F32,
+/// applies the device's gain and channel selection and passes each
+/// converted buffer to `on_samples`.
+fn build_input_stream<T, F>(
     device: &cpal::Device,
     config: &cpal::StreamConfig,
-    samples: Arc<Mutex<Vec<f32>>>,
-    state: SharedRecordingState,
+    processing: InputProcessing,
+    mut on_samples: F,
 ) -> Result<cpal::Stream, String>
 where
     T: cpal::Sample + cpal::SizedSample,
     f32: cpal::FromSample<T>,
+    F: FnMut(&[f32]) + Send + 'static,
 {
-    let err_fn = |err| eprintln!("An error occurred on the input stream: {}", err);
+    let err_fn = |err| log::error!("An error occurred on the input stream: {}", err);
 
     let stream = device
         .build_input_stream(
             config,
             move |data: &[T], _: &cpal::InputCallbackInfo| {
-                // Only collect samples if actively recording (not paused)
-                if let Ok(state_guard) = state.lock() {
-                    if state_guard.is_recording() {
-                        if let Ok(mut samples_guard) = samples.lock() {
-                            for &sample in data {
-                                // Convert sample to f32 using FromSample trait
-                                let float_val = f32::from_sample(sample);
-                                samples_guard.push(float_val);
-                            }
-                        }
-                    }
-                }
+                // Convert samples to f32 using FromSample trait
+                let converted: Vec<f32> =
+                    data.iter().map(|&sample| f32::from_sample(sample)).collect();
+                on_samples(&processing.apply(&converted));
             },
             err_fn,
             None,
@@ -145,3 +473,45 @@ where
 
     Ok(stream)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selects_channel_and_applies_gain() {
+        let settings = InputDeviceSettings {
+            gain: 2.0,
+            channel: Some(1),
+            sample_rate: None,
+        };
+        let processing = InputProcessing::new(&settings, 2);
+
+        assert_eq!(processing.output_channels(), 1);
+        assert_eq!(processing.apply(&[0.1, 0.2, 0.3, 0.6]), vec![0.4, 1.0]);
+    }
+
+    #[test]
+    fn test_recognises_busy_device_errors() {
+        assert!(is_device_busy(
+            "Failed to build input stream: A backend-specific error has occurred: \
+             AUDCLNT_E_DEVICE_IN_USE"
+        ));
+        assert!(is_device_busy(
+            "Failed to get default input config: Device or resource busy"
+        ));
+        assert!(!is_device_busy("Failed to build input stream: InvalidArgument"));
+    }
+
+    #[test]
+    fn test_missing_channel_keeps_all_channels() {
+        let settings = InputDeviceSettings {
+            channel: Some(4),
+            ..InputDeviceSettings::default()
+        };
+        let processing = InputProcessing::new(&settings, 2);
+
+        assert_eq!(processing.output_channels(), 2);
+        assert_eq!(processing.apply(&[0.1, 0.2]), vec![0.1, 0.2]);
+    }
+}
@@ -0,0 +1,114 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::thread;
+use std::time::Duration;
+
+/// Chime tone frequency in Hz
+const CHIME_FREQUENCY: f32 = 880.0;
+
+/// Chime length in seconds
+const CHIME_SECONDS: f32 = 0.25;
+
+/// Play a short chime on the default output device
+///
+/// Runs in a background thread; failures are logged, never surfaced,
+/// since the chime is only a cue
+pub fn play_chime() {
+    thread::spawn(|| {
+        if let Err(e) = run_chime() {
+            log::warn!("Failed to play chime: {}", e);
+        }
+    });
+}
+
+fn run_chime() -> Result<(), String> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or("No audio output device available")?;
+
+    let config = device
+        .default_output_config()
+        .map_err(|e| format!("Failed to get default output config: {}", e))?;
+
+    let sample_rate = config.sample_rate().0 as f32;
+    let channels = config.channels() as usize;
+
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            build_chime_stream::<f32>(&device, &config.into(), sample_rate, channels)
+        }
+        cpal::SampleFormat::I16 => {
+            build_chime_stream::<i16>(&device, &config.into(), sample_rate, channels)
+        }
+        cpal::SampleFormat::U16 => {
+            build_chime_stream::<u16>(&device, &config.into(), sample_rate, channels)
+        }
+        _ => return Err("Unsupported sample format".to_string()),
+    }?;
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to play chime: {}", e))?;
+
+    // Keep the stream alive until the tone has finished
+    thread::sleep(Duration::from_secs_f32(CHIME_SECONDS + 0.1));
+    Ok(())
+}
+
+fn build_chime_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sample_rate: f32,
+    channels: usize,
+) -> Result<cpal::Stream, String>
+where
+    T: cpal::SizedSample + cpal::FromSample<f32>,
+{
+    let mut frame = 0usize;
+
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                for samples in data.chunks_mut(channels) {
+                    let value = chime_sample(frame as f32 / sample_rate);
+                    for sample in samples.iter_mut() {
+                        *sample = T::from_sample(value);
+                    }
+                    frame += 1;
+                }
+            },
+            |err| log::error!("An error occurred on the chime stream: {}", err),
+            None,
+        )
+        .map_err(|e| format!("Failed to build chime stream: {}", e))
+}
+
+/// Chime waveform: a soft sine with an exponential decay, silent afterwards
+fn chime_sample(t: f32) -> f32 {
+    if t >= CHIME_SECONDS {
+        return 0.0;
+    }
+
+    let envelope = (-t * 12.0).exp() * (t * 200.0).min(1.0);
+    0.3 * envelope * (t * CHIME_FREQUENCY * std::f32::consts::TAU).sin()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chime_is_bounded_and_ends() {
+        let samples: Vec<f32> = (0..48000).map(|i| chime_sample(i as f32 / 48000.0)).collect();
+
+        assert!(samples.iter().all(|s| s.abs() <= 0.3));
+        assert!(samples.iter().any(|s| s.abs() > 0.1));
+        assert_eq!(chime_sample(CHIME_SECONDS), 0.0);
+    }
+
+    #[test]
+    fn test_chime_starts_without_click() {
+        assert_eq!(chime_sample(0.0), 0.0);
+    }
+}
@@ -0,0 +1,56 @@
+use cpal::traits::{DeviceTrait, HostTrait};
+use std::thread;
+use std::time::Duration;
+
+use crate::recording::models::AudioDeviceList;
+
+/// How often the device list is checked for changes
+///
+/// cpal has no portable device change notification, so the list is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Input and output devices currently available
+pub fn list_audio_devices() -> AudioDeviceList {
+    let host = cpal::default_host();
+
+    AudioDeviceList {
+        inputs: host
+            .input_devices()
+            .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+            .unwrap_or_default(),
+        outputs: host
+            .output_devices()
+            .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+            .unwrap_or_default(),
+        default_input: host.default_input_device().and_then(|d| d.name().ok()),
+        default_output: host.default_output_device().and_then(|d| d.name().ok()),
+    }
+}
+
+/// Watch for audio devices being plugged in or removed
+///
+/// Invokes the callback with the refreshed list whenever the devices (or
+/// the system defaults) change.
+pub fn start_device_watcher<F>(on_change: F)
+where
+    F: Fn(AudioDeviceList) + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut known = list_audio_devices();
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let devices = list_audio_devices();
+            if devices != known {
+                log::info!(
+                    "Audio devices changed: inputs {:?}, outputs {:?}",
+                    devices.inputs,
+                    devices.outputs
+                );
+                known = devices.clone();
+                on_change(devices);
+            }
+        }
+    });
+}
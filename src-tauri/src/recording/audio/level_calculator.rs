@@ -10,7 +10,7 @@ const MAX_LEVELS: usize = 20; // Store last 20 levels (~1 second of history)
 /// than simple peak or average amplitude.
 ///
 /// Returns a value between 0.0 (silence) and 1.0 (maximum amplitude)
-fn calculate_rms_amplitude(samples: &[f32]) -> f32 {
+pub fn calculate_rms_amplitude(samples: &[f32]) -> f32 {
     if samples.is_empty() {
         return 0.0;
     }
@@ -1,7 +1,22 @@
+pub mod backend;
 pub mod capture;
+pub mod chime;
+pub mod devices;
 pub mod level_calculator;
+pub mod monitor;
+pub mod permission;
+pub mod pre_roll;
+pub mod vad;
 pub mod writer;
 
-pub use capture::start_capture;
-pub use level_calculator::get_audio_levels;
-pub use writer::write_wav_file;
+pub use capture::{check_input_device, save_input_device_settings, start_capture};
+pub use chime::play_chime;
+pub use devices::{list_audio_devices, start_device_watcher};
+pub use level_calculator::{calculate_rms_amplitude, get_audio_levels};
+pub use monitor::set_monitor_volume;
+pub use permission::check_microphone_permission;
+pub use pre_roll::{
+    is_pre_roll_active, reload_pre_roll, set_pre_roll_enabled, start_configured_pre_roll,
+};
+pub use vad::{speech_seconds, wav_speech_seconds};
+pub use writer::{append_wav_samples, AudioFormat, audio_fingerprint, capture_gap, copy_wav_range, read_wav_samples, wav_duration, write_wav_file, MAX_WAV_BYTES, WAV_SAMPLE_RATE};
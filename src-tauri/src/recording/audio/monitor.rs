@@ -0,0 +1,210 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::recording::models::MonitorConfig;
+
+/// Upper bound on the monitor volume (2 = +6 dB)
+pub const MAX_MONITOR_VOLUME: f32 = 2.0;
+
+/// Input audio held for playback; older audio is dropped so the monitor
+/// never falls noticeably behind the microphone
+const MAX_LATENCY_SECONDS: f64 = 0.1;
+
+/// Current monitor volume as `f32` bits, adjustable while recording
+static VOLUME: AtomicU32 = AtomicU32::new(0x3f80_0000); // 1.0
+
+/// Set the monitor volume (0 to `MAX_MONITOR_VOLUME`), applied immediately
+pub fn set_monitor_volume(volume: f32) {
+    let volume = if volume.is_finite() { volume } else { 1.0 };
+    VOLUME.store(
+        volume.clamp(0.0, MAX_MONITOR_VOLUME).to_bits(),
+        Ordering::Relaxed,
+    );
+}
+
+fn monitor_volume() -> f32 {
+    f32::from_bits(VOLUME.load(Ordering::Relaxed))
+}
+
+/// Microphone audio waiting to be played back on the monitor output
+///
+/// Converts from the input's sample rate to the output's by picking the
+/// nearest input sample, which is plenty for listening in. Holds nothing
+/// until `configure` is called.
+#[derive(Default)]
+pub struct MonitorBuffer {
+    samples: VecDeque<f32>,
+    input_rate: usize,
+    output_rate: usize,
+    /// Position of the next output sample within `samples`
+    position: f64,
+}
+
+impl MonitorBuffer {
+    /// Set the input samples per second (sample rate × channels) and the
+    /// output sample rate
+    pub fn configure(&mut self, input_rate: usize, output_rate: usize) {
+        self.input_rate = input_rate;
+        self.output_rate = output_rate;
+        self.samples.clear();
+        self.position = 0.0;
+    }
+
+    /// Append microphone samples, dropping the oldest beyond the latency cap
+    pub fn push(&mut self, data: &[f32]) {
+        if self.input_rate == 0 {
+            return;
+        }
+
+        self.samples.extend(data);
+        let capacity = (MAX_LATENCY_SECONDS * self.input_rate as f64) as usize;
+        let overflow = self.samples.len().saturating_sub(capacity);
+        self.samples.drain(..overflow);
+        self.position = (self.position - overflow as f64).max(0.0);
+    }
+
+    /// Next output sample, or silence while the input hasn't caught up
+    pub fn next_sample(&mut self) -> f32 {
+        let Some(&sample) = self.samples.get(self.position as usize) else {
+            return 0.0;
+        };
+
+        self.position += self.input_rate as f64 / self.output_rate as f64;
+        let consumed = (self.position as usize).min(self.samples.len());
+        self.samples.drain(..consumed);
+        self.position -= consumed as f64;
+
+        sample
+    }
+}
+
+/// Start playing `buffer` on the configured output device (or the default
+/// one) at the configured volume
+///
+/// Returns the playing stream, which stops when dropped.
+pub fn start_monitor(
+    buffer: Arc<Mutex<MonitorBuffer>>,
+    config: &MonitorConfig,
+    input_samples_per_second: usize,
+) -> Result<cpal::Stream, String> {
+    set_monitor_volume(config.volume);
+
+    let host = cpal::default_host();
+    let device = select_output_device(&host, config.output_device.as_deref())?;
+    let output_config = device
+        .default_output_config()
+        .map_err(|e| format!("Failed to get default output config: {}", e))?;
+
+    buffer.lock().unwrap().configure(
+        input_samples_per_second,
+        output_config.sample_rate().0 as usize,
+    );
+    let channels = output_config.channels() as usize;
+
+    let stream = match output_config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            build_monitor_stream::<f32>(&device, &output_config.into(), channels, buffer)
+        }
+        cpal::SampleFormat::I16 => {
+            build_monitor_stream::<i16>(&device, &output_config.into(), channels, buffer)
+        }
+        cpal::SampleFormat::U16 => {
+            build_monitor_stream::<u16>(&device, &output_config.into(), channels, buffer)
+        }
+        _ => return Err("Unsupported sample format".to_string()),
+    }?;
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start monitoring: {}", e))?;
+
+    Ok(stream)
+}
+
+/// Find an output device by name, or the default output device
+fn select_output_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::Device, String> {
+    if let Some(name) = name {
+        let named = host
+            .output_devices()
+            .ok()
+            .and_then(|mut devices| devices.find(|d| d.name().is_ok_and(|n| n == name)));
+
+        match named {
+            Some(device) => return Ok(device),
+            None => log::warn!("Output device '{}' not found, using default", name),
+        }
+    }
+
+    host.default_output_device()
+        .ok_or_else(|| "No audio output device available".to_string())
+}
+
+fn build_monitor_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    channels: usize,
+    buffer: Arc<Mutex<MonitorBuffer>>,
+) -> Result<cpal::Stream, String>
+where
+    T: cpal::SizedSample + cpal::FromSample<f32>,
+{
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                let volume = monitor_volume();
+                let mut buffer = buffer.lock().unwrap();
+                for frame in data.chunks_mut(channels) {
+                    let value = (buffer.next_sample() * volume).clamp(-1.0, 1.0);
+                    for sample in frame.iter_mut() {
+                        *sample = T::from_sample(value);
+                    }
+                }
+            },
+            |err| log::error!("An error occurred on the monitor stream: {}", err),
+            None,
+        )
+        .map_err(|e| format!("Failed to build monitor stream: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resamples_to_output_rate() {
+        let mut buffer = MonitorBuffer::default();
+        buffer.configure(96_000, 48_000);
+        buffer.push(&[0.1, 0.2, 0.3, 0.4]);
+
+        assert_eq!(buffer.next_sample(), 0.1);
+        assert_eq!(buffer.next_sample(), 0.3);
+        // Silence until more input arrives
+        assert_eq!(buffer.next_sample(), 0.0);
+    }
+
+    #[test]
+    fn test_drops_audio_beyond_latency_cap() {
+        let mut buffer = MonitorBuffer::default();
+        buffer.configure(100, 100);
+        buffer.push(&[0.1; 50]);
+        buffer.push(&[0.9]);
+
+        // Only the most recent 0.1 s (10 samples) is kept
+        for _ in 0..9 {
+            assert_eq!(buffer.next_sample(), 0.1);
+        }
+        assert_eq!(buffer.next_sample(), 0.9);
+    }
+
+    #[test]
+    fn test_volume_is_clamped() {
+        set_monitor_volume(5.0);
+        assert_eq!(monitor_volume(), MAX_MONITOR_VOLUME);
+
+        set_monitor_volume(f32::NAN);
+        assert_eq!(monitor_volume(), 1.0);
+    }
+}
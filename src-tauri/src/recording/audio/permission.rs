@@ -0,0 +1,240 @@
+use cpal::traits::StreamTrait;
+use std::thread;
+use std::time::Duration;
+
+use crate::recording::audio::capture::open_cpal_stream;
+use crate::recording::models::{
+    MicrophonePermission, MicrophonePermissionAction, MicrophonePermissionStatus,
+};
+use crate::recording::utils::open_url;
+
+/// How long the microphone is held open to bring up the system prompt
+const PROMPT_STREAM_DURATION: Duration = Duration::from_secs(1);
+
+/// Check the OS microphone permission, optionally prompting for it or
+/// opening the settings pane where it is granted
+///
+/// The prompt is shown asynchronously, so the returned permission is the
+/// one from before the user answers; check again afterwards.
+pub fn check_microphone_permission(
+    action: Option<MicrophonePermissionAction>,
+) -> Result<MicrophonePermissionStatus, String> {
+    let status = microphone_permission_status();
+
+    match action {
+        Some(MicrophonePermissionAction::Prompt) => request_microphone_access(),
+        Some(MicrophonePermissionAction::OpenSettings) => match &status.settings_url {
+            Some(url) => open_url(url)?,
+            None => return Err("There are no microphone privacy settings to open".to_string()),
+        },
+        None => {}
+    }
+
+    Ok(status)
+}
+
+/// Fail with guidance when the OS blocks the microphone, so recording
+/// doesn't start on a stream that errors or delivers only silence
+pub fn require_microphone_permission() -> Result<(), String> {
+    let status = microphone_permission_status();
+    match (status.permission, status.guidance) {
+        (MicrophonePermission::Denied | MicrophonePermission::Restricted, Some(guidance)) => {
+            Err(guidance)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn microphone_permission_status() -> MicrophonePermissionStatus {
+    let permission = platform::microphone_permission();
+
+    MicrophonePermissionStatus {
+        permission,
+        guidance: guidance(permission).map(str::to_string),
+        settings_url: platform::SETTINGS_URL.map(str::to_string),
+    }
+}
+
+fn guidance(permission: MicrophonePermission) -> Option<&'static str> {
+    match permission {
+        MicrophonePermission::Granted | MicrophonePermission::Unknown => None,
+        MicrophonePermission::NotDetermined => {
+            Some("ThoughtCast will ask for microphone access when you first record.")
+        }
+        MicrophonePermission::Denied => Some(platform::DENIED_GUIDANCE),
+        MicrophonePermission::Restricted => Some(
+            "Microphone access is blocked by a system policy. \
+             Please ask your administrator to allow it.",
+        ),
+    }
+}
+
+/// Open the microphone briefly, which makes the OS ask for permission if it
+/// hasn't yet
+fn request_microphone_access() {
+    thread::spawn(|| {
+        if let Err(e) = hold_microphone_open() {
+            log::warn!(
+                "Failed to open the microphone for the permission prompt: {}",
+                e
+            );
+        }
+    });
+}
+
+fn hold_microphone_open() -> Result<(), String> {
+    let (input, _) = open_cpal_stream(|_| {})?;
+    input.stream.play().map_err(|e| e.to_string())?;
+    thread::sleep(PROMPT_STREAM_DURATION);
+    Ok(())
+}
+
+/// macOS: the TCC status from AVFoundation
+#[cfg(target_os = "macos")]
+mod platform {
+    use crate::recording::models::MicrophonePermission;
+    use std::ffi::c_void;
+    use std::os::raw::c_char;
+
+    pub const SETTINGS_URL: Option<&str> =
+        Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone");
+
+    pub const DENIED_GUIDANCE: &str = "No microphone access. Please grant microphone \
+         permission in System Settings → Privacy & Security → Microphone → ThoughtCast";
+
+    type Id = *mut c_void;
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: Id;
+    }
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> Id;
+        fn sel_registerName(name: *const c_char) -> Id;
+        fn objc_msgSend();
+    }
+
+    /// `[AVCaptureDevice authorizationStatusForMediaType:AVMediaTypeAudio]`
+    pub fn microphone_permission() -> MicrophonePermission {
+        let status = unsafe {
+            let class = objc_getClass(b"AVCaptureDevice\0".as_ptr().cast());
+            if class.is_null() {
+                return MicrophonePermission::Unknown;
+            }
+            let selector = sel_registerName(b"authorizationStatusForMediaType:\0".as_ptr().cast());
+            let send: unsafe extern "C" fn(Id, Id, Id) -> isize =
+                std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            send(class, selector, AVMediaTypeAudio)
+        };
+
+        // AVAuthorizationStatus
+        match status {
+            0 => MicrophonePermission::NotDetermined,
+            1 => MicrophonePermission::Restricted,
+            2 => MicrophonePermission::Denied,
+            3 => MicrophonePermission::Granted,
+            _ => MicrophonePermission::Unknown,
+        }
+    }
+}
+
+/// Windows: the privacy settings' consent store in the registry
+#[cfg(windows)]
+mod platform {
+    use super::parse_consent_value;
+    use crate::recording::models::MicrophonePermission;
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    pub const SETTINGS_URL: Option<&str> = Some("ms-settings:privacy-microphone");
+
+    pub const DENIED_GUIDANCE: &str = "No microphone access. Please turn on \"Microphone \
+         access\" and \"Let desktop apps access your microphone\" in Settings → Privacy & \
+         security → Microphone";
+
+    const CONSENT_STORE: &str = r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    /// Denied if any of the device-wide, per-user or desktop-app switches is
+    /// off; unknown if none could be read
+    pub fn microphone_permission() -> MicrophonePermission {
+        let keys = [
+            format!(r"HKLM\{}", CONSENT_STORE),
+            format!(r"HKCU\{}", CONSENT_STORE),
+            format!(r"HKCU\{}\NonPackaged", CONSENT_STORE),
+        ];
+        let values: Vec<bool> = keys.iter().filter_map(|key| consent(key)).collect();
+
+        if values.is_empty() {
+            MicrophonePermission::Unknown
+        } else if values.contains(&false) {
+            MicrophonePermission::Denied
+        } else {
+            MicrophonePermission::Granted
+        }
+    }
+
+    fn consent(key: &str) -> Option<bool> {
+        let output = Command::new("reg")
+            .args(["query", key, "/v", "Value"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        parse_consent_value(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Other platforms have no microphone permission
+#[cfg(not(any(target_os = "macos", windows)))]
+mod platform {
+    use crate::recording::models::MicrophonePermission;
+
+    pub const SETTINGS_URL: Option<&str> = None;
+
+    pub const DENIED_GUIDANCE: &str = "No microphone access.";
+
+    pub fn microphone_permission() -> MicrophonePermission {
+        MicrophonePermission::Unknown
+    }
+}
+
+/// Whether a consent store `Value` allows access, from `reg query` output
+/// such as `    Value    REG_SZ    Allow`
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_consent_value(output: &str) -> Option<bool> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.next() != Some("Value") || fields.next() != Some("REG_SZ") {
+            return None;
+        }
+        match fields.next() {
+            Some("Allow") => Some(true),
+            Some("Deny") => Some(false),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_consent_value() {
+        let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\\
+                      CapabilityAccessManager\\ConsentStore\\microphone\r\n    \
+                      Value    REG_SZ    Deny\r\n\r\n";
+        assert_eq!(parse_consent_value(output), Some(false));
+        assert_eq!(
+            parse_consent_value("    Value    REG_SZ    Allow"),
+            Some(true)
+        );
+        assert_eq!(
+            parse_consent_value("ERROR: The system was unable to find the specified registry key"),
+            None
+        );
+    }
+}
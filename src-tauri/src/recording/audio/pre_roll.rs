@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::recording::audio::capture::open_input_stream;
+use crate::recording::audio::writer::AudioFormat;
+use crate::recording::config::load_config;
+use crate::recording::models::PreRollConfig;
+use crate::recording::state::{RecordingState, RecordingStatus, SharedRecordingState};
+
+/// Upper bound on the pre-roll length, whatever the config says
+///
+/// Privacy: only the last few seconds are ever held, in memory only
+pub const MAX_PRE_ROLL_SECONDS: f64 = 10.0;
+
+/// Rolling buffer of the most recent microphone audio while idle
+///
+/// Audio is kept in memory only and never written to disk unless a
+/// recording is started, in which case it becomes the recording's start.
+pub struct PreRollBuffer {
+    samples: VecDeque<f32>,
+    seconds: f64,
+    format: Option<AudioFormat>,
+}
+
+/// Audio taken from the pre-roll buffer when a recording starts
+#[derive(Default)]
+pub struct PreRoll {
+    pub samples: Vec<f32>,
+    pub duration_seconds: f64,
+    /// Format of `samples`; None when the stream never opened
+    pub format: Option<AudioFormat>,
+}
+
+impl PreRollBuffer {
+    /// Create an empty buffer holding up to `seconds` of audio
+    ///
+    /// Holds nothing until `configure` sets the stream's format
+    pub fn new(seconds: f64) -> Self {
+        PreRollBuffer {
+            samples: VecDeque::new(),
+            seconds: seconds.clamp(0.0, MAX_PRE_ROLL_SECONDS),
+            format: None,
+        }
+    }
+
+    /// Set the format of the samples the stream delivers
+    pub fn configure(&mut self, format: AudioFormat) {
+        self.format = Some(format);
+        self.samples.clear();
+    }
+
+    fn capacity(&self) -> usize {
+        let samples_per_second = self.format.map_or(0, AudioFormat::samples_per_second);
+        (self.seconds * samples_per_second as f64) as usize
+    }
+
+    /// Append samples, dropping the oldest beyond capacity
+    pub fn push(&mut self, data: &[f32]) {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return;
+        }
+
+        let data = &data[data.len().saturating_sub(capacity)..];
+        let overflow = (self.samples.len() + data.len()).saturating_sub(capacity);
+        self.samples.drain(..overflow);
+        self.samples.extend(data);
+    }
+
+    /// Take the buffered audio, leaving the buffer empty
+    pub fn take(&mut self) -> PreRoll {
+        let duration_seconds = self
+            .format
+            .map_or(0.0, |format| format.seconds(self.samples.len()));
+
+        PreRoll {
+            samples: self.samples.drain(..).collect(),
+            duration_seconds,
+            format: self.format,
+        }
+    }
+
+    /// Discard all buffered audio
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// Start pre-roll at launch if enabled in the config
+pub fn start_configured_pre_roll(state: SharedRecordingState) -> Result<(), String> {
+    let pre_roll = load_config()?.audio.pre_roll;
+    if pre_roll.enabled {
+        start_pre_roll(state, pre_roll.seconds)?;
+    }
+    Ok(())
+}
+
+/// Enable or disable pre-roll at runtime, using the configured length
+pub fn set_pre_roll_enabled(state: SharedRecordingState, enabled: bool) -> Result<(), String> {
+    if !enabled {
+        stop_pre_roll(state);
+        return Ok(());
+    }
+
+    let seconds = load_config()
+        .map(|config| config.audio.pre_roll.seconds)
+        .unwrap_or_else(|_| PreRollConfig::default().seconds);
+    start_pre_roll(state, seconds)
+}
+
+/// Restart pre-roll after a config change so new settings (length, input device) apply
+pub fn reload_pre_roll(state: SharedRecordingState, config: &PreRollConfig) -> Result<(), String> {
+    stop_pre_roll(Arc::clone(&state));
+
+    if config.enabled {
+        start_pre_roll(state, config.seconds)?;
+    }
+    Ok(())
+}
+
+/// Start buffering microphone audio while idle (opt-in)
+///
+/// Spawns a background thread with its own input stream that keeps the
+/// last `seconds` of audio until pre-roll is stopped. Does nothing if
+/// pre-roll is already running.
+pub fn start_pre_roll(state: SharedRecordingState, seconds: f64) -> Result<(), String> {
+    let mut state_guard = state.lock().unwrap();
+
+    if state_guard.pre_roll.is_some() {
+        return Ok(());
+    }
+
+    let buffer = Arc::new(Mutex::new(PreRollBuffer::new(seconds)));
+    state_guard.pre_roll = Some(Arc::clone(&buffer));
+    drop(state_guard);
+
+    thread::spawn(move || {
+        if let Err(e) = run_pre_roll_loop(Arc::clone(&buffer), Arc::clone(&state)) {
+            log::error!("Pre-roll capture error: {}", e);
+
+            // Don't report pre-roll as active without a running stream
+            if let Ok(mut state_guard) = state.lock() {
+                if is_current_buffer(&state_guard, &buffer) {
+                    state_guard.pre_roll = None;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop buffering and discard any buffered audio
+pub fn stop_pre_roll(state: SharedRecordingState) {
+    let mut state_guard = state.lock().unwrap();
+
+    if let Some(buffer) = state_guard.pre_roll.take() {
+        if let Ok(mut buffer) = buffer.lock() {
+            buffer.clear();
+        }
+    }
+}
+
+/// Whether the pre-roll stream is currently listening
+pub fn is_pre_roll_active(state: SharedRecordingState) -> bool {
+    state.lock().unwrap().pre_roll.is_some()
+}
+
+/// Take buffered pre-roll audio for a recording that is starting
+pub fn take_pre_roll(state: &RecordingState) -> PreRoll {
+    state
+        .pre_roll
+        .as_ref()
+        .and_then(|buffer| buffer.lock().ok().map(|mut buffer| buffer.take()))
+        .unwrap_or_default()
+}
+
+/// Pre-roll capture loop running in background thread
+///
+/// Buffers audio only while idle; exits once pre-roll is stopped
+fn run_pre_roll_loop(
+    buffer: Arc<Mutex<PreRollBuffer>>,
+    state: SharedRecordingState,
+) -> Result<(), String> {
+    let buffer_for_stream = Arc::clone(&buffer);
+    let state_for_stream = Arc::clone(&state);
+
+    let (stream, format) = open_input_stream(move |data| {
+        let idle = state_for_stream
+            .lock()
+            .map(|state_guard| state_guard.status() == RecordingStatus::Idle)
+            .unwrap_or(false);
+
+        if idle {
+            if let Ok(mut buffer_guard) = buffer_for_stream.lock() {
+                buffer_guard.push(data);
+            }
+        }
+    })?;
+
+    buffer.lock().unwrap().configure(format);
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start pre-roll capture: {}", e))?;
+
+    // Keep the stream alive until pre-roll is stopped (or restarted)
+    loop {
+        thread::sleep(std::time::Duration::from_millis(100));
+
+        if let Ok(state_guard) = state.lock() {
+            if !is_current_buffer(&state_guard, &buffer) {
+                break;
+            }
+        }
+    }
+
+    // Stream will be dropped here, releasing the microphone
+    Ok(())
+}
+
+fn is_current_buffer(state: &RecordingState, buffer: &Arc<Mutex<PreRollBuffer>>) -> bool {
+    state
+        .pre_roll
+        .as_ref()
+        .is_some_and(|current| Arc::ptr_eq(current, buffer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mono audio at `rate` samples per second
+    fn mono(rate: u32) -> AudioFormat {
+        AudioFormat {
+            sample_rate: rate,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_buffer_keeps_most_recent_samples() {
+        let mut buffer = PreRollBuffer::new(1.0);
+        buffer.configure(mono(4));
+
+        buffer.push(&[1.0, 2.0, 3.0]);
+        buffer.push(&[4.0, 5.0, 6.0]);
+
+        let pre_roll = buffer.take();
+        assert_eq!(pre_roll.samples, vec![3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(pre_roll.duration_seconds, 1.0);
+    }
+
+    #[test]
+    fn test_oversized_push_keeps_tail() {
+        let mut buffer = PreRollBuffer::new(0.5);
+        buffer.configure(mono(4));
+
+        buffer.push(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        assert_eq!(buffer.take().samples, vec![4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_take_empties_buffer() {
+        let mut buffer = PreRollBuffer::new(1.0);
+        buffer.configure(mono(4));
+        buffer.push(&[1.0, 2.0]);
+
+        assert_eq!(buffer.take().samples.len(), 2);
+        assert!(buffer.take().samples.is_empty());
+    }
+
+    #[test]
+    fn test_unconfigured_buffer_holds_nothing() {
+        let mut buffer = PreRollBuffer::new(3.0);
+        buffer.push(&[1.0, 2.0]);
+
+        let pre_roll = buffer.take();
+        assert!(pre_roll.samples.is_empty());
+        assert_eq!(pre_roll.duration_seconds, 0.0);
+    }
+
+    #[test]
+    fn test_length_is_capped() {
+        let mut buffer = PreRollBuffer::new(60.0);
+        buffer.configure(mono(1));
+        buffer.push(&[0.0; 30]);
+
+        assert_eq!(buffer.take().samples.len(), MAX_PRE_ROLL_SECONDS as usize);
+    }
+
+    #[test]
+    fn test_length_counts_interleaved_channels() {
+        let mut buffer = PreRollBuffer::new(1.0);
+        let stereo = AudioFormat {
+            sample_rate: 4,
+            channels: 2,
+        };
+        buffer.configure(stereo);
+        buffer.push(&[0.0; 12]);
+
+        let pre_roll = buffer.take();
+        assert_eq!(pre_roll.samples.len(), 8);
+        assert_eq!(pre_roll.duration_seconds, 1.0);
+        assert_eq!(pre_roll.format, Some(stereo));
+    }
+
+    #[test]
+    fn test_take_pre_roll_without_buffer_is_empty() {
+        let state = RecordingState::new();
+        assert!(take_pre_roll(&state).samples.is_empty());
+    }
+}
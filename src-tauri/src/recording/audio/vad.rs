@@ -0,0 +1,104 @@
+use super::writer::read_wav_samples;
+use std::path::Path;
+
+/// Length of the frames speech is detected in
+const FRAME_SECONDS: f64 = 0.03;
+
+/// RMS below which a frame is never speech (about -46 dBFS)
+const MIN_SPEECH_RMS: f32 = 0.005;
+
+/// RMS above which a frame is always speech, however noisy the background
+const MAX_SPEECH_THRESHOLD: f32 = 0.02;
+
+/// How much louder than the background noise a frame must be to be speech
+const NOISE_MARGIN: f32 = 3.0;
+
+/// Pauses up to this long between words count as speech
+const HANGOVER_SECONDS: f64 = 0.3;
+
+/// Seconds of speech in a recording, leaving out silence and longer pauses
+///
+/// Energy-based voice activity detection: a frame is speech when it is
+/// clearly louder than the recording's background noise (its quietest
+/// tenth). `samples_per_second` is the sample rate times the channels.
+pub fn speech_seconds(samples: &[f32], samples_per_second: usize) -> f64 {
+    if samples.is_empty() || samples_per_second == 0 {
+        return 0.0;
+    }
+
+    let frame_len = ((samples_per_second as f64 * FRAME_SECONDS) as usize).max(1);
+    let levels: Vec<f32> = samples.chunks(frame_len).map(frame_rms).collect();
+
+    let mut sorted = levels.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor = sorted[sorted.len() / 10];
+    let threshold = (noise_floor * NOISE_MARGIN).clamp(MIN_SPEECH_RMS, MAX_SPEECH_THRESHOLD);
+
+    let hangover_frames = (HANGOVER_SECONDS / FRAME_SECONDS).round() as usize;
+    let mut frames_since_speech = usize::MAX;
+    let speech_frames = levels
+        .iter()
+        .filter(|&&level| {
+            if level >= threshold {
+                frames_since_speech = 0;
+            } else {
+                frames_since_speech = frames_since_speech.saturating_add(1);
+            }
+            frames_since_speech <= hangover_frames
+        })
+        .count();
+
+    let total_seconds = samples.len() as f64 / samples_per_second as f64;
+    (speech_frames as f64 * frame_len as f64 / samples_per_second as f64).min(total_seconds)
+}
+
+/// Seconds of speech in a WAV file, if it can be read
+pub fn wav_speech_seconds(path: &Path) -> Option<f64> {
+    match read_wav_samples(path) {
+        Ok((samples, samples_per_second)) => Some(speech_seconds(&samples, samples_per_second)),
+        Err(e) => {
+            log::warn!("Failed to detect speech in {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn frame_rms(frame: &[f32]) -> f32 {
+    let sum_of_squares: f32 = frame.iter().map(|&sample| sample * sample).sum();
+    (sum_of_squares / frame.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: usize = 16_000;
+
+    fn tone(seconds: f64, amplitude: f32) -> Vec<f32> {
+        (0..(seconds * RATE as f64) as usize)
+            .map(|i| amplitude * (i as f32 * 0.1).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_counts_speech_between_silences() {
+        let mut samples = tone(1.0, 0.001);
+        samples.extend(tone(2.0, 0.1));
+        samples.extend(tone(2.0, 0.001));
+
+        let speech = speech_seconds(&samples, RATE);
+
+        // The pause after the last word is kept, up to the hangover
+        assert!(
+            (2.0..=2.0 + HANGOVER_SECONDS + 0.05).contains(&speech),
+            "{}",
+            speech
+        );
+    }
+
+    #[test]
+    fn test_silence_has_no_speech() {
+        assert_eq!(speech_seconds(&tone(3.0, 0.0), RATE), 0.0);
+        assert_eq!(speech_seconds(&[], RATE), 0.0);
+    }
+}
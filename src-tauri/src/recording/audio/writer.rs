@@ -1,14 +1,59 @@
-use hound::{WavSpec, WavWriter};
+use hound::{WavReader, WavSpec, WavWriter};
+use std::io::Read;
 use std::path::Path;
 
+/// Sample rate of generated audio (the virtual microphone, benchmarks)
+pub const WAV_SAMPLE_RATE: u32 = 44100;
+
+/// Largest possible WAV file (its chunk sizes are 32-bit)
+pub const MAX_WAV_BYTES: u64 = u32::MAX as u64;
+
+/// Missing audio tolerated before a recording is flagged as having stalled
+const MIN_CAPTURE_GAP_SECONDS: f64 = 2.0;
+const MIN_CAPTURE_GAP_RATIO: f64 = 0.05;
+
+/// Sample rate and channel count of captured audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFormat {
+    pub sample_rate: u32,
+    /// Channels interleaved in the samples
+    pub channels: u16,
+}
+
+impl AudioFormat {
+    /// Number of samples per second (sample rate × channels)
+    pub fn samples_per_second(self) -> usize {
+        self.sample_rate as usize * self.channels as usize
+    }
+
+    /// Length in seconds of `sample_count` interleaved samples
+    pub fn seconds(self, sample_count: usize) -> f64 {
+        sample_count as f64 / self.samples_per_second() as f64
+    }
+}
+
+impl Default for AudioFormat {
+    /// Mono audio at `WAV_SAMPLE_RATE`
+    fn default() -> Self {
+        AudioFormat {
+            sample_rate: WAV_SAMPLE_RATE,
+            channels: 1,
+        }
+    }
+}
+
 /// Write audio samples to a WAV file
 ///
-/// Converts F32 samples to 16-bit signed integer format
-/// with 44.1kHz sample rate and mono channel
-pub fn write_wav_file(samples: &[f32], output_path: &Path) -> Result<(), String> {
+/// Converts F32 samples to 16-bit signed integer format, labelled with the
+/// sample rate and channels they were captured at
+pub fn write_wav_file(
+    samples: &[f32],
+    format: AudioFormat,
+    output_path: &Path,
+) -> Result<(), String> {
     let spec = WavSpec {
-        channels: 1,
-        sample_rate: 44100,
+        channels: format.channels,
+        sample_rate: format.sample_rate,
         bits_per_sample: 16,
         sample_format: hound::SampleFormat::Int,
     };
@@ -30,3 +75,229 @@ pub fn write_wav_file(samples: &[f32], output_path: &Path) -> Result<(), String>
 
     Ok(())
 }
+
+/// Add audio samples to the end of a WAV file written by `write_wav_file`
+pub fn append_wav_samples(samples: &[f32], path: &Path) -> Result<(), String> {
+    let mut writer =
+        WavWriter::append(path).map_err(|e| format!("Failed to open WAV file: {}", e))?;
+
+    for &sample in samples {
+        let amplitude = i16::MAX as f32;
+        writer
+            .write_sample((sample * amplitude) as i16)
+            .map_err(|e| format!("Failed to write sample: {}", e))?;
+    }
+
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize WAV file: {}", e))?;
+
+    Ok(())
+}
+
+/// Length of a WAV file in seconds, from its sample count and sample rate
+pub fn wav_duration(path: &Path) -> Result<f64, String> {
+    let reader = WavReader::open(path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    Ok(reader.duration() as f64 / reader.spec().sample_rate as f64)
+}
+
+/// Samples of a WAV file as f32 (channels interleaved) and the number of
+/// samples per second (sample rate × channels)
+pub fn read_wav_samples(path: &Path) -> Result<(Vec<f32>, usize), String> {
+    let mut reader =
+        WavReader::open(path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    let spec = reader.spec();
+
+    let samples: Result<Vec<f32>, _> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect(),
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|sample| sample.map(|value| value as f32 / scale))
+                .collect()
+        }
+    };
+
+    let samples = samples.map_err(|e| format!("Failed to read WAV samples: {}", e))?;
+    Ok((samples, spec.sample_rate as usize * spec.channels as usize))
+}
+
+/// Copy the audio between `start_seconds` and `end_seconds` of a WAV file
+/// into a new WAV file with the same format
+///
+/// The range is clamped to the recording. Returns the copied length in
+/// seconds.
+pub fn copy_wav_range(
+    source: &Path,
+    destination: &Path,
+    start_seconds: f64,
+    end_seconds: f64,
+) -> Result<f64, String> {
+    let mut reader =
+        WavReader::open(source).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    let spec = reader.spec();
+    let frames = reader.duration();
+    let frame_at = |seconds: f64| ((seconds.max(0.0) * spec.sample_rate as f64) as u32).min(frames);
+    let start = frame_at(start_seconds);
+    let end = frame_at(end_seconds).max(start);
+
+    reader
+        .seek(start)
+        .map_err(|e| format!("Failed to seek in WAV file: {}", e))?;
+    let mut writer = WavWriter::create(destination, spec)
+        .map_err(|e| format!("Failed to create WAV file: {}", e))?;
+    let sample_count = (end - start) as usize * spec.channels as usize;
+
+    let copied: Result<(), hound::Error> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .take(sample_count)
+            .try_for_each(|sample| writer.write_sample(sample?)),
+        hound::SampleFormat::Int => reader
+            .samples::<i32>()
+            .take(sample_count)
+            .try_for_each(|sample| writer.write_sample(sample?)),
+    };
+    copied.map_err(|e| format!("Failed to copy WAV samples: {}", e))?;
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize WAV file: {}", e))?;
+
+    Ok((end - start) as f64 / spec.sample_rate as f64)
+}
+
+/// Fingerprint of a WAV file's audio
+///
+/// A 64-bit FNV-1a hash of the sample format and sample data, so the same
+/// recording matches under another file name or with different metadata
+/// chunks. Stable across app versions, unlike the standard library hasher.
+pub fn audio_fingerprint(path: &Path) -> Result<u64, String> {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let reader = WavReader::open(path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+    let spec = reader.spec();
+    let data_len = reader.len() as u64 * u64::from(spec.bits_per_sample).div_ceil(8);
+
+    let mut hash = FNV_OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    };
+    feed(&spec.channels.to_le_bytes());
+    feed(&spec.sample_rate.to_le_bytes());
+    feed(&spec.bits_per_sample.to_le_bytes());
+
+    // The reader is positioned at the start of the sample data
+    let mut data = reader.into_inner().take(data_len);
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = data
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read WAV file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        feed(&buffer[..read]);
+    }
+
+    Ok(hash)
+}
+
+/// Seconds of recording time missing from the audio, if enough to suggest
+/// the capture stalled
+///
+/// Audio longer than the clock time (e.g. from pre-roll) is not a gap.
+pub fn capture_gap(wall_clock_seconds: f64, audio_seconds: f64) -> Option<f64> {
+    let gap = wall_clock_seconds - audio_seconds;
+    let tolerance = MIN_CAPTURE_GAP_SECONDS.max(wall_clock_seconds * MIN_CAPTURE_GAP_RATIO);
+
+    (gap > tolerance).then_some(gap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    #[test]
+    fn test_capture_gap() {
+        assert_eq!(capture_gap(60.0, 59.5), None);
+        assert_eq!(capture_gap(60.0, 50.0), Some(10.0));
+        // Longer recordings tolerate proportionally more drift
+        assert_eq!(capture_gap(600.0, 580.0), None);
+        // Pre-roll makes the audio longer than the clock time
+        assert_eq!(capture_gap(30.0, 35.0), None);
+    }
+
+    #[test]
+    fn test_wav_duration_from_samples() {
+        let dir = fixtures::temp_dir("wav-duration");
+        let path = dir.join("recording.wav");
+        let samples = vec![0.0; WAV_SAMPLE_RATE as usize * 3 / 2];
+
+        write_wav_file(&samples, AudioFormat::default(), &path).unwrap();
+        assert_eq!(wav_duration(&path).unwrap(), 1.5);
+
+        append_wav_samples(&samples, &path).unwrap();
+        assert_eq!(wav_duration(&path).unwrap(), 3.0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_wav_keeps_captured_rate_and_channels() {
+        let dir = fixtures::temp_dir("wav-stereo");
+        let path = dir.join("recording.wav");
+        let format = AudioFormat {
+            sample_rate: 48000,
+            channels: 2,
+        };
+        // 1.5 seconds of interleaved stereo frames
+        let samples = vec![0.0; 48000 * 2 * 3 / 2];
+        assert_eq!(format.seconds(samples.len()), 1.5);
+
+        write_wav_file(&samples, format, &path).unwrap();
+        assert_eq!(wav_duration(&path).unwrap(), 1.5);
+        let (read, samples_per_second) = read_wav_samples(&path).unwrap();
+        assert_eq!(read.len(), samples.len());
+        assert_eq!(samples_per_second, format.samples_per_second());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_copy_wav_range_clamps_to_recording() {
+        let dir = fixtures::temp_dir("wav-clip");
+        let source = dir.join("source.wav");
+        let clip = dir.join("clip.wav");
+        let samples = vec![0.0; WAV_SAMPLE_RATE as usize * 2];
+        write_wav_file(&samples, AudioFormat::default(), &source).unwrap();
+
+        assert_eq!(copy_wav_range(&source, &clip, 0.5, 1.0).unwrap(), 0.5);
+        assert_eq!(wav_duration(&clip).unwrap(), 0.5);
+        assert_eq!(copy_wav_range(&source, &clip, 1.5, 60.0).unwrap(), 0.5);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_audio_fingerprint_matches_same_samples() {
+        let dir = fixtures::temp_dir("wav-fingerprint");
+        let original = dir.join("a.wav");
+        let renamed = dir.join("b.wav");
+        let other = dir.join("c.wav");
+
+        write_wav_file(&[0.1, 0.2, 0.3], AudioFormat::default(), &original).unwrap();
+        write_wav_file(&[0.1, 0.2, 0.3], AudioFormat::default(), &renamed).unwrap();
+        write_wav_file(&[0.1, 0.2, 0.4], AudioFormat::default(), &other).unwrap();
+
+        let fingerprint = audio_fingerprint(&original).unwrap();
+        assert_eq!(audio_fingerprint(&renamed).unwrap(), fingerprint);
+        assert_ne!(audio_fingerprint(&other).unwrap(), fingerprint);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::recording::config::loader::{
+    config_file_path, load_base_config, local_config_path, save_local_overrides,
+};
+use crate::recording::models::SetupStatus;
+use crate::recording::transcription::download::download_named_model;
+use serde_json::json;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Model downloaded on first run so recordings are transcribed without any
+/// setup, at reduced quality
+const STARTER_MODEL: &str = "ggml-tiny.en.bin";
+
+/// Model the setup wizard suggests in place of the starter model
+const RECOMMENDED_MODEL: &str = "ggml-base.en.bin";
+
+/// Names Whisper.cpp's command-line tool is installed under: `whisper-cli`
+/// as bundled with release builds (`scripts/build-whisper.js`), or
+/// `whisper-cpp` by Homebrew
+const WHISPER_BINARY_NAMES: &[&str] = &["whisper-cli", "whisper-cpp"];
+
+/// Whether transcription is set up, and whether with the starter model
+pub fn setup_status() -> SetupStatus {
+    let config = load_base_config();
+
+    SetupStatus {
+        configured: config.is_ok(),
+        starter_model: config.as_ref().is_ok_and(|config| {
+            Path::new(&config.transcription.model_path).file_name() == Some(STARTER_MODEL.as_ref())
+        }),
+        recommended_model: RECOMMENDED_MODEL.to_string(),
+        error: config.err(),
+    }
+}
+
+/// Configure transcription on first run, when there is no config yet
+///
+/// Uses the Whisper.cpp binary shipped next to the app (or found on the
+/// PATH) and downloads the starter model, writing both paths to this
+/// machine's config.local.json. Returns whether a config was written.
+pub fn ensure_starter_setup() -> Result<bool, String> {
+    if config_file_path()?.exists() || local_config_path()?.exists() {
+        return Ok(false);
+    }
+
+    let whisper_path = find_whisper_binary().ok_or_else(|| {
+        "Whisper.cpp wasn't found next to ThoughtCast or on the PATH. \
+         Please see the README for setup instructions."
+            .to_string()
+    })?;
+    let model_path = download_named_model(STARTER_MODEL)?;
+
+    save_local_overrides(json!({
+        "transcription": {
+            "whisperPath": whisper_path.to_string_lossy(),
+            "modelPath": model_path.to_string_lossy(),
+        }
+    }))?;
+    log::info!(
+        "Set up transcription with {} and the starter model",
+        whisper_path.display()
+    );
+    Ok(true)
+}
+
+/// Whisper.cpp bundled in the app's folder, otherwise the first on the PATH
+fn find_whisper_binary() -> Option<PathBuf> {
+    let app_dir = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let path_dirs = env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    app_dir
+        .into_iter()
+        .chain(path_dirs)
+        .flat_map(|dir| {
+            WHISPER_BINARY_NAMES
+                .iter()
+                .map(move |name| dir.join(format!("{}{}", name, env::consts::EXE_SUFFIX)))
+        })
+        .find(|path| path.is_file())
+}
@@ -1,39 +1,187 @@
-use crate::recording::models::WhisperConfig;
+use crate::recording::config::migration::migrate_layer;
+use crate::recording::config::profiles::{active_profile_name, apply_profile};
+use crate::recording::models::{AppConfig, CONFIG_VERSION};
 use crate::recording::utils::get_storage_dir;
+use crate::recording::utils::storage::test_storage_root;
+use serde_json::Value;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-/// Load the Whisper configuration from the config.json file
+/// Load the app configuration from the config.json file
 ///
-/// Returns an error with helpful setup instructions if the config file
-/// doesn't exist or can't be parsed
-pub fn load_config() -> Result<WhisperConfig, String> {
-    let storage_dir = get_storage_dir()?;
-    let config_file = storage_dir.join("config.json");
+/// The active recording profile (if any) is applied on top of the base
+/// settings. Returns an error with helpful setup instructions if the config
+/// file doesn't exist or can't be parsed
+pub fn load_config() -> Result<AppConfig, String> {
+    let config = load_base_config()?;
 
-    if !config_file.exists() {
+    match active_profile_name(&config) {
+        Some(name) => apply_profile(config.clone(), &name).or_else(|e| {
+            log::warn!("{}; using base settings", e);
+            Ok(config)
+        }),
+        None => Ok(config),
+    }
+}
+
+/// Load the layered config without applying a recording profile
+///
+/// The shared config.json in the storage folder (which may be synced between
+/// machines) is loaded first, then machine-local overrides from
+/// `config.local.json` in the OS config directory are merged on top. This
+/// lets paths such as `whisperPath` and `modelPath` differ per machine.
+/// Legacy (unversioned) files are migrated as they are read.
+pub fn load_base_config() -> Result<AppConfig, String> {
+    let config_file = config_file_path()?;
+    let local_file = local_config_path()?;
+
+    let shared = read_config_layer(&config_file)?;
+    let local = read_config_layer(&local_file)?;
+
+    if shared.is_none() && local.is_none() {
         return Err(format!(
             "Whisper.cpp is not set up. Please create config.json at: {}\n\
             See README for setup instructions.\n\
             Example content:\n\
             {{\n\
-              \"whisperPath\": \"C:\\\\whisper\\\\whisper.exe\",\n\
-              \"modelPath\": \"C:\\\\whisper\\\\models\\\\ggml-base.bin\"\n\
-            }}",
-            config_file.display()
+              \"version\": 1,\n\
+              \"transcription\": {{\n\
+                \"whisperPath\": \"C:\\\\whisper\\\\whisper.exe\",\n\
+                \"modelPath\": \"C:\\\\whisper\\\\models\\\\ggml-base.bin\"\n\
+              }}\n\
+            }}\n\
+            Machine-specific settings can go in: {}",
+            config_file.display(),
+            local_file.display()
         ));
     }
 
-    let content = fs::read_to_string(&config_file)
-        .map_err(|e| format!("Failed to read config file: {}", e))?;
+    let mut merged = shared.unwrap_or_else(|| Value::Object(Default::default()));
+    if let Some(local) = local {
+        merge_layer(&mut merged, local);
+    }
+
+    config_from_value(merged)
+}
+
+fn config_from_value(value: Value) -> Result<AppConfig, String> {
+    serde_json::from_value(value).map_err(|e| format!("Failed to parse config file: {}", e))
+}
+
+/// Save settings changed in-app to the shared config.json
+///
+/// Values that match a machine-local override are left out so machine
+/// specific paths don't leak into the synced config.
+/// The file is always written in the current schema version.
+pub fn save_config(config: &AppConfig) -> Result<(), String> {
+    let mut value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    if let Some(local) = read_config_layer(&local_config_path()?)? {
+        strip_layer(&mut value, &local);
+    }
+    value["version"] = Value::from(CONFIG_VERSION);
+
+    let content = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse config file: {}", e))
+    fs::write(config_file_path()?, content)
+        .map_err(|e| format!("Failed to write config file: {}", e))
+}
+
+/// Merge settings into the machine-local config.local.json, e.g. paths
+/// that only exist on this machine
+pub fn save_local_overrides(overrides: Value) -> Result<(), String> {
+    let local_file = local_config_path()?;
+    let mut value =
+        read_config_layer(&local_file)?.unwrap_or_else(|| Value::Object(Default::default()));
+    merge_layer(&mut value, overrides);
+    value["version"] = Value::from(CONFIG_VERSION);
+
+    let content = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    if let Some(dir) = local_file.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create config folder: {}", e))?;
+    }
+    fs::write(&local_file, content).map_err(|e| format!("Failed to write config file: {}", e))
+}
+
+/// Path to the shared config.json in the storage folder
+pub fn config_file_path() -> Result<PathBuf, String> {
+    Ok(get_storage_dir()?.join("config.json"))
+}
+
+/// Path to the machine-local config overrides (never inside the synced storage folder)
+pub fn local_config_path() -> Result<PathBuf, String> {
+    if let Some(root) = test_storage_root() {
+        return Ok(root.join("local").join("config.local.json"));
+    }
+    let config_dir = dirs::config_local_dir().ok_or("Could not find config directory")?;
+    Ok(config_dir.join("ThoughtCast").join("config.local.json"))
+}
+
+/// Read one config layer, migrated to the current schema version
+///
+/// Returns None if the file doesn't exist
+fn read_config_layer(path: &Path) -> Result<Option<Value>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read config file {}: {}", path.display(), e))?;
+
+    let layer = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse config file {}: {}", path.display(), e))?;
+
+    migrate_layer(layer)
+        .map(Some)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Merge an override layer into the base: objects merge key by key,
+/// any other value in the override replaces the base value
+fn merge_layer(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_layer(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+/// Remove values from `config` that are identical in the override layer
+fn strip_layer(config: &mut Value, overrides: &Value) {
+    if let (Value::Object(config), Value::Object(overrides)) = (config, overrides) {
+        for (key, value) in overrides {
+            match config.get_mut(key) {
+                Some(existing) if existing == value => {
+                    config.remove(key);
+                }
+                Some(existing) => strip_layer(existing, value),
+                None => {}
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse_config(json: &str) -> Result<AppConfig, String> {
+        let layer = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        config_from_value(migrate_layer(layer)?)
+    }
+
     #[test]
     fn test_parse_valid_config() {
         let json = r#"{
@@ -41,13 +189,13 @@ mod tests {
             "modelPath": "/models/ggml-base.bin"
         }"#;
 
-        let config: Result<WhisperConfig, _> = serde_json::from_str(json);
+        let config = parse_config(json);
         assert!(config.is_ok());
 
         let config = config.unwrap();
-        assert_eq!(config.whisper_path, "/usr/local/bin/whisper-cli");
-        assert_eq!(config.model_path, "/models/ggml-base.bin");
-        assert_eq!(config.voice_notes_dir, None);
+        assert_eq!(config.transcription.whisper_path, "/usr/local/bin/whisper-cli");
+        assert_eq!(config.transcription.model_path, "/models/ggml-base.bin");
+        assert_eq!(config.storage.voice_notes_dir, None);
     }
 
     #[test]
@@ -58,11 +206,11 @@ mod tests {
             "voiceNotesDir": "/custom/notes"
         }"#;
 
-        let config: Result<WhisperConfig, _> = serde_json::from_str(json);
+        let config = parse_config(json);
         assert!(config.is_ok());
 
         let config = config.unwrap();
-        assert_eq!(config.voice_notes_dir, Some("/custom/notes".to_string()));
+        assert_eq!(config.storage.voice_notes_dir, Some("/custom/notes".to_string()));
     }
 
     #[test]
@@ -72,7 +220,7 @@ mod tests {
             "modelPath": "/models/ggml-base.bin"
         }"#;
 
-        let config: Result<WhisperConfig, _> = serde_json::from_str(json);
+        let config = parse_config(json);
         assert!(config.is_err());
     }
 
@@ -82,7 +230,7 @@ mod tests {
             "whisperPath": "/usr/local/bin/whisper-cli"
         }"#;
 
-        let config: Result<WhisperConfig, _> = serde_json::from_str(json);
+        let config = parse_config(json);
         assert!(config.is_err());
     }
 
@@ -93,12 +241,12 @@ mod tests {
             "modelPath": "C:\\whisper\\models\\ggml-base.bin"
         }"#;
 
-        let config: Result<WhisperConfig, _> = serde_json::from_str(json);
+        let config = parse_config(json);
         assert!(config.is_ok());
 
         let config = config.unwrap();
-        assert_eq!(config.whisper_path, "C:\\whisper\\whisper.exe");
-        assert_eq!(config.model_path, "C:\\whisper\\models\\ggml-base.bin");
+        assert_eq!(config.transcription.whisper_path, "C:\\whisper\\whisper.exe");
+        assert_eq!(config.transcription.model_path, "C:\\whisper\\models\\ggml-base.bin");
     }
 
     #[test]
@@ -109,7 +257,71 @@ mod tests {
             "extraField": "should be ignored"
         }"#;
 
-        let config: Result<WhisperConfig, _> = serde_json::from_str(json);
+        let config = parse_config(json);
         assert!(config.is_ok());
     }
+
+    #[test]
+    fn test_local_layer_overrides_machine_paths() {
+        let mut config = serde_json::json!({
+            "whisperPath": "/usr/local/bin/whisper-cli",
+            "modelPath": "/models/ggml-base.bin",
+            "autoCopy": { "mode": "raw" }
+        });
+        let local = serde_json::json!({
+            "whisperPath": "C:\\whisper\\whisper.exe",
+            "modelPath": "C:\\whisper\\models\\ggml-base.bin"
+        });
+
+        merge_layer(&mut config, local);
+        let config = config_from_value(migrate_layer(config).unwrap()).unwrap();
+
+        assert_eq!(config.transcription.whisper_path, "C:\\whisper\\whisper.exe");
+        assert_eq!(config.transcription.model_path, "C:\\whisper\\models\\ggml-base.bin");
+        assert_eq!(config.integrations.auto_copy.mode, crate::recording::models::AutoCopyMode::Raw);
+    }
+
+    #[test]
+    fn test_merge_layer_merges_nested_objects() {
+        let mut config = serde_json::json!({
+            "preRoll": { "enabled": true, "seconds": 5.0 },
+            "modelRules": [{ "maxDurationSeconds": 30, "modelPath": "/a.bin" }]
+        });
+
+        merge_layer(
+            &mut config,
+            serde_json::json!({
+                "preRoll": { "seconds": 2.0 },
+                "modelRules": []
+            }),
+        );
+
+        assert_eq!(config["preRoll"]["enabled"], true);
+        assert_eq!(config["preRoll"]["seconds"], 2.0);
+        assert_eq!(config["modelRules"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_strip_layer_keeps_shared_and_changed_values() {
+        let mut config = serde_json::json!({
+            "whisperPath": "/local/whisper-cli",
+            "modelPath": "/models/ggml-small.bin",
+            "preRoll": { "enabled": true, "seconds": 3.0 }
+        });
+        let local = serde_json::json!({
+            "whisperPath": "/local/whisper-cli",
+            "modelPath": "/models/ggml-base.bin",
+            "preRoll": { "enabled": true }
+        });
+
+        strip_layer(&mut config, &local);
+
+        assert_eq!(
+            config,
+            serde_json::json!({
+                "modelPath": "/models/ggml-small.bin",
+                "preRoll": { "seconds": 3.0 }
+            })
+        );
+    }
 }
@@ -0,0 +1,132 @@
+use crate::recording::models::CONFIG_VERSION;
+use serde_json::{Map, Value};
+
+/// Where each setting of the legacy flat config (version 0) now lives
+const LEGACY_KEYS: &[(&str, &str)] = &[
+    ("whisperPath", "transcription"),
+    ("modelPath", "transcription"),
+    ("modelRules", "transcription"),
+    ("draftModelPath", "transcription"),
+    ("acceleration", "transcription"),
+    ("inputDevice", "audio"),
+    ("preRoll", "audio"),
+    ("emptyRecordingGuard", "audio"),
+    ("segmentation", "audio"),
+    ("voiceNotesDir", "storage"),
+    ("sessionIdScheme", "storage"),
+    ("keepAudio", "storage"),
+    ("autoCopy", "integrations"),
+    ("calendar", "integrations"),
+];
+
+/// Bring one config layer up to the current schema version
+///
+/// Layers may be partial (e.g. machine-local overrides), so legacy settings
+/// are moved into their sections key by key and anything else is left as is.
+pub fn migrate_layer(layer: Value) -> Result<Value, String> {
+    let Value::Object(mut layer) = layer else {
+        return Err("Config file must contain a JSON object".to_string());
+    };
+
+    let version = match layer.get("version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format!("Invalid config version: {}", version))?,
+    };
+
+    if version > u64::from(CONFIG_VERSION) {
+        return Err(format!(
+            "Config file version {} is newer than this app supports ({}). \
+             Please update ThoughtCast.",
+            version, CONFIG_VERSION
+        ));
+    }
+
+    if version == 0 {
+        move_legacy_keys(&mut layer);
+    }
+
+    layer.insert("version".to_string(), Value::from(CONFIG_VERSION));
+    Ok(Value::Object(layer))
+}
+
+/// Version 0 → 1: group the flat settings into sections
+fn move_legacy_keys(layer: &mut Map<String, Value>) {
+    for (key, section) in LEGACY_KEYS {
+        let Some(value) = layer.remove(*key) else {
+            continue;
+        };
+
+        let section = layer
+            .entry(section.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        if let Value::Object(section) = section {
+            section.entry(key.to_string()).or_insert(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let legacy = json!({
+            "whisperPath": "/usr/local/bin/whisper-cli",
+            "modelPath": "/models/ggml-base.bin",
+            "voiceNotesDir": "/notes",
+            "autoCopy": { "mode": "off" },
+            "preRoll": { "enabled": true },
+            "schedules": []
+        });
+
+        assert_eq!(
+            migrate_layer(legacy).unwrap(),
+            json!({
+                "version": CONFIG_VERSION,
+                "transcription": {
+                    "whisperPath": "/usr/local/bin/whisper-cli",
+                    "modelPath": "/models/ggml-base.bin"
+                },
+                "audio": { "preRoll": { "enabled": true } },
+                "storage": { "voiceNotesDir": "/notes" },
+                "integrations": { "autoCopy": { "mode": "off" } },
+                "schedules": []
+            })
+        );
+    }
+
+    #[test]
+    fn test_current_config_unchanged() {
+        let current = json!({
+            "version": 1,
+            "transcription": { "whisperPath": "/bin/whisper", "modelPath": "/m.bin" }
+        });
+
+        assert_eq!(migrate_layer(current.clone()).unwrap(), current);
+    }
+
+    #[test]
+    fn test_partial_layer_without_version() {
+        // A machine-local override written in the new layout but without a version
+        let layer = json!({ "transcription": { "modelPath": "/m.bin" }, "whisperPath": "/w" });
+
+        assert_eq!(
+            migrate_layer(layer).unwrap(),
+            json!({
+                "version": CONFIG_VERSION,
+                "transcription": { "modelPath": "/m.bin", "whisperPath": "/w" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_newer_version_is_error() {
+        let result = migrate_layer(json!({ "version": CONFIG_VERSION + 1 }));
+        assert!(result.unwrap_err().contains("newer"));
+    }
+}
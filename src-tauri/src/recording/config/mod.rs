@@ -1,3 +1,10 @@
+pub mod first_run;
 pub mod loader;
+pub mod migration;
+pub mod profiles;
+pub mod watcher;
 
-pub use loader::load_config;
+pub use first_run::{ensure_starter_setup, setup_status};
+pub use loader::{load_base_config, load_config, save_config, save_local_overrides};
+pub use profiles::{active_profile_name, select_profile};
+pub use watcher::{reload_config, start_config_watcher};
@@ -0,0 +1,133 @@
+use crate::recording::models::{AppConfig, RecordingProfile};
+use std::sync::Mutex;
+
+/// Profile selected at runtime, overriding `activeProfile` from the config
+static SELECTED_PROFILE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Select the active recording profile for this run of the app
+///
+/// `None` reverts to the config's `activeProfile`
+pub fn select_profile(config: &AppConfig, name: Option<String>) -> Result<(), String> {
+    if let Some(name) = &name {
+        if !config.profiles.contains_key(name) {
+            return Err(format!("Unknown profile: {}", name));
+        }
+    }
+
+    *SELECTED_PROFILE.lock().unwrap() = name;
+    Ok(())
+}
+
+/// Name of the profile currently in effect, if any
+pub fn active_profile_name(config: &AppConfig) -> Option<String> {
+    SELECTED_PROFILE
+        .lock()
+        .unwrap()
+        .clone()
+        .or_else(|| config.active_profile.clone())
+}
+
+/// Apply the named profile's settings on top of the base config
+///
+/// Settings the profile leaves unset keep their base values
+pub fn apply_profile(config: AppConfig, name: &str) -> Result<AppConfig, String> {
+    let profile = config
+        .profiles
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Unknown profile: {}", name))?;
+
+    Ok(merge_profile(config, profile))
+}
+
+fn merge_profile(mut config: AppConfig, profile: RecordingProfile) -> AppConfig {
+    let transcription = &mut config.transcription;
+    if let Some(model_path) = profile.model_path {
+        transcription.model_path = model_path;
+    }
+    if let Some(model_rules) = profile.model_rules {
+        transcription.model_rules = model_rules;
+    }
+    if profile.draft_model_path.is_some() {
+        transcription.draft_model_path = profile.draft_model_path;
+    }
+    if profile.postprocess.is_some() {
+        transcription.postprocess = profile.postprocess;
+    }
+
+    if profile.input_device.is_some() {
+        config.audio.input_device = profile.input_device;
+    }
+    if let Some(segmentation) = profile.segmentation {
+        config.audio.segmentation = segmentation;
+    }
+    if let Some(keep_audio) = profile.keep_audio {
+        config.storage.keep_audio = keep_audio;
+    }
+    if let Some(auto_copy) = profile.auto_copy {
+        config.integrations.auto_copy = auto_copy;
+    }
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::models::AutoCopyMode;
+
+    fn config_with_profiles() -> AppConfig {
+        serde_json::from_str(
+            r#"{
+                "version": 1,
+                "transcription": {
+                    "whisperPath": "/path/to/whisper",
+                    "modelPath": "/models/ggml-medium.bin"
+                },
+                "integrations": { "autoCopy": { "mode": "raw" } },
+                "profiles": {
+                    "quick-note": {
+                        "modelPath": "/models/ggml-tiny.en.bin",
+                        "keepAudio": false
+                    },
+                    "meeting": {
+                        "inputDevice": "USB Conference Mic",
+                        "autoCopy": { "mode": "off" },
+                        "segmentation": { "everyMinutes": 15 }
+                    }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_profile_overrides_set_fields() {
+        let config = apply_profile(config_with_profiles(), "quick-note").unwrap();
+
+        assert_eq!(config.transcription.model_path, "/models/ggml-tiny.en.bin");
+        assert!(!config.storage.keep_audio);
+        // Unset in the profile: base value kept
+        assert_eq!(config.integrations.auto_copy.mode, AutoCopyMode::Raw);
+        assert_eq!(config.audio.input_device, None);
+    }
+
+    #[test]
+    fn test_apply_meeting_profile() {
+        let config = apply_profile(config_with_profiles(), "meeting").unwrap();
+
+        assert_eq!(
+            config.audio.input_device,
+            Some("USB Conference Mic".to_string())
+        );
+        assert_eq!(config.integrations.auto_copy.mode, AutoCopyMode::Off);
+        assert_eq!(config.audio.segmentation.every_minutes, Some(15));
+        assert_eq!(config.transcription.model_path, "/models/ggml-medium.bin");
+        assert!(config.storage.keep_audio);
+    }
+
+    #[test]
+    fn test_apply_unknown_profile_is_error() {
+        assert!(apply_profile(config_with_profiles(), "podcast").is_err());
+    }
+}
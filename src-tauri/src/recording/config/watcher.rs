@@ -0,0 +1,122 @@
+use crate::recording::audio::reload_pre_roll;
+use crate::recording::config::loader::{config_file_path, load_config, local_config_path};
+use crate::recording::models::AppConfig;
+use crate::recording::state::SharedRecordingState;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the config files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Modification times of the shared and machine-local config layers
+type ConfigFingerprint = (Option<SystemTime>, Option<SystemTime>);
+
+/// Config as last applied to the running app
+struct AppliedConfig {
+    fingerprint: ConfigFingerprint,
+    config: Option<AppConfig>,
+}
+
+static APPLIED: Mutex<Option<AppliedConfig>> = Mutex::new(None);
+
+/// Watch config.json (and config.local.json) and hot-reload changes
+///
+/// Invokes the callback with the new config whenever the effective settings
+/// change, after reloading affected subsystems.
+pub fn start_config_watcher<F>(state: SharedRecordingState, on_change: F)
+where
+    F: Fn(AppConfig) + Send + 'static,
+{
+    // Record the config the app started with so it isn't reported as a change
+    if let Ok(fingerprint) = config_fingerprint() {
+        *APPLIED.lock().unwrap() = Some(AppliedConfig {
+            fingerprint,
+            config: load_config().ok(),
+        });
+    }
+
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let Ok(fingerprint) = config_fingerprint() else {
+            continue;
+        };
+        let unchanged = APPLIED
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|applied| applied.fingerprint == fingerprint);
+
+        if !unchanged {
+            if let Some(config) = reload_config(Arc::clone(&state)) {
+                on_change(config);
+            }
+        }
+    });
+}
+
+/// Re-read the config and reload subsystems whose settings changed
+///
+/// Returns the new config if the effective settings differ from those last
+/// applied. Used by the watcher and after settings are changed in-app.
+pub fn reload_config(state: SharedRecordingState) -> Option<AppConfig> {
+    let fingerprint = config_fingerprint().ok()?;
+
+    let mut applied = APPLIED.lock().unwrap();
+    let previous = applied.take().and_then(|applied| applied.config);
+
+    let config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            // Keep running with the previous settings until the file is fixed
+            log::warn!("Config not reloaded: {}", e);
+            *applied = Some(AppliedConfig {
+                fingerprint,
+                config: previous,
+            });
+            return None;
+        }
+    };
+
+    *applied = Some(AppliedConfig {
+        fingerprint,
+        config: Some(config.clone()),
+    });
+    drop(applied);
+
+    if previous.as_ref() == Some(&config) {
+        return None;
+    }
+
+    // Recording, transcription and clipboard settings are read from the config
+    // on each use; only the long-running pre-roll stream needs restarting
+    let pre_roll_changed = match &previous {
+        Some(previous) => {
+            previous.audio.pre_roll != config.audio.pre_roll
+                || previous.audio.input_device != config.audio.input_device
+                || previous.audio.input_devices != config.audio.input_devices
+        }
+        None => true,
+    };
+    if pre_roll_changed {
+        if let Err(e) = reload_pre_roll(state, &config.audio.pre_roll) {
+            log::warn!("Pre-roll not restarted: {}", e);
+        }
+    }
+
+    Some(config)
+}
+
+fn config_fingerprint() -> Result<ConfigFingerprint, String> {
+    Ok((
+        modified_time(&config_file_path()?),
+        modified_time(&local_config_path()?),
+    ))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,152 @@
+use crate::recording::audio::check_input_device;
+use crate::recording::config::load_config;
+use crate::recording::models::{HealthCheck, TranscriptionConfig};
+use crate::recording::transcription::engine::check_model_language;
+use crate::recording::transcription::integrity::verify_model;
+use crate::recording::utils::{check_clipboard_access, get_storage_dir};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+/// Check that everything a recording depends on is working
+///
+/// Covers the microphone, the Whisper.cpp binary and model, storage and the
+/// clipboard, so problems show up in settings rather than mid-recording.
+/// Every check runs even if an earlier one fails.
+pub fn run_health_check() -> Vec<HealthCheck> {
+    let transcription = load_config().map(|config| config.transcription);
+
+    vec![
+        health_check(
+            "microphone",
+            check_input_device().map(|name| format!("Using {}", name)),
+        ),
+        health_check(
+            "whisper",
+            transcription
+                .clone()
+                .and_then(|config| check_whisper_binary(&config)),
+        ),
+        health_check(
+            "model",
+            transcription
+                .clone()
+                .and_then(|config| check_model(&config)),
+        ),
+        health_check(
+            "language",
+            transcription.and_then(|config| check_model_languages(&config)),
+        ),
+        health_check("storage", check_storage_writable()),
+        health_check(
+            "clipboard",
+            check_clipboard_access().map(|_| "Clipboard is accessible".to_string()),
+        ),
+    ]
+}
+
+fn health_check(name: &str, result: Result<String, String>) -> HealthCheck {
+    let (passed, message) = match result {
+        Ok(message) => (true, message),
+        Err(message) => (false, message),
+    };
+
+    HealthCheck {
+        name: name.to_string(),
+        passed,
+        message,
+    }
+}
+
+/// Run `whisper --help` to confirm the binary starts
+fn check_whisper_binary(config: &TranscriptionConfig) -> Result<String, String> {
+    let output = Command::new(&config.whisper_path)
+        .arg("--help")
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", config.whisper_path, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} --help exited with {}",
+            config.whisper_path, output.status
+        ));
+    }
+
+    Ok(format!("{} runs", config.whisper_path))
+}
+
+/// Check the model file exists, has a Whisper model header and matches its
+/// known checksum
+fn check_model(config: &TranscriptionConfig) -> Result<String, String> {
+    let mut header = [0u8; 4];
+    File::open(&config.model_path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map_err(|e| format!("Failed to read model {}: {}", config.model_path, e))?;
+
+    let format = model_format(&header)
+        .ok_or_else(|| format!("{} is not a Whisper model file", config.model_path))?;
+    verify_model(Path::new(&config.model_path))?;
+
+    let name = Path::new(&config.model_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(format!("{} ({})", name, format))
+}
+
+/// Check every model in use (default, per-length rules and draft) suits the
+/// configured language
+fn check_model_languages(config: &TranscriptionConfig) -> Result<String, String> {
+    let language = config.language.as_deref();
+    std::iter::once(&config.model_path)
+        .chain(config.model_rules.iter().map(|rule| &rule.model_path))
+        .chain(&config.draft_model_path)
+        .try_for_each(|model_path| check_model_language(model_path, language))?;
+
+    Ok(match language {
+        Some(language) => format!("Models support the language \"{}\"", language),
+        None => "Models support English".to_string(),
+    })
+}
+
+/// Identify the model file format from its magic bytes
+fn model_format(header: &[u8]) -> Option<&'static str> {
+    match header {
+        // GGML_FILE_MAGIC (0x67676d6c) stored little-endian
+        b"lmgg" => Some("ggml"),
+        b"GGUF" => Some("gguf"),
+        _ => None,
+    }
+}
+
+/// Write and remove a probe file in the storage directory
+fn check_storage_writable() -> Result<String, String> {
+    let storage_dir = get_storage_dir()?;
+    let probe = storage_dir.join(".health-check");
+
+    fs::write(&probe, b"ok").map_err(|e| format!("Storage is not writable: {}", e))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(format!("{} is writable", storage_dir.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_format() {
+        assert_eq!(model_format(b"lmgg"), Some("ggml"));
+        assert_eq!(model_format(b"GGUF"), Some("gguf"));
+        assert_eq!(model_format(b"RIFF"), None);
+    }
+
+    #[test]
+    fn test_failed_check_keeps_reason() {
+        let check = health_check("model", Err("Model file is missing".to_string()));
+
+        assert!(!check.passed);
+        assert_eq!(check.message, "Model file is missing");
+    }
+}
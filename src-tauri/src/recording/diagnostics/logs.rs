@@ -0,0 +1,120 @@
+use crate::recording::utils::get_local_state_dir;
+use std::fs;
+use std::path::PathBuf;
+
+/// Log file name (without extension); rotated files get a date suffix
+pub const LOG_FILE_NAME: &str = "thoughtcast";
+
+/// Size at which the log file is rotated
+pub const MAX_LOG_FILE_BYTES: u128 = 1_000_000;
+
+/// Number of rotated log files kept besides the current one
+pub const KEPT_LOG_FILES: usize = 5;
+
+/// Lines returned when no count is requested
+pub const DEFAULT_LOG_LINES: usize = 200;
+
+/// Upper bound for `read_recent_logs` so a bug report stays attachable
+const MAX_LOG_LINES: usize = 5000;
+
+/// Directory holding the log files, in the machine-local state folder so
+/// they aren't synced along with the storage folder
+pub fn get_log_dir() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join("logs"))
+}
+
+/// Read the last `lines` log lines, oldest first
+///
+/// Continues into rotated files when the current one is shorter than requested
+pub fn read_recent_logs(lines: usize) -> Result<Vec<String>, String> {
+    let lines = lines.min(MAX_LOG_LINES);
+    let log_dir = get_log_dir()?;
+
+    if !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let names = fs::read_dir(&log_dir)
+        .map_err(|e| format!("Failed to read log directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+
+    let mut recent: Vec<String> = Vec::new();
+    for name in newest_log_files_first(names) {
+        if recent.len() >= lines {
+            break;
+        }
+
+        let content = fs::read_to_string(log_dir.join(&name))
+            .map_err(|e| format!("Failed to read log file {}: {}", name, e))?;
+
+        let mut older = last_lines(&content, lines - recent.len());
+        older.append(&mut recent);
+        recent = older;
+    }
+
+    Ok(recent)
+}
+
+/// Order log files newest first: the current file, then rotated files
+/// (`thoughtcast_2024-11-02_15-30-00.log`) by descending date
+fn newest_log_files_first(names: Vec<String>) -> Vec<String> {
+    let current = format!("{}.log", LOG_FILE_NAME);
+    let rotated_prefix = format!("{}_", LOG_FILE_NAME);
+
+    let mut rotated: Vec<String> = names
+        .iter()
+        .filter(|name| name.starts_with(&rotated_prefix) && name.ends_with(".log"))
+        .cloned()
+        .collect();
+    rotated.sort_by(|a, b| b.cmp(a));
+
+    names
+        .into_iter()
+        .filter(|name| *name == current)
+        .chain(rotated)
+        .collect()
+}
+
+/// Last `count` non-empty lines of a log file, oldest first
+fn last_lines(content: &str, count: usize) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().filter(|line| !line.is_empty()).collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newest_log_files_first() {
+        let names = vec![
+            "thoughtcast_2024-11-01_09-00-00.log".to_string(),
+            "other.txt".to_string(),
+            "thoughtcast.log".to_string(),
+            "thoughtcast_2024-11-02_15-30-00.log".to_string(),
+        ];
+
+        assert_eq!(
+            newest_log_files_first(names),
+            vec![
+                "thoughtcast.log",
+                "thoughtcast_2024-11-02_15-30-00.log",
+                "thoughtcast_2024-11-01_09-00-00.log",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_lines() {
+        let content = "[INFO] one\n[WARN] two\n\n[ERROR] three\n";
+
+        assert_eq!(last_lines(content, 2), vec!["[WARN] two", "[ERROR] three"]);
+        assert_eq!(last_lines(content, 10).len(), 3);
+        assert!(last_lines(content, 0).is_empty());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod health;
+pub mod logs;
+
+pub use health::run_health_check;
+pub use logs::{
+    get_log_dir, read_recent_logs, DEFAULT_LOG_LINES, KEPT_LOG_FILES, LOG_FILE_NAME,
+    MAX_LOG_FILE_BYTES,
+};
@@ -0,0 +1,149 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+#[cfg(test)]
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Mutex,
+};
+
+/// File operations used by session storage, replaceable in tests
+pub trait Storage: Send + Sync {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()>;
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    fn exists(&self, path: &Path) -> bool;
+    /// Paths of the entries directly inside a directory
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+}
+
+/// The real filesystem
+pub struct DiskStorage;
+
+impl Storage for DiskStorage {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        fs::write(path, contents)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        Ok(fs::read_dir(path)?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect())
+    }
+}
+
+/// Files and folders kept in memory, for tests without real file IO
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStorage {
+    files: Mutex<BTreeMap<PathBuf, String>>,
+    dirs: Mutex<BTreeSet<PathBuf>>,
+}
+
+#[cfg(test)]
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Paths of every stored file
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.files.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found", path.display()),
+        )
+    }
+}
+
+#[cfg(test)]
+impl Storage for MemoryStorage {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            self.dirs.lock().unwrap().insert(parent.to_path_buf());
+        }
+        self.files
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), contents.to_string());
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let contents = self
+            .files
+            .lock()
+            .unwrap()
+            .remove(from)
+            .ok_or_else(|| Self::not_found(from))?;
+        self.write(to, &contents)
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.files
+            .lock()
+            .unwrap()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| Self::not_found(path))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.dirs.lock().unwrap().insert(path.to_path_buf());
+        Ok(())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.files.lock().unwrap().contains_key(path) || self.dirs.lock().unwrap().contains(path)
+    }
+
+    fn list_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        if !self.dirs.lock().unwrap().contains(path) {
+            return Err(Self::not_found(path));
+        }
+
+        Ok(self
+            .files
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|file| file.parent() == Some(path))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::recording::models::Session;
+use std::fs;
+use std::path::PathBuf;
+
+/// A transcribed session recorded 2024-11-02 15:30 UTC
+///
+/// Tests override the fields they care about with struct update syntax,
+/// e.g. `Session { duration: 45.0, ..session("a") }`.
+pub fn session(id: &str) -> Session {
+    Session {
+        id: id.to_string(),
+        timestamp: "2024-11-02T15:30:00Z".to_string(),
+        audio_path: format!("audio/{}.wav", id),
+        duration: 10.0,
+        transcript_path: format!("text/{}.txt", id),
+        ..Session::default()
+    }
+}
+
+/// An empty directory for one test, emptied again if a previous run left it
+///
+/// `name` must be unique across tests, since they run in parallel.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("thoughtcast-test-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,57 @@
+use crate::recording::models::HotkeyConfig;
+
+/// Accelerator of the media play/pause key, which Bluetooth and wired
+/// headset buttons also send
+const MEDIA_PLAY_PAUSE: &str = "MediaPlayPause";
+
+/// Global shortcuts that start or stop recording
+pub fn recording_hotkeys(config: &HotkeyConfig) -> Vec<String> {
+    let mut hotkeys: Vec<String> = config
+        .toggle_recording
+        .iter()
+        .map(|accelerator| accelerator.trim())
+        .filter(|accelerator| !accelerator.is_empty())
+        .map(String::from)
+        .collect();
+
+    if config.media_play_pause {
+        hotkeys.push(MEDIA_PLAY_PAUSE.to_string());
+    }
+
+    hotkeys
+}
+
+/// Global shortcut that copies the latest transcript, if one is set
+pub fn copy_transcript_hotkey(config: &HotkeyConfig) -> Option<String> {
+    config
+        .copy_last_transcript
+        .as_deref()
+        .map(str::trim)
+        .filter(|accelerator| !accelerator.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_hotkeys_by_default() {
+        assert!(recording_hotkeys(&HotkeyConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_shortcut_and_media_key() {
+        let config = HotkeyConfig {
+            toggle_recording: Some("CmdOrCtrl+Shift+Space".to_string()),
+            media_play_pause: true,
+            copy_last_transcript: Some(" ".to_string()),
+        };
+
+        assert_eq!(
+            recording_hotkeys(&config),
+            vec!["CmdOrCtrl+Shift+Space", "MediaPlayPause"]
+        );
+        assert_eq!(copy_transcript_hotkey(&config), None);
+    }
+}
@@ -0,0 +1,813 @@
+use crate::recording::config::load_config;
+use crate::recording::models::CalendarEvent;
+use chrono::{
+    DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Occurrences of recurring events are listed from this many days ago...
+const RECURRENCE_PAST_DAYS: i64 = 1;
+
+/// ...to this many days ahead
+const RECURRENCE_AHEAD_DAYS: i64 = 31;
+
+/// Most days, weeks, months or years a recurrence rule is followed for
+const MAX_RECURRENCE_PERIODS: u32 = 5000;
+
+/// Load events from the configured ICS calendar file, with the occurrences
+/// of recurring events around now
+///
+/// Returns an empty list when no calendar is configured
+pub fn load_calendar_events() -> Result<Vec<CalendarEvent>, String> {
+    let calendar = load_config()?.integrations.calendar;
+    match calendar.ics_path {
+        Some(path) => Ok(expand_events(&read_ics_file(Path::new(&path))?, Utc::now())),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Read and parse an ICS file
+pub fn read_ics_file(path: &Path) -> Result<Vec<IcsEvent>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read calendar file {}: {}", path.display(), e))?;
+    Ok(parse_ics(&content))
+}
+
+/// A calendar file kept parsed between checks, re-read only when it changes
+#[derive(Default)]
+pub struct CalendarFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    events: Vec<IcsEvent>,
+    /// Last error logged, so a missing or unreadable file is reported once
+    /// rather than on every check
+    error: Option<String>,
+}
+
+impl CalendarFile {
+    /// Events in the ICS file at `path`, read again only when the path or
+    /// the file's modification time changed since the last call
+    pub fn events(&mut self, path: &Path) -> &[IcsEvent] {
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|e| format!("Failed to read calendar file {}: {}", path.display(), e));
+
+        let unchanged = self.path == path && modified.as_ref().ok() == self.modified.as_ref();
+        if !unchanged {
+            match modified.and_then(|modified| Ok((modified, read_ics_file(path)?))) {
+                Ok((modified, events)) => {
+                    self.events = events;
+                    self.modified = Some(modified);
+                    self.error = None;
+                }
+                Err(e) => {
+                    if self.error.as_ref() != Some(&e) {
+                        log::warn!("{}", e);
+                        self.error = Some(e);
+                    }
+                    self.events.clear();
+                    self.modified = None;
+                }
+            }
+            self.path = path.to_path_buf();
+        }
+
+        &self.events
+    }
+}
+
+/// Find a calendar event by its UID
+pub fn find_calendar_event(uid: &str) -> Result<CalendarEvent, String> {
+    load_calendar_events()?
+        .into_iter()
+        .find(|event| event.uid == uid)
+        .ok_or_else(|| format!("Calendar event not found: {}", uid))
+}
+
+/// First event starting in the local-time window `(since, until]`
+pub fn event_starting_between(
+    events: &[CalendarEvent],
+    since: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Option<&CalendarEvent> {
+    events.iter().find(|event| {
+        chrono::DateTime::parse_from_rfc3339(&event.start)
+            .map(|start| start.with_timezone(&Local).naive_local())
+            .is_ok_and(|start| start > since && start <= until)
+    })
+}
+
+/// A VEVENT as written in the calendar file, before its recurrences are
+/// expanded
+#[derive(Debug, Clone)]
+pub struct IcsEvent {
+    uid: String,
+    title: String,
+    location: Option<String>,
+    /// Start as written, in `zone`; recurrences keep its wall-clock time
+    start: NaiveDateTime,
+    zone: Zone,
+    /// Time from start to end (`DTEND`)
+    length: Option<Duration>,
+    rule: Option<RecurrenceRule>,
+    /// Occurrences removed from the recurrence (`EXDATE`)
+    exceptions: Vec<DateTime<Utc>>,
+    /// Occurrence of a recurring event this event replaces (`RECURRENCE-ID`)
+    recurrence_id: Option<DateTime<Utc>>,
+}
+
+/// Time zone of an ICS date-time
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Zone {
+    /// `...Z`
+    Utc,
+    /// No zone given, or a `TZID` that isn't an IANA name; local time
+    Floating,
+    Named(Tz),
+}
+
+/// Supported part of an `RRULE`
+#[derive(Debug, Clone, PartialEq)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<DateTime<Utc>>,
+    /// `BYDAY` weekdays, with the ordinal within the month (`2TU`, `-1FR`)
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// Parse VEVENTs from ICS (iCalendar) content
+///
+/// Supports UTC (`...Z`), local and `TZID` (IANA names) start/end times;
+/// other `TZID`s are interpreted as local time. All-day events are skipped
+/// (they aren't meetings to record).
+pub fn parse_ics(content: &str) -> Vec<IcsEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in unfold_lines(content) {
+        match line.as_str() {
+            "BEGIN:VEVENT" => current = Some(Vec::new()),
+            "END:VEVENT" => {
+                if let Some(event) = current.take().and_then(|props| build_event(&props)) {
+                    events.push(event);
+                }
+            }
+            _ => {
+                if let (Some(props), Some((name, value))) = (current.as_mut(), line.split_once(':'))
+                {
+                    props.push((name.to_string(), value.to_string()));
+                }
+            }
+        }
+    }
+
+    events
+}
+
+/// Calendar events, with each recurring event expanded into its
+/// occurrences from a day before `now` to a month after
+///
+/// `DAILY`, `WEEKLY`, `MONTHLY` and `YEARLY` rules are expanded with
+/// `INTERVAL`, `COUNT`, `UNTIL`, `BYDAY` and `BYMONTHDAY`; `EXDATE`s are
+/// left out and occurrences moved with `RECURRENCE-ID` are replaced. Events
+/// with other rules list only their first occurrence. Occurrence UIDs are
+/// the event's UID followed by `/` and the occurrence's UTC start.
+pub fn expand_events(events: &[IcsEvent], now: DateTime<Utc>) -> Vec<CalendarEvent> {
+    let from = now - Duration::days(RECURRENCE_PAST_DAYS);
+    let to = now + Duration::days(RECURRENCE_AHEAD_DAYS);
+    let mut expanded = Vec::new();
+
+    for event in events {
+        let Some(start) = event.zone.to_utc(event.start) else {
+            continue;
+        };
+
+        if let Some(original) = event.recurrence_id {
+            expanded.push(event.occurrence(occurrence_uid(&event.uid, original), start));
+            continue;
+        }
+        let Some(rule) = &event.rule else {
+            expanded.push(event.occurrence(event.uid.clone(), start));
+            continue;
+        };
+
+        let moved = |occurrence: &DateTime<Utc>| {
+            events.iter().any(|other| {
+                other.uid == event.uid && other.recurrence_id.as_ref() == Some(occurrence)
+            })
+        };
+        for occurrence in occurrences(event, rule, from, to) {
+            if !event.exceptions.contains(&occurrence) && !moved(&occurrence) {
+                expanded.push(event.occurrence(occurrence_uid(&event.uid, occurrence), occurrence));
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Join folded lines (continuations start with a space or tab)
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+fn build_event(props: &[(String, String)]) -> Option<IcsEvent> {
+    // Property names may carry parameters, e.g. `DTSTART;TZID=Europe/Berlin`
+    let named = |name: &'static str| {
+        props
+            .iter()
+            .filter(move |(key, _)| key.split(';').next() == Some(name))
+    };
+    let property = |name: &'static str| named(name).next().map(|(_, value)| value.as_str());
+
+    let (start, zone) = named("DTSTART")
+        .next()
+        .and_then(|(key, value)| parse_ics_datetime(key, value))?;
+    let start_utc = zone.to_utc(start)?;
+    let length = named("DTEND")
+        .next()
+        .and_then(|(key, value)| parse_ics_datetime(key, value))
+        .and_then(|(end, end_zone)| end_zone.to_utc(end))
+        .map(|end| end - start_utc);
+    let exceptions = named("EXDATE")
+        .flat_map(|(key, values)| values.split(',').map(move |value| (key, value)))
+        .filter_map(|(key, value)| parse_ics_datetime(key, value))
+        .filter_map(|(exception, zone)| zone.to_utc(exception))
+        .collect();
+
+    Some(IcsEvent {
+        uid: property("UID")
+            .map(str::to_string)
+            .unwrap_or_else(|| start_utc.to_rfc3339()),
+        title: property("SUMMARY")
+            .map(unescape_text)
+            .unwrap_or_else(|| "Meeting".to_string()),
+        location: property("LOCATION")
+            .map(unescape_text)
+            .filter(|location| !location.is_empty()),
+        start,
+        zone,
+        length,
+        rule: property("RRULE").and_then(|rule| parse_rule(rule, zone)),
+        exceptions,
+        recurrence_id: named("RECURRENCE-ID")
+            .next()
+            .and_then(|(key, value)| parse_ics_datetime(key, value))
+            .and_then(|(original, zone)| zone.to_utc(original)),
+    })
+}
+
+impl IcsEvent {
+    fn occurrence(&self, uid: String, start: DateTime<Utc>) -> CalendarEvent {
+        CalendarEvent {
+            uid,
+            title: self.title.clone(),
+            start: self.zone.format(start),
+            end: self.length.map(|length| self.zone.format(start + length)),
+            location: self.location.clone(),
+        }
+    }
+}
+
+impl Zone {
+    /// Zone of a `TZID` parameter; calendar apps may prefix the IANA name,
+    /// e.g. `/mozilla.org/20050126_1/Europe/Berlin`
+    fn from_tzid(tzid: &str) -> Zone {
+        let tzid = tzid.trim_matches('"');
+        std::iter::once(tzid)
+            .chain(tzid.match_indices('/').map(|(i, _)| &tzid[i + 1..]))
+            .find_map(|name| name.parse::<Tz>().ok())
+            .map_or(Zone::Floating, Zone::Named)
+    }
+
+    /// The instant a wall-clock time in this zone refers to; times skipped
+    /// by a DST change are moved forward an hour
+    fn to_utc(self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        fn resolve<T: TimeZone>(zone: &T, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+            zone.from_local_datetime(&naive)
+                .earliest()
+                .or_else(|| {
+                    zone.from_local_datetime(&(naive + Duration::hours(1)))
+                        .earliest()
+                })
+                .map(|time| time.with_timezone(&Utc))
+        }
+
+        match self {
+            Zone::Utc => Some(Utc.from_utc_datetime(&naive)),
+            Zone::Floating => resolve(&Local, naive),
+            Zone::Named(tz) => resolve(&tz, naive),
+        }
+    }
+
+    /// RFC 3339 time for a `CalendarEvent`: UTC events stay in UTC, others
+    /// are given in local time
+    fn format(self, time: DateTime<Utc>) -> String {
+        match self {
+            Zone::Utc => time.to_rfc3339(),
+            _ => time.with_timezone(&Local).to_rfc3339(),
+        }
+    }
+}
+
+/// Parse an ICS date-time with the zone given by the property's `TZID`;
+/// None for all-day dates
+fn parse_ics_datetime(key: &str, value: &str) -> Option<(NaiveDateTime, Zone)> {
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((naive, Zone::Utc));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zone = key
+        .split(';')
+        .skip(1)
+        .find_map(|param| param.strip_prefix("TZID="))
+        .map_or(Zone::Floating, Zone::from_tzid);
+    Some((naive, zone))
+}
+
+/// Parse an `RRULE` value; None when it uses parts that aren't supported
+fn parse_rule(value: &str, zone: Zone) -> Option<RecurrenceRule> {
+    let mut frequency = None;
+    let mut rule = RecurrenceRule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        by_month_day: Vec::new(),
+    };
+
+    for part in value.split(';') {
+        let (name, value) = part.split_once('=')?;
+        match name {
+            "FREQ" => {
+                frequency = Some(match value {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    _ => return None,
+                })
+            }
+            "INTERVAL" => rule.interval = value.parse().ok().filter(|i| *i > 0)?,
+            "COUNT" => rule.count = Some(value.parse().ok()?),
+            "UNTIL" => rule.until = Some(parse_until(value, zone)?),
+            "BYDAY" => {
+                rule.by_day = value
+                    .split(',')
+                    .map(parse_weekday)
+                    .collect::<Option<Vec<_>>>()?
+            }
+            "BYMONTHDAY" => {
+                rule.by_month_day = value
+                    .split(',')
+                    .map(|day| day.parse().ok())
+                    .collect::<Option<Vec<_>>>()?
+            }
+            // Weeks start on Monday unless set otherwise, which only
+            // matters for the rules below
+            "WKST" => {}
+            _ => return None,
+        }
+    }
+
+    rule.frequency = frequency?;
+    let by_day_ordinal = rule.by_day.iter().any(|(ordinal, _)| ordinal.is_some());
+    let unsupported = match rule.frequency {
+        Frequency::Daily | Frequency::Weekly => by_day_ordinal || !rule.by_month_day.is_empty(),
+        Frequency::Monthly => false,
+        Frequency::Yearly => !rule.by_day.is_empty() || !rule.by_month_day.is_empty(),
+    };
+    (!unsupported).then_some(rule)
+}
+
+/// `UNTIL` as a date-time, or a date (inclusive)
+fn parse_until(value: &str, zone: Zone) -> Option<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return zone.to_utc(date.and_hms_opt(23, 59, 59)?);
+    }
+    let (until, until_zone) = parse_ics_datetime("", value)?;
+    match until_zone {
+        Zone::Utc => until_zone.to_utc(until),
+        _ => zone.to_utc(until),
+    }
+}
+
+/// `BYDAY` entry such as `MO`, `2TU` or `-1FR`
+fn parse_weekday(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let weekday = match value.get(split..)? {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let ordinal = match &value[..split] {
+        "" => None,
+        ordinal => Some(ordinal.parse().ok().filter(|n| *n != 0)?),
+    };
+    Some((ordinal, weekday))
+}
+
+/// Starts of a recurring event's occurrences in `from..=to`
+fn occurrences(
+    event: &IcsEvent,
+    rule: &RecurrenceRule,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let first_date = event.start.date();
+    // Without a COUNT, periods long before the window can be skipped
+    let first_period = match rule.count {
+        Some(_) => 0,
+        None => periods_before(rule, first_date, from.date_naive()),
+    };
+
+    let mut found = Vec::new();
+    let mut generated = 0;
+    for period in first_period..first_period.saturating_add(MAX_RECURRENCE_PERIODS) {
+        for date in period_dates(rule, first_date, period) {
+            let naive = date.and_time(event.start.time());
+            if naive < event.start {
+                continue;
+            }
+            let Some(start) = event.zone.to_utc(naive) else {
+                continue;
+            };
+            generated += 1;
+            if start > to
+                || rule.until.is_some_and(|until| start > until)
+                || rule.count.is_some_and(|count| generated > count)
+            {
+                return found;
+            }
+            if start >= from {
+                found.push(start);
+            }
+        }
+    }
+
+    found
+}
+
+/// Whole periods of the rule between its first date and `date`, less one
+fn periods_before(rule: &RecurrenceRule, first_date: NaiveDate, date: NaiveDate) -> u32 {
+    let elapsed = match rule.frequency {
+        Frequency::Daily => (date - first_date).num_days(),
+        Frequency::Weekly => (date - first_date).num_days() / 7,
+        Frequency::Monthly => {
+            i64::from(date.year() - first_date.year()) * 12 + i64::from(date.month())
+                - i64::from(first_date.month())
+        }
+        Frequency::Yearly => i64::from(date.year() - first_date.year()),
+    };
+    let periods = elapsed / i64::from(rule.interval) - 1;
+    u32::try_from(periods.max(0)).unwrap_or(u32::MAX)
+}
+
+/// Dates the rule generates in its `period`-th day, week, month or year
+fn period_dates(rule: &RecurrenceRule, first_date: NaiveDate, period: u32) -> Vec<NaiveDate> {
+    let Some(step) = period.checked_mul(rule.interval) else {
+        return Vec::new();
+    };
+
+    let mut dates = match rule.frequency {
+        Frequency::Daily => first_date
+            .checked_add_signed(Duration::days(step.into()))
+            .filter(|date| {
+                rule.by_day.is_empty() || rule.by_day.iter().any(|(_, day)| *day == date.weekday())
+            })
+            .into_iter()
+            .collect(),
+        Frequency::Weekly => {
+            let monday = first_date.week(Weekday::Mon).first_day();
+            let weekdays = match rule.by_day.is_empty() {
+                true => vec![first_date.weekday()],
+                false => rule.by_day.iter().map(|(_, day)| *day).collect(),
+            };
+            weekdays
+                .into_iter()
+                .filter_map(|day| {
+                    let offset = i64::from(step) * 7 + i64::from(day.num_days_from_monday());
+                    monday.checked_add_signed(Duration::days(offset))
+                })
+                .collect()
+        }
+        Frequency::Monthly => first_date
+            .with_day(1)
+            .and_then(|first| first.checked_add_months(Months::new(step)))
+            .map(|month| month_dates(rule, month, first_date.day()))
+            .unwrap_or_default(),
+        Frequency::Yearly => first_date
+            .with_day(1)
+            .and_then(|first| first.checked_add_months(Months::new(step.checked_mul(12)?)))
+            .and_then(|month| month.with_day(first_date.day()))
+            .into_iter()
+            .collect(),
+    };
+
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// Dates a monthly rule generates in the month starting on `month`
+fn month_dates(rule: &RecurrenceRule, month: NaiveDate, default_day: u32) -> Vec<NaiveDate> {
+    let days: Vec<NaiveDate> = month
+        .iter_days()
+        .take_while(|d| d.month() == month.month())
+        .collect();
+    let last_day = days.len() as i32;
+
+    if !rule.by_month_day.is_empty() {
+        return rule
+            .by_month_day
+            .iter()
+            .map(|day| if *day < 0 { last_day + 1 + day } else { *day })
+            .filter(|day| (1..=last_day).contains(day))
+            .map(|day| days[day as usize - 1])
+            .collect();
+    }
+
+    if rule.by_day.is_empty() {
+        return days
+            .get(default_day as usize - 1)
+            .copied()
+            .into_iter()
+            .collect();
+    }
+
+    rule.by_day
+        .iter()
+        .flat_map(|(ordinal, weekday)| {
+            let matching: Vec<NaiveDate> = days
+                .iter()
+                .copied()
+                .filter(|d| d.weekday() == *weekday)
+                .collect();
+            match ordinal {
+                None => matching,
+                Some(n) => {
+                    let index = if *n > 0 {
+                        *n - 1
+                    } else {
+                        matching.len() as i32 + n
+                    };
+                    usize::try_from(index)
+                        .ok()
+                        .and_then(|i| matching.get(i).copied())
+                        .into_iter()
+                        .collect()
+                }
+            }
+        })
+        .collect()
+}
+
+fn occurrence_uid(uid: &str, start: DateTime<Utc>) -> String {
+    format!("{}/{}", uid, start.format("%Y%m%dT%H%M%SZ"))
+}
+
+/// Unescape ICS text values (`\,` `\;` `\n` `\\`)
+fn unescape_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup-1@example.com\r\n\
+DTSTART:20241104T170000Z\r\n\
+DTEND:20241104T171500Z\r\n\
+SUMMARY:Team standup\\, daily\r\n\
+LOCATION:Room 4\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:offsite@example.com\r\n\
+DTSTART;VALUE=DATE:20241105\r\n\
+SUMMARY:Offsite\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:review@example.com\r\n\
+DTSTART;TZID=Europe/Berlin:20241106T090000\r\n\
+SUMMARY:Design review with a very long title that was folded by the\r\n\
+\x20\x20calendar app\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    fn events(content: &str) -> Vec<CalendarEvent> {
+        expand_events(&parse_ics(content), utc("2024-11-04T12:00:00Z"))
+    }
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn starts(events: &[CalendarEvent]) -> Vec<DateTime<Utc>> {
+        events.iter().map(|event| utc(&event.start)).collect()
+    }
+
+    fn recurring(rule: &str, extra: &str) -> String {
+        format!(
+            "BEGIN:VEVENT\r\nUID:weekly\r\nDTSTART;TZID=Europe/Berlin:20241007T090000\r\n\
+DTEND;TZID=Europe/Berlin:20241007T093000\r\nRRULE:{}\r\n{}END:VEVENT\r\n",
+            rule, extra
+        )
+    }
+
+    #[test]
+    fn test_parse_ics_events() {
+        let events = events(ICS);
+
+        // All-day event is skipped
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].uid, "standup-1@example.com");
+        assert_eq!(events[0].title, "Team standup, daily");
+        assert_eq!(events[0].start, "2024-11-04T17:00:00+00:00");
+        assert_eq!(events[0].end, Some("2024-11-04T17:15:00+00:00".to_string()));
+        assert_eq!(events[0].location, Some("Room 4".to_string()));
+    }
+
+    #[test]
+    fn test_folded_lines_and_tzid() {
+        let events = events(ICS);
+        let review = &events[1];
+
+        assert_eq!(
+            review.title,
+            "Design review with a very long title that was folded by the calendar app"
+        );
+        assert_eq!(utc(&review.start), utc("2024-11-06T08:00:00Z"));
+    }
+
+    #[test]
+    fn test_unknown_tzid_as_local() {
+        let event = "BEGIN:VEVENT\r\nUID:a\r\n\
+DTSTART;TZID=W. Europe Standard Time:20241106T090000\r\nEND:VEVENT\r\n\
+BEGIN:VEVENT\r\nUID:b\r\n\
+DTSTART;TZID=/mozilla.org/20050126_1/America/New_York:20241106T090000\r\nEND:VEVENT\r\n";
+        let events = events(event);
+
+        let local = DateTime::parse_from_rfc3339(&events[0].start)
+            .unwrap()
+            .with_timezone(&Local)
+            .naive_local();
+        assert_eq!(
+            local.format("%Y-%m-%d %H:%M").to_string(),
+            "2024-11-06 09:00"
+        );
+        assert_eq!(utc(&events[1].start), utc("2024-11-06T14:00:00Z"));
+    }
+
+    #[test]
+    fn test_expands_weekly_recurrence_across_dst() {
+        let events = events(&recurring("FREQ=WEEKLY;BYDAY=MO,WE", ""));
+
+        // From a day before "now" to a month after; 9:00 Berlin is 8:00 UTC
+        // in winter, after DST ended on 27 October
+        assert_eq!(events[0].uid, "weekly/20241104T080000Z");
+        assert_eq!(
+            starts(&events[..3]),
+            vec![
+                utc("2024-11-04T08:00:00Z"),
+                utc("2024-11-06T08:00:00Z"),
+                utc("2024-11-11T08:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            utc(events[0].end.as_ref().unwrap()),
+            utc("2024-11-04T08:30:00Z")
+        );
+        assert_eq!(events.len(), 10);
+    }
+
+    #[test]
+    fn test_recurrence_limits_and_exceptions() {
+        let counted = events(&recurring("FREQ=DAILY;COUNT=30", ""));
+        assert_eq!(starts(&counted).last(), Some(&utc("2024-11-05T08:00:00Z")));
+
+        let until = events(&recurring("FREQ=WEEKLY;UNTIL=20241111", ""));
+        assert_eq!(
+            starts(&until),
+            vec![utc("2024-11-04T08:00:00Z"), utc("2024-11-11T08:00:00Z")]
+        );
+
+        let moved = format!(
+            "{}BEGIN:VEVENT\r\nUID:weekly\r\n\
+RECURRENCE-ID;TZID=Europe/Berlin:20241111T090000\r\n\
+DTSTART;TZID=Europe/Berlin:20241112T100000\r\nSUMMARY:Moved\r\nEND:VEVENT\r\n",
+            recurring(
+                "FREQ=WEEKLY;INTERVAL=1;COUNT=8",
+                "EXDATE;TZID=Europe/Berlin:20241104T090000\r\n"
+            )
+        );
+        let events = events(&moved);
+        assert_eq!(
+            starts(&events),
+            vec![
+                utc("2024-11-18T08:00:00Z"),
+                utc("2024-11-25T08:00:00Z"),
+                utc("2024-11-12T09:00:00Z"),
+            ]
+        );
+        assert_eq!(events[2].uid, "weekly/20241111T080000Z");
+        assert_eq!(events[2].title, "Moved");
+    }
+
+    #[test]
+    fn test_monthly_recurrence() {
+        let first_thursday = events(&recurring("FREQ=MONTHLY;BYDAY=1TH", ""));
+        assert_eq!(
+            starts(&first_thursday),
+            vec![utc("2024-11-07T08:00:00Z"), utc("2024-12-05T08:00:00Z")]
+        );
+
+        let second_tuesday = events(&recurring("FREQ=MONTHLY;BYDAY=2TU", ""));
+        assert_eq!(starts(&second_tuesday), vec![utc("2024-11-12T08:00:00Z")]);
+
+        let last_day = events(&recurring("FREQ=MONTHLY;BYMONTHDAY=-1", ""));
+        assert_eq!(starts(&last_day), vec![utc("2024-11-30T08:00:00Z")]);
+
+        // Unsupported rules keep only the first occurrence
+        let hourly = events(&recurring("FREQ=HOURLY", ""));
+        assert_eq!(starts(&hourly), vec![utc("2024-10-07T07:00:00Z")]);
+    }
+
+    #[test]
+    fn test_event_starting_between() {
+        let events = events(ICS);
+        let review_start = utc("2024-11-06T08:00:00Z")
+            .with_timezone(&Local)
+            .naive_local();
+
+        let found = event_starting_between(
+            &events,
+            review_start - chrono::Duration::seconds(5),
+            review_start,
+        );
+        assert_eq!(found.map(|e| e.uid.as_str()), Some("review@example.com"));
+
+        let missed = event_starting_between(
+            &events,
+            review_start,
+            review_start + chrono::Duration::seconds(5),
+        );
+        assert!(missed.is_none());
+    }
+
+    #[test]
+    fn test_unescape_text() {
+        assert_eq!(unescape_text(r"a\, b\; c\nd\\e"), "a, b; c\nd\\e");
+    }
+}
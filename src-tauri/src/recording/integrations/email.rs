@@ -0,0 +1,141 @@
+use crate::recording::config::load_config;
+use crate::recording::integrations::share::{build_mailto_url, share_title};
+use crate::recording::models::{EmailDelivery, SmtpConfig, SmtpSecurity};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::files::session_audio_path;
+use crate::recording::session::load_transcript;
+use crate::recording::session::storage::get_session;
+use crate::recording::utils::{open_url, require_network, require_online, reveal_in_file_manager};
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use std::fs;
+use std::path::Path;
+
+/// E-mail a session's transcript to `to`
+///
+/// Sends through the configured SMTP server, attaching the audio if
+/// requested. Without an SMTP server a message is composed in the default
+/// mail client instead; `mailto:` links can't carry attachments, so the
+/// audio file is revealed for dragging into the message.
+pub fn email_session(
+    session_id: &str,
+    to: &[String],
+    attach_audio: bool,
+) -> Result<EmailDelivery, String> {
+    if to.iter().all(|address| address.trim().is_empty()) {
+        return Err("Add at least one recipient.".to_string());
+    }
+
+    let session = get_session(session_id)?;
+    let transcript = redact_outgoing(&load_transcript(session_id)?);
+    if transcript.is_empty() {
+        return Err("No transcript available for this session".to_string());
+    }
+
+    let subject = session
+        .title
+        .clone()
+        .unwrap_or_else(|| share_title(session_id, &transcript));
+
+    let audio_path = if attach_audio {
+        let path = session_audio_path(&session)?;
+        if session.audio_path.is_empty() || !path.exists() {
+            return Err("This session's audio was not kept.".to_string());
+        }
+        Some(path)
+    } else {
+        None
+    };
+
+    match load_config()?.integrations.email.smtp {
+        Some(smtp) => {
+            send_email(&smtp, to, &subject, &transcript, audio_path.as_deref())?;
+            log::info!("E-mailed session {} to {}", session_id, to.join(", "));
+            Ok(EmailDelivery::Sent)
+        }
+        None => {
+            require_online("E-mail")?;
+            open_url(&build_mailto_url(to, &subject, &transcript))?;
+            if let Some(path) = audio_path {
+                reveal_in_file_manager(&path)?;
+            }
+            Ok(EmailDelivery::Composed)
+        }
+    }
+}
+
+/// E-mail plain text (e.g. a weekly report) through the configured SMTP
+/// server
+pub(crate) fn send_text_email(to: &[String], subject: &str, body: &str) -> Result<(), String> {
+    let smtp = load_config()?
+        .integrations
+        .email
+        .smtp
+        .ok_or("E-mail is not set up: add an SMTP server to the config")?;
+    send_email(&smtp, to, subject, &redact_outgoing(body), None)
+}
+
+/// Send a plain-text e-mail, optionally with a WAV attachment
+fn send_email(
+    smtp: &SmtpConfig,
+    to: &[String],
+    subject: &str,
+    body: &str,
+    attachment: Option<&Path>,
+) -> Result<(), String> {
+    require_network("E-mail", &smtp.host)?;
+
+    let mut builder = Message::builder()
+        .from(parse_mailbox(&smtp.from)?)
+        .subject(subject);
+    for address in to.iter().filter(|address| !address.trim().is_empty()) {
+        builder = builder.to(parse_mailbox(address)?);
+    }
+
+    let mut parts = MultiPart::mixed().singlepart(SinglePart::plain(body.to_string()));
+    if let Some(path) = attachment {
+        let audio = fs::read(path).map_err(|e| format!("Failed to read audio: {}", e))?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content_type = ContentType::parse("audio/wav").expect("valid content type");
+        parts = parts.singlepart(Attachment::new(file_name).body(audio, content_type));
+    }
+
+    let message = builder
+        .multipart(parts)
+        .map_err(|e| format!("Failed to compose e-mail: {}", e))?;
+
+    let transport = match smtp.security {
+        SmtpSecurity::StartTls => SmtpTransport::starttls_relay(&smtp.host),
+        SmtpSecurity::Tls => SmtpTransport::relay(&smtp.host),
+        SmtpSecurity::None => Ok(SmtpTransport::builder_dangerous(&smtp.host)),
+    }
+    .map_err(|e| format!("Invalid SMTP server '{}': {}", smtp.host, e))?
+    .port(smtp.port);
+
+    let transport = match &smtp.username {
+        Some(username) => transport.credentials(Credentials::new(
+            username.clone(),
+            smtp.password.clone().unwrap_or_default(),
+        )),
+        None => transport,
+    };
+
+    transport
+        .build()
+        .send(&message)
+        .map_err(|e| format!("Failed to send e-mail: {}", e))?;
+
+    Ok(())
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, String> {
+    address
+        .trim()
+        .parse()
+        .map_err(|e| format!("Invalid e-mail address '{}': {}", address, e))
+}
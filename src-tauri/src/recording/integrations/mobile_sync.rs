@@ -0,0 +1,229 @@
+use crate::recording::audio::MAX_WAV_BYTES;
+use crate::recording::integrations::pairing::{authorize_device, complete_pairing};
+use crate::recording::models::{MobileSyncConfig, PairedDevice, Session};
+use crate::recording::session::lifecycle::add_received_recording;
+use crate::recording::session::{orchestrate_async_transcription, TranscriptionResult};
+use crate::recording::state::SharedRecordingState;
+use crate::recording::utils::{get_storage_dir, require_network};
+use crate::recording::utils::http::{bearer_token, error_response, header, json_response};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::thread;
+use tiny_http::{Method, Request, Server};
+
+/// Mobile sync notifications for event emission
+pub enum MobileSyncEvent {
+    /// A phone entered the pairing code
+    Paired(PairedDevice),
+    /// A recording was received and added to the session index
+    Received(Session),
+    /// Transcription of a received recording finished
+    Transcription(TranscriptionResult),
+}
+
+/// Body of `POST /pair`
+#[derive(Deserialize)]
+struct PairRequest {
+    code: String,
+    device_name: String,
+}
+
+#[derive(Serialize)]
+struct PairResponse {
+    device_id: String,
+    /// Sent by the phone as `Authorization: Bearer {token}` with each upload
+    token: String,
+}
+
+#[derive(Serialize)]
+struct ReceivedResponse {
+    session_id: String,
+    /// The recording was already in the archive, as `session_id`
+    duplicate: bool,
+}
+
+/// Handled request: HTTP status and JSON body
+type HttpResponse = (u16, String);
+
+/// Failed request: HTTP status and message returned to the phone
+type HttpError = (u16, String);
+
+/// Start the endpoint receiving recordings from paired phones
+///
+/// Serves two routes on all network interfaces:
+/// - `POST /pair` with `{"code", "device_name"}`: exchanges the code shown by
+///   `start_pairing` for an access token
+/// - `POST /recordings` with a WAV file as the body and the token as a bearer
+///   `Authorization` header: adds the recording as a session and transcribes
+///   it. Optional headers `X-Recorded-At` (RFC 3339) and `X-Title` set the
+///   session time and title. A recording already in the archive is not added
+///   again; the response names the existing session instead.
+///
+/// # Arguments
+/// * `state` - Shared recording state for the transcription workflow
+/// * `config` - Port to listen on
+/// * `event_emitter` - Callback to emit Tauri events (injected dependency)
+pub fn start_mobile_sync<F>(
+    state: SharedRecordingState,
+    config: &MobileSyncConfig,
+    event_emitter: F,
+) -> Result<(), String>
+where
+    F: Fn(MobileSyncEvent) + Send + Sync + 'static,
+{
+    require_network("Mobile sync", &Ipv4Addr::UNSPECIFIED.to_string())?;
+
+    let server = Server::http((Ipv4Addr::UNSPECIFIED, config.port))
+        .map_err(|e| format!("Failed to listen on port {}: {}", config.port, e))?;
+    log::info!("Mobile sync listening on port {}", config.port);
+
+    let event_emitter = Arc::new(event_emitter);
+
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let state = Arc::clone(&state);
+            let event_emitter = Arc::clone(&event_emitter);
+            thread::spawn(move || handle_request(request, state, event_emitter));
+        }
+    });
+
+    Ok(())
+}
+
+fn handle_request<F>(mut request: Request, state: SharedRecordingState, event_emitter: Arc<F>)
+where
+    F: Fn(MobileSyncEvent) + Send + Sync + 'static,
+{
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let result = match (request.method(), path.as_str()) {
+        (Method::Post, "/pair") => pair(&mut request, event_emitter.as_ref()),
+        (Method::Post, "/recordings") => receive_recording(&mut request, state, event_emitter),
+        _ => Err((404, "Not found".to_string())),
+    };
+
+    let response = match result {
+        Ok((status, body)) => json_response(status, body),
+        Err((status, message)) => {
+            log::warn!("Mobile sync request {} failed: {}", path, message);
+            error_response(status, &message)
+        }
+    };
+
+    if let Err(e) = request.respond(response) {
+        log::warn!("Failed to respond to mobile sync request: {}", e);
+    }
+}
+
+fn pair<F>(request: &mut Request, event_emitter: &F) -> Result<HttpResponse, HttpError>
+where
+    F: Fn(MobileSyncEvent),
+{
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(64 * 1024)
+        .read_to_string(&mut body)
+        .map_err(|e| (400, format!("Failed to read request: {}", e)))?;
+    let pair_request: PairRequest = serde_json::from_str(&body)
+        .map_err(|e| (400, format!("Invalid pairing request: {}", e)))?;
+
+    let (device, token) =
+        complete_pairing(&pair_request.code, &pair_request.device_name).map_err(|e| (403, e))?;
+    let response = PairResponse {
+        device_id: device.id.clone(),
+        token,
+    };
+    event_emitter(MobileSyncEvent::Paired(device));
+
+    Ok((200, serde_json::to_string(&response).unwrap_or_default()))
+}
+
+fn receive_recording<F>(
+    request: &mut Request,
+    state: SharedRecordingState,
+    event_emitter: Arc<F>,
+) -> Result<HttpResponse, HttpError>
+where
+    F: Fn(MobileSyncEvent) + Send + Sync + 'static,
+{
+    let device = header(request, "Authorization")
+        .and_then(bearer_token)
+        .and_then(authorize_device)
+        .ok_or_else(|| (401, "Device is not paired".to_string()))?;
+
+    let recorded_at = parse_recorded_at(header(request, "X-Recorded-At")).map_err(|e| (400, e))?;
+    let title = header(request, "X-Title")
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(String::from);
+
+    if request.body_length().unwrap_or_default() as u64 > MAX_WAV_BYTES {
+        return Err((413, "Recording is too large".to_string()));
+    }
+
+    let mut audio = request.as_reader().take(MAX_WAV_BYTES);
+    let (session, created) = add_received_recording(&mut audio, recorded_at, title, &device.name)
+        .map_err(|e| (400, e))?;
+
+    if !created {
+        log::info!(
+            "Recording from '{}' is already in the archive as {}",
+            device.name,
+            session.id
+        );
+        let response = ReceivedResponse {
+            session_id: session.id,
+            duplicate: true,
+        };
+        return Ok((200, serde_json::to_string(&response).unwrap_or_default()));
+    }
+    log::info!("Received recording {} from '{}'", session.id, device.name);
+
+    let audio_path = get_storage_dir()
+        .map_err(|e| (500, e))?
+        .join(&session.audio_path);
+    let response = ReceivedResponse {
+        session_id: session.id.clone(),
+        duplicate: false,
+    };
+    event_emitter(MobileSyncEvent::Received(session.clone()));
+
+    orchestrate_async_transcription(state, session.id, audio_path, move |result| {
+        event_emitter(MobileSyncEvent::Transcription(result))
+    });
+
+    Ok((201, serde_json::to_string(&response).unwrap_or_default()))
+}
+
+/// Recording time sent by the phone, defaulting to now
+fn parse_recorded_at(value: Option<&str>) -> Result<DateTime<Utc>, String> {
+    match value {
+        None => Ok(Utc::now()),
+        Some(value) => DateTime::parse_from_rfc3339(value.trim())
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|e| format!("Invalid X-Recorded-At '{}': {}", value, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recorded_at() {
+        let time = parse_recorded_at(Some("2024-11-02T15:30:00+01:00")).unwrap();
+        assert_eq!(time.to_rfc3339(), "2024-11-02T14:30:00+00:00");
+
+        assert!(parse_recorded_at(Some("yesterday")).is_err());
+        assert!(parse_recorded_at(None).is_ok());
+    }
+}
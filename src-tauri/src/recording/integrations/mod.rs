@@ -0,0 +1,17 @@
+pub mod calendar;
+pub mod email;
+pub mod mobile_sync;
+pub mod notion;
+pub mod pairing;
+pub mod remote_control;
+pub mod share;
+pub mod tasks;
+
+pub use calendar::find_calendar_event;
+pub use email::email_session;
+pub use mobile_sync::{start_mobile_sync, MobileSyncEvent};
+pub use notion::publish_to_notion;
+pub use pairing::{list_paired_devices, start_pairing, unpair_device};
+pub use remote_control::{get_remote_control_token, start_remote_control, RemoteCommand};
+pub use share::{share_session, NativeWindow, ShareTarget};
+pub use tasks::{create_tasks, set_integration_token};
@@ -0,0 +1,282 @@
+use crate::recording::config::load_config;
+use crate::recording::integrations::share::share_title;
+use crate::recording::models::{NotionConfig, Session};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::auto_copy::format_duration;
+use crate::recording::session::load_transcript;
+use crate::recording::session::storage::{get_session, update_session};
+use crate::recording::utils::http_request;
+use serde_json::{json, Value};
+
+const NOTION_API_URL: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Notion's limit on the text of a single rich text object
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// Notion's limit on blocks created by a single request
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+/// Publish a session's transcript as a new Notion page
+///
+/// Returns the page URL, which is also stored on the session. Publishing
+/// again creates another page.
+pub fn publish_to_notion(session_id: &str) -> Result<String, String> {
+    let config = load_config()?.integrations.notion;
+    let session = get_session(session_id)?;
+
+    let transcript = redact_outgoing(&load_transcript(session_id)?);
+    if transcript.is_empty() {
+        return Err("No transcript available for this session".to_string());
+    }
+
+    let url = publish(&config, &session, &transcript)?;
+    update_session(session_id, |session| {
+        session.notion_page_url = Some(url.clone())
+    })?;
+    log::info!("Published session {} to Notion", session_id);
+
+    Ok(url)
+}
+
+/// Publish a freshly transcribed session if `autoPublish` is on and it
+/// wasn't published before
+pub fn auto_publish_to_notion(session: &Session) {
+    let auto_publish = load_config()
+        .map(|config| config.integrations.notion.auto_publish)
+        .unwrap_or(false);
+    if !auto_publish || session.notion_page_url.is_some() {
+        return;
+    }
+
+    if let Err(e) = publish_to_notion(&session.id) {
+        log::warn!("Failed to publish session {} to Notion: {}", session.id, e);
+    }
+}
+
+/// Create the page, then append transcript blocks beyond the per-request limit
+fn publish(config: &NotionConfig, session: &Session, transcript: &str) -> Result<String, String> {
+    let token = config
+        .token
+        .as_deref()
+        .filter(|token| !token.trim().is_empty())
+        .ok_or("Notion is not set up: add an integration token to the config")?;
+
+    let title = session
+        .title
+        .clone()
+        .unwrap_or_else(|| share_title(&session.id, transcript));
+    let mut blocks = page_blocks(config, session, transcript);
+    let remaining = blocks.split_off(blocks.len().min(MAX_BLOCKS_PER_REQUEST));
+
+    let page = notion_request(
+        token,
+        "POST",
+        "/pages",
+        page_request(config, session, &title, blocks)?,
+    )?;
+    let page_id = page["id"]
+        .as_str()
+        .ok_or("Notion did not return the new page's id")?;
+
+    for chunk in remaining.chunks(MAX_BLOCKS_PER_REQUEST) {
+        notion_request(
+            token,
+            "PATCH",
+            &format!("/blocks/{}/children", page_id),
+            json!({ "children": chunk }),
+        )?;
+    }
+
+    Ok(page["url"].as_str().unwrap_or_default().to_string())
+}
+
+fn notion_request(token: &str, method: &str, path: &str, body: Value) -> Result<Value, String> {
+    let url = format!("{}{}", NOTION_API_URL, path);
+    let response = http_request("Notion publishing", method, &url)?
+        .set("Authorization", &format!("Bearer {}", token))
+        .set("Notion-Version", NOTION_VERSION)
+        .send_json(body);
+
+    match response {
+        Ok(response) => response
+            .into_json()
+            .map_err(|e| format!("Failed to read Notion response: {}", e)),
+        Err(ureq::Error::Status(status, response)) => {
+            let detail: Value = response.into_json().unwrap_or_default();
+            Err(format!(
+                "Notion request failed ({}): {}",
+                status,
+                detail["message"].as_str().unwrap_or("unknown error")
+            ))
+        }
+        Err(e) => Err(format!("Failed to reach Notion: {}", e)),
+    }
+}
+
+/// Body of the create-page request
+///
+/// Database entries get the title, duration, date and tags as properties;
+/// sub-pages only have a title, so the blocks start with those details.
+fn page_request(
+    config: &NotionConfig,
+    session: &Session,
+    title: &str,
+    children: Vec<Value>,
+) -> Result<Value, String> {
+    if let Some(database_id) = &config.database_id {
+        let mut properties = json!({});
+        properties[&config.title_property] = json!({ "title": rich_text(title) });
+        if let Some(property) = &config.duration_property {
+            properties[property] = json!({ "number": session.duration.round() });
+        }
+        if let Some(property) = &config.date_property {
+            properties[property] = json!({ "date": { "start": session.timestamp } });
+        }
+        if let Some(property) = config
+            .tags_property
+            .as_ref()
+            .filter(|_| !config.tags.is_empty())
+        {
+            let options: Vec<Value> = config
+                .tags
+                .iter()
+                .map(|tag| json!({ "name": tag }))
+                .collect();
+            properties[property] = json!({ "multi_select": options });
+        }
+
+        return Ok(json!({
+            "parent": { "database_id": database_id },
+            "properties": properties,
+            "children": children
+        }));
+    }
+
+    if let Some(page_id) = &config.parent_page_id {
+        return Ok(json!({
+            "parent": { "page_id": page_id },
+            "properties": { "title": { "title": rich_text(title) } },
+            "children": children
+        }));
+    }
+
+    Err("Set a Notion database or parent page to publish to".to_string())
+}
+
+/// Page content: one paragraph per transcript line, preceded by the session
+/// details when publishing as a sub-page
+fn page_blocks(config: &NotionConfig, session: &Session, transcript: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+
+    if config.database_id.is_none() {
+        let mut details = format!(
+            "Recorded {} · {}",
+            session.timestamp,
+            format_duration(session.duration)
+        );
+        if !config.tags.is_empty() {
+            details.push_str(&format!(" · Tags: {}", config.tags.join(", ")));
+        }
+        blocks.push(paragraph(&details));
+    }
+
+    blocks.extend(
+        transcript
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(paragraph),
+    );
+    blocks
+}
+
+fn paragraph(text: &str) -> Value {
+    json!({
+        "object": "block",
+        "type": "paragraph",
+        "paragraph": { "rich_text": rich_text(text) }
+    })
+}
+
+/// Rich text array for `text`, split to stay within Notion's length limit
+fn rich_text(text: &str) -> Vec<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(MAX_TEXT_CHARS)
+        .map(|chunk| json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn session() -> Session {
+        Session {
+            timestamp: "2024-11-02T15:30:00+00:00".to_string(),
+            duration: 75.4,
+            ..fixtures::session("2024-11-02_15-30-00")
+        }
+    }
+
+    #[test]
+    fn test_database_entry_properties() {
+        let config = NotionConfig {
+            database_id: Some("db".to_string()),
+            duration_property: Some("Duration".to_string()),
+            tags_property: Some("Tags".to_string()),
+            tags: vec!["voice".to_string()],
+            ..NotionConfig::default()
+        };
+
+        let request = page_request(&config, &session(), "Standup", Vec::new()).unwrap();
+
+        assert_eq!(request["parent"]["database_id"], "db");
+        assert_eq!(
+            request["properties"]["Name"]["title"][0]["text"]["content"],
+            "Standup"
+        );
+        assert_eq!(request["properties"]["Duration"]["number"], 75.0);
+        assert_eq!(
+            request["properties"]["Tags"]["multi_select"][0]["name"],
+            "voice"
+        );
+        assert!(request["properties"].get("Date").is_none());
+    }
+
+    #[test]
+    fn test_sub_page_lists_details_first() {
+        let config = NotionConfig {
+            parent_page_id: Some("page".to_string()),
+            ..NotionConfig::default()
+        };
+
+        let blocks = page_blocks(&config, &session(), "First line\n\nSecond line");
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(
+            blocks[0]["paragraph"]["rich_text"][0]["text"]["content"],
+            "Recorded 2024-11-02T15:30:00+00:00 · 1:15"
+        );
+        assert_eq!(
+            blocks[2]["paragraph"]["rich_text"][0]["text"]["content"],
+            "Second line"
+        );
+    }
+
+    #[test]
+    fn test_missing_destination_is_error() {
+        assert!(page_request(&NotionConfig::default(), &session(), "T", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_rich_text_splits_long_text() {
+        let text = "é".repeat(MAX_TEXT_CHARS + 1);
+        let parts = rich_text(&text);
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1]["text"]["content"], "é");
+    }
+}
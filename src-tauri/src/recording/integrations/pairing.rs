@@ -0,0 +1,276 @@
+use crate::recording::config::load_config;
+use crate::recording::models::{PairedDevice, PairingCode};
+use crate::recording::utils::get_local_state_dir;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Devices allowed to send recordings, kept on this machine only (the file
+/// holds their access tokens)
+const DEVICES_FILE: &str = "paired-devices.json";
+
+/// How long a pairing code shown on the desktop stays valid
+const PAIRING_CODE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Wrong codes accepted before the pairing code is invalidated
+const MAX_PAIRING_ATTEMPTS: u32 = 5;
+
+/// Pairing code currently shown to the user
+struct PendingPairing {
+    code: String,
+    expires_at: Instant,
+    attempts_left: u32,
+}
+
+static PENDING: Mutex<Option<PendingPairing>> = Mutex::new(None);
+
+/// Serializes read-modify-write cycles of the devices file
+static DEVICES_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize)]
+struct StoredDevice {
+    token: String,
+    #[serde(flatten)]
+    device: PairedDevice,
+}
+
+/// Show a new pairing code for a phone to enter
+///
+/// Replaces any previous code. Requires mobile sync to be enabled, since the
+/// phone pairs through the sync endpoint.
+pub fn start_pairing() -> Result<PairingCode, String> {
+    let config = load_config()?.integrations.mobile_sync;
+    if !config.enabled {
+        return Err("Enable mobile sync in the settings before pairing a phone.".to_string());
+    }
+
+    let code = format!("{:06}", random_u64()? % 1_000_000);
+    *PENDING.lock().unwrap() = Some(PendingPairing {
+        code: code.clone(),
+        expires_at: Instant::now() + PAIRING_CODE_TTL,
+        attempts_left: MAX_PAIRING_ATTEMPTS,
+    });
+
+    Ok(PairingCode {
+        code,
+        expires_in_seconds: PAIRING_CODE_TTL.as_secs(),
+        port: config.port,
+    })
+}
+
+/// Pair a phone that entered the current pairing code
+///
+/// Returns the new device and the access token it must send with uploads.
+/// The code is single-use.
+pub fn complete_pairing(code: &str, device_name: &str) -> Result<(PairedDevice, String), String> {
+    check_pairing_code(&mut PENDING.lock().unwrap(), code, Instant::now())?;
+
+    let device = PairedDevice {
+        id: random_hex(8)?,
+        name: device_name.trim().to_string(),
+        paired_at: Utc::now().to_rfc3339(),
+    };
+    let token = random_hex(64)?;
+
+    update_devices(|devices| {
+        devices.push(StoredDevice {
+            token: token.clone(),
+            device: device.clone(),
+        })
+    })?;
+    log::info!("Paired mobile device '{}'", device.name);
+
+    Ok((device, token))
+}
+
+/// Device holding `token`, if it is paired
+pub fn authorize_device(token: &str) -> Option<PairedDevice> {
+    let _guard = DEVICES_LOCK.lock().unwrap();
+    read_devices()
+        .map_err(|e| log::warn!("{}", e))
+        .ok()?
+        .into_iter()
+        .find(|stored| tokens_match(&stored.token, token))
+        .map(|stored| stored.device)
+}
+
+/// Phones allowed to send recordings
+pub fn list_paired_devices() -> Result<Vec<PairedDevice>, String> {
+    let _guard = DEVICES_LOCK.lock().unwrap();
+    Ok(read_devices()?
+        .into_iter()
+        .map(|stored| stored.device)
+        .collect())
+}
+
+/// Revoke a phone's access
+pub fn unpair_device(device_id: &str) -> Result<(), String> {
+    let mut found = false;
+    update_devices(|devices| {
+        let before = devices.len();
+        devices.retain(|stored| stored.device.id != device_id);
+        found = devices.len() != before;
+    })?;
+
+    if !found {
+        return Err(format!("No paired device with id {}", device_id));
+    }
+    Ok(())
+}
+
+/// Accept `code` if it matches the pending pairing code, consuming it
+fn check_pairing_code(
+    pending: &mut Option<PendingPairing>,
+    code: &str,
+    now: Instant,
+) -> Result<(), String> {
+    let Some(current) = pending.as_mut() else {
+        return Err("No pairing in progress. Start pairing on the desktop first.".to_string());
+    };
+
+    if now >= current.expires_at {
+        *pending = None;
+        return Err("Pairing code expired.".to_string());
+    }
+
+    if current.code != code.trim() {
+        current.attempts_left -= 1;
+        if current.attempts_left == 0 {
+            *pending = None;
+            return Err("Too many wrong codes. Start pairing again.".to_string());
+        }
+        return Err("Wrong pairing code.".to_string());
+    }
+
+    *pending = None;
+    Ok(())
+}
+
+/// Random value from the operating system's secure random source
+fn random_u64() -> Result<u64, String> {
+    let mut bytes = [0u8; 8];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate token: {}", e))?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Random lowercase hex string of `len` characters, for access tokens
+pub(crate) fn random_hex(len: usize) -> Result<String, String> {
+    let mut bytes = vec![0u8; len.div_ceil(2)];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate token: {}", e))?;
+
+    let mut hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    hex.truncate(len);
+    Ok(hex)
+}
+
+/// Compare access tokens in constant time, so response timing doesn't
+/// reveal how much of a guessed token is right
+pub(crate) fn tokens_match(expected: &str, given: &str) -> bool {
+    let (expected, given) = (expected.as_bytes(), given.as_bytes());
+    if expected.len() != given.len() {
+        return false;
+    }
+    expected
+        .iter()
+        .zip(given)
+        .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+        == 0
+}
+
+fn update_devices(update: impl FnOnce(&mut Vec<StoredDevice>)) -> Result<(), String> {
+    let _guard = DEVICES_LOCK.lock().unwrap();
+
+    let mut devices = read_devices()?;
+    update(&mut devices);
+
+    let content = serde_json::to_string_pretty(&devices)
+        .map_err(|e| format!("Failed to serialize paired devices: {}", e))?;
+    fs::write(devices_file_path()?, content)
+        .map_err(|e| format!("Failed to write paired devices: {}", e))
+}
+
+fn read_devices() -> Result<Vec<StoredDevice>, String> {
+    let path = devices_file_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read paired devices: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse paired devices: {}", e))
+}
+
+fn devices_file_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(DEVICES_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(code: &str) -> Option<PendingPairing> {
+        Some(PendingPairing {
+            code: code.to_string(),
+            expires_at: Instant::now() + PAIRING_CODE_TTL,
+            attempts_left: MAX_PAIRING_ATTEMPTS,
+        })
+    }
+
+    #[test]
+    fn test_pairing_code_is_single_use() {
+        let mut state = pending("123456");
+
+        assert!(check_pairing_code(&mut state, " 123456 ", Instant::now()).is_ok());
+        assert!(check_pairing_code(&mut state, "123456", Instant::now()).is_err());
+    }
+
+    #[test]
+    fn test_expired_pairing_code() {
+        let mut state = pending("123456");
+        let later = Instant::now() + PAIRING_CODE_TTL;
+
+        assert!(check_pairing_code(&mut state, "123456", later)
+            .unwrap_err()
+            .contains("expired"));
+        assert!(state.is_none());
+    }
+
+    #[test]
+    fn test_wrong_codes_invalidate_pairing() {
+        let mut state = pending("123456");
+
+        for _ in 1..MAX_PAIRING_ATTEMPTS {
+            assert_eq!(
+                check_pairing_code(&mut state, "000000", Instant::now()),
+                Err("Wrong pairing code.".to_string())
+            );
+        }
+        assert!(check_pairing_code(&mut state, "000000", Instant::now())
+            .unwrap_err()
+            .contains("Too many"));
+
+        // Even the right code is rejected once the pairing was invalidated
+        assert!(check_pairing_code(&mut state, "123456", Instant::now()).is_err());
+    }
+
+    #[test]
+    fn test_random_hex() {
+        let token = random_hex(64).unwrap();
+
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, random_hex(64).unwrap());
+        assert_eq!(random_hex(5).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("a1b2c3", "a1b2c3"));
+        assert!(!tokens_match("a1b2c3", "a1b2c4"));
+        assert!(!tokens_match("a1b2c3", "a1b2c"));
+        assert!(!tokens_match("a1b2c3", ""));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::recording::audio::{get_audio_levels, speech_seconds};
+use crate::recording::integrations::pairing::{random_hex, tokens_match};
+use crate::recording::models::RemoteControlConfig;
+use crate::recording::state::{RecordingStatus, SharedRecordingState};
+use crate::recording::statistics::estimate_for_duration;
+use crate::recording::utils::get_local_state_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::{Error, Message, WebSocket};
+
+/// How often each client is sent status changes
+const TICK: Duration = Duration::from_millis(250);
+
+/// Longest wait for a client to finish the WebSocket handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Token clients must connect with, kept on this machine only
+const TOKEN_FILE: &str = "remote-control-token";
+
+/// Command sent by a client, e.g. `{"command": "toggle"}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum RemoteCommand {
+    Start,
+    Stop,
+    /// Start recording, or stop and transcribe
+    Toggle,
+    Pause,
+    Resume,
+}
+
+/// Message sent to clients
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RemoteMessage {
+    Status(RemoteStatus),
+    Error { message: String },
+}
+
+/// Live recording status shown on the controller
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct RemoteStatus {
+    status: RecordingStatus,
+    /// Recorded time of the active recording, in whole seconds
+    duration_seconds: u64,
+    /// Current input level (0.0-1.0), while recording
+    level: f32,
+    /// Estimated seconds until the transcription finishes, while processing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eta_seconds: Option<u64>,
+}
+
+/// Start the local WebSocket server for hardware controllers
+///
+/// Each connected client (e.g. a Stream Deck plugin) receives the recording
+/// status whenever it changes and may send `RemoteCommand`s, which are passed
+/// to `on_command`; a failed command is answered with an error message.
+/// Only connections from this machine are accepted, and only from clients
+/// that connect with the token (`ws://127.0.0.1:{port}/?token=...`, see
+/// `get_remote_control_token`). Connections from web pages (any with an
+/// `Origin` header) are refused, so a website can't start the microphone.
+///
+/// # Arguments
+/// * `state` - Shared recording state reported to clients
+/// * `config` - Port to listen on
+/// * `on_command` - Callback carrying out client commands (injected dependency)
+pub fn start_remote_control<F>(
+    state: SharedRecordingState,
+    config: &RemoteControlConfig,
+    on_command: F,
+) -> Result<(), String>
+where
+    F: Fn(RemoteCommand) -> Result<(), String> + Send + Sync + 'static,
+{
+    let token: Arc<str> = get_remote_control_token()?.into();
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, config.port))
+        .map_err(|e| format!("Failed to listen on port {}: {}", config.port, e))?;
+    log::info!("Remote control listening on ws://127.0.0.1:{}", config.port);
+
+    let on_command = Arc::new(on_command);
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let state = Arc::clone(&state);
+            let on_command = Arc::clone(&on_command);
+            let token = Arc::clone(&token);
+            thread::spawn(move || serve_client(stream, &token, &state, on_command.as_ref()));
+        }
+    });
+
+    Ok(())
+}
+
+/// Token controllers connect with, created the first time it's needed
+pub fn get_remote_control_token() -> Result<String, String> {
+    let path = token_file_path()?;
+    if let Ok(token) = fs::read_to_string(&path) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let token = random_hex(32)?;
+    fs::write(&path, &token).map_err(|e| format!("Failed to save remote control token: {}", e))?;
+    Ok(token)
+}
+
+fn serve_client<F>(stream: TcpStream, token: &str, state: &SharedRecordingState, on_command: &F)
+where
+    F: Fn(RemoteCommand) -> Result<(), String>,
+{
+    if let Err(e) = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT)) {
+        log::warn!("Remote control client rejected: {}", e);
+        return;
+    }
+
+    // The error response type is set by tungstenite
+    #[allow(clippy::result_large_err)]
+    let check = |request: &Request, response: Response| match reject_reason(request, token) {
+        Some(reason) => Err(forbidden(reason)),
+        None => Ok(response),
+    };
+    let mut socket = match tungstenite::accept_hdr(stream, check) {
+        Ok(socket) => socket,
+        Err(e) => {
+            log::warn!("Remote control handshake failed: {}", e);
+            return;
+        }
+    };
+
+    // Reads time out so status changes are pushed between commands
+    if let Err(e) = socket.get_ref().set_read_timeout(Some(TICK)) {
+        log::warn!("Remote control client rejected: {}", e);
+        return;
+    }
+
+    let mut eta = EtaTracker::default();
+    let mut last_status = None;
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let result = serde_json::from_str::<RemoteCommand>(&text)
+                    .map_err(|e| format!("Invalid command: {}", e))
+                    .and_then(on_command);
+                if let Err(message) = result {
+                    if !send(&mut socket, &RemoteMessage::Error { message }) {
+                        break;
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+
+        let status = current_status(state, &mut eta);
+        if last_status.as_ref() != Some(&status) {
+            if !send(&mut socket, &RemoteMessage::Status(status.clone())) {
+                break;
+            }
+            last_status = Some(status);
+        }
+    }
+}
+
+/// Why a connection request is refused, if it is
+fn reject_reason(request: &Request, token: &str) -> Option<&'static str> {
+    // Browsers always send an Origin; native controllers don't
+    if request.headers().contains_key("origin") {
+        return Some("Connections from web pages are not allowed");
+    }
+
+    let given = request
+        .uri()
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="));
+    match given {
+        Some(given) if tokens_match(token, given) => None,
+        _ => Some("Missing or wrong remote control token"),
+    }
+}
+
+fn forbidden(reason: &str) -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some(reason.to_string()));
+    *response.status_mut() = StatusCode::FORBIDDEN;
+    response
+}
+
+fn token_file_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(TOKEN_FILE))
+}
+
+/// Send a message, returning whether the client is still connected
+fn send(socket: &mut WebSocket<TcpStream>, message: &RemoteMessage) -> bool {
+    let json = serde_json::to_string(message).unwrap_or_default();
+    socket.send(Message::Text(json)).is_ok()
+}
+
+fn current_status(state: &SharedRecordingState, eta: &mut EtaTracker) -> RemoteStatus {
+    let (status, duration_seconds, samples, format) = {
+        let state_guard = state.lock().unwrap();
+        let duration_seconds = if state_guard.is_active() {
+            state_guard.timer.elapsed_seconds() as u64
+        } else {
+            0
+        };
+        (
+            state_guard.status(),
+            duration_seconds,
+            Arc::clone(&state_guard.samples),
+            state_guard.audio_format,
+        )
+    };
+
+    let level = match status {
+        RecordingStatus::Recording => get_audio_levels(Arc::clone(&samples))
+            .last()
+            .copied()
+            .unwrap_or(0.0),
+        _ => 0.0,
+    };
+
+    // Length and speech of the recording being transcribed
+    let recording_seconds = || {
+        let samples = samples.lock().unwrap();
+        (
+            format.seconds(samples.len()),
+            speech_seconds(&samples, format.samples_per_second()),
+        )
+    };
+
+    RemoteStatus {
+        status,
+        duration_seconds,
+        // Round so the level only triggers an update when it visibly changes
+        level: (level * 20.0).round() / 20.0,
+        eta_seconds: eta.remaining(status == RecordingStatus::Processing, recording_seconds),
+    }
+}
+
+/// Transcription estimate taken when processing starts, counted down after
+#[derive(Default)]
+struct EtaTracker {
+    started: Option<(Instant, Option<f64>)>,
+}
+
+impl EtaTracker {
+    fn remaining(
+        &mut self,
+        processing: bool,
+        recording_seconds: impl FnOnce() -> (f64, f64),
+    ) -> Option<u64> {
+        if !processing {
+            self.started = None;
+            return None;
+        }
+
+        let (started_at, estimate) = *self.started.get_or_insert_with(|| {
+            let (audio_seconds, speech_seconds) = recording_seconds();
+            let estimate = estimate_for_duration(audio_seconds, Some(speech_seconds))
+                .ok()
+                .flatten()
+                .map(|estimate| estimate.estimated_seconds);
+            (Instant::now(), estimate)
+        });
+
+        estimate.map(|seconds| {
+            (seconds - started_at.elapsed().as_secs_f64())
+                .max(0.0)
+                .ceil() as u64
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            serde_json::from_str::<RemoteCommand>(r#"{"command": "toggle"}"#).unwrap(),
+            RemoteCommand::Toggle
+        );
+        assert!(serde_json::from_str::<RemoteCommand>(r#"{"command": "explode"}"#).is_err());
+    }
+
+    #[test]
+    fn test_rejects_web_pages_and_wrong_tokens() {
+        let request = |uri: &str, origin: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(origin) = origin {
+                builder = builder.header("Origin", origin);
+            }
+            builder.body(()).unwrap()
+        };
+
+        assert_eq!(
+            reject_reason(&request("/?token=abc123", None), "abc123"),
+            None
+        );
+        assert!(reject_reason(
+            &request("/?token=abc123", Some("https://example.com")),
+            "abc123"
+        )
+        .unwrap()
+        .contains("web pages"));
+        assert!(reject_reason(&request("/?token=abc124", None), "abc123").is_some());
+        assert!(reject_reason(&request("/", None), "abc123").is_some());
+    }
+
+    #[test]
+    fn test_status_message_format() {
+        let message = RemoteMessage::Status(RemoteStatus {
+            status: RecordingStatus::Recording,
+            duration_seconds: 42,
+            level: 0.25,
+            eta_seconds: None,
+        });
+
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            serde_json::json!({
+                "type": "status",
+                "status": "recording",
+                "duration_seconds": 42,
+                "level": 0.25
+            })
+        );
+    }
+}
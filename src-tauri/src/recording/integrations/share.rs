@@ -0,0 +1,324 @@
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::load_transcript;
+use crate::recording::utils::{open_url, require_online};
+use serde::Deserialize;
+#[cfg(target_os = "macos")]
+use std::cell::RefCell;
+use std::ffi::c_void;
+#[cfg(target_os = "macos")]
+use std::process::Command;
+#[cfg(target_os = "windows")]
+use std::sync::Mutex;
+
+/// Maximum transcript characters placed into share URLs
+///
+/// URL handlers (especially on Windows) reject very long URLs, so long
+/// transcripts are truncated in the composed message
+const MAX_URL_BODY_CHARS: usize = 1500;
+
+/// Native view of the window the share sheet opens over: the window's
+/// NSView on macOS, its HWND on Windows
+pub type NativeWindow = *mut c_void;
+
+#[cfg(target_os = "macos")]
+thread_local! {
+    /// Picker of the share sheet last shown; AppKit doesn't keep it alive
+    static SHARE_PICKER: RefCell<Option<objc2::rc::Retained<objc2::runtime::AnyObject>>> =
+        const { RefCell::new(None) };
+}
+
+/// Handler that fills the Windows share sheet, replaced on every share
+#[cfg(target_os = "windows")]
+static DATA_REQUESTED_TOKEN: Mutex<Option<i64>> = Mutex::new(None);
+
+/// Destination app for sharing a transcript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareTarget {
+    /// Default mail client (all platforms, via `mailto:`)
+    Mail,
+    /// Messages app (macOS only, via `sms:`)
+    Messages,
+    /// Apple Notes (macOS only, via AppleScript)
+    Notes,
+    /// The system share sheet, offering every app that accepts text (macOS
+    /// and Windows)
+    Sheet,
+}
+
+/// Share a session's transcript with another app
+///
+/// Composes a new message/note containing the transcript in the target app;
+/// the user still confirms sending from there. The share sheet opens over
+/// `window` and must be opened from the main thread.
+pub fn share_session(
+    session_id: &str,
+    target: ShareTarget,
+    window: Option<NativeWindow>,
+) -> Result<(), String> {
+    require_online("Sharing")?;
+    let transcript = redact_outgoing(&load_transcript(session_id)?);
+    if transcript.is_empty() {
+        return Err("No transcript available for this session".to_string());
+    }
+
+    let title = share_title(session_id, &transcript);
+
+    match target {
+        ShareTarget::Mail => open_url(&build_mailto_url(&[], &title, &transcript)),
+        ShareTarget::Messages => share_to_messages(&transcript),
+        ShareTarget::Notes => share_to_notes(&title, &transcript),
+        ShareTarget::Sheet => {
+            let window = window.ok_or("No window to open the share sheet over")?;
+            show_share_sheet(window, &title, &transcript)
+        }
+    }
+}
+
+/// Open the share sheet (`NSSharingServicePicker`) below the window's view
+///
+/// The picker takes the transcript only; the services name it themselves.
+#[cfg(target_os = "macos")]
+fn show_share_sheet(window: NativeWindow, _title: &str, transcript: &str) -> Result<(), String> {
+    use objc2::rc::{Allocated, Retained};
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send, MainThreadMarker};
+    use objc2_foundation::{NSArray, NSRect, NSString};
+
+    /// `NSMinYEdge`: below the rectangle in the view's coordinates
+    const PREFERRED_EDGE: usize = 1;
+
+    if MainThreadMarker::new().is_none() {
+        return Err("The share sheet can only be opened from the main thread".to_string());
+    }
+
+    let text = NSString::from_str(transcript);
+    let items = NSArray::from_slice(&[&*text]);
+
+    // SAFETY: `window` is the NSView of one of the app's windows, and AppKit
+    // is used from the main thread
+    let picker = unsafe {
+        let view = &*(window as *const AnyObject);
+        let picker: Allocated<AnyObject> = msg_send![class!(NSSharingServicePicker), alloc];
+        let picker: Retained<AnyObject> = msg_send![picker, initWithItems: &*items];
+        let bounds: NSRect = msg_send![view, bounds];
+        let _: () = msg_send![
+            &*picker,
+            showRelativeToRect: bounds,
+            ofView: view,
+            preferredEdge: PREFERRED_EDGE
+        ];
+        picker
+    };
+
+    SHARE_PICKER.with(|current| *current.borrow_mut() = Some(picker));
+    Ok(())
+}
+
+/// Open the Windows share sheet (`DataTransferManager`) for the window
+///
+/// Windows asks for the content through the DataRequested event once the
+/// user is sharing.
+#[cfg(target_os = "windows")]
+fn show_share_sheet(window: NativeWindow, title: &str, transcript: &str) -> Result<(), String> {
+    use windows::core::{factory, HSTRING};
+    use windows::ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager};
+    use windows::Foundation::TypedEventHandler;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::IDataTransferManagerInterop;
+
+    let failed = |e: windows::core::Error| format!("Failed to open the share sheet: {}", e);
+    let hwnd = HWND(window);
+    let interop = factory::<DataTransferManager, IDataTransferManagerInterop>().map_err(failed)?;
+    // SAFETY: `hwnd` is one of the app's top-level windows
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd) }.map_err(failed)?;
+
+    let (title, text) = (HSTRING::from(title), HSTRING::from(transcript));
+    let handler =
+        TypedEventHandler::<DataTransferManager, DataRequestedEventArgs>::new(move |_, args| {
+            let data = args.ok()?.Request()?.Data()?;
+            data.Properties()?.SetTitle(&title)?;
+            data.SetText(&text)
+        });
+
+    {
+        let mut token = DATA_REQUESTED_TOKEN.lock().unwrap();
+        if let Some(previous) = token.take() {
+            let _ = manager.RemoveDataRequested(previous);
+        }
+        *token = Some(manager.DataRequested(&handler).map_err(failed)?);
+    }
+
+    // SAFETY: as above
+    unsafe { interop.ShowShareUIForWindow(hwnd) }.map_err(failed)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn show_share_sheet(_window: NativeWindow, _title: &str, _transcript: &str) -> Result<(), String> {
+    Err("The share sheet is only available on macOS and Windows".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn share_to_messages(transcript: &str) -> Result<(), String> {
+    open_url(&format!(
+        "sms:&body={}",
+        percent_encode(&truncate_chars(transcript, MAX_URL_BODY_CHARS))
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn share_to_messages(_transcript: &str) -> Result<(), String> {
+    Err("Sharing to Messages is only available on macOS".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn share_to_notes(title: &str, transcript: &str) -> Result<(), String> {
+    let script = format!(
+        "tell application \"Notes\" to make new note with properties {{name:\"{}\", body:\"{}\"}}",
+        escape_applescript(title),
+        escape_applescript(&notes_html_body(title, transcript))
+    );
+
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to create note: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn share_to_notes(_title: &str, _transcript: &str) -> Result<(), String> {
+    Err("Sharing to Notes is only available on macOS".to_string())
+}
+
+/// Title for shared content: first sentence/line of the transcript
+pub fn share_title(session_id: &str, transcript: &str) -> String {
+    let first_line = transcript.lines().find(|line| !line.trim().is_empty());
+    match first_line {
+        Some(line) => truncate_chars(line.trim(), 60),
+        None => format!("ThoughtCast {}", session_id),
+    }
+}
+
+/// Build a `mailto:` URL with recipients, subject and body
+pub fn build_mailto_url(to: &[String], subject: &str, body: &str) -> String {
+    let recipients: Vec<String> = to
+        .iter()
+        .map(|address| percent_encode(address.trim()).replace("%40", "@"))
+        .collect();
+
+    format!(
+        "mailto:{}?subject={}&body={}",
+        recipients.join(","),
+        percent_encode(subject),
+        percent_encode(&truncate_chars(body, MAX_URL_BODY_CHARS))
+    )
+}
+
+/// Truncate to at most `max_chars` characters, adding an ellipsis if cut
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}...", truncated)
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+pub fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Escape a string for use inside an AppleScript string literal
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn escape_applescript(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Notes stores HTML; escape the transcript and keep line breaks
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn notes_html_body(title: &str, transcript: &str) -> String {
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    format!(
+        "<h1>{}</h1>{}",
+        escape(title),
+        escape(transcript).replace('\n', "<br>")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("a b&c"), "a%20b%26c");
+        assert_eq!(percent_encode("safe-_.~"), "safe-_.~");
+        assert_eq!(percent_encode("é"), "%C3%A9");
+    }
+
+    #[test]
+    fn test_mailto_url() {
+        let url = build_mailto_url(&[], "Meeting notes", "Line 1\nLine 2");
+        assert_eq!(
+            url,
+            "mailto:?subject=Meeting%20notes&body=Line%201%0ALine%202"
+        );
+    }
+
+    #[test]
+    fn test_mailto_url_with_recipients() {
+        let to = vec!["ana@example.com".to_string(), " bo+notes@example.com ".to_string()];
+        assert_eq!(
+            build_mailto_url(&to, "Notes", "Hi"),
+            "mailto:ana@example.com,bo%2Bnotes@example.com?subject=Notes&body=Hi"
+        );
+    }
+
+    #[test]
+    fn test_truncate_chars_is_utf8_safe() {
+        assert_eq!(truncate_chars("héllo", 10), "héllo");
+        assert_eq!(truncate_chars("héllo", 2), "hé...");
+    }
+
+    #[test]
+    fn test_share_title_uses_first_line() {
+        assert_eq!(share_title("id", "\n  First thought\nSecond"), "First thought");
+        assert_eq!(share_title("id", "   "), "ThoughtCast id");
+    }
+
+    #[test]
+    fn test_applescript_escaping() {
+        assert_eq!(escape_applescript(r#"say "hi" \ bye"#), r#"say \"hi\" \\ bye"#);
+    }
+
+    #[test]
+    fn test_notes_html_body() {
+        assert_eq!(
+            notes_html_body("T", "a < b\nc"),
+            "<h1>T</h1>a &lt; b<br>c"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::recording::config::load_config;
+use crate::recording::integrations::share::{percent_encode, share_title};
+use crate::recording::models::{Session, TaskProvider, TasksConfig};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::action_items::extract_action_items;
+use crate::recording::session::files::session_transcript_path;
+use crate::recording::session::load_transcript;
+use crate::recording::session::storage::{get_session, update_session};
+use crate::recording::utils::{http_request, open_url, require_online};
+use crate::recording::utils::secrets::{delete_secret, load_secret, store_secret};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::path::Path;
+
+const TODOIST_TASKS_URL: &str = "https://api.todoist.com/rest/v2/tasks";
+
+/// Keychain entry holding the Todoist API token
+const TODOIST_SECRET: &str = "todoist";
+
+/// Store or (with `None`) remove the API token of a task manager
+///
+/// Things needs no token: its URL scheme only adds to-dos.
+pub fn set_integration_token(provider: TaskProvider, token: Option<&str>) -> Result<(), String> {
+    let token = token.map(str::trim).filter(|token| !token.is_empty());
+    match (provider, token) {
+        (TaskProvider::Todoist, Some(token)) => store_secret(TODOIST_SECRET, token),
+        (TaskProvider::Todoist, None) => delete_secret(TODOIST_SECRET),
+        (TaskProvider::Things, _) => Err("Things does not use a token.".to_string()),
+    }
+}
+
+/// Send a session's action items to the configured task manager
+///
+/// Returns the number of tasks created. Each task notes the session it came
+/// from and links to its transcript. Sending again creates the tasks again.
+pub fn create_tasks(session_id: &str) -> Result<usize, String> {
+    let config = load_config()?.integrations.tasks;
+    let provider = config
+        .provider
+        .ok_or("Choose Todoist or Things as the task manager in the settings.")?;
+    let session = get_session(session_id)?;
+
+    let items = extract_action_items(&redact_outgoing(&load_transcript(session_id)?));
+    if items.is_empty() {
+        return Ok(0);
+    }
+
+    let notes = task_notes(&session, &session_transcript_path(&session)?);
+    match provider {
+        TaskProvider::Todoist => create_todoist_tasks(&config, &items, &notes)?,
+        TaskProvider::Things => create_things_todos(&config, &items, &notes)?,
+    }
+
+    update_session(session_id, |session| {
+        session.tasks_created_at = Some(Utc::now().to_rfc3339())
+    })?;
+    log::info!(
+        "Created {} task(s) from session {}",
+        items.len(),
+        session_id
+    );
+
+    Ok(items.len())
+}
+
+/// Create tasks for a freshly transcribed session if `autoCreate` is on and
+/// they weren't created before
+pub fn auto_create_tasks(session: &Session) {
+    let auto_create = load_config()
+        .map(|config| {
+            let tasks = config.integrations.tasks;
+            tasks.auto_create && tasks.provider.is_some()
+        })
+        .unwrap_or(false);
+    if !auto_create || session.tasks_created_at.is_some() {
+        return;
+    }
+
+    if let Err(e) = create_tasks(&session.id) {
+        log::warn!("Failed to create tasks for session {}: {}", session.id, e);
+    }
+}
+
+fn create_todoist_tasks(config: &TasksConfig, items: &[String], notes: &str) -> Result<(), String> {
+    let token = load_secret(TODOIST_SECRET)?
+        .ok_or("Todoist is not set up: add an API token in the settings.")?;
+
+    for item in items {
+        let response = http_request("Task creation", "POST", TODOIST_TASKS_URL)?
+            .set("Authorization", &format!("Bearer {}", token))
+            .send_json(todoist_task(config, item, notes));
+
+        match response {
+            Ok(_) => {}
+            Err(ureq::Error::Status(status, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                return Err(format!(
+                    "Todoist request failed ({}): {}",
+                    status,
+                    detail.trim()
+                ));
+            }
+            Err(e) => return Err(format!("Failed to reach Todoist: {}", e)),
+        }
+    }
+
+    Ok(())
+}
+
+fn todoist_task(config: &TasksConfig, item: &str, notes: &str) -> Value {
+    let mut task = json!({ "content": item, "description": notes });
+    if let Some(project_id) = &config.todoist_project_id {
+        task["project_id"] = json!(project_id);
+    }
+    task
+}
+
+/// Add all items at once through the `things:///add` URL, which needs no
+/// authorization
+fn create_things_todos(config: &TasksConfig, items: &[String], notes: &str) -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Err("Things is only available on macOS.".to_string());
+    }
+    // Things syncs its to-dos through its cloud
+    require_online("Things")?;
+    open_url(&things_add_url(config, items, notes))
+}
+
+fn things_add_url(config: &TasksConfig, items: &[String], notes: &str) -> String {
+    let mut url = format!(
+        "things:///add?titles={}&notes={}",
+        percent_encode(&items.join("\n")),
+        percent_encode(notes)
+    );
+    if let Some(list) = &config.things_list {
+        url.push_str(&format!("&list={}", percent_encode(list)));
+    }
+    url
+}
+
+/// Task description pointing back at the session
+fn task_notes(session: &Session, transcript_path: &Path) -> String {
+    let title = session
+        .title
+        .clone()
+        .unwrap_or_else(|| share_title(&session.id, &session.preview));
+    format!(
+        "From ThoughtCast recording \"{}\" ({})\n{}",
+        title,
+        session.timestamp,
+        file_url(transcript_path)
+    )
+}
+
+/// `file://` URL of a local path, openable from the task manager
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let encoded: Vec<String> = path.split('/').map(percent_encode).collect();
+    let encoded = encoded.join("/").replacen("%3A", ":", 1);
+
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        // Windows drive path (C:/...)
+        format!("file:///{}", encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_things_add_url() {
+        let config = TasksConfig {
+            things_list: Some("Work".to_string()),
+            ..TasksConfig::default()
+        };
+        let items = vec!["Send slides".to_string(), "Book venue".to_string()];
+
+        assert_eq!(
+            things_add_url(&config, &items, "From standup"),
+            "things:///add?titles=Send%20slides%0ABook%20venue&notes=From%20standup&list=Work"
+        );
+    }
+
+    #[test]
+    fn test_todoist_task_project_is_optional() {
+        let task = todoist_task(&TasksConfig::default(), "Send slides", "notes");
+        assert!(task.get("project_id").is_none());
+
+        let config = TasksConfig {
+            todoist_project_id: Some("42".to_string()),
+            ..TasksConfig::default()
+        };
+        assert_eq!(
+            todoist_task(&config, "Send slides", "notes")["project_id"],
+            "42"
+        );
+    }
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(
+            file_url(Path::new("/Users/ana/My Notes/text/a.txt")),
+            "file:///Users/ana/My%20Notes/text/a.txt"
+        );
+        assert_eq!(
+            file_url(Path::new("C:\\Users\\ana\\text\\a.txt")),
+            "file:///C:/Users/ana/text/a.txt"
+        );
+    }
+}
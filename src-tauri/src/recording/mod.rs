@@ -1,10 +1,22 @@
 // Core modules
 mod audio;
 mod config;
+mod diagnostics;
+mod file_store;
+#[cfg(test)]
+mod fixtures;
+mod hotkeys;
+mod integrations;
 mod models;
+mod postprocess;
+mod redaction;
+mod power;
+mod scheduler;
+mod server;
 mod session;
 mod state;
 mod statistics;
+mod timing;
 mod transcription;
 mod utils;
 
@@ -12,30 +24,117 @@ mod utils;
 
 // Data models
 pub use models::{
-    Session, SessionIndex, TranscriptionCompleteEvent, TranscriptionErrorEvent, WhisperConfig,
+    AppConfig, AudioDeviceList, BulkOperationSummary, ClipExport, ClipFormat, DayActivity,
+    DecodingConfig, DragExportBundle, EmailDelivery, HealthCheck, HotkeyConfig, ImportSummary,
+    InputDeviceSettings, IntegrityFix, JournaledOperation, MicrophonePermissionAction,
+    MicrophonePermissionStatus, ModelComparison, PairedDevice, PairingCode, PausedTooLongEvent,
+    QualityFlag, RecordingAutoPausedEvent, RetentionCandidate, SavedTranscriptVersion, Session,
+    SessionAccess, SessionDayGroup, SessionFilter, SessionIndex, SessionSort, SetupStatus,
+    StorageIntegrityReport, TaskProvider, TranscriptDiff, TranscriptRefinedEvent,
+    TranscriptSegment, TranscriptVersion, TranscriptionCompleteEvent, TranscriptionDeferredEvent,
+    TranscriptionErrorEvent, WaveformStyle, WeeklyReport, WhisperBinaryInfo,
 };
 
 // State management
-pub use state::{RecordingState, RecordingStatus, SharedRecordingState};
+pub use state::{
+    RecordingState, RecordingStatus, RecordingStatusChangedEvent, SharedRecordingState,
+};
 
 // Configuration
-pub use config::load_config;
+pub use config::{
+    active_profile_name, ensure_starter_setup, load_base_config, load_config, reload_config,
+    save_config, select_profile, setup_status, start_config_watcher,
+};
 
 // Session operations (main API surface)
 pub use session::{
-    cancel_recording, load_sessions, load_transcript, orchestrate_async_transcription,
-    pause_recording, resume_recording, retranscribe_session, start_recording, stop_recording,
-    TranscriptionResult,
+    apply_retention_policy, cancel_recording, cancel_transcription, cleanup_drag_exports,
+    compare_models, continue_rapid_capture, copy_last_transcript, copy_session_audio_to_clipboard,
+    delete_sessions, diff_transcripts, export_clip, export_sessions, export_waveform_image,
+    filter_sessions, finalize_for_exit, generate_weekly_report, get_action_items,
+    get_frequently_viewed, get_last_session, get_recently_viewed, get_retention_candidates,
+    get_session_access, get_transcript_diff, get_transcript_history, group_sessions_by_day,
+    import_from_directory, list_transcript_versions, load_romanized_transcript, load_sessions,
+    load_transcript, migrate_legacy_sessions, open_transcript_in_editor,
+    orchestrate_async_transcription, pause_recording, prepare_drag_export, process_pending_now,
+    processing_speech_seconds, record_session_access, recording_activity, recover_checkpoint,
+    redo_last_operation, regenerate_previews, regenerate_titles, restart_recording,
+    restore_transcript_version, resume_recording, retranscribe_session, retranscribe_sessions,
+    reveal_session_audio, scan_storage_integrity, search_sessions, set_session_quality_flag,
+    set_session_rating, sort_sessions, start_auto_pause_monitor, start_batch_transcription,
+    start_meeting_recording, start_pause_reminder, start_recording, start_report_scheduler,
+    start_retention_task, start_segment_timer, start_transcript_versioning,
+    start_transcription_queue, stop_recording, tag_sessions, toggle_recording, undo_last_operation,
+    update_session_notes, use_session_cache, wait_for_transcriptions, AutoPauseEvent, SessionCache,
+    SharedSessionCache, TranscriptionResult,
+};
+
+// Integrations with other apps
+pub use integrations::{
+    create_tasks, email_session, find_calendar_event, get_remote_control_token,
+    list_paired_devices, publish_to_notion, set_integration_token, share_session,
+    start_mobile_sync, start_pairing, start_remote_control, unpair_device, MobileSyncEvent,
+    NativeWindow, RemoteCommand, ShareTarget,
 };
 
 // Utility functions
-pub use utils::{copy_to_clipboard, get_storage_dir};
+pub use utils::{copy_to_clipboard, get_storage_dir, ClipboardMechanism};
+
+// Redaction of transcripts leaving the app
+pub use redaction::redact_outgoing;
 
 // Audio level calculation
-pub use audio::get_audio_levels;
+pub use audio::{
+    check_microphone_permission, get_audio_levels, is_pre_roll_active, list_audio_devices,
+    save_input_device_settings, set_monitor_volume, set_pre_roll_enabled,
+    start_configured_pre_roll, start_device_watcher,
+};
+
+// Diagnostics
+pub use diagnostics::{
+    get_log_dir, read_recent_logs, run_health_check, DEFAULT_LOG_LINES, KEPT_LOG_FILES,
+    LOG_FILE_NAME, MAX_LOG_FILE_BYTES,
+};
+
+// Global hotkeys
+pub use hotkeys::{copy_transcript_hotkey, recording_hotkeys};
+
+// Power management
+pub use power::start_sleep_guard;
+
+// Scheduled recordings
+pub use scheduler::{
+    cancel_scheduled_recording, start_scheduler, SchedulerEvent, SchedulerState,
+    SharedSchedulerState,
+};
+
+// Headless transcription server (--serve)
+pub use server::run_transcription_server;
+
+// Transcript segments and confidence
+pub use transcription::{
+    load_structured_transcript, load_transcription_log, low_confidence_segments,
+    DEFAULT_LOW_CONFIDENCE_THRESHOLD,
+};
+
+// One-off transcription of files outside the archive
+pub use transcription::transcribe_standalone_file;
+
+// Whisper.cpp version and flag compatibility
+pub use transcription::{check_whisper_compatibility, load_whisper_binary_info};
+
+// Whisper model downloads and checksums
+pub use transcription::{download_model, verify_models};
+
+// Transcription benchmarking
+pub use transcription::{benchmark_model, ensure_benchmark_baseline, DEFAULT_BENCHMARK_SECONDS};
 
 // Transcription statistics and estimation
-pub use statistics::{estimate_transcription_time, extract_transcription_stats, TranscriptionEstimate};
+pub use statistics::{
+    estimate_for_duration, get_estimate_accuracy, get_model_stats, get_throughput_history,
+    get_transcription_stats, BenchmarkResult, EstimateAccuracy, ModelStats, ThroughputSample,
+    TranscriptionEstimate, TranscriptionStats,
+};
 
 // Note: Internal modules (audio, transcription) are kept private
 // They are implementation details and should not be accessed directly from outside
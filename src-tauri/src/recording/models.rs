@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Represents a single recording session with its metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Session {
     pub id: String,
     pub timestamp: String,
@@ -18,6 +19,148 @@ pub struct Session {
     /// Model used for transcription (for filtering estimates by model)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_path: Option<String>,
+    /// ISO 8601 timestamp of the last write to this session's metadata file
+    /// (used to resolve conflicting copies created by sync tools)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Local UTC offset (minutes east of UTC) at recording time, so the
+    /// session can be shown on the local calendar day it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utc_offset_minutes: Option<i32>,
+    /// Display title (e.g. the calendar event the session was recorded for)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Calendar event the session was recorded during
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meeting: Option<CalendarEvent>,
+    /// Preceding segment of an auto-segmented recording
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_session_id: Option<String>,
+    /// Whisper.cpp output and timing of the last transcription run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcription_log_path: Option<String>,
+    /// Recording time measured by the clock (excluding pauses); `duration`
+    /// is the length of the saved audio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wall_clock_duration: Option<f64>,
+    /// Seconds of recording time missing from the audio, set when capture
+    /// stalled noticeably (see `capture_gap`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture_gap_seconds: Option<f64>,
+    /// Name of the paired phone the recording was received from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_device: Option<String>,
+    /// Notion page the session was last published to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notion_page_url: Option<String>,
+    /// ISO 8601 timestamp of when action items were last sent to the task
+    /// manager
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tasks_created_at: Option<String>,
+    /// Language the transcript is in (ISO 639-1 code, e.g. "en"), as
+    /// reported by Whisper.cpp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Freeform notes about the recording that aren't part of the transcript
+    /// (e.g. "recorded in car, poor audio")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Star rating from 1 to 5 given by the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+    /// Quality problem found after transcription (or set by the user)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality_flag: Option<QualityFlag>,
+    /// Labels given by the user, e.g. "idea" or "work"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// How `title` was generated; None for titles from a meeting or phone,
+    /// which are never replaced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_source: Option<TitleSource>,
+    /// Whisper.cpp decoding settings of the last transcription; None when
+    /// Whisper.cpp's defaults were used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoding: Option<DecodingConfig>,
+    /// Microphone the recording was captured from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_device: Option<String>,
+    /// Seconds of the recording with speech, leaving out silence and pauses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speech_seconds: Option<f64>,
+}
+
+/// Where an automatic session title came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TitleSource {
+    /// The transcript's first sentence
+    FirstSentence,
+    /// A one-line summary written by a language model
+    Llm,
+}
+
+/// Why a recording was flagged as low quality
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityFlag {
+    /// Whisper.cpp was unsure of much of the transcript
+    LowConfidence,
+    /// The input level was too high and the audio distorted
+    Clipped,
+    /// Flagged by the user
+    Manual,
+}
+
+/// Criteria for listing sessions; unset criteria match every session
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SessionFilter {
+    /// Only sessions rated at least / at most this many stars
+    pub min_rating: Option<u8>,
+    pub max_rating: Option<u8>,
+    /// Only flagged (true) or unflagged (false) sessions
+    pub flagged: Option<bool>,
+    /// Only sessions with this tag (case-insensitive)
+    pub tag: Option<String>,
+}
+
+/// What a session listing is ordered by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSortField {
+    /// Recording time
+    #[default]
+    Recorded,
+    Duration,
+    /// Words in the transcript
+    WordCount,
+    Title,
+    /// When the transcript was last opened
+    LastAccessed,
+}
+
+/// Order of a session listing; newest first by default
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct SessionSort {
+    pub field: SessionSortField,
+    /// Lowest (oldest, shortest, A first) first instead of highest
+    pub ascending: bool,
+}
+
+/// Calendar event metadata (from the configured ICS calendar)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub title: String,
+    /// Start time in RFC 3339 format
+    pub start: String,
+    /// End time in RFC 3339 format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
 }
 
 /// Index containing all recording sessions
@@ -26,15 +169,1415 @@ pub struct SessionIndex {
     pub sessions: Vec<Session>,
 }
 
-/// Configuration for Whisper.cpp integration
+/// Transcript with per-segment timing and confidence
+///
+/// Stored as `text/{id}.json` next to the plain-text transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredTranscript {
+    pub version: u32,
+    pub segments: Vec<TranscriptSegment>,
+    /// Language Whisper.cpp transcribed in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// A transcript segment as reported by Whisper.cpp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// Segment start offset in milliseconds
+    pub start_ms: i64,
+    /// Segment end offset in milliseconds
+    pub end_ms: i64,
+    pub text: String,
+    /// Average log probability of the segment's tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_logprob: Option<f64>,
+    /// Probability that the segment contains no speech
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_speech_prob: Option<f64>,
+    /// Confidence in 0.0-1.0 (geometric mean token probability)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+
+/// Sessions recorded on the same local calendar day
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionDayGroup {
+    /// Local date in `YYYY-MM-DD` format
+    pub date: String,
+    /// Total recorded duration of the day's sessions in seconds
+    pub total_duration: f64,
+    /// Sessions recorded that day, newest first
+    pub sessions: Vec<Session>,
+}
+
+/// Recordings made on one local calendar day, for an activity heatmap
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DayActivity {
+    /// Local date in `YYYY-MM-DD` format
+    pub date: String,
+    pub count: usize,
+    /// Total recorded minutes
+    pub minutes: f64,
+}
+
+/// Temporary files prepared for dragging a session out of the app
+#[derive(Debug, Clone, Serialize)]
+pub struct DragExportBundle {
+    /// Absolute path of the temporary bundle folder
+    pub directory: String,
+    /// Absolute paths of the exported files (audio, transcript)
+    pub files: Vec<String>,
+}
+
+/// Action applied to several selected sessions at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOperation {
+    Delete,
+    Tag,
+    Export,
+    Retranscribe,
+}
+
+/// Session a bulk operation could not be applied to
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkFailure {
+    pub session_id: String,
+    pub error: String,
+}
+
+/// Outcome of a bulk operation, emitted once as `bulk-operation-complete`
+///
+/// Delete, tag and export apply to every session or none, so only
+/// retranscription reports failures.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkOperationSummary {
+    pub operation: BulkOperation,
+    pub succeeded: Vec<String>,
+    pub failed: Vec<BulkFailure>,
+    /// Folder holding the exported files (export only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_directory: Option<String>,
+}
+
+/// Destructive operation kept in the undo journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UndoableOperation {
+    Delete,
+    Retranscribe,
+    Tag,
+}
+
+/// Operation reverted by `undo_last_operation` or reapplied by
+/// `redo_last_operation`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WhisperConfig {
-    #[serde(rename = "whisperPath")]
+pub struct JournaledOperation {
+    pub operation: UndoableOperation,
+    pub session_ids: Vec<String>,
+    /// When the operation was first applied (RFC 3339)
+    pub created_at: String,
+}
+
+/// A week's sessions, their summaries and action items as one Markdown report
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyReport {
+    /// ISO week, e.g. `2024-W45`
+    pub week: String,
+    pub session_count: usize,
+    /// Total recorded seconds
+    pub total_duration: f64,
+    pub action_item_count: usize,
+    pub markdown: String,
+}
+
+/// Result of importing sessions from another ThoughtCast folder
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    /// Number of sessions added to the local index
+    pub imported: usize,
+    /// Number of sessions skipped because their id already exists locally
+    pub skipped_duplicates: usize,
+    /// Sessions skipped because their audio is already in the archive under
+    /// another id: imported session id → existing session id
+    pub linked_duplicates: BTreeMap<String, String>,
+    /// Relative paths of audio/transcript files missing in the source folder
+    pub missing_files: Vec<String>,
+    /// Sessions skipped because their id or file paths would point outside
+    /// the storage folder
+    pub skipped_invalid: Vec<String>,
+}
+
+/// Current schema version of config.json
+///
+/// Files without a `version` are the legacy flat format (version 0) and are
+/// migrated when loaded
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Application configuration, grouped into sections
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppConfig {
+    /// Schema version the file was written with
+    #[serde(default)]
+    pub version: u32,
+    pub transcription: TranscriptionConfig,
+    #[serde(default)]
+    pub audio: AudioConfig,
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+    #[serde(default)]
+    pub power: PowerConfig,
+    #[serde(default)]
+    pub server: ServerConfig,
+    /// Recordings started automatically at configured times or intervals
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<RecordingSchedule>,
+    /// Named bundles of settings, e.g. "quick-note" or "meeting"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, RecordingProfile>,
+    /// Profile applied at startup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    #[serde(default)]
+    pub reports: ReportConfig,
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+}
+
+/// Whisper.cpp binary, models and how they are run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionConfig {
     pub whisper_path: String,
-    #[serde(rename = "modelPath")]
     pub model_path: String,
-    #[serde(rename = "voiceNotesDir")]
+    /// Per-length model overrides; `modelPath` is used when no rule matches
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_rules: Vec<ModelRule>,
+    /// Fast model for two-pass transcription
+    ///
+    /// When set, a draft is transcribed (and auto-copied) with this model
+    /// first, then replaced by a pass with the regular model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_model_path: Option<String>,
+    /// Hardware acceleration flags passed to Whisper.cpp
+    #[serde(default)]
+    pub acceleration: AccelerationConfig,
+    /// Scheduling priority of the Whisper.cpp process
+    #[serde(default)]
+    pub priority: ProcessPriority,
+    /// Upper bound on Whisper.cpp CPU threads, applied to `acceleration.threads`
+    /// or to Whisper.cpp's default when no thread count is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_threads: Option<u32>,
+    /// Parallel Whisper.cpp processes for batch transcription (e.g. imported
+    /// sessions); interactive transcriptions always run one at a time
+    #[serde(default = "default_batch_workers")]
+    pub batch_workers: usize,
+    /// Minutes a Whisper.cpp run may take before it is stopped, so a hung
+    /// process can't hold up transcription; 0 for no limit
+    #[serde(default = "default_timeout_minutes")]
+    pub timeout_minutes: u32,
+    /// Spoken language passed to Whisper.cpp (e.g. "de"), or "auto" to
+    /// detect it per recording; Whisper.cpp's default (English) when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Replacements applied to every new transcript, e.g. for names Whisper
+    /// keeps misspelling
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub corrections: Vec<CorrectionRule>,
+    /// Also save a romanized copy of transcripts written in Cyrillic, Greek
+    /// or Japanese kana (`text/{id}.romanized.txt`)
+    #[serde(default)]
+    pub romanize: bool,
+    /// Steps run on each new transcript, in order (see `postprocess`); the
+    /// built-in pipeline when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postprocess: Option<Vec<PostprocessStep>>,
+    /// Sampling and beam search settings passed to Whisper.cpp
+    #[serde(default)]
+    pub decoding: DecodingConfig,
+    /// Download a missing model (e.g. `ggml-base.en.bin`) from the
+    /// Whisper.cpp model repository instead of failing the transcription
+    #[serde(default)]
+    pub auto_download_models: bool,
+}
+
+/// Whisper.cpp decoding settings, trading determinism for quality
+///
+/// Unset values keep Whisper.cpp's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DecodingConfig {
+    /// Sampling temperature; 0 always picks the likeliest text, higher
+    /// values allow more varied (and sometimes better) transcripts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Candidates sampled per segment when the temperature is above 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_of: Option<u32>,
+    /// Beams kept during beam search; larger is slower but more accurate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beam_size: Option<u32>,
+}
+
+/// A step of the post-transcription pipeline
+///
+/// The text steps (`clean`, `corrections`, `paragraphs`, `summarize`)
+/// rewrite Whisper's output in the listed order. `romanize` and `clipboard`
+/// run as soon as the transcript is saved, whatever their position.
+/// `title` and `export` run once the transcript is final (after any
+/// refinement pass), one after the other in the listed order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PostprocessStep {
+    /// Remove Whisper's timestamp lines
+    Clean,
+    /// Apply `corrections` and the punctuation rules of the language
+    Corrections,
+    /// Break the text into paragraphs of a few sentences
+    Paragraphs,
+    /// Start the transcript with a summary of its leading sentences
+    Summarize,
+    /// Save a romanized copy of Cyrillic, Greek or kana transcripts
+    Romanize,
+    /// Copy to the clipboard as set by `autoCopy`
+    Clipboard,
+    /// Title the session as set by `titles`
+    Title,
+    /// Publish to Notion and create tasks, as set up for each
+    Export,
+}
+
+/// OS scheduling priority for background transcription
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessPriority {
+    #[default]
+    Normal,
+    /// `nice 10` on macOS/Linux, below-normal priority class on Windows
+    BelowNormal,
+    /// Runs only when the machine is otherwise idle (`nice 19`, idle class)
+    Idle,
+}
+
+/// Audio capture settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioConfig {
+    /// Input device name; the system default is used when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_device: Option<String>,
+    /// Settings remembered per input device, keyed by device name, applied
+    /// whenever that device is used
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub input_devices: BTreeMap<String, InputDeviceSettings>,
+    /// Opt-in rolling buffer of audio captured before recording starts
+    pub pre_roll: PreRollConfig,
+    /// Thresholds below which a stopped recording is discarded as empty
+    pub empty_recording_guard: EmptyRecordingGuard,
+    /// Automatic splitting of long recordings into linked sessions
+    pub segmentation: SegmentationConfig,
+    /// Pause recording while the microphone is muted or unavailable
+    pub auto_pause: AutoPauseConfig,
+    /// Remind that a recording is still paused
+    pub pause_reminder: PauseReminderConfig,
+    /// Listen to the microphone while recording
+    pub monitor: MonitorConfig,
+    /// Start a new recording as soon as one is stopped, so each thought of a
+    /// brainstorm becomes its own note; stopping a recording too short or
+    /// quiet to keep ends the run
+    pub rapid_capture: bool,
+    /// Synthetic microphone used instead of the input device (for demos and
+    /// machines without audio hardware)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtual_input: Option<VirtualInput>,
+}
+
+/// Keyboard shortcuts, as accelerator strings (e.g. `"CmdOrCtrl+Shift+Space"`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HotkeyConfig {
+    /// Start or stop recording
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toggle_recording: Option<String>,
+    /// Copy the transcript of the latest transcribed session
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_last_transcript: Option<String>,
+    /// Also start or stop recording with the media play/pause key, including
+    /// headset and earbud buttons (other apps then won't receive it)
+    pub media_play_pause: bool,
+}
+
+/// Where and how recordings are stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StorageConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub voice_notes_dir: Option<String>,
+    /// How new session ids are generated
+    pub session_id_scheme: SessionIdScheme,
+    /// Keep the audio file after a successful transcription
+    pub keep_audio: bool,
+    /// Keep the history of transcripts in a git repository inside `text/`,
+    /// committing after each transcription and edit
+    pub version_transcripts: bool,
+    /// Name of new audio and transcript files, e.g. `{date}_{title}_{id}`
+    ///
+    /// Placeholders: `{date}` (2024-11-02), `{time}` (15-30-00), `{title}`
+    /// and `{id}`; the session id is appended when `{id}` is missing. Files
+    /// are named after the session id when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename_pattern: Option<String>,
+    /// How long recordings are kept
+    pub retention: RetentionConfig,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            voice_notes_dir: None,
+            session_id_scheme: SessionIdScheme::default(),
+            keep_audio: true,
+            version_transcripts: false,
+            filename_pattern: None,
+            retention: RetentionConfig::default(),
+        }
+    }
+}
+
+/// Age in days after which recordings are removed; unset keeps them forever
+///
+/// E.g. `{"audioDays": 30}` keeps transcripts forever and deletes audio a
+/// month after recording.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionConfig {
+    /// Delete the audio of transcribed sessions; untranscribed audio is kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_days: Option<u32>,
+    /// Delete whole sessions (audio, transcripts and logs) permanently
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript_days: Option<u32>,
+}
+
+/// What the retention policy removes from a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    DeleteAudio,
+    DeleteSession,
+}
+
+/// Session due for removal under the retention policy
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetentionCandidate {
+    pub session_id: String,
+    pub timestamp: String,
+    pub title: Option<String>,
+    pub action: RetentionAction,
+    /// Size of the files removed
+    pub bytes: u64,
+}
+
+/// How often and when a session's transcript was last opened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionAccess {
+    /// When the transcript was last opened (RFC 3339)
+    pub last_accessed: String,
+    pub access_count: u32,
+}
+
+/// Problems found by checking session records against the storage folder
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StorageIntegrityReport {
+    /// Sessions whose audio file is missing
+    pub missing_audio: Vec<String>,
+    /// Sessions whose transcript file is missing
+    pub missing_transcripts: Vec<String>,
+    /// Files (relative to the storage root) in the audio and text folders
+    /// that no session refers to
+    pub orphaned_files: Vec<String>,
+}
+
+/// How `scan_storage_integrity` resolves the problems it finds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityFix {
+    /// Point sessions at the files that exist: relink transcripts named
+    /// after their audio and clear paths of missing files (sessions that
+    /// still have audio are queued for transcription)
+    Repair,
+    /// Create a session for each orphaned audio file
+    Reindex,
+    /// Delete orphaned files; can be undone with `undo_last_operation`
+    Delete,
+}
+
+/// Power management
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PowerConfig {
+    /// Keep the system from sleeping while recording or transcribing
+    pub prevent_sleep: bool,
+    /// Queue transcriptions while on battery below `minBatteryPercent`
+    pub defer_on_battery: bool,
+    pub min_battery_percent: u8,
+    /// Queue transcriptions until no recording is in progress
+    pub defer_while_recording: bool,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        PowerConfig {
+            prevent_sleep: true,
+            defer_on_battery: false,
+            min_battery_percent: 50,
+            defer_while_recording: false,
+        }
+    }
+}
+
+/// Headless transcription server (`--serve`)
+///
+/// Other devices send WAV files to `http://{bindAddress}:{port}/jobs` and
+/// poll for the transcript. Listens on this machine only unless
+/// `bindAddress` is changed (e.g. to `"0.0.0.0"` for the local network),
+/// in which case clients must send `token` as a bearer token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerConfig {
+    pub bind_address: String,
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            bind_address: "127.0.0.1".to_string(),
+            port: 47823,
+            token: None,
+        }
+    }
+}
+
+/// What may leave this machine
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrivacyConfig {
+    /// Privacy mode: refuse every network feature (integrations, language
+    /// model titles, model downloads, e-mail, sharing, mobile sync and a
+    /// network-facing server), so audio and transcripts never leave the
+    /// machine. Services on this machine (e.g. a local language model) stay
+    /// available. A config that exists but can't be read counts as on.
+    pub offline: bool,
+    /// Sensitive data hidden from transcripts leaving the app
+    pub redaction: RedactionConfig,
+}
+
+/// Redaction of transcripts copied to the clipboard, exported or sent to
+/// other apps; the saved transcript keeps the original text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    /// E-mail addresses, e.g. `jane@example.com`
+    pub emails: bool,
+    /// Numbers of 7 to 15 digits, optionally grouped, e.g. `+1 (555) 123-4567`
+    pub phone_numbers: bool,
+    /// Numbers of 13 to 19 digits with a valid card checksum
+    pub credit_cards: bool,
+    /// Words or phrases to hide (case-insensitive, whole words), e.g. client
+    /// names
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub terms: Vec<String>,
+    /// Text put in place of each redacted match
+    pub replacement: String,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        RedactionConfig {
+            enabled: false,
+            emails: true,
+            phone_numbers: true,
+            credit_cards: true,
+            terms: Vec::new(),
+            replacement: "[redacted]".to_string(),
+        }
+    }
+}
+
+/// Interaction with other apps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IntegrationsConfig {
+    /// What gets copied to the clipboard automatically after transcription
+    pub auto_copy: AutoCopyConfig,
+    /// Meeting capture from a local calendar
+    pub calendar: CalendarConfig,
+    /// Local WebSocket server for hardware controllers (e.g. Stream Deck)
+    pub remote_control: RemoteControlConfig,
+    /// Endpoint receiving recordings from paired phones
+    pub mobile_sync: MobileSyncConfig,
+    /// How transcripts are e-mailed
+    pub email: EmailConfig,
+    /// Publishing transcripts to Notion
+    pub notion: NotionConfig,
+    /// Sending action items to a task manager
+    pub tasks: TasksConfig,
+    /// Automatic session titles
+    pub titles: TitleConfig,
+}
+
+/// Automatic session titles
+///
+/// Transcribed sessions without a title get the first sentence of their
+/// transcript. With `llmUrl` set to an OpenAI-compatible chat completions
+/// endpoint (e.g. `http://localhost:11434/v1/chat/completions` for Ollama),
+/// the model writes a one-line title instead, falling back to the first
+/// sentence when it can't be reached. Keep `llmApiKey` in
+/// `config.local.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TitleConfig {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_api_key: Option<String>,
+}
+
+impl Default for TitleConfig {
+    fn default() -> Self {
+        TitleConfig {
+            enabled: true,
+            llm_url: None,
+            llm_model: None,
+            llm_api_key: None,
+        }
+    }
+}
+
+/// Publishing transcripts to Notion
+///
+/// Each session becomes a page: an entry of `databaseId`, or a sub-page of
+/// `parentPageId`. `token` is the secret of a Notion internal integration
+/// with access to that database or page; keep it in `config.local.json`.
+/// With `autoPublish`, sessions are published once transcribed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotionConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_page_id: Option<String>,
+    pub auto_publish: bool,
+    /// Database properties to fill in; unset ones are skipped
+    pub title_property: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_property: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_property: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags_property: Option<String>,
+    /// Tags added to every published session
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Default for NotionConfig {
+    fn default() -> Self {
+        NotionConfig {
+            token: None,
+            database_id: None,
+            parent_page_id: None,
+            auto_publish: false,
+            title_property: "Name".to_string(),
+            duration_property: None,
+            date_property: None,
+            tags_property: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// Sending action items found in transcripts to a task manager
+///
+/// Each action item becomes a task linking back to the session's transcript.
+/// The Todoist API token is kept in the system keychain (see
+/// `set_integration_token`), not in the config file. With `autoCreate`,
+/// tasks are created once a session is transcribed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TasksConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<TaskProvider>,
+    pub auto_create: bool,
+    /// Todoist project for new tasks; the inbox when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub todoist_project_id: Option<String>,
+    /// Things list or project for new to-dos; the inbox when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub things_list: Option<String>,
+}
+
+/// Task manager receiving action items
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskProvider {
+    /// Todoist, through its REST API
+    Todoist,
+    /// Things 3 on macOS, through its URL scheme
+    Things,
+}
+
+/// E-mailing transcripts
+///
+/// With `smtp` set, e-mails are sent directly (optionally with the audio
+/// attached); otherwise a message is composed in the default mail client.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EmailConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<SmtpConfig>,
+}
+
+/// Outgoing mail server
+///
+/// Keep the password in the machine-local `config.local.json` rather than
+/// the synced config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Sender address, e.g. `"Ana <ana@example.com>"`
+    pub from: String,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        SmtpConfig {
+            host: String::new(),
+            port: 587,
+            security: SmtpSecurity::default(),
+            username: None,
+            password: None,
+            from: String::new(),
+        }
+    }
+}
+
+/// Encryption of the SMTP connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection (usually port 587)
+    #[default]
+    StartTls,
+    /// TLS from the start (usually port 465)
+    Tls,
+    /// Unencrypted, e.g. a relay on the local network
+    None,
+}
+
+/// Local WebSocket server reporting recording status and accepting commands
+///
+/// Listens on `127.0.0.1:{port}` only, for clients connecting with the
+/// remote control token. Changes apply after restarting the app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemoteControlConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for RemoteControlConfig {
+    fn default() -> Self {
+        RemoteControlConfig {
+            enabled: false,
+            port: 47821,
+        }
+    }
+}
+
+/// Endpoint for the mobile companion app
+///
+/// Listens on all network interfaces at `port` so phones on the same network
+/// can pair with a code shown on the desktop and then send recordings, which
+/// are added to the session index and transcribed. Changes apply after
+/// restarting the app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MobileSyncConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for MobileSyncConfig {
+    fn default() -> Self {
+        MobileSyncConfig {
+            enabled: false,
+            port: 47822,
+        }
+    }
+}
+
+/// Recording profile: settings applied on top of the base config
+///
+/// Lets switching between e.g. "dictate into clipboard, discard audio" and
+/// "archive full meeting" be a single action. Unset fields keep base values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_rules: Option<Vec<ModelRule>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_model_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_copy: Option<AutoCopyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_audio: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segmentation: Option<SegmentationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postprocess: Option<Vec<PostprocessStep>>,
+}
+
+/// Pomodoro-style auto-segmentation
+///
+/// Every `everyMinutes` of recorded audio, the current chunk is saved as its
+/// own session and transcribed while recording continues into the next,
+/// producing a chain of linked sessions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SegmentationConfig {
+    /// Segment length in minutes; segmentation is off when unset
+    pub every_minutes: Option<u32>,
+    /// Play a short chime at each segment boundary
+    pub chime: bool,
+}
+
+impl Default for SegmentationConfig {
+    fn default() -> Self {
+        SegmentationConfig {
+            every_minutes: None,
+            chime: true,
+        }
+    }
+}
+
+/// Calendar-aware meeting capture
+///
+/// Reads meetings from an ICS file (exported or synced from the calendar
+/// app) and prompts or automatically starts recording when one begins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CalendarConfig {
+    /// Path to the `.ics` calendar file; meeting capture is off when unset
+    pub ics_path: Option<String>,
+    pub action: MeetingAction,
+    /// Seconds between the announcement and an automatic start
+    pub grace_seconds: u32,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        CalendarConfig {
+            ics_path: None,
+            action: MeetingAction::default(),
+            grace_seconds: default_grace_seconds(),
+        }
+    }
+}
+
+/// What to do when a calendar meeting begins
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MeetingAction {
+    /// Ignore meetings
+    Off,
+    /// Emit a `meeting-starting` event so the UI can offer to record
+    #[default]
+    Prompt,
+    /// Start recording after the grace period (cancellable)
+    Auto,
+}
+
+/// Weekly summary reports
+///
+/// With `autoGenerate`, the current week's report is written every `day` at
+/// `at` (local `HH:MM`) to `exportDir` (the `reports` folder in the storage
+/// directory by default) and e-mailed to `emailTo` through the configured
+/// SMTP server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReportConfig {
+    pub auto_generate: bool,
+    pub day: chrono::Weekday,
+    pub at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_dir: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub email_to: Vec<String>,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        ReportConfig {
+            auto_generate: false,
+            day: chrono::Weekday::Fri,
+            at: "17:00".to_string(),
+            export_dir: None,
+            email_to: Vec::new(),
+        }
+    }
+}
+
+/// Automatic recording schedule
+///
+/// Either a daily time (`"at": "17:00"`) or an interval (`"everyMinutes": 60`,
+/// counted from local midnight), optionally limited to `days`
+/// (e.g. `["mon", "tue", "wed", "thu", "fri"]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingSchedule {
+    /// Shown in the start announcement, e.g. "Standup debrief"
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Local time of day in `HH:MM` format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+    /// Interval in minutes (used when `at` is not set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every_minutes: Option<u32>,
+    /// Days the schedule applies to; empty means every day
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<chrono::Weekday>,
+    /// Seconds between the announcement and the start, during which the
+    /// automatic start can be cancelled
+    #[serde(default = "default_grace_seconds")]
+    pub grace_seconds: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_grace_seconds() -> u32 {
+    10
+}
+
+fn default_batch_workers() -> usize {
+    1
+}
+
+fn default_timeout_minutes() -> u32 {
+    120
+}
+
+fn default_tone_amplitude() -> f32 {
+    0.5
+}
+
+/// Pre-roll capture settings
+///
+/// When enabled the microphone stays open while idle and the last `seconds`
+/// of audio (capped at 10) are kept in memory, so recordings include words
+/// spoken just before pressing record. Disabled by default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PreRollConfig {
+    pub enabled: bool,
+    pub seconds: f64,
+}
+
+impl Default for PreRollConfig {
+    fn default() -> Self {
+        PreRollConfig {
+            enabled: false,
+            seconds: 3.0,
+        }
+    }
+}
+
+/// Input settings for one audio device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputDeviceSettings {
+    /// Multiplier applied to the captured signal (1 = unchanged)
+    pub gain: f32,
+    /// Record only this channel (0-based) of a multi-channel interface;
+    /// all channels are recorded when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<u16>,
+    /// Preferred sample rate in Hz; the device default is used when unset
+    /// or unsupported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+}
+
+impl Default for InputDeviceSettings {
+    fn default() -> Self {
+        InputDeviceSettings {
+            gain: 1.0,
+            channel: None,
+            sample_rate: None,
+        }
+    }
+}
+
+/// Monitoring (listen-through) while recording
+///
+/// Plays the captured microphone signal back on `outputDevice` (the system
+/// default when unset) while recording, not while paused. Use headphones to
+/// avoid feedback. `volume` ranges from 0 to 2 and can be changed while
+/// recording. Disabled by default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MonitorConfig {
+    pub enabled: bool,
+    pub volume: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_device: Option<String>,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        MonitorConfig {
+            enabled: false,
+            volume: 1.0,
+            output_device: None,
+        }
+    }
+}
+
+/// Audio produced by the virtual microphone, e.g.
+/// `{ "type": "tone", "frequency": 440 }` or `{ "type": "wav", "path": "..." }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum VirtualInput {
+    /// Sine tone
+    Tone {
+        frequency: f32,
+        #[serde(default = "default_tone_amplitude")]
+        amplitude: f32,
+    },
+    /// Digital silence
+    Silence,
+    /// A WAV file, played once and followed by silence
+    Wav { path: String },
+}
+
+/// Automatic pause when the microphone stops delivering usable audio
+///
+/// Recording pauses once the input has been digitally silent (muted at the
+/// OS or hardware level) or has stopped delivering audio altogether (e.g.
+/// another app took exclusive access) for `seconds`, and resumes when audio
+/// returns. Disabled by default, since some noise suppression drivers also
+/// output pure silence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoPauseConfig {
+    pub enabled: bool,
+    pub seconds: u32,
+}
+
+impl Default for AutoPauseConfig {
+    fn default() -> Self {
+        AutoPauseConfig {
+            enabled: false,
+            seconds: 5,
+        }
+    }
+}
+
+/// Reminder for a recording left paused
+///
+/// Every `afterMinutes` a recording stays paused, a `paused-too-long` event
+/// is emitted (and a chime played), so a forgotten pause doesn't lose the
+/// rest of a meeting. Off when `afterMinutes` is unset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PauseReminderConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_minutes: Option<u32>,
+    /// Play a short chime with each reminder
+    pub chime: bool,
+}
+
+impl Default for PauseReminderConfig {
+    fn default() -> Self {
+        PauseReminderConfig {
+            after_minutes: None,
+            chime: true,
+        }
+    }
+}
+
+/// Discard accidental recordings (e.g. a double-pressed hotkey)
+///
+/// A recording is discarded if it is shorter than `minDurationSeconds` or its
+/// overall level stays below `minLevel` (same 0.0-1.0 scale as the level
+/// meter). Set a threshold to 0 to disable that check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EmptyRecordingGuard {
+    pub min_duration_seconds: f64,
+    pub min_level: f32,
+}
+
+impl Default for EmptyRecordingGuard {
+    fn default() -> Self {
+        EmptyRecordingGuard {
+            min_duration_seconds: 1.0,
+            min_level: 0.02,
+        }
+    }
+}
+
+/// Use a different model for recordings shorter than a threshold
+///
+/// e.g. `{ "maxDurationSeconds": 60, "modelPath": ".../ggml-tiny.en.bin" }`
+/// transcribes quick notes with a fast model and leaves longer dictation to
+/// the default `modelPath`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRule {
+    /// Rule applies to recordings shorter than this many seconds
+    pub max_duration_seconds: f64,
+    /// Model file used for matching recordings
+    pub model_path: String,
+}
+
+/// Replacement applied to transcripts
+///
+/// Matches whole words, case-insensitively
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionRule {
+    pub find: String,
+    pub replace: String,
+    /// Only applies to transcripts in this language (e.g. "de"); all
+    /// transcripts when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Hardware acceleration settings for Whisper.cpp
+///
+/// GPU backends (CUDA/Metal/Vulkan/OpenVINO) are chosen when whisper.cpp is
+/// compiled; these options select devices and tune the build at runtime.
+/// Flags specific to a particular build (e.g. `-ngl`) go in `extraArgs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccelerationConfig {
+    /// Force CPU inference (`--no-gpu`)
+    #[serde(default)]
+    pub disable_gpu: bool,
+    /// GPU device index (`--device`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_device: Option<u32>,
+    /// Number of CPU threads (`-t`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+    /// Enable flash attention (`-fa`)
+    #[serde(default)]
+    pub flash_attention: bool,
+    /// OpenVINO encoder device, e.g. `"GPU"` (`-oved`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub openvino_device: Option<String>,
+    /// Additional raw arguments appended to the Whisper.cpp command line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+}
+
+/// Automatic clipboard copy behavior after transcription completes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoCopyConfig {
+    #[serde(default)]
+    pub mode: AutoCopyMode,
+    /// Template for `template` mode, e.g. `"{date} {time}\n{transcript}"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// What to place on the clipboard when a transcription completes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoCopyMode {
+    /// Leave the clipboard untouched
+    Off,
+    /// Copy the cleaned transcript (original behavior)
+    #[default]
+    Raw,
+    /// Copy the transcript formatted with the configured template
+    Template,
+    /// Copy the transcript's leading sentences
+    Summary,
+}
+
+/// Scheme used to generate new session ids
+///
+/// Both schemes start with the recording timestamp; collisions are always
+/// resolved with a numeric counter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionIdScheme {
+    /// `2024-11-02_15-30-00` (legacy format)
+    Timestamp,
+    /// `2024-11-02_15-30-00_a3f9` (timestamp + short random suffix)
+    #[default]
+    TimestampSuffix,
+}
+
+/// Result of one environment self-check
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    /// Machine-readable check id, e.g. `"microphone"`
+    pub name: String,
+    pub passed: bool,
+    /// What was found, or why the check failed
+    pub message: String,
+}
+
+/// How an e-mailed transcript was handed off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailDelivery {
+    /// Sent through the configured SMTP server
+    Sent,
+    /// Opened as a draft in the default mail client
+    Composed,
+}
+
+/// Audio devices available to the app, by name
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioDeviceList {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub default_input: Option<String>,
+    pub default_output: Option<String>,
+}
+
+/// Whether the OS lets the app record from the microphone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)] // Each platform reports only some of these
+pub enum MicrophonePermission {
+    Granted,
+    Denied,
+    /// Not asked yet; the system prompt shows on the first recording
+    NotDetermined,
+    /// Blocked by a policy (e.g. parental controls or MDM) the user can't change
+    Restricted,
+    /// The platform has no microphone permission, or it couldn't be read
+    Unknown,
+}
+
+/// Step taken along with a microphone permission check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MicrophonePermissionAction {
+    /// Briefly open the microphone so the system asks for permission
+    Prompt,
+    /// Open the system settings pane where the permission is granted
+    OpenSettings,
+}
+
+/// Microphone permission and how to grant it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MicrophonePermissionStatus {
+    pub permission: MicrophonePermission,
+    /// What to do to allow recording, when it isn't granted
+    pub guidance: Option<String>,
+    /// Link to the system settings pane for the permission, if there is one
+    pub settings_url: Option<String>,
+}
+
+/// Code a phone enters to pair with this desktop
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingCode {
+    /// Six-digit single-use code
+    pub code: String,
+    pub expires_in_seconds: u64,
+    /// Port of the mobile sync endpoint
+    pub port: u16,
+}
+
+/// Phone allowed to send recordings to this desktop
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairedDevice {
+    pub id: String,
+    pub name: String,
+    /// Time of pairing in RFC 3339 format
+    pub paired_at: String,
+}
+
+/// Saved version of a session's transcript
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptVersion {
+    pub commit_id: String,
+    /// What changed, e.g. "Transcribe 2024-11-02_15-30-00"
+    pub message: String,
+    /// Time of the change in RFC 3339 format
+    pub timestamp: String,
+}
+
+/// Earlier transcript of a session, kept when it was transcribed again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedTranscriptVersion {
+    /// Version number, counting up from 1
+    pub version: u32,
+    /// When the transcript was replaced (RFC 3339)
+    pub saved_at: String,
+    /// Model that produced the transcript
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub preview: String,
+}
+
+/// Word-level comparison of two transcripts of a session
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptDiff {
+    /// Model that produced each transcript, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_a: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_b: Option<String>,
+    /// Runs of words kept, removed from the first transcript or added in the
+    /// second, in reading order
+    pub chunks: Vec<DiffChunk>,
+    pub words_removed: usize,
+    pub words_added: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffChunk {
+    pub change: DiffChange,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffChange {
+    Equal,
+    Removed,
+    Added,
+}
+
+/// Transcripts of the same session audio by two models, for choosing the
+/// default model
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelComparison {
+    pub session_id: String,
+    /// Length of the audio in seconds
+    pub audio_duration: f64,
+    pub model_a: ModelRun,
+    pub model_b: ModelRun,
+    /// Word-level changes from model A's transcript to model B's
+    pub diff: TranscriptDiff,
+}
+
+/// One model's transcription in a `ModelComparison`
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelRun {
+    pub model_path: String,
+    pub transcript: String,
+    pub word_count: usize,
+    /// Wall-clock time Whisper.cpp took in seconds
+    pub elapsed_seconds: f64,
+    /// Processing time / audio duration (lower is faster)
+    pub realtime_factor: f64,
+}
+
+/// Appearance of an exported waveform image
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WaveformStyle {
+    pub format: WaveformFormat,
+    /// Image size in pixels
+    pub width: u32,
+    pub height: u32,
+    /// Number of bars the waveform is drawn with
+    pub bars: u32,
+    /// Bar color as `#rrggbb`
+    pub color: String,
+    /// Background color as `#rrggbb`; transparent when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// Snippet to draw; the whole recording when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_seconds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_seconds: Option<f64>,
+}
+
+impl Default for WaveformStyle {
+    fn default() -> Self {
+        Self {
+            format: WaveformFormat::Png,
+            width: 1200,
+            height: 300,
+            bars: 120,
+            color: "#6366f1".to_string(),
+            background: None,
+            start_seconds: None,
+            end_seconds: None,
+        }
+    }
+}
+
+/// Image format of an exported waveform
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WaveformFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+/// Transcript format of an exported clip; the audio is always WAV
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipFormat {
+    /// Plain text
+    #[default]
+    Txt,
+    /// SubRip subtitles, timed from the start of the clip
+    Srt,
+}
+
+/// Files written by a clip export
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipExport {
+    pub audio_path: String,
+    /// None when the session has no segment data to cut the transcript from
+    pub transcript_path: Option<String>,
 }
 
 /// Event payload for transcription completion
@@ -43,6 +1586,106 @@ pub struct TranscriptionCompleteEvent {
     pub session: Session,
 }
 
+/// Event payload for a refined (second-pass) transcript replacing the draft
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptRefinedEvent {
+    pub session: Session,
+}
+
+/// Progress of one session in a batch transcription
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProgressEvent {
+    /// Index of the worker handling the session
+    pub worker: usize,
+    pub session_id: String,
+    pub status: BatchItemStatus,
+    /// Updated session once transcribed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<Session>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Sessions finished so far (successfully or not) out of `total`
+    pub completed: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchItemStatus {
+    Started,
+    Completed,
+    Failed,
+}
+
+/// Event payload for scheduled recording announcements, starts and cancellations
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledRecordingEvent {
+    /// Name of the schedule (or meeting title) that fired
+    pub schedule: String,
+    /// Grace period before the automatic start, in seconds
+    pub grace_seconds: u32,
+    /// Calendar event that triggered the start (meeting capture)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meeting: Option<CalendarEvent>,
+}
+
+/// Event payload for a recording paused automatically
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingAutoPausedEvent {
+    pub reason: String,
+}
+
+/// Event payload for a recording that has been paused longer than
+/// `audio.pauseReminder.afterMinutes`
+#[derive(Debug, Clone, Serialize)]
+pub struct PausedTooLongEvent {
+    pub paused_seconds: f64,
+}
+
+/// Event payload for a transcription queued until conditions allow it
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionDeferredEvent {
+    pub session_id: String,
+    pub reason: String,
+}
+
+/// Whether transcription is set up, for the setup wizard
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupStatus {
+    pub configured: bool,
+    /// Transcribing with the small starter model downloaded on first run
+    pub starter_model: bool,
+    /// Model the setup wizard suggests for better transcripts
+    pub recommended_model: String,
+    /// Why transcription isn't set up
+    pub error: Option<String>,
+}
+
+/// What was found out about the Whisper.cpp binary, checked when settings
+/// are saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhisperBinaryInfo {
+    pub whisper_path: String,
+    /// Version reported by the binary, if it reports one
+    pub version: Option<String>,
+    /// Configured flags missing from the binary's `--help` output
+    pub unsupported_flags: Vec<String>,
+    /// ISO 8601 timestamp of the check
+    pub checked_at: String,
+}
+
+/// Event payload for a recording made on the default input device because
+/// the selected one was held by another app
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InputDeviceFallbackEvent {
+    /// The configured device
+    pub requested: String,
+    /// The device recorded from instead
+    pub used: String,
+    /// Error the configured device was opened with
+    pub reason: String,
+}
+
 /// Event payload for transcription errors
 #[derive(Debug, Clone, Serialize)]
 pub struct TranscriptionErrorEvent {
@@ -66,6 +1709,7 @@ mod tests {
             clipboard_copied: true,
             transcription_time_seconds: Some(6.8),
             model_path: Some("/path/to/model.bin".to_string()),
+            ..Session::default()
         };
 
         let json = serde_json::to_string(&session).unwrap();
@@ -101,6 +1745,7 @@ mod tests {
         assert_eq!(session.clipboard_copied, false);
         assert_eq!(session.transcription_time_seconds, None);
         assert_eq!(session.model_path, None);
+        assert_eq!(session.updated_at, None);
     }
 
     #[test]
@@ -116,6 +1761,7 @@ mod tests {
                 clipboard_copied: true,
                 transcription_time_seconds: Some(4.5),
                 model_path: Some("/model.bin".to_string()),
+                ..Session::default()
             },
             Session {
                 id: "session2".to_string(),
@@ -124,9 +1770,7 @@ mod tests {
                 duration: 45.0,
                 preview: "Second session".to_string(),
                 transcript_path: "text/session2.txt".to_string(),
-                clipboard_copied: false,
-                transcription_time_seconds: None,
-                model_path: None,
+                ..Session::default()
             },
         ];
 
@@ -143,48 +1787,188 @@ mod tests {
     }
 
     #[test]
-    fn test_whisper_config_serialization() {
-        let config = WhisperConfig {
-            whisper_path: "/path/to/whisper".to_string(),
-            model_path: "/path/to/model.bin".to_string(),
-            voice_notes_dir: Some("/path/to/notes".to_string()),
+    fn test_app_config_serialization() {
+        let config = AppConfig {
+            version: CONFIG_VERSION,
+            transcription: TranscriptionConfig {
+                whisper_path: "/path/to/whisper".to_string(),
+                model_path: "/path/to/model.bin".to_string(),
+                model_rules: Vec::new(),
+                draft_model_path: None,
+                acceleration: AccelerationConfig::default(),
+                priority: ProcessPriority::default(),
+                max_threads: None,
+                batch_workers: 1,
+                timeout_minutes: 120,
+                language: None,
+                corrections: Vec::new(),
+                romanize: false,
+                postprocess: None,
+                decoding: DecodingConfig::default(),
+                auto_download_models: false,
+            },
+            audio: AudioConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            storage: StorageConfig {
+                voice_notes_dir: Some("/path/to/notes".to_string()),
+                session_id_scheme: SessionIdScheme::Timestamp,
+                keep_audio: true,
+                version_transcripts: false,
+                filename_pattern: None,
+                retention: RetentionConfig::default(),
+            },
+            integrations: IntegrationsConfig::default(),
+            power: PowerConfig::default(),
+            server: ServerConfig::default(),
+            schedules: Vec::new(),
+            profiles: BTreeMap::new(),
+            active_profile: None,
+            reports: ReportConfig::default(),
+            privacy: PrivacyConfig::default(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
-        let deserialized: WhisperConfig = serde_json::from_str(&json).unwrap();
+        let deserialized: AppConfig = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(deserialized.whisper_path, config.whisper_path);
-        assert_eq!(deserialized.model_path, config.model_path);
-        assert_eq!(deserialized.voice_notes_dir, config.voice_notes_dir);
+        assert_eq!(deserialized, config);
+        assert_eq!(deserialized.storage.session_id_scheme, SessionIdScheme::Timestamp);
     }
 
     #[test]
-    fn test_whisper_config_camel_case_fields() {
+    fn test_app_config_camel_case_fields() {
         // Test that JSON uses camelCase as expected by frontend
         let json = r#"{
-            "whisperPath": "/usr/bin/whisper",
-            "modelPath": "/models/base.bin",
-            "voiceNotesDir": "/notes"
+            "version": 1,
+            "transcription": { "whisperPath": "/usr/bin/whisper", "modelPath": "/models/base.bin" },
+            "storage": { "voiceNotesDir": "/notes" }
+        }"#;
+
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.transcription.whisper_path, "/usr/bin/whisper");
+        assert_eq!(config.transcription.model_path, "/models/base.bin");
+        assert_eq!(config.storage.voice_notes_dir, Some("/notes".to_string()));
+    }
+
+    #[test]
+    fn test_app_config_section_defaults() {
+        let json = r#"{
+            "version": 1,
+            "transcription": { "whisperPath": "/usr/bin/whisper", "modelPath": "/models/base.bin" }
+        }"#;
+
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.transcription.whisper_path, "/usr/bin/whisper");
+        assert_eq!(config.transcription.model_path, "/models/base.bin");
+        assert_eq!(config.storage.voice_notes_dir, None);
+        assert_eq!(config.storage.session_id_scheme, SessionIdScheme::TimestampSuffix);
+        assert!(config.storage.keep_audio);
+        assert_eq!(config.integrations.auto_copy.mode, AutoCopyMode::Raw);
+        assert_eq!(config.hotkeys.toggle_recording, None);
+    }
+
+    #[test]
+    fn test_app_config_auto_copy() {
+        let json = r#"{
+            "version": 1,
+            "transcription": { "whisperPath": "/usr/bin/whisper", "modelPath": "/models/base.bin" },
+            "integrations": {
+                "autoCopy": { "mode": "template", "template": "{date}: {transcript}" }
+            }
+        }"#;
+
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.integrations.auto_copy.mode, AutoCopyMode::Template);
+        assert_eq!(
+            config.integrations.auto_copy.template,
+            Some("{date}: {transcript}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_empty_recording_guard_defaults() {
+        let json = r#"{
+            "version": 1,
+            "transcription": { "whisperPath": "/path/to/whisper", "modelPath": "/path/to/model.bin" },
+            "audio": { "emptyRecordingGuard": { "minDurationSeconds": 0 } }
+        }"#;
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.audio.empty_recording_guard.min_duration_seconds, 0.0);
+        assert_eq!(
+            config.audio.empty_recording_guard.min_level,
+            EmptyRecordingGuard::default().min_level
+        );
+    }
+
+    #[test]
+    fn test_recording_schedule_parsing() {
+        let json = r#"{
+            "version": 1,
+            "transcription": { "whisperPath": "/path/to/whisper", "modelPath": "/path/to/model.bin" },
+            "schedules": [{ "name": "Standup debrief", "at": "17:00", "days": ["mon", "fri"] }]
+        }"#;
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+        let schedule = &config.schedules[0];
+
+        assert!(schedule.enabled);
+        assert_eq!(schedule.grace_seconds, 10);
+        assert_eq!(schedule.days, vec![chrono::Weekday::Mon, chrono::Weekday::Fri]);
+    }
+
+    #[test]
+    fn test_app_config_model_rules() {
+        let json = r#"{
+            "version": 1,
+            "transcription": {
+                "whisperPath": "/path/to/whisper",
+                "modelPath": "/path/to/ggml-medium.bin",
+                "modelRules": [{ "maxDurationSeconds": 60, "modelPath": "/path/to/ggml-tiny.en.bin" }]
+            }
+        }"#;
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            config.transcription.model_rules,
+            vec![ModelRule {
+                max_duration_seconds: 60.0,
+                model_path: "/path/to/ggml-tiny.en.bin".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_app_config_acceleration() {
+        let json = r#"{
+            "version": 1,
+            "transcription": {
+                "whisperPath": "/usr/bin/whisper",
+                "modelPath": "/models/base.bin",
+                "acceleration": { "threads": 8, "flashAttention": true, "extraArgs": ["-ngl", "99"] }
+            }
         }"#;
 
-        let config: WhisperConfig = serde_json::from_str(json).unwrap();
+        let config: AppConfig = serde_json::from_str(json).unwrap();
+        let acceleration = &config.transcription.acceleration;
 
-        assert_eq!(config.whisper_path, "/usr/bin/whisper");
-        assert_eq!(config.model_path, "/models/base.bin");
-        assert_eq!(config.voice_notes_dir, Some("/notes".to_string()));
+        assert_eq!(acceleration.threads, Some(8));
+        assert!(acceleration.flash_attention);
+        assert!(!acceleration.disable_gpu);
+        assert_eq!(acceleration.extra_args, vec!["-ngl", "99"]);
     }
 
     #[test]
-    fn test_whisper_config_optional_voice_notes_dir() {
+    fn test_app_config_session_id_scheme() {
         let json = r#"{
-            "whisperPath": "/usr/bin/whisper",
-            "modelPath": "/models/base.bin"
+            "version": 1,
+            "transcription": { "whisperPath": "/usr/bin/whisper", "modelPath": "/models/base.bin" },
+            "storage": { "sessionIdScheme": "timestamp" }
         }"#;
 
-        let config: WhisperConfig = serde_json::from_str(json).unwrap();
+        let config: AppConfig = serde_json::from_str(json).unwrap();
 
-        assert_eq!(config.whisper_path, "/usr/bin/whisper");
-        assert_eq!(config.model_path, "/models/base.bin");
-        assert_eq!(config.voice_notes_dir, None);
+        assert_eq!(config.storage.session_id_scheme, SessionIdScheme::Timestamp);
     }
 }
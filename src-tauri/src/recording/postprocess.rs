@@ -0,0 +1,207 @@
+use crate::recording::models::{PostprocessStep, TranscriptionConfig};
+use crate::recording::transcription::text_processor::{
+    apply_language_rules, clean_transcript, extract_summary,
+};
+
+/// Sentences per paragraph for the `Paragraphs` step
+const PARAGRAPH_SENTENCES: usize = 4;
+
+/// Leading sentences quoted by the `Summarize` step
+const SUMMARY_SENTENCES: usize = 2;
+
+/// Steps run on new transcripts: the configured `postprocess` list, or
+/// the built-in pipeline when none is set
+///
+/// The built-in pipeline cleans, corrects, romanizes (when `romanize` is
+/// on), copies to the clipboard, titles and exports. Where the steps run is
+/// described on `PostprocessStep`.
+pub fn pipeline(config: &TranscriptionConfig) -> Vec<PostprocessStep> {
+    if let Some(steps) = &config.postprocess {
+        return steps.clone();
+    }
+
+    let mut steps = vec![PostprocessStep::Clean, PostprocessStep::Corrections];
+    if config.romanize {
+        steps.push(PostprocessStep::Romanize);
+    }
+    steps.extend([
+        PostprocessStep::Clipboard,
+        PostprocessStep::Title,
+        PostprocessStep::Export,
+    ]);
+    steps
+}
+
+/// Whether the pipeline of the current config includes `step`
+pub fn step_enabled(step: PostprocessStep) -> bool {
+    crate::recording::load_config()
+        .map(|config| pipeline(&config.transcription).contains(&step))
+        .unwrap_or(true)
+}
+
+/// Steps of the current config's pipeline that act on the saved transcript
+/// once it is final (`Title`, `Export`), in the listed order
+pub fn saved_transcript_steps() -> Vec<PostprocessStep> {
+    crate::recording::load_config()
+        .map(|config| after_save(&pipeline(&config.transcription)))
+        .unwrap_or_else(|_| vec![PostprocessStep::Title, PostprocessStep::Export])
+}
+
+/// Run the text steps of a pipeline, in order, on Whisper's raw output
+///
+/// The other steps act on the saved transcript and are run by the code that
+/// saves it.
+pub fn process_text(
+    steps: &[PostprocessStep],
+    raw_transcript: &str,
+    language: Option<&str>,
+    config: &TranscriptionConfig,
+) -> String {
+    steps
+        .iter()
+        .fold(raw_transcript.to_string(), |text, step| match step {
+            PostprocessStep::Clean => clean_transcript(&text),
+            PostprocessStep::Corrections => {
+                apply_language_rules(&text, language, &config.corrections)
+            }
+            PostprocessStep::Paragraphs => paragraphs(&text),
+            PostprocessStep::Summarize => summarize(&text),
+            PostprocessStep::Romanize
+            | PostprocessStep::Clipboard
+            | PostprocessStep::Title
+            | PostprocessStep::Export => text,
+        })
+}
+
+/// The steps of `steps` run after the transcript is final, in order
+fn after_save(steps: &[PostprocessStep]) -> Vec<PostprocessStep> {
+    steps
+        .iter()
+        .copied()
+        .filter(|step| matches!(step, PostprocessStep::Title | PostprocessStep::Export))
+        .collect()
+}
+
+/// Start the text with a summary paragraph of its leading sentences
+fn summarize(text: &str) -> String {
+    let summary = extract_summary(text, SUMMARY_SENTENCES);
+    if summary.is_empty() {
+        return text.to_string();
+    }
+    format!("Summary: {}\n\n{}", summary, text)
+}
+
+/// Rejoin the text's lines and break it into paragraphs of a few sentences
+fn paragraphs(text: &str) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut sentences = 0;
+
+    for word in text.split_whitespace() {
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+
+        if word.ends_with(['.', '!', '?']) {
+            sentences += 1;
+            if sentences == PARAGRAPH_SENTENCES {
+                paragraphs.push(std::mem::take(&mut current));
+                sentences = 0;
+            }
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::models::CorrectionRule;
+
+    fn config(postprocess: Option<Vec<PostprocessStep>>) -> TranscriptionConfig {
+        serde_json::from_value::<TranscriptionConfig>(serde_json::json!({
+            "whisperPath": "/usr/bin/whisper",
+            "modelPath": "/models/base.bin",
+        }))
+        .map(|config| TranscriptionConfig {
+            postprocess,
+            corrections: vec![CorrectionRule {
+                find: "thought cast".to_string(),
+                replace: "ThoughtCast".to_string(),
+                language: None,
+            }],
+            ..config
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_default_pipeline_matches_built_in_behavior() {
+        let default = config(None);
+        assert_eq!(
+            pipeline(&default),
+            vec![
+                PostprocessStep::Clean,
+                PostprocessStep::Corrections,
+                PostprocessStep::Clipboard,
+                PostprocessStep::Title,
+                PostprocessStep::Export,
+            ]
+        );
+
+        let raw = "[00:00:00.000 --> 00:00:02.000]\nI like thought cast.";
+        assert_eq!(
+            process_text(&pipeline(&default), raw, None, &default),
+            "I like ThoughtCast."
+        );
+    }
+
+    #[test]
+    fn test_runs_only_configured_steps_in_order() {
+        let custom = config(Some(vec![
+            PostprocessStep::Clean,
+            PostprocessStep::Paragraphs,
+        ]));
+        let raw = "One. Two.\nThree. Four. Five thought cast.";
+
+        assert_eq!(
+            process_text(&pipeline(&custom), raw, None, &custom),
+            "One. Two. Three. Four.\n\nFive thought cast."
+        );
+    }
+
+    #[test]
+    fn test_summarize_step() {
+        let custom = config(Some(vec![
+            PostprocessStep::Clean,
+            PostprocessStep::Summarize,
+        ]));
+        let raw = "First point. Second point! Third point.";
+
+        assert_eq!(
+            process_text(&pipeline(&custom), raw, None, &custom),
+            "Summary: First point. Second point!\n\nFirst point. Second point! Third point."
+        );
+        assert_eq!(summarize(""), "");
+    }
+
+    #[test]
+    fn test_saved_transcript_steps_keep_pipeline_order() {
+        let steps = [
+            PostprocessStep::Export,
+            PostprocessStep::Clean,
+            PostprocessStep::Clipboard,
+            PostprocessStep::Title,
+        ];
+
+        assert_eq!(
+            after_save(&steps),
+            vec![PostprocessStep::Export, PostprocessStep::Title]
+        );
+    }
+}
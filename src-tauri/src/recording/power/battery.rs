@@ -0,0 +1,228 @@
+use crate::recording::config::load_config;
+use crate::recording::models::PowerConfig;
+use crate::recording::state::SharedRecordingState;
+
+/// Power source and charge level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatteryStatus {
+    pub on_battery: bool,
+    /// Charge percentage, when the platform reports it
+    pub percent: Option<u8>,
+}
+
+/// Why transcription should wait right now under the configured power settings
+pub fn transcription_deferral(state: &SharedRecordingState) -> Option<String> {
+    let config = load_config().ok()?.power;
+    let recording = state.lock().map(|s| s.is_active()).unwrap_or(false);
+    let battery = if config.defer_on_battery {
+        battery_status()
+    } else {
+        None
+    };
+
+    deferral_reason(&config, battery, recording)
+}
+
+/// Why heavy transcription work should wait, if it should
+///
+/// `recording` is whether a recording is in progress.
+pub fn deferral_reason(
+    config: &PowerConfig,
+    battery: Option<BatteryStatus>,
+    recording: bool,
+) -> Option<String> {
+    if config.defer_while_recording && recording {
+        return Some("Waiting for the current recording to finish".to_string());
+    }
+
+    let battery = battery.filter(|battery| config.defer_on_battery && battery.on_battery)?;
+    match battery.percent {
+        Some(percent) if percent >= config.min_battery_percent => None,
+        Some(percent) => Some(format!(
+            "On battery at {}% (below {}%)",
+            percent, config.min_battery_percent
+        )),
+        None => Some("On battery power".to_string()),
+    }
+}
+
+/// Current battery status, or None on machines without a battery
+pub fn battery_status() -> Option<BatteryStatus> {
+    platform::battery_status()
+}
+
+/// Parse `pmset -g batt` output, e.g.
+/// `Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1)\t85%; discharging;`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_pmset(output: &str) -> Option<BatteryStatus> {
+    let mut lines = output.lines();
+    let on_battery = lines.next()?.contains("'Battery Power'");
+
+    let percent = lines
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ';'))
+        .find_map(|word| word.strip_suffix('%')?.parse().ok());
+
+    // Desktops report AC power and no battery
+    if !on_battery && percent.is_none() {
+        return None;
+    }
+
+    Some(BatteryStatus {
+        on_battery,
+        percent,
+    })
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{parse_pmset, BatteryStatus};
+    use std::process::Command;
+
+    pub fn battery_status() -> Option<BatteryStatus> {
+        let output = Command::new("pmset").args(["-g", "batt"]).output().ok()?;
+        parse_pmset(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// Linux: read the first battery under /sys/class/power_supply
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use super::BatteryStatus;
+    use std::fs;
+    use std::path::Path;
+
+    const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+    pub fn battery_status() -> Option<BatteryStatus> {
+        let battery = fs::read_dir(POWER_SUPPLY_DIR)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|path| read_attribute(path, "type").as_deref() == Some("Battery"))?;
+
+        Some(BatteryStatus {
+            on_battery: read_attribute(&battery, "status").as_deref() == Some("Discharging"),
+            percent: read_attribute(&battery, "capacity").and_then(|c| c.parse().ok()),
+        })
+    }
+
+    fn read_attribute(device: &Path, name: &str) -> Option<String> {
+        fs::read_to_string(device.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::BatteryStatus;
+
+    /// SYSTEM_POWER_STATUS
+    #[repr(C)]
+    #[derive(Default)]
+    struct SystemPowerStatus {
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_life_percent: u8,
+        system_status_flag: u8,
+        battery_life_time: u32,
+        battery_full_life_time: u32,
+    }
+
+    const AC_OFFLINE: u8 = 0;
+    const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+    const BATTERY_PERCENT_UNKNOWN: u8 = 255;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+    }
+
+    pub fn battery_status() -> Option<BatteryStatus> {
+        let mut status = SystemPowerStatus::default();
+        if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+            return None;
+        }
+
+        if status.battery_flag & BATTERY_FLAG_NO_BATTERY != 0 {
+            return None;
+        }
+
+        Some(BatteryStatus {
+            on_battery: status.ac_line_status == AC_OFFLINE,
+            percent: (status.battery_life_percent != BATTERY_PERCENT_UNKNOWN)
+                .then_some(status.battery_life_percent),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PowerConfig {
+        PowerConfig {
+            defer_on_battery: true,
+            min_battery_percent: 50,
+            ..Default::default()
+        }
+    }
+
+    fn on_battery(percent: u8) -> Option<BatteryStatus> {
+        Some(BatteryStatus {
+            on_battery: true,
+            percent: Some(percent),
+        })
+    }
+
+    #[test]
+    fn test_defers_on_low_battery() {
+        assert!(deferral_reason(&config(), on_battery(30), false).is_some());
+        assert!(deferral_reason(&config(), on_battery(80), false).is_none());
+    }
+
+    #[test]
+    fn test_no_deferral_on_ac_or_without_battery() {
+        let charging = Some(BatteryStatus {
+            on_battery: false,
+            percent: Some(10),
+        });
+
+        assert!(deferral_reason(&config(), charging, false).is_none());
+        assert!(deferral_reason(&config(), None, false).is_none());
+    }
+
+    #[test]
+    fn test_battery_deferral_disabled_by_default() {
+        assert!(deferral_reason(&PowerConfig::default(), on_battery(5), false).is_none());
+    }
+
+    #[test]
+    fn test_defers_while_recording() {
+        let config = PowerConfig {
+            defer_while_recording: true,
+            ..Default::default()
+        };
+
+        assert!(deferral_reason(&config, None, true).is_some());
+        assert!(deferral_reason(&config, None, false).is_none());
+    }
+
+    #[test]
+    fn test_parse_pmset() {
+        let battery = "Now drawing from 'Battery Power'\n \
+                       -InternalBattery-0 (id=4653155)\t85%; discharging; 5:12 remaining present: true";
+        let charging = "Now drawing from 'AC Power'\n \
+                        -InternalBattery-0 (id=4653155)\t100%; charged; 0:00 remaining present: true";
+
+        assert_eq!(
+            parse_pmset(battery),
+            Some(BatteryStatus {
+                on_battery: true,
+                percent: Some(85)
+            })
+        );
+        assert_eq!(parse_pmset(charging).map(|b| b.on_battery), Some(false));
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), None);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::recording::config::load_config;
+use crate::recording::state::{RecordingStatus, SharedRecordingState};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// How often the recording status is checked
+const TICK: Duration = Duration::from_secs(1);
+
+/// Shown by the OS when listing what is blocking sleep
+const INHIBIT_REASON: &str = "Recording or transcribing audio";
+
+/// Number of outstanding `AwakeGuard`s and the OS inhibitor they share
+struct Inhibition {
+    holders: usize,
+    inhibitor: Option<platform::Inhibitor>,
+}
+
+static INHIBITION: Mutex<Inhibition> = Mutex::new(Inhibition {
+    holders: 0,
+    inhibitor: None,
+});
+
+/// Keeps the system awake until dropped
+///
+/// Guards are reference counted: the OS sleep inhibitor is taken by the first
+/// guard and released when the last one is dropped.
+pub struct AwakeGuard {
+    held: bool,
+}
+
+impl Drop for AwakeGuard {
+    fn drop(&mut self) {
+        if !self.held {
+            return;
+        }
+
+        let mut inhibition = INHIBITION.lock().unwrap();
+        inhibition.holders -= 1;
+        if inhibition.holders == 0 {
+            // Dropping the inhibitor releases it
+            inhibition.inhibitor = None;
+        }
+    }
+}
+
+/// Prevent system sleep while the returned guard is alive
+///
+/// Does nothing when `power.preventSleep` is off or the platform mechanism
+/// is unavailable (a warning is logged).
+pub fn keep_awake() -> AwakeGuard {
+    let enabled = load_config()
+        .map(|config| config.power.prevent_sleep)
+        .unwrap_or(true);
+    if !enabled {
+        return AwakeGuard { held: false };
+    }
+
+    let mut inhibition = INHIBITION.lock().unwrap();
+    if inhibition.inhibitor.is_none() {
+        match platform::Inhibitor::acquire(INHIBIT_REASON) {
+            Ok(inhibitor) => inhibition.inhibitor = Some(inhibitor),
+            Err(e) => {
+                log::warn!("Could not prevent system sleep: {}", e);
+                return AwakeGuard { held: false };
+            }
+        }
+    }
+
+    inhibition.holders += 1;
+    AwakeGuard { held: true }
+}
+
+/// Keep the system awake while recording or processing
+///
+/// Polls the recording status and holds an `AwakeGuard` while it is
+/// Recording or Processing, releasing it once the app is idle or paused.
+pub fn start_sleep_guard(state: SharedRecordingState) {
+    thread::spawn(move || {
+        let mut guard: Option<AwakeGuard> = None;
+
+        loop {
+            let status = state.lock().unwrap().status();
+
+            match (should_stay_awake(status), guard.is_some()) {
+                (true, false) => guard = Some(keep_awake()),
+                (false, true) => guard = None,
+                _ => {}
+            }
+
+            thread::sleep(TICK);
+        }
+    });
+}
+
+fn should_stay_awake(status: RecordingStatus) -> bool {
+    matches!(
+        status,
+        RecordingStatus::Recording | RecordingStatus::Processing
+    )
+}
+
+/// macOS and Linux: a helper process holds the inhibitor for as long as it runs
+///
+/// `caffeinate -w` and `tail --pid` exit on their own if the app dies, so a
+/// crash never leaves the system unable to sleep.
+#[cfg(unix)]
+mod platform {
+    use std::process::{Child, Command, Stdio};
+
+    pub struct Inhibitor(Child);
+
+    impl Inhibitor {
+        pub fn acquire(reason: &str) -> Result<Self, String> {
+            inhibitor_command(reason, std::process::id())
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map(Inhibitor)
+                .map_err(|e| format!("Failed to start sleep inhibitor: {}", e))
+        }
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn inhibitor_command(_reason: &str, pid: u32) -> Command {
+        let mut command = Command::new("caffeinate");
+        command.args(["-i", "-w", &pid.to_string()]);
+        command
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn inhibitor_command(reason: &str, pid: u32) -> Command {
+        let mut command = Command::new("systemd-inhibit");
+        command.args([
+            "--what=sleep:idle",
+            "--who=ThoughtCast",
+            &format!("--why={}", reason),
+            "--mode=block",
+            "tail",
+            &format!("--pid={}", pid),
+            "-f",
+            "/dev/null",
+        ]);
+        command
+    }
+}
+
+/// Windows: execution state is per thread, so a dedicated thread holds it
+/// until the inhibitor is dropped
+#[cfg(windows)]
+mod platform {
+    use std::sync::mpsc::{channel, Sender};
+    use std::thread;
+
+    const ES_CONTINUOUS: u32 = 0x8000_0000;
+    const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+
+    pub struct Inhibitor {
+        // Dropping the sender ends the holding thread
+        _release: Sender<()>,
+    }
+
+    impl Inhibitor {
+        pub fn acquire(_reason: &str) -> Result<Self, String> {
+            let (release, released) = channel::<()>();
+
+            thread::spawn(move || {
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED) };
+                // Blocks until the sender is dropped
+                let _ = released.recv();
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+            });
+
+            Ok(Inhibitor { _release: release })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_stay_awake() {
+        assert!(should_stay_awake(RecordingStatus::Recording));
+        assert!(should_stay_awake(RecordingStatus::Processing));
+        assert!(!should_stay_awake(RecordingStatus::Paused));
+        assert!(!should_stay_awake(RecordingStatus::Idle));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod battery;
+pub mod inhibitor;
+
+pub use battery::transcription_deferral;
+pub use inhibitor::{keep_awake, start_sleep_guard};
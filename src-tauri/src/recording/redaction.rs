@@ -0,0 +1,223 @@
+use crate::recording::config::load_config;
+use crate::recording::models::RedactionConfig;
+use std::ops::Range;
+
+/// Characters allowed between the digits of a phone or card number
+const NUMBER_SEPARATORS: &[u8] = b" -.()";
+
+/// Transcript text as it may leave the app: redacted as set in
+/// `privacy.redaction`, unchanged when redaction is off
+pub fn redact_outgoing(text: &str) -> String {
+    match load_config() {
+        Ok(config) if config.privacy.redaction.enabled => redact(text, &config.privacy.redaction),
+        _ => text.to_string(),
+    }
+}
+
+/// Replace the e-mail addresses, phone and card numbers and terms selected
+/// in `config` with its replacement text
+pub fn redact(text: &str, config: &RedactionConfig) -> String {
+    let mut ranges = Vec::new();
+    if config.emails {
+        ranges.extend(email_ranges(text));
+    }
+    if config.phone_numbers || config.credit_cards {
+        ranges.extend(number_ranges(text, config));
+    }
+    for term in config.terms.iter().map(|term| term.trim()) {
+        if !term.is_empty() {
+            ranges.extend(term_ranges(text, term));
+        }
+    }
+
+    replace_ranges(text, ranges, &config.replacement)
+}
+
+/// Replace each range once, merging overlapping ones
+fn replace_ranges(text: &str, mut ranges: Vec<Range<usize>>, replacement: &str) -> String {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut position = 0;
+    for range in ranges {
+        if range.end <= position {
+            continue;
+        }
+        if range.start >= position {
+            redacted.push_str(&text[position..range.start]);
+            redacted.push_str(replacement);
+        }
+        position = range.end;
+    }
+    redacted.push_str(&text[position..]);
+    redacted
+}
+
+/// `local@domain.tld` addresses; a sentence's final period isn't included
+fn email_ranges(text: &str) -> Vec<Range<usize>> {
+    let is_local = |c: char| c.is_ascii_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '.';
+
+    text.match_indices('@')
+        .filter_map(|(at, _)| {
+            let start = text[..at]
+                .char_indices()
+                .rev()
+                .take_while(|(_, c)| is_local(*c))
+                .last()
+                .map(|(i, _)| i)?;
+            let after = &text[at + 1..];
+            let domain =
+                after[..after.find(|c| !is_domain(c)).unwrap_or(after.len())].trim_end_matches('.');
+            let (_, tld) = domain.rsplit_once('.')?;
+
+            (tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()))
+                .then_some(start..at + 1 + domain.len())
+        })
+        .collect()
+}
+
+/// Phone numbers and card numbers: digits optionally grouped by spaces,
+/// dashes, dots or parentheses, possibly after a `+`
+fn number_ranges(text: &str, config: &RedactionConfig) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let starts_number = |i: usize| {
+        let next_is_digit = bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        bytes[i].is_ascii_digit() || (matches!(bytes[i], b'+' | b'(') && next_is_digit)
+    };
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        // Digits inside a word (e.g. an ID like "abc123") aren't numbers
+        if !starts_number(start) || (start > 0 && bytes[start - 1].is_ascii_alphanumeric()) {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start + 1;
+        while end < bytes.len()
+            && (bytes[end].is_ascii_digit() || NUMBER_SEPARATORS.contains(&bytes[end]))
+        {
+            end += 1;
+        }
+        while !bytes[end - 1].is_ascii_digit() {
+            end -= 1;
+        }
+
+        let groups: Vec<&str> = text[start..end]
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|group| !group.is_empty())
+            .collect();
+        let is_card = config.credit_cards && is_card_number(&groups);
+        if is_card || (config.phone_numbers && is_phone_number(&groups)) {
+            ranges.push(start..end);
+        }
+        start = end;
+    }
+    ranges
+}
+
+/// 13 to 19 digits passing the Luhn check
+fn is_card_number(groups: &[&str]) -> bool {
+    let digits: Vec<u32> = groups
+        .iter()
+        .flat_map(|group| group.chars())
+        .filter_map(|c| c.to_digit(10))
+        .collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    // Valid numbers sum to a multiple of 10
+    let remainder = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| match (i % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum::<u32>()
+        % 10;
+    remainder == 0
+}
+
+/// 7 to 15 digits, either ungrouped or in groups of at most 4 (a longer
+/// group is more likely a decimal or an amount); `2024-11-03` style dates
+/// aren't phone numbers
+fn is_phone_number(groups: &[&str]) -> bool {
+    let digits: usize = groups.iter().map(|group| group.len()).sum();
+    let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+
+    (7..=15).contains(&digits)
+        && (groups.len() == 1 || lengths.iter().all(|&length| length <= 4))
+        && lengths != [4, 2, 2]
+}
+
+/// Whole-word, case-insensitive occurrences of `term`
+fn term_ranges(text: &str, term: &str) -> Vec<Range<usize>> {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let term: Vec<char> = term.chars().map(lower).collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let is_word = |i: usize| chars.get(i).is_some_and(|(_, c)| c.is_alphanumeric());
+
+    (0..chars.len())
+        .filter(|&i| i + term.len() <= chars.len())
+        .filter(|&i| (i == 0 || !is_word(i - 1)) && !is_word(i + term.len()))
+        .filter(|&i| {
+            chars[i..i + term.len()]
+                .iter()
+                .zip(&term)
+                .all(|((_, c), t)| lower(*c) == *t)
+        })
+        .map(|i| {
+            let end = chars
+                .get(i + term.len())
+                .map_or(text.len(), |(end, _)| *end);
+            chars[i].0..end
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RedactionConfig {
+        RedactionConfig {
+            enabled: true,
+            terms: vec!["Acme Corp".to_string()],
+            replacement: "[x]".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_redacts_contact_details_and_terms() {
+        let text = "Mail jane.doe@example.com. Call +1 (555) 123-4567 about acme corp's \
+                    card 4111 1111 1111 1111.";
+        assert_eq!(
+            redact(text, &config()),
+            "Mail [x]. Call [x] about [x]'s card [x]."
+        );
+    }
+
+    #[test]
+    fn test_keeps_ordinary_numbers() {
+        let text = "On 2024-11-03 we spent 3.14159265 hours on 12 items for Acme Corporation.";
+        assert_eq!(redact(text, &config()), text);
+    }
+
+    #[test]
+    fn test_respects_disabled_categories() {
+        let config = RedactionConfig {
+            emails: false,
+            phone_numbers: false,
+            ..config()
+        };
+        let text = "jane@example.com, 555-123-4567, 4111111111111111";
+        assert_eq!(redact(text, &config), "jane@example.com, 555-123-4567, [x]");
+    }
+}
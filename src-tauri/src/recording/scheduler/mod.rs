@@ -0,0 +1,7 @@
+pub mod runner;
+pub mod schedule;
+
+pub use runner::{
+    cancel_scheduled_recording, start_scheduler, SchedulerEvent, SchedulerState,
+    SharedSchedulerState,
+};
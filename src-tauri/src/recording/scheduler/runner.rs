@@ -0,0 +1,198 @@
+use crate::recording::config::load_config;
+use crate::recording::integrations::calendar::{
+    event_starting_between, expand_events, CalendarFile,
+};
+use crate::recording::models::{
+    CalendarConfig, CalendarEvent, MeetingAction, ScheduledRecordingEvent,
+};
+use crate::recording::scheduler::schedule::due_schedule;
+use crate::recording::session::{start_meeting_recording, start_recording};
+use crate::recording::state::SharedRecordingState;
+use chrono::{Local, NaiveDateTime, Utc};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the scheduler checks for due schedules
+const TICK: Duration = Duration::from_secs(5);
+
+/// Pending automatic start, cancellable during its grace period
+#[derive(Default)]
+pub struct SchedulerState {
+    pending: Option<String>,
+    cancel_requested: bool,
+}
+
+/// Type alias for thread-safe shared scheduler state
+pub type SharedSchedulerState = Arc<Mutex<SchedulerState>>;
+
+/// Scheduler notifications for event emission
+pub enum SchedulerEvent {
+    /// A schedule fired; recording starts after the grace period
+    Pending(ScheduledRecordingEvent),
+    /// The grace period elapsed and recording started
+    Started(ScheduledRecordingEvent),
+    /// The user cancelled during the grace period
+    Cancelled(ScheduledRecordingEvent),
+    /// A calendar meeting is starting (prompt mode)
+    MeetingStarting(CalendarEvent),
+}
+
+/// Start the background scheduler thread
+///
+/// Every few seconds the thread reloads the configured schedules and calendar
+/// (so edits apply without a restart) and, when a schedule fires or a meeting
+/// starts in auto mode:
+/// 1. Emits a pending event announcing the automatic start
+/// 2. Waits out the grace period, during which it can be cancelled
+/// 3. Starts recording (skipped if a recording is already in progress)
+///
+/// Meetings in prompt mode only emit a meeting-starting event.
+///
+/// # Arguments
+/// * `recording` - Shared recording state used to start the recording
+/// * `scheduler` - Shared scheduler state tracking the pending start
+/// * `event_emitter` - Callback to emit Tauri events (injected dependency)
+pub fn start_scheduler<F>(
+    recording: SharedRecordingState,
+    scheduler: SharedSchedulerState,
+    event_emitter: F,
+) where
+    F: Fn(SchedulerEvent) + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut last_check = Local::now().naive_local();
+        let mut calendar_file = CalendarFile::default();
+
+        loop {
+            thread::sleep(TICK);
+            let now = Local::now().naive_local();
+
+            let config = match load_config() {
+                Ok(config) => config,
+                Err(_) => {
+                    last_check = now;
+                    continue;
+                }
+            };
+
+            if let Some(schedule) = due_schedule(&config.schedules, last_check, now) {
+                let event = ScheduledRecordingEvent {
+                    schedule: schedule.name.clone(),
+                    grace_seconds: schedule.grace_seconds,
+                    meeting: None,
+                };
+                run_scheduled_start(event, &recording, &scheduler, &event_emitter);
+            } else if let Some(meeting) = meeting_starting(
+                &config.integrations.calendar,
+                &mut calendar_file,
+                last_check,
+                now,
+            ) {
+                if config.integrations.calendar.action == MeetingAction::Auto {
+                    let event = ScheduledRecordingEvent {
+                        schedule: meeting.title.clone(),
+                        grace_seconds: config.integrations.calendar.grace_seconds,
+                        meeting: Some(meeting),
+                    };
+                    run_scheduled_start(event, &recording, &scheduler, &event_emitter);
+                } else if !is_recording_active(&recording) {
+                    event_emitter(SchedulerEvent::MeetingStarting(meeting));
+                }
+            }
+
+            // Time spent in the grace period is covered by the next window
+            last_check = now;
+        }
+    });
+}
+
+/// Request cancellation of the pending automatic start
+pub fn cancel_scheduled_recording(scheduler: SharedSchedulerState) -> Result<(), String> {
+    let mut scheduler_guard = scheduler.lock().unwrap();
+
+    if scheduler_guard.pending.is_none() {
+        return Err("No scheduled recording is about to start.".to_string());
+    }
+
+    scheduler_guard.cancel_requested = true;
+    Ok(())
+}
+
+/// Calendar meeting starting in the window `(since, until]`, if capture is enabled
+///
+/// The calendar file is only read again after it changes.
+fn meeting_starting(
+    calendar: &CalendarConfig,
+    calendar_file: &mut CalendarFile,
+    since: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Option<CalendarEvent> {
+    if calendar.action == MeetingAction::Off {
+        return None;
+    }
+
+    let events = expand_events(
+        calendar_file.events(Path::new(calendar.ics_path.as_ref()?)),
+        Utc::now(),
+    );
+    event_starting_between(&events, since, until).cloned()
+}
+
+fn is_recording_active(recording: &SharedRecordingState) -> bool {
+    recording.lock().map(|state| state.is_active()).unwrap_or(true)
+}
+
+/// Announce, wait out the grace period, then start recording unless cancelled
+fn run_scheduled_start<F>(
+    event: ScheduledRecordingEvent,
+    recording: &SharedRecordingState,
+    scheduler: &SharedSchedulerState,
+    event_emitter: &F,
+) where
+    F: Fn(SchedulerEvent),
+{
+    if is_recording_active(recording) {
+        log::info!("Skipping scheduled recording '{}': already recording", event.schedule);
+        return;
+    }
+
+    {
+        let mut scheduler_guard = scheduler.lock().unwrap();
+        scheduler_guard.pending = Some(event.schedule.clone());
+        scheduler_guard.cancel_requested = false;
+    }
+    event_emitter(SchedulerEvent::Pending(event.clone()));
+
+    let deadline = Instant::now() + Duration::from_secs(event.grace_seconds as u64);
+    let mut cancelled = false;
+    while Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(200));
+        if scheduler.lock().map(|s| s.cancel_requested).unwrap_or(false) {
+            cancelled = true;
+            break;
+        }
+    }
+
+    {
+        let mut scheduler_guard = scheduler.lock().unwrap();
+        scheduler_guard.pending = None;
+        scheduler_guard.cancel_requested = false;
+    }
+
+    if cancelled {
+        event_emitter(SchedulerEvent::Cancelled(event));
+        return;
+    }
+
+    let started = match &event.meeting {
+        Some(meeting) => start_meeting_recording(Arc::clone(recording), meeting.clone()),
+        None => start_recording(Arc::clone(recording)),
+    };
+
+    match started {
+        Ok(()) => event_emitter(SchedulerEvent::Started(event)),
+        Err(e) => log::error!("Scheduled recording '{}' failed to start: {}", event.schedule, e),
+    }
+}
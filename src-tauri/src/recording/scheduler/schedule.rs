@@ -0,0 +1,158 @@
+use crate::recording::models::RecordingSchedule;
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Timelike};
+
+/// Seconds in a day
+const DAY_SECONDS: u32 = 24 * 60 * 60;
+
+/// Next time (local wall clock) a schedule fires strictly after `after`
+///
+/// Daily schedules fire at `at` ("HH:MM"); interval schedules fire every
+/// `everyMinutes` counted from local midnight (so 60 means on the hour).
+/// Returns None for disabled or incomplete schedules.
+pub fn next_occurrence(
+    schedule: &RecordingSchedule,
+    after: NaiveDateTime,
+) -> Option<NaiveDateTime> {
+    if !schedule.enabled {
+        return None;
+    }
+
+    let at = match &schedule.at {
+        Some(at) => Some(
+            NaiveTime::parse_from_str(at, "%H:%M")
+                .map_err(|e| log::warn!("Invalid schedule time '{}': {}", at, e))
+                .ok()?,
+        ),
+        None => None,
+    };
+    let interval_seconds = schedule
+        .every_minutes
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| minutes * 60);
+
+    if at.is_none() && interval_seconds.is_none() {
+        return None;
+    }
+
+    // Look up to a week ahead for an allowed day with a remaining slot
+    (0..=7).find_map(|day_offset| {
+        let date = after.date() + Duration::days(day_offset);
+        if !schedule.days.is_empty() && !schedule.days.contains(&date.weekday()) {
+            return None;
+        }
+
+        let candidate = match (at, interval_seconds) {
+            (Some(time), _) => date.and_time(time),
+            (None, Some(interval)) => {
+                // First slot strictly after `after` on its own day, midnight otherwise
+                let slot = if day_offset == 0 {
+                    (after.num_seconds_from_midnight() / interval + 1) * interval
+                } else {
+                    0
+                };
+                if slot >= DAY_SECONDS {
+                    return None;
+                }
+                date.and_time(NaiveTime::MIN) + Duration::seconds(slot as i64)
+            }
+            (None, None) => return None,
+        };
+
+        (candidate > after).then_some(candidate)
+    })
+}
+
+/// First schedule that fired in the window `(since, until]`
+pub fn due_schedule(
+    schedules: &[RecordingSchedule],
+    since: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Option<&RecordingSchedule> {
+    schedules.iter().find(|schedule| {
+        next_occurrence(schedule, since).is_some_and(|occurrence| occurrence <= until)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Weekday};
+
+    fn datetime(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2024-11-04 is a Monday
+        NaiveDate::from_ymd_opt(2024, 11, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn daily(at: &str, days: Vec<Weekday>) -> RecordingSchedule {
+        RecordingSchedule {
+            name: "Standup debrief".to_string(),
+            enabled: true,
+            at: Some(at.to_string()),
+            every_minutes: None,
+            days,
+            grace_seconds: 10,
+        }
+    }
+
+    fn interval(minutes: u32) -> RecordingSchedule {
+        RecordingSchedule {
+            name: "Check-in".to_string(),
+            enabled: true,
+            at: None,
+            every_minutes: Some(minutes),
+            days: Vec::new(),
+            grace_seconds: 10,
+        }
+    }
+
+    #[test]
+    fn test_daily_schedule_later_today() {
+        let schedule = daily("17:00", vec![]);
+        assert_eq!(next_occurrence(&schedule, datetime(4, 9, 0)), Some(datetime(4, 17, 0)));
+    }
+
+    #[test]
+    fn test_daily_schedule_rolls_to_tomorrow() {
+        let schedule = daily("17:00", vec![]);
+        assert_eq!(next_occurrence(&schedule, datetime(4, 17, 0)), Some(datetime(5, 17, 0)));
+    }
+
+    #[test]
+    fn test_weekday_schedule_skips_weekend() {
+        let weekdays = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        let schedule = daily("17:00", weekdays);
+
+        // Friday evening → Monday
+        assert_eq!(next_occurrence(&schedule, datetime(8, 18, 0)), Some(datetime(11, 17, 0)));
+    }
+
+    #[test]
+    fn test_interval_schedule_aligned_to_midnight() {
+        let schedule = interval(60);
+        assert_eq!(next_occurrence(&schedule, datetime(4, 9, 15)), Some(datetime(4, 10, 0)));
+        assert_eq!(next_occurrence(&schedule, datetime(4, 10, 0)), Some(datetime(4, 11, 0)));
+        assert_eq!(next_occurrence(&schedule, datetime(4, 23, 30)), Some(datetime(5, 0, 0)));
+    }
+
+    #[test]
+    fn test_disabled_or_invalid_schedules_never_fire() {
+        let mut disabled = daily("17:00", vec![]);
+        disabled.enabled = false;
+        assert_eq!(next_occurrence(&disabled, datetime(4, 9, 0)), None);
+
+        assert_eq!(next_occurrence(&daily("5pm", vec![]), datetime(4, 9, 0)), None);
+        assert_eq!(next_occurrence(&interval(0), datetime(4, 9, 0)), None);
+    }
+
+    #[test]
+    fn test_due_schedule_window() {
+        let schedules = vec![daily("17:00", vec![])];
+
+        assert!(due_schedule(&schedules, datetime(4, 16, 59), datetime(4, 17, 0)).is_some());
+        assert!(due_schedule(&schedules, datetime(4, 17, 0), datetime(4, 17, 1)).is_none());
+        assert!(due_schedule(&schedules, datetime(4, 16, 0), datetime(4, 16, 58)).is_none());
+    }
+}
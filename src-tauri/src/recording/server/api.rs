@@ -0,0 +1,162 @@
+use crate::recording::audio::MAX_WAV_BYTES;
+use crate::recording::config::load_config;
+use crate::recording::integrations::pairing::tokens_match;
+use crate::recording::models::ServerConfig;
+use crate::recording::server::jobs::JobQueue;
+use crate::recording::utils::http::{bearer_token, error_response, header, json_response};
+use crate::recording::utils::require_network;
+use std::io::Read;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::thread;
+use tiny_http::{Method, Request, Server};
+
+/// Run the headless transcription server until the process is stopped
+///
+/// Started with `--serve` instead of the app window. Serves:
+/// - `POST /jobs` with a WAV file as the body: queues it and returns the job
+///   (`202 Accepted`)
+/// - `GET /jobs/{id}`: the job's status, with the transcript once completed
+/// - `GET /health`: `{"status": "ok", "pending": n}`
+///
+/// Uses the app's transcription settings. Log output goes to stderr.
+pub fn run_transcription_server() -> Result<(), String> {
+    let _ = log::set_logger(&StderrLogger).map(|()| log::set_max_level(log::LevelFilter::Info));
+
+    let config = load_config()?.server;
+    require_network("Serving on the network", &config.bind_address)?;
+    if requires_token(&config) && config.token.is_none() {
+        return Err(format!(
+            "Set server.token in config.json to serve on {}; only loopback addresses \
+             are allowed without a token.",
+            config.bind_address
+        ));
+    }
+
+    let server = Server::http((config.bind_address.as_str(), config.port)).map_err(|e| {
+        format!(
+            "Failed to listen on {}:{}: {}",
+            config.bind_address, config.port, e
+        )
+    })?;
+    let queue = JobQueue::start()?;
+    log::info!(
+        "Transcription server listening on http://{}:{}",
+        config.bind_address,
+        config.port
+    );
+
+    let token: Arc<Option<String>> = Arc::new(config.token);
+    for request in server.incoming_requests() {
+        let queue = Arc::clone(&queue);
+        let token = Arc::clone(&token);
+        thread::spawn(move || handle_request(request, &queue, token.as_deref()));
+    }
+
+    Ok(())
+}
+
+fn handle_request(mut request: Request, queue: &JobQueue, token: Option<&str>) {
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let authorized = match token {
+        Some(token) => header(&request, "Authorization")
+            .and_then(bearer_token)
+            .is_some_and(|given| tokens_match(token, given)),
+        None => true,
+    };
+
+    let response = if !authorized {
+        error_response(401, "Missing or wrong token")
+    } else {
+        match (request.method(), path.as_str()) {
+            (Method::Get, "/health") => json_response(
+                200,
+                serde_json::json!({ "status": "ok", "pending": queue.pending_count() }).to_string(),
+            ),
+            (Method::Post, "/jobs") => submit_job(&mut request, queue),
+            (Method::Get, _) if path.starts_with("/jobs/") => {
+                match queue.get(&path["/jobs/".len()..]) {
+                    Some(job) => {
+                        json_response(200, serde_json::to_string(&job).unwrap_or_default())
+                    }
+                    None => error_response(404, "No such job"),
+                }
+            }
+            _ => error_response(404, "Not found"),
+        }
+    };
+
+    if let Err(e) = request.respond(response) {
+        log::warn!("Failed to respond to {}: {}", path, e);
+    }
+}
+
+fn submit_job(
+    request: &mut Request,
+    queue: &JobQueue,
+) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    if request.body_length().unwrap_or_default() as u64 > MAX_WAV_BYTES {
+        return error_response(413, "Audio is too large");
+    }
+
+    match queue.submit(&mut request.as_reader().take(MAX_WAV_BYTES)) {
+        Ok(job) => {
+            log::info!("Queued transcription job {} ({:.1}s)", job.id, job.duration);
+            json_response(202, serde_json::to_string(&job).unwrap_or_default())
+        }
+        Err(e) => error_response(400, &e),
+    }
+}
+
+/// Whether the server is reachable from other machines, so needs a token
+fn requires_token(config: &ServerConfig) -> bool {
+    match config.bind_address.parse::<IpAddr>() {
+        Ok(address) => !address.is_loopback(),
+        Err(_) => config.bind_address != "localhost",
+    }
+}
+
+/// Logger for the headless server, which runs without the app's log plugin
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bind_address: &str) -> ServerConfig {
+        ServerConfig {
+            bind_address: bind_address.to_string(),
+            ..ServerConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_requires_token_off_loopback() {
+        assert!(!requires_token(&config("127.0.0.1")));
+        assert!(!requires_token(&config("::1")));
+        assert!(!requires_token(&config("localhost")));
+        assert!(requires_token(&config("0.0.0.0")));
+        assert!(requires_token(&config("192.168.1.20")));
+        assert!(requires_token(&config("desktop.local")));
+    }
+}
@@ -0,0 +1,237 @@
+use crate::recording::audio::wav_duration;
+use crate::recording::transcription::transcribe_file;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// Finished jobs kept for clients to collect; older ones are dropped
+const MAX_FINISHED_JOBS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Audio file submitted for transcription
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionJob {
+    pub id: String,
+    pub status: JobStatus,
+    /// Time the job was submitted in RFC 3339 format
+    pub created_at: String,
+    /// Length of the audio in seconds
+    pub duration: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_time_seconds: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Jobs transcribed one at a time, in submission order, by a worker thread
+///
+/// Jobs only live in memory: audio is kept in a temporary folder until it is
+/// transcribed, and nothing is added to the session index.
+pub struct JobQueue {
+    jobs: Mutex<BTreeMap<String, TranscriptionJob>>,
+    sender: Mutex<Sender<String>>,
+    jobs_dir: PathBuf,
+    next_id: AtomicU64,
+}
+
+impl JobQueue {
+    /// Create the queue and start its worker thread
+    pub fn start() -> Result<Arc<JobQueue>, String> {
+        let jobs_dir = std::env::temp_dir().join("thoughtcast-jobs");
+        fs::create_dir_all(&jobs_dir)
+            .map_err(|e| format!("Failed to create jobs directory: {}", e))?;
+
+        let (sender, receiver) = mpsc::channel::<String>();
+        let queue = Arc::new(JobQueue {
+            jobs: Mutex::new(BTreeMap::new()),
+            sender: Mutex::new(sender),
+            jobs_dir,
+            next_id: AtomicU64::new(1),
+        });
+
+        let worker_queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            for id in receiver {
+                worker_queue.run(&id);
+            }
+        });
+
+        Ok(queue)
+    }
+
+    /// Save the submitted audio and queue it for transcription
+    ///
+    /// Audio that isn't a readable WAV file is rejected.
+    pub fn submit(&self, audio: &mut dyn Read) -> Result<TranscriptionJob, String> {
+        let now = Utc::now();
+        let id = format!(
+            "{}-{:06}",
+            now.format("%Y%m%d%H%M%S"),
+            self.next_id.fetch_add(1, Ordering::SeqCst)
+        );
+
+        let audio_path = self.audio_path(&id);
+        let duration = fs::File::create(&audio_path)
+            .and_then(|mut file| io::copy(audio, &mut file))
+            .map_err(|e| format!("Failed to save audio: {}", e))
+            .and_then(|_| wav_duration(&audio_path));
+        let duration = match duration {
+            Ok(duration) => duration,
+            Err(e) => {
+                let _ = fs::remove_file(&audio_path);
+                return Err(e);
+            }
+        };
+
+        let job = TranscriptionJob {
+            id: id.clone(),
+            status: JobStatus::Queued,
+            created_at: now.to_rfc3339(),
+            duration,
+            text: None,
+            model_path: None,
+            transcription_time_seconds: None,
+            error: None,
+        };
+        self.jobs.lock().unwrap().insert(id.clone(), job.clone());
+
+        self.sender
+            .lock()
+            .unwrap()
+            .send(id)
+            .map_err(|_| "Transcription worker stopped".to_string())?;
+
+        Ok(job)
+    }
+
+    pub fn get(&self, id: &str) -> Option<TranscriptionJob> {
+        self.jobs.lock().unwrap().get(id).cloned()
+    }
+
+    /// Number of jobs waiting for or in transcription
+    pub fn pending_count(&self) -> usize {
+        self.jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| matches!(job.status, JobStatus::Queued | JobStatus::Running))
+            .count()
+    }
+
+    fn run(&self, id: &str) {
+        self.update(id, |job| job.status = JobStatus::Running);
+
+        let audio_path = self.audio_path(id);
+        let start = Instant::now();
+        let result = transcribe_file(&audio_path);
+        let elapsed = start.elapsed().as_secs_f64();
+        let _ = fs::remove_file(&audio_path);
+
+        self.update(id, |job| match result {
+            Ok((text, model_path)) => {
+                job.status = JobStatus::Completed;
+                job.text = Some(text);
+                job.model_path = Some(model_path);
+                job.transcription_time_seconds = Some(elapsed);
+            }
+            Err(error) => {
+                log::warn!("Transcription job {} failed: {}", job.id, error);
+                job.status = JobStatus::Failed;
+                job.error = Some(error);
+            }
+        });
+
+        prune_finished(&mut self.jobs.lock().unwrap(), MAX_FINISHED_JOBS);
+    }
+
+    fn update(&self, id: &str, update: impl FnOnce(&mut TranscriptionJob)) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(id) {
+            update(job);
+        }
+    }
+
+    fn audio_path(&self, id: &str) -> PathBuf {
+        self.jobs_dir.join(format!("{}.wav", id))
+    }
+}
+
+/// Drop the oldest finished jobs beyond `max_finished`
+///
+/// Job ids sort by submission time, so map order is oldest first.
+fn prune_finished(jobs: &mut BTreeMap<String, TranscriptionJob>, max_finished: usize) {
+    let finished: Vec<String> = jobs
+        .values()
+        .filter(|job| matches!(job.status, JobStatus::Completed | JobStatus::Failed))
+        .map(|job| job.id.clone())
+        .collect();
+
+    for id in finished
+        .iter()
+        .take(finished.len().saturating_sub(max_finished))
+    {
+        jobs.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, status: JobStatus) -> (String, TranscriptionJob) {
+        let job = TranscriptionJob {
+            id: id.to_string(),
+            status,
+            created_at: "2024-11-02T15:30:00+00:00".to_string(),
+            duration: 10.0,
+            text: None,
+            model_path: None,
+            transcription_time_seconds: None,
+            error: None,
+        };
+        (id.to_string(), job)
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_finished_and_pending_jobs() {
+        let mut jobs: BTreeMap<_, _> = [
+            job("20241102153000-000001", JobStatus::Completed),
+            job("20241102153000-000002", JobStatus::Failed),
+            job("20241102153000-000003", JobStatus::Queued),
+            job("20241102153000-000004", JobStatus::Completed),
+        ]
+        .into_iter()
+        .collect();
+
+        prune_finished(&mut jobs, 2);
+
+        let remaining: Vec<&str> = jobs.keys().map(String::as_str).collect();
+        assert_eq!(
+            remaining,
+            [
+                "20241102153000-000002",
+                "20241102153000-000003",
+                "20241102153000-000004"
+            ]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod api;
+pub mod jobs;
+
+pub use api::run_transcription_server;
@@ -0,0 +1,140 @@
+use crate::recording::models::{Session, SessionAccess};
+use crate::recording::session::storage::load_sessions;
+use crate::recording::utils::get_local_state_dir;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// When and how often each session's transcript was opened, keyed by
+/// session id; kept on this machine only, so viewing a note doesn't change
+/// the synced session files
+const ACCESS_FILE: &str = "session-access.json";
+
+/// Days after which an opening counts half as much when ranking
+const ACCESS_HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Serializes read-modify-write access to the access file
+static ACCESS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Record that a session's transcript was opened
+pub fn record_session_access(session_id: &str) -> Result<(), String> {
+    let _guard = ACCESS_LOCK.lock().unwrap();
+    let mut access = read_access();
+
+    let now = Utc::now().to_rfc3339();
+    access
+        .entry(session_id.to_string())
+        .and_modify(|entry| {
+            entry.last_accessed = now.clone();
+            entry.access_count = entry.access_count.saturating_add(1);
+        })
+        .or_insert_with(|| SessionAccess {
+            last_accessed: now.clone(),
+            access_count: 1,
+        });
+
+    // Drop sessions that were deleted since
+    if let Ok(index) = load_sessions() {
+        access.retain(|id, _| index.sessions.iter().any(|session| &session.id == id));
+    }
+
+    let content = serde_json::to_string(&access)
+        .map_err(|e| format!("Failed to serialize session access: {}", e))?;
+    fs::write(access_file_path()?, content)
+        .map_err(|e| format!("Failed to write session access: {}", e))
+}
+
+/// When and how often each session's transcript was opened, for sorting
+pub fn get_session_access() -> BTreeMap<String, SessionAccess> {
+    let _guard = ACCESS_LOCK.lock().unwrap();
+    read_access()
+}
+
+/// Sessions whose transcript was opened most recently, newest first
+pub fn get_recently_viewed(limit: usize) -> Result<Vec<Session>, String> {
+    let access = get_session_access();
+    ranked_sessions(&access, limit, |entry| entry.last_accessed.clone())
+}
+
+/// Sessions whose transcript is opened most, weighing recent openings more
+pub fn get_frequently_viewed(limit: usize) -> Result<Vec<Session>, String> {
+    let access = get_session_access();
+    let now = Utc::now();
+    ranked_sessions(&access, limit, |entry| access_score(entry, now))
+}
+
+/// Viewed sessions ordered by `key`, highest first
+fn ranked_sessions<K, F>(
+    access: &BTreeMap<String, SessionAccess>,
+    limit: usize,
+    key: F,
+) -> Result<Vec<Session>, String>
+where
+    K: PartialOrd,
+    F: Fn(&SessionAccess) -> K,
+{
+    let mut viewed: Vec<(K, Session)> = load_sessions()?
+        .sessions
+        .into_iter()
+        .filter_map(|session| Some((key(access.get(&session.id)?), session)))
+        .collect();
+    viewed.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(viewed
+        .into_iter()
+        .take(limit)
+        .map(|(_, session)| session)
+        .collect())
+}
+
+/// Number of openings, halved for every `ACCESS_HALF_LIFE_DAYS` since the
+/// last one
+fn access_score(entry: &SessionAccess, now: DateTime<Utc>) -> f64 {
+    let days = DateTime::parse_from_rfc3339(&entry.last_accessed)
+        .map(|accessed| (now - accessed.with_timezone(&Utc)).num_seconds() as f64 / 86_400.0)
+        .unwrap_or(f64::MAX)
+        .max(0.0);
+
+    entry.access_count as f64 * 0.5f64.powf(days / ACCESS_HALF_LIFE_DAYS)
+}
+
+fn read_access() -> BTreeMap<String, SessionAccess> {
+    access_file_path()
+        .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        .unwrap_or_default()
+}
+
+fn access_file_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(ACCESS_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(last_accessed: &str, access_count: u32) -> SessionAccess {
+        SessionAccess {
+            last_accessed: last_accessed.to_string(),
+            access_count,
+        }
+    }
+
+    #[test]
+    fn test_recent_openings_weigh_more() {
+        let now = DateTime::parse_from_rfc3339("2024-12-31T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let today = access_score(&access("2024-12-31T09:00:00Z", 3), now);
+        let two_weeks_ago = access_score(&access("2024-12-17T12:00:00Z", 4), now);
+        let long_ago = access_score(&access("2024-06-01T12:00:00Z", 50), now);
+
+        assert!((two_weeks_ago - 2.0).abs() < 1e-9);
+        assert!(today > two_weeks_ago);
+        assert!(two_weeks_ago > long_ago);
+        assert_eq!(access_score(&access("not a date", 5), now), 0.0);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::recording::session::storage::load_transcript;
+
+/// Phrases that introduce an action item; the task is the text after them
+const TASK_CUES: &[&str] = &[
+    "action item",
+    "todo",
+    "to-do",
+    "to do:",
+    "remind me to",
+    "remember to",
+    "don't forget to",
+    "make sure to",
+    "i need to",
+    "i have to",
+    "i must",
+    "i should",
+    "we need to",
+    "we should",
+];
+
+/// Filler words spoken before a cue ("So, I need to...")
+const LEADING_FILLERS: &[&str] = &["so", "and", "also", "okay", "ok", "oh", "um", "uh", "then"];
+
+/// Action items spoken in a session
+pub fn get_action_items(session_id: &str) -> Result<Vec<String>, String> {
+    Ok(extract_action_items(&load_transcript(session_id)?))
+}
+
+/// Find action items in a transcript
+///
+/// Looks for sentences starting with a cue such as "I need to" or "remember
+/// to" and returns what follows as the task, capitalized and without the
+/// closing punctuation. Duplicates are dropped.
+pub fn extract_action_items(transcript: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+
+    for sentence in transcript.split(['.', '!', '?', '\n']) {
+        let Some(task) = task_in_sentence(sentence) else {
+            continue;
+        };
+        if !items.iter().any(|item| item.eq_ignore_ascii_case(&task)) {
+            items.push(task);
+        }
+    }
+
+    items
+}
+
+fn task_in_sentence(sentence: &str) -> Option<String> {
+    let normalized = sentence.replace('’', "'");
+    let mut rest = normalized.trim();
+
+    // Skip filler words, keeping the original casing of the task
+    while let Some(word) = LEADING_FILLERS
+        .iter()
+        .find(|filler| starts_with_word(rest, filler))
+    {
+        rest = rest[word.len()..].trim_start_matches([',', ' ']);
+    }
+
+    let cue = TASK_CUES.iter().find(|cue| starts_with_word(rest, cue))?;
+    let task = rest[cue.len()..]
+        .trim_start_matches([':', ',', '-', ' '])
+        .trim_end_matches([',', ';', ' ']);
+
+    let mut chars = task.chars();
+    let first = chars.next()?;
+    let task = first.to_uppercase().chain(chars).collect::<String>();
+
+    // A single word ("I need to go.") is rarely a task
+    (task.split_whitespace().count() >= 2).then_some(task)
+}
+
+/// Whether `text` starts with `prefix` (case-insensitively) followed by a
+/// word boundary
+fn starts_with_word(text: &str, prefix: &str) -> bool {
+    let Some(head) = text.get(..prefix.len()) else {
+        return false;
+    };
+    head.eq_ignore_ascii_case(prefix)
+        && !text[prefix.len()..].starts_with(|c: char| c.is_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_tasks_after_cues() {
+        let transcript = "The demo went well. So, I need to send Sam the slides. \
+            Remember to book the venue!\nAction item: update the roadmap";
+
+        assert_eq!(
+            extract_action_items(transcript),
+            vec![
+                "Send Sam the slides",
+                "Book the venue",
+                "Update the roadmap"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_cues_inside_words_and_bare_cues() {
+        let transcript = "Todoist is great. I should. I mustard the hot dogs.";
+
+        assert!(extract_action_items(transcript).is_empty());
+    }
+
+    #[test]
+    fn test_drops_duplicates_and_normalizes_apostrophes() {
+        let transcript = "Don’t forget to call the bank. Don't forget to call the bank.";
+
+        assert_eq!(extract_action_items(transcript), vec!["Call the bank"]);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::recording::models::{AutoCopyConfig, AutoCopyMode};
+use crate::recording::transcription::text_processor::extract_summary;
+use chrono::{DateTime, Local};
+
+/// Number of leading sentences used for the summary copy mode
+const SUMMARY_SENTENCES: usize = 2;
+
+/// Template used when `template` mode is configured without a template
+const DEFAULT_TEMPLATE: &str = "{transcript}";
+
+/// Session details available to clipboard templates
+pub struct TemplateContext<'a> {
+    pub session_id: &'a str,
+    /// RFC 3339 recording timestamp
+    pub timestamp: &'a str,
+    pub duration_seconds: f64,
+}
+
+/// Determine the text to copy automatically after transcription
+///
+/// Returns None when automatic copying is disabled or there is nothing to copy
+pub fn auto_copy_text(
+    config: &AutoCopyConfig,
+    transcript: &str,
+    context: &TemplateContext,
+) -> Option<String> {
+    if transcript.is_empty() {
+        return None;
+    }
+
+    let text = match config.mode {
+        AutoCopyMode::Off => return None,
+        AutoCopyMode::Raw => transcript.to_string(),
+        AutoCopyMode::Summary => extract_summary(transcript, SUMMARY_SENTENCES),
+        AutoCopyMode::Template => render_template(
+            config.template.as_deref().unwrap_or(DEFAULT_TEMPLATE),
+            transcript,
+            context,
+        ),
+    };
+
+    Some(text)
+}
+
+/// Render a clipboard template
+///
+/// Supported placeholders: `{transcript}`, `{summary}`, `{id}`, `{date}`,
+/// `{time}` (local time of recording) and `{duration}` (`m:ss`)
+pub fn render_template(template: &str, transcript: &str, context: &TemplateContext) -> String {
+    let local_time = DateTime::parse_from_rfc3339(context.timestamp)
+        .ok()
+        .map(|time| time.with_timezone(&Local));
+
+    let date = local_time
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let time = local_time
+        .map(|time| time.format("%H:%M").to_string())
+        .unwrap_or_default();
+
+    template
+        .replace("{id}", context.session_id)
+        .replace("{date}", &date)
+        .replace("{time}", &time)
+        .replace("{duration}", &format_duration(context.duration_seconds))
+        .replace("{summary}", &extract_summary(transcript, SUMMARY_SENTENCES))
+        .replace("{transcript}", transcript)
+}
+
+/// Format seconds as `m:ss`
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> TemplateContext<'static> {
+        TemplateContext {
+            session_id: "2024-11-02_15-30-00",
+            timestamp: "2024-11-02T15:30:00+00:00",
+            duration_seconds: 75.4,
+        }
+    }
+
+    fn config(mode: AutoCopyMode, template: Option<&str>) -> AutoCopyConfig {
+        AutoCopyConfig {
+            mode,
+            template: template.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_off_mode_copies_nothing() {
+        let result = auto_copy_text(&config(AutoCopyMode::Off, None), "Hello.", &context());
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_raw_mode_copies_transcript() {
+        let result = auto_copy_text(&config(AutoCopyMode::Raw, None), "Hello.", &context());
+        assert_eq!(result, Some("Hello.".to_string()));
+    }
+
+    #[test]
+    fn test_empty_transcript_copies_nothing() {
+        let result = auto_copy_text(&config(AutoCopyMode::Raw, None), "", &context());
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_summary_mode_copies_lead_sentences() {
+        let result = auto_copy_text(
+            &config(AutoCopyMode::Summary, None),
+            "First idea. Second idea. Third idea.",
+            &context(),
+        );
+        assert_eq!(result, Some("First idea. Second idea.".to_string()));
+    }
+
+    #[test]
+    fn test_template_mode_replaces_placeholders() {
+        let result = auto_copy_text(
+            &config(AutoCopyMode::Template, Some("[{id}] ({duration}) {transcript}")),
+            "Hello.",
+            &context(),
+        );
+        assert_eq!(
+            result,
+            Some("[2024-11-02_15-30-00] (1:15) Hello.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_template_mode_without_template_uses_transcript() {
+        let result = auto_copy_text(&config(AutoCopyMode::Template, None), "Hello.", &context());
+        assert_eq!(result, Some("Hello.".to_string()));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::recording::config::load_config;
+use crate::recording::models::AutoPauseConfig;
+use crate::recording::session::lifecycle::{pause_recording, resume_recording};
+use crate::recording::state::{RecordingStatus, SharedRecordingState};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often the input health is checked
+const TICK: Duration = Duration::from_secs(1);
+
+/// Automatic pause/resume notifications for event emission
+pub enum AutoPauseEvent {
+    /// Recording paused because the microphone stopped delivering audio
+    Paused { reason: String },
+    /// Audio returned and the automatically paused recording resumed
+    Resumed,
+}
+
+/// Start the background thread that pauses recording on a muted or lost mic
+///
+/// When `audio.autoPause` is enabled, a recording whose input stays digitally
+/// silent or stops delivering audio for `seconds` is paused, so the silence
+/// doesn't count as recording time. Once audio returns, the recording resumes.
+/// Recordings paused by the user are never resumed automatically.
+///
+/// # Arguments
+/// * `state` - Shared recording state
+/// * `event_emitter` - Callback to emit Tauri events (injected dependency)
+pub fn start_auto_pause_monitor<F>(state: SharedRecordingState, event_emitter: F)
+where
+    F: Fn(AutoPauseEvent) + Send + 'static,
+{
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+
+        if !state.lock().unwrap().is_active() {
+            continue;
+        }
+
+        let config = match load_config() {
+            Ok(config) if config.audio.auto_pause.enabled => config.audio.auto_pause,
+            _ => continue,
+        };
+
+        let (status, auto_paused, reason) = {
+            let state_guard = state.lock().unwrap();
+            let reason = interruption_reason(
+                &config,
+                Instant::now(),
+                state_guard.last_input_at,
+                state_guard.input_silent_since,
+            );
+            (state_guard.status(), state_guard.auto_paused, reason)
+        };
+
+        match (status, reason) {
+            (RecordingStatus::Recording, Some(reason)) => {
+                auto_pause(&state, reason, &event_emitter)
+            }
+            (RecordingStatus::Paused, None) if auto_paused => auto_resume(&state, &event_emitter),
+            _ => {}
+        }
+    });
+}
+
+fn auto_pause<F>(state: &SharedRecordingState, reason: String, event_emitter: &F)
+where
+    F: Fn(AutoPauseEvent),
+{
+    if pause_recording(Arc::clone(state)).is_ok() {
+        state.lock().unwrap().auto_paused = true;
+        log::info!("Recording paused automatically: {}", reason);
+        event_emitter(AutoPauseEvent::Paused { reason });
+    }
+}
+
+fn auto_resume<F>(state: &SharedRecordingState, event_emitter: &F)
+where
+    F: Fn(AutoPauseEvent),
+{
+    if resume_recording(Arc::clone(state)).is_ok() {
+        log::info!("Microphone audio returned, recording resumed");
+        event_emitter(AutoPauseEvent::Resumed);
+    }
+}
+
+/// Why the input is not delivering usable audio, if it has been that way
+/// for at least `config.seconds`
+fn interruption_reason(
+    config: &AutoPauseConfig,
+    now: Instant,
+    last_input_at: Option<Instant>,
+    silent_since: Option<Instant>,
+) -> Option<String> {
+    let limit = Duration::from_secs(config.seconds as u64);
+    let lasted = |since: Option<Instant>| since.is_some_and(|t| now.duration_since(t) >= limit);
+
+    if lasted(last_input_at) {
+        Some("Microphone stopped delivering audio (another app may be using it)".to_string())
+    } else if lasted(silent_since) {
+        Some("Microphone is muted".to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AutoPauseConfig {
+        AutoPauseConfig {
+            enabled: true,
+            seconds: 5,
+        }
+    }
+
+    #[test]
+    fn test_no_interruption_while_audio_flows() {
+        let now = Instant::now();
+        assert_eq!(interruption_reason(&config(), now, Some(now), None), None);
+    }
+
+    #[test]
+    fn test_muted_input_detected_after_limit() {
+        let start = Instant::now();
+        let silent_since = Some(start);
+
+        let later = start + Duration::from_secs(2);
+        assert_eq!(
+            interruption_reason(&config(), later, Some(later), silent_since),
+            None
+        );
+
+        let later = start + Duration::from_secs(6);
+        assert!(
+            interruption_reason(&config(), later, Some(later), silent_since)
+                .unwrap()
+                .contains("muted")
+        );
+    }
+
+    #[test]
+    fn test_stalled_input_detected_after_limit() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(6);
+
+        assert!(interruption_reason(&config(), later, Some(start), None)
+            .unwrap()
+            .contains("stopped delivering"));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::recording::config::load_config;
+use crate::recording::models::{BatchItemStatus, BatchProgressEvent};
+use crate::recording::power::keep_awake;
+use crate::recording::session::completion::on_transcription_complete;
+use crate::recording::session::lifecycle::transcribe_session;
+use crate::recording::session::recovery::claim_transcription;
+use crate::recording::session::storage::load_sessions;
+use crate::recording::utils::get_storage_dir;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Set while a batch is running; only one batch runs at a time
+static BATCH_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Transcribe many sessions using a pool of parallel workers
+///
+/// Runs `transcription.batchWorkers` Whisper.cpp processes at once, separately
+/// from the interactive transcription queue. With no `session_ids`, every
+/// session that has audio but no transcript (e.g. freshly imported ones) is
+/// transcribed. Each worker reports when it starts and finishes a session.
+///
+/// Returns the number of sessions queued.
+pub fn start_batch_transcription<F>(
+    session_ids: Option<Vec<String>>,
+    event_emitter: F,
+) -> Result<usize, String>
+where
+    F: Fn(BatchProgressEvent) + Send + Sync + 'static,
+{
+    let session_ids = match session_ids {
+        Some(session_ids) => session_ids,
+        None => untranscribed_sessions()?,
+    };
+    let total = session_ids.len();
+    if total == 0 {
+        return Ok(0);
+    }
+
+    if BATCH_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A batch transcription is already running.".to_string());
+    }
+
+    let workers = load_config()?.transcription.batch_workers.clamp(1, total);
+    log::info!("Transcribing {} sessions with {} workers", total, workers);
+
+    let queue = Arc::new(Mutex::new(VecDeque::from(session_ids)));
+    let completed = Arc::new(AtomicUsize::new(0));
+    let active_workers = Arc::new(AtomicUsize::new(workers));
+    let event_emitter = Arc::new(event_emitter);
+
+    for worker in 0..workers {
+        let queue = Arc::clone(&queue);
+        let completed = Arc::clone(&completed);
+        let active_workers = Arc::clone(&active_workers);
+        let event_emitter = Arc::clone(&event_emitter);
+
+        thread::spawn(move || {
+            let _awake = keep_awake();
+
+            loop {
+                let Some(session_id) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let report = |status, session, error| {
+                    event_emitter(BatchProgressEvent {
+                        worker,
+                        session_id: session_id.clone(),
+                        status,
+                        session,
+                        error,
+                        completed: completed.load(Ordering::SeqCst),
+                        total,
+                    })
+                };
+
+                report(BatchItemStatus::Started, None, None);
+
+                let result = match claim_transcription(&session_id) {
+                    Some(_claim) => transcribe_session(&session_id, None),
+                    None => Err("Session is already being transcribed".to_string()),
+                };
+                completed.fetch_add(1, Ordering::SeqCst);
+
+                match result {
+                    Ok((session, _)) => {
+                        on_transcription_complete(&session);
+                        report(BatchItemStatus::Completed, Some(session), None)
+                    }
+                    Err(e) => {
+                        log::warn!("Batch transcription of {} failed: {}", session_id, e);
+                        report(BatchItemStatus::Failed, None, Some(e));
+                    }
+                }
+            }
+
+            if active_workers.fetch_sub(1, Ordering::SeqCst) == 1 {
+                BATCH_RUNNING.store(false, Ordering::SeqCst);
+            }
+        });
+    }
+
+    Ok(total)
+}
+
+/// Sessions with audio on disk but no transcript, oldest first
+fn untranscribed_sessions() -> Result<Vec<String>, String> {
+    let storage_dir = get_storage_dir()?;
+
+    Ok(load_sessions()?
+        .sessions
+        .into_iter()
+        .rev()
+        .filter(|s| s.transcript_path.is_empty() && !s.audio_path.is_empty())
+        .filter(|s| storage_dir.join(&s.audio_path).exists())
+        .map(|s| s.id)
+        .collect())
+}
@@ -0,0 +1,236 @@
+use crate::recording::models::{
+    BatchItemStatus, BulkFailure, BulkOperation, BulkOperationSummary, Session, UndoableOperation,
+};
+use crate::recording::session::batch::start_batch_transcription;
+use crate::recording::session::export::build_bundle;
+use crate::recording::session::journal::{
+    journal_copy, journal_move, session_files, session_record_file, transcript_files,
+};
+use crate::recording::session::recovery::claim_transcription;
+use crate::recording::session::storage::{load_sessions, write_session_record, SESSIONS_DIR};
+use crate::recording::utils::{export_relative_path, get_storage_dir};
+use chrono::Local;
+use std::fs;
+use std::sync::Mutex;
+
+/// Delete the selected sessions with their audio, transcripts and logs
+///
+/// Either every session is deleted or none is. The files are moved into the
+/// undo journal, so the delete can be undone with `undo_last_operation`.
+/// Sessions being transcribed can't be deleted.
+pub fn delete_sessions(session_ids: &[String]) -> Result<BulkOperationSummary, String> {
+    let sessions = find_sessions(session_ids)?;
+    let _claims = sessions
+        .iter()
+        .map(|session| {
+            claim_transcription(&session.id)
+                .ok_or_else(|| format!("Session {} is being transcribed", session.id))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    journal_move(
+        UndoableOperation::Delete,
+        &session_ids_of(&sessions),
+        sessions.iter().flat_map(session_files).collect(),
+    )?;
+
+    log::info!("Deleted {} sessions", sessions.len());
+    Ok(summary(BulkOperation::Delete, &sessions))
+}
+
+/// Add and remove tags on the selected sessions
+///
+/// Tags are trimmed and compared case-insensitively. If any session can't be
+/// saved, the sessions already updated are restored. Can be undone with
+/// `undo_last_operation`.
+pub fn tag_sessions(
+    session_ids: &[String],
+    add: &[String],
+    remove: &[String],
+) -> Result<BulkOperationSummary, String> {
+    let sessions = find_sessions(session_ids)?;
+    let sessions_dir = get_storage_dir()?.join(SESSIONS_DIR);
+    let add = normalize_tags(add);
+    let remove = normalize_tags(remove);
+    let entry = journal_copy(
+        UndoableOperation::Tag,
+        &session_ids_of(&sessions),
+        sessions
+            .iter()
+            .map(|session| session_record_file(&session.id))
+            .collect(),
+    )?;
+
+    for (i, session) in sessions.iter().enumerate() {
+        let mut tagged = session.clone();
+        apply_tags(&mut tagged.tags, &add, &remove);
+
+        if let Err(e) = write_session_record(&sessions_dir, &tagged) {
+            for original in &sessions[..i] {
+                if let Err(e) = write_session_record(&sessions_dir, original) {
+                    log::error!("Failed to restore tags of {}: {}", original.id, e);
+                }
+            }
+            entry.discard();
+            return Err(e);
+        }
+    }
+
+    Ok(summary(BulkOperation::Tag, &sessions))
+}
+
+/// Copy the selected sessions' audio and transcripts into one folder under
+/// `exports/`, a subfolder per session
+///
+/// The folder is removed again if any session fails to export.
+pub fn export_sessions(session_ids: &[String]) -> Result<BulkOperationSummary, String> {
+    let sessions = find_sessions(session_ids)?;
+    let storage_dir = get_storage_dir()?;
+    let export_dir = storage_dir.join(export_relative_path(&format!(
+        "sessions-{}",
+        Local::now().format("%Y-%m-%d_%H-%M-%S")
+    )));
+
+    for session in &sessions {
+        if let Err(e) = build_bundle(session, &storage_dir, &export_dir, false) {
+            let _ = fs::remove_dir_all(&export_dir);
+            return Err(e);
+        }
+    }
+
+    log::info!(
+        "Exported {} sessions to {}",
+        sessions.len(),
+        export_dir.display()
+    );
+
+    Ok(BulkOperationSummary {
+        export_directory: Some(export_dir.to_string_lossy().to_string()),
+        ..summary(BulkOperation::Export, &sessions)
+    })
+}
+
+/// Transcribe the selected sessions again with the batch workers
+///
+/// Invokes the callback once, with every session's outcome, after the last
+/// one finishes. The previous transcripts are kept in the undo journal.
+/// Returns the number of sessions queued.
+pub fn retranscribe_sessions<F>(session_ids: &[String], on_complete: F) -> Result<usize, String>
+where
+    F: Fn(BulkOperationSummary) + Send + Sync + 'static,
+{
+    let sessions = find_sessions(session_ids)?;
+    let total = sessions.len();
+    let outcome = Mutex::new(summary(BulkOperation::Retranscribe, &[]));
+    let session_ids = session_ids_of(&sessions);
+    let entry = journal_copy(
+        UndoableOperation::Retranscribe,
+        &session_ids,
+        sessions.iter().flat_map(transcript_files).collect(),
+    )?;
+
+    let queued = start_batch_transcription(Some(session_ids), move |progress| {
+        let mut outcome = outcome.lock().unwrap();
+        match progress.status {
+            BatchItemStatus::Started => return,
+            BatchItemStatus::Completed => outcome.succeeded.push(progress.session_id),
+            BatchItemStatus::Failed => outcome.failed.push(BulkFailure {
+                session_id: progress.session_id,
+                error: progress.error.unwrap_or_default(),
+            }),
+        }
+
+        if outcome.succeeded.len() + outcome.failed.len() == total {
+            on_complete(outcome.clone());
+        }
+    });
+    if queued.is_err() {
+        entry.discard();
+    }
+
+    queued
+}
+
+/// The selected sessions, in selection order and without repeats
+///
+/// Fails without touching anything if a session doesn't exist.
+fn find_sessions(session_ids: &[String]) -> Result<Vec<Session>, String> {
+    if session_ids.is_empty() {
+        return Err("No sessions selected".to_string());
+    }
+
+    let index = load_sessions()?;
+    let mut sessions: Vec<Session> = Vec::new();
+    for session_id in session_ids {
+        if sessions.iter().any(|session| &session.id == session_id) {
+            continue;
+        }
+        let session = index
+            .sessions
+            .iter()
+            .find(|session| &session.id == session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        sessions.push(session.clone());
+    }
+
+    Ok(sessions)
+}
+
+fn summary(operation: BulkOperation, sessions: &[Session]) -> BulkOperationSummary {
+    BulkOperationSummary {
+        operation,
+        succeeded: session_ids_of(sessions),
+        failed: Vec::new(),
+        export_directory: None,
+    }
+}
+
+fn session_ids_of(sessions: &[Session]) -> Vec<String> {
+    sessions.iter().map(|session| session.id.clone()).collect()
+}
+
+/// Trimmed, non-empty tags without case-insensitive repeats
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !contains_tag(&normalized, tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+
+    normalized
+}
+
+fn apply_tags(tags: &mut Vec<String>, add: &[String], remove: &[String]) {
+    tags.retain(|tag| !contains_tag(remove, tag));
+    for tag in add {
+        if !contains_tag(tags, tag) {
+            tags.push(tag.clone());
+        }
+    }
+}
+
+/// Whether the tags include `tag`, ignoring case
+pub(crate) fn contains_tag(tags: &[String], tag: &str) -> bool {
+    let tag = tag.to_lowercase();
+    tags.iter().any(|existing| existing.to_lowercase() == tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn test_applies_tags_case_insensitively() {
+        let add = normalize_tags(&tags(&["  Work ", "idea", "work", ""]));
+        assert_eq!(add, tags(&["Work", "idea"]));
+
+        let mut session_tags = tags(&["work", "draft"]);
+        apply_tags(&mut session_tags, &add, &tags(&["DRAFT"]));
+        assert_eq!(session_tags, tags(&["work", "idea"]));
+    }
+}
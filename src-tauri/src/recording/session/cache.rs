@@ -0,0 +1,156 @@
+use crate::recording::models::SessionIndex;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Session cache shared by the app's commands and background threads
+pub type SharedSessionCache = Arc<SessionCache>;
+
+/// In-memory copy of the session index
+///
+/// Created by the app at startup, kept in its managed state and handed to
+/// the storage layer with `use_session_cache`. Reused until it is
+/// invalidated (every session write does this) or a file in the sessions
+/// folder is added, removed or changed on disk, e.g. when a sync tool
+/// delivers or rewrites a record from another machine or the storage folder
+/// is switched.
+#[derive(Default)]
+pub struct SessionCache {
+    cached: Mutex<Option<CachedIndex>>,
+    /// Bumped on every invalidation so a load that raced a write is not cached
+    generation: AtomicU64,
+}
+
+struct CachedIndex {
+    sessions_dir: PathBuf,
+    files: Option<FileSnapshot>,
+    index: SessionIndex,
+}
+
+/// Modification time and size of each file in the sessions folder, by name
+type FileSnapshot = BTreeMap<OsString, (Option<SystemTime>, u64)>;
+
+impl SessionCache {
+    /// Cached index for `sessions_dir`, or the result of `load` if stale
+    ///
+    /// `load` runs without holding the cache lock, since loading may write
+    /// (and so invalidate) session records.
+    pub fn get_or_load<F>(&self, sessions_dir: &Path, load: F) -> Result<SessionIndex, String>
+    where
+        F: FnOnce() -> Result<SessionIndex, String>,
+    {
+        let files = snapshot(sessions_dir);
+
+        if let Some(cached) = self.cached.lock().unwrap().as_ref() {
+            if cached.sessions_dir == sessions_dir && cached.files == files {
+                return Ok(cached.index.clone());
+            }
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let index = load()?;
+
+        let mut cached = self.cached.lock().unwrap();
+        if self.generation.load(Ordering::SeqCst) == generation {
+            *cached = Some(CachedIndex {
+                sessions_dir: sessions_dir.to_path_buf(),
+                // Re-read: loading may have collapsed conflict copies
+                files: snapshot(sessions_dir),
+                index: index.clone(),
+            });
+        }
+
+        Ok(index)
+    }
+
+    /// Drop the cached index so the next load reads from disk
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        *self.cached.lock().unwrap() = None;
+    }
+}
+
+/// Snapshot of the files in `dir`; None if it can't be listed
+fn snapshot(dir: &Path) -> Option<FileSnapshot> {
+    let entries = fs::read_dir(dir).ok()?;
+
+    Some(
+        entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((
+                    entry.file_name(),
+                    (metadata.modified().ok(), metadata.len()),
+                ))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_cache_reused_until_invalidated() {
+        let dir = fixtures::temp_dir("cache");
+        let cache = SessionCache::default();
+        let loads = Cell::new(0);
+        let load = || {
+            loads.set(loads.get() + 1);
+            Ok(SessionIndex {
+                sessions: Vec::new(),
+            })
+        };
+
+        cache.get_or_load(&dir, load).unwrap();
+        cache.get_or_load(&dir, load).unwrap();
+        assert_eq!(loads.get(), 1);
+
+        cache.invalidate();
+        cache.get_or_load(&dir, load).unwrap();
+        assert_eq!(loads.get(), 2);
+
+        // A different storage folder is never served from the cache
+        cache.get_or_load(&dir.join("other"), load).unwrap();
+        assert_eq!(loads.get(), 3);
+    }
+
+    #[test]
+    fn test_cache_reloads_when_a_record_changes_on_disk() {
+        let dir = fixtures::temp_dir("cache-change");
+        let record = dir.join("a.json");
+        fs::write(&record, "{}").unwrap();
+
+        let cache = SessionCache::default();
+        let loads = Cell::new(0);
+        let load = || {
+            loads.set(loads.get() + 1);
+            Ok(SessionIndex {
+                sessions: Vec::new(),
+            })
+        };
+
+        cache.get_or_load(&dir, load).unwrap();
+        cache.get_or_load(&dir, load).unwrap();
+        assert_eq!(loads.get(), 1);
+
+        // Rewritten in place, as sync tools do, without renaming
+        fs::write(&record, "{\"id\": \"a\"}").unwrap();
+        cache.get_or_load(&dir, load).unwrap();
+        assert_eq!(loads.get(), 2);
+
+        fs::write(dir.join("b.json"), "{}").unwrap();
+        cache.get_or_load(&dir, load).unwrap();
+        assert_eq!(loads.get(), 3);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::recording::audio::{append_wav_samples, write_wav_file, AudioFormat};
+use crate::recording::models::Session;
+use crate::recording::session::integrity::orphan_session;
+use crate::recording::session::journal::move_file;
+use crate::recording::session::lifecycle::generate_unique_session_id;
+use crate::recording::session::recovery::mark_transcription_pending;
+use crate::recording::session::storage::add_session;
+use crate::recording::state::RecordingState;
+use crate::recording::utils::{audio_relative_path, get_local_state_dir, get_storage_dir};
+use chrono::{DateTime, Local, Utc};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+use std::thread;
+
+/// Audio of the active recording written out while it is paused, so a crash
+/// during a long pause doesn't lose it; removed once the recording is saved
+/// or cancelled
+const CHECKPOINT_FILE: &str = "recording-checkpoint.wav";
+
+/// When the checkpointed recording started (RFC 3339), next to its audio
+const CHECKPOINT_STARTED_FILE: &str = "recording-checkpoint-started";
+
+/// Checkpoint writes, carried out in order by one background thread so the
+/// recording state isn't locked while audio is written to disk
+enum CheckpointJob {
+    /// Start a new checkpoint with the recording so far
+    Write {
+        samples: Vec<f32>,
+        format: AudioFormat,
+        started_at: DateTime<Utc>,
+    },
+    /// Add the audio captured since the last pause
+    Append { samples: Vec<f32> },
+    /// Remove the checkpoint
+    Clear,
+}
+
+static WRITER: OnceLock<Sender<CheckpointJob>> = OnceLock::new();
+
+/// Write the samples recorded since the last pause to the checkpoint file
+///
+/// The first pause writes the whole recording; later pauses append only
+/// what was captured after resuming. Only the copying happens here, with the
+/// state locked; the file is written in the background.
+pub(crate) fn write_checkpoint(state: &mut RecordingState) {
+    let samples = state.samples.lock().unwrap();
+    let checkpointed = state.checkpointed_samples;
+
+    let job = if checkpointed > 0 && checkpointed <= samples.len() {
+        CheckpointJob::Append {
+            samples: samples[checkpointed..].to_vec(),
+        }
+    } else {
+        CheckpointJob::Write {
+            samples: samples.to_vec(),
+            format: state.audio_format,
+            started_at: state.timer.started_at().unwrap_or_else(Utc::now),
+        }
+    };
+
+    state.checkpointed_samples = samples.len();
+    queue(job);
+}
+
+/// Remove the checkpoint of a recording that was saved or discarded
+pub(crate) fn clear_checkpoint(state: &mut RecordingState) {
+    if state.checkpointed_samples == 0 {
+        return;
+    }
+    state.checkpointed_samples = 0;
+    queue(CheckpointJob::Clear);
+}
+
+/// Save the checkpoint left by a recording the app quit during as a session
+///
+/// Runs on startup, before queued transcriptions resume, so the recovered
+/// session is transcribed with them. The session is dated when the
+/// recording started. Returns the session, if there was a checkpoint.
+pub fn recover_checkpoint() -> Result<Option<Session>, String> {
+    let checkpoint = checkpoint_path()?;
+    if !checkpoint.is_file() {
+        return Ok(None);
+    }
+
+    let started_path = started_path()?;
+    let started_at = fs::read_to_string(&started_path)
+        .ok()
+        .and_then(|started| DateTime::parse_from_rfc3339(started.trim()).ok())
+        .map(|started| started.with_timezone(&Utc));
+    let recorded_at = match started_at {
+        Some(started_at) => started_at,
+        None => fs::metadata(&checkpoint)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|e| format!("Failed to read recording checkpoint: {}", e))?
+            .into(),
+    };
+
+    let audio_relative = audio_relative_path(&generate_unique_session_id(recorded_at)?);
+    let storage_dir = get_storage_dir()?;
+    move_file(&checkpoint, &storage_dir.join(&audio_relative))
+        .map_err(|e| format!("Failed to recover recording checkpoint: {}", e))?;
+    let _ = fs::remove_file(&started_path);
+
+    let mut session = orphan_session(&audio_relative, &storage_dir)?;
+    session.timestamp = recorded_at.to_rfc3339();
+    session.utc_offset_minutes =
+        Some(recorded_at.with_timezone(&Local).offset().local_minus_utc() / 60);
+    add_session(session.clone())?;
+    mark_transcription_pending(&session.id)?;
+
+    log::info!(
+        "Recovered {:.1}s of an interrupted recording as session {}",
+        session.duration,
+        session.id
+    );
+    Ok(Some(session))
+}
+
+/// Hand a job to the checkpoint writer, starting it on first use
+fn queue(job: CheckpointJob) {
+    let writer = WRITER.get_or_init(|| {
+        let (sender, jobs) = mpsc::channel();
+        thread::spawn(move || {
+            for job in jobs {
+                if let Err(e) = run_job(job) {
+                    log::warn!("Failed to update recording checkpoint: {}", e);
+                }
+            }
+        });
+        sender
+    });
+
+    if writer.send(job).is_err() {
+        log::warn!("Recording checkpoint writer has stopped");
+    }
+}
+
+fn run_job(job: CheckpointJob) -> Result<(), String> {
+    let path = checkpoint_path()?;
+
+    match job {
+        CheckpointJob::Write {
+            samples,
+            format,
+            started_at,
+        } => {
+            write_wav_file(&samples, format, &path)?;
+            fs::write(started_path()?, started_at.to_rfc3339())
+                .map_err(|e| format!("Failed to write recording start: {}", e))
+        }
+        CheckpointJob::Append { samples } => append_wav_samples(&samples, &path),
+        CheckpointJob::Clear => {
+            let _ = fs::remove_file(started_path()?);
+            fs::remove_file(&path).map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn checkpoint_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(CHECKPOINT_FILE))
+}
+
+fn started_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(CHECKPOINT_STARTED_FILE))
+}
@@ -0,0 +1,188 @@
+use crate::recording::audio::copy_wav_range;
+use crate::recording::models::{ClipExport, ClipFormat, TranscriptSegment};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::storage::get_session;
+use crate::recording::transcription::load_structured_transcript;
+use crate::recording::utils::{export_relative_path, get_storage_dir};
+use std::fs;
+
+/// Cut the audio between `start_seconds` and `end_seconds` of a session, and
+/// the transcript spoken in it, into a standalone pair of files
+///
+/// The files are written to `exports/` in the storage directory. The
+/// transcript needs the session's segment data; sessions without it export
+/// just the audio.
+pub fn export_clip(
+    session_id: &str,
+    start_seconds: f64,
+    end_seconds: f64,
+    format: ClipFormat,
+) -> Result<ClipExport, String> {
+    if !(start_seconds >= 0.0 && end_seconds > start_seconds) {
+        return Err("The clip must end after it starts".to_string());
+    }
+
+    let session = get_session(session_id)?;
+    let storage_dir = get_storage_dir()?;
+    let name = format!(
+        "{}-clip-{}-{}",
+        session.id,
+        start_seconds.round(),
+        end_seconds.round()
+    );
+
+    let audio_path = storage_dir.join(export_relative_path(&format!("{}.wav", name)));
+    if let Some(export_dir) = audio_path.parent() {
+        fs::create_dir_all(export_dir)
+            .map_err(|e| format!("Failed to create export folder: {}", e))?;
+    }
+    let clip_seconds = copy_wav_range(
+        &storage_dir.join(&session.audio_path),
+        &audio_path,
+        start_seconds,
+        end_seconds,
+    )?;
+    if clip_seconds <= 0.0 {
+        let _ = fs::remove_file(&audio_path);
+        return Err("The selected range contains no audio".to_string());
+    }
+
+    let transcript_path = match load_structured_transcript(&session.id) {
+        Ok(transcript) => {
+            let start_ms = (start_seconds * 1000.0) as i64;
+            let end_ms = start_ms + (clip_seconds * 1000.0) as i64;
+            // Redacted like every other export
+            let redacted: Vec<TranscriptSegment> =
+                segments_in_range(&transcript.segments, start_ms, end_ms)
+                    .into_iter()
+                    .map(|segment| TranscriptSegment {
+                        text: redact_outgoing(&segment.text),
+                        ..segment.clone()
+                    })
+                    .collect();
+            let segments: Vec<&TranscriptSegment> = redacted.iter().collect();
+            let (contents, extension) = match format {
+                ClipFormat::Txt => (clip_text(&segments), "txt"),
+                ClipFormat::Srt => (clip_srt(&segments, start_ms, end_ms), "srt"),
+            };
+
+            let path = storage_dir.join(export_relative_path(&format!("{}.{}", name, extension)));
+            fs::write(&path, contents)
+                .map_err(|e| format!("Failed to write clip transcript: {}", e))?;
+            Some(path.to_string_lossy().to_string())
+        }
+        Err(e) => {
+            log::info!("Exporting clip of {} without transcript: {}", session.id, e);
+            None
+        }
+    };
+
+    log::info!(
+        "Exported {:.1}s clip of {} to {}",
+        clip_seconds,
+        session.id,
+        audio_path.display()
+    );
+
+    Ok(ClipExport {
+        audio_path: audio_path.to_string_lossy().to_string(),
+        transcript_path,
+    })
+}
+
+/// Segments spoken at least partly between `start_ms` and `end_ms`
+fn segments_in_range(
+    segments: &[TranscriptSegment],
+    start_ms: i64,
+    end_ms: i64,
+) -> Vec<&TranscriptSegment> {
+    segments
+        .iter()
+        .filter(|segment| segment.end_ms > start_ms && segment.start_ms < end_ms)
+        .collect()
+}
+
+fn clip_text(segments: &[&TranscriptSegment]) -> String {
+    let text = segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!("{}\n", text)
+}
+
+/// SubRip subtitles for the clip, with times relative to its start
+fn clip_srt(segments: &[&TranscriptSegment], start_ms: i64, end_ms: i64) -> String {
+    let clip_time = |ms: i64| srt_timestamp(ms.clamp(start_ms, end_ms) - start_ms);
+
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                clip_time(segment.start_ms),
+                clip_time(segment.end_ms),
+                segment.text
+            )
+        })
+        .collect()
+}
+
+/// `HH:MM:SS,mmm` timestamp used by SubRip
+fn srt_timestamp(ms: i64) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: i64, end_ms: i64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            avg_logprob: None,
+            no_speech_prob: None,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_selects_segments_overlapping_range() {
+        let segments = vec![
+            segment(0, 5_000, "Before."),
+            segment(5_000, 12_000, "Straddling."),
+            segment(12_000, 20_000, "Inside."),
+            segment(30_000, 35_000, "After."),
+        ];
+
+        let selected = segments_in_range(&segments, 10_000, 30_000);
+        assert_eq!(clip_text(&selected), "Straddling. Inside.\n");
+    }
+
+    #[test]
+    fn test_srt_times_are_relative_to_clip() {
+        let segments = [
+            segment(5_000, 12_000, "Hello"),
+            segment(12_000, 75_500, "World"),
+        ];
+        let selected: Vec<&TranscriptSegment> = segments.iter().collect();
+
+        assert_eq!(
+            clip_srt(&selected, 10_000, 70_000),
+            "1\n00:00:00,000 --> 00:00:02,000\nHello\n\n\
+             2\n00:00:02,000 --> 00:01:00,000\nWorld\n\n"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use crate::recording::integrations::notion::auto_publish_to_notion;
+use crate::recording::integrations::tasks::auto_create_tasks;
+use crate::recording::models::{PostprocessStep, Session};
+use crate::recording::postprocess::saved_transcript_steps;
+use crate::recording::session::quality::auto_flag_quality;
+use crate::recording::session::storage::get_session;
+use crate::recording::session::titles::auto_title_session;
+use std::thread;
+
+/// Run follow-up integrations for a session whose transcript is final
+///
+/// Called once per transcription, after any refinement pass. The steps of
+/// the post-processing pipeline that act on the saved transcript run in a
+/// background thread, one after another in the listed order, each seeing
+/// the session as the previous step left it (e.g. exported with the title
+/// given by `title` when it comes first).
+pub fn on_transcription_complete(session: &Session) {
+    auto_flag_quality(session);
+
+    let steps = saved_transcript_steps();
+    if steps.is_empty() {
+        return;
+    }
+
+    let session_id = session.id.clone();
+    thread::spawn(move || {
+        for step in steps {
+            let session = match get_session(&session_id) {
+                Ok(session) => session,
+                Err(e) => {
+                    log::warn!("Stopped post-processing {}: {}", session_id, e);
+                    return;
+                }
+            };
+
+            match step {
+                PostprocessStep::Title => auto_title_session(&session),
+                PostprocessStep::Export => {
+                    auto_publish_to_notion(&session);
+                    auto_create_tasks(&session);
+                }
+                _ => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,87 @@
+use crate::recording::audio::audio_fingerprint;
+use crate::recording::models::Session;
+use std::path::Path;
+
+/// Sessions whose stored durations differ by less than this may share audio
+const DURATION_TOLERANCE_SECONDS: f64 = 0.01;
+
+/// Id of an archived session with the same audio as the WAV file at `path`
+///
+/// Only sessions of the same length are fingerprinted, so the rest of the
+/// archive is never read. Files that can't be fingerprinted never match.
+pub fn find_duplicate_session(
+    path: &Path,
+    duration: f64,
+    sessions: &[Session],
+    storage_dir: &Path,
+) -> Option<String> {
+    let mut candidates = sessions.iter().filter(|session| {
+        !session.audio_path.is_empty()
+            && (session.duration - duration).abs() < DURATION_TOLERANCE_SECONDS
+    });
+
+    let first = candidates.next()?;
+    let fingerprint = audio_fingerprint(path)
+        .map_err(|e| log::debug!("Not checking {} for duplicates: {}", path.display(), e))
+        .ok()?;
+
+    std::iter::once(first)
+        .chain(candidates)
+        .find(|session| {
+            let candidate = storage_dir.join(&session.audio_path);
+            candidate != path && audio_fingerprint(&candidate).ok() == Some(fingerprint)
+        })
+        .map(|session| session.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::audio::{wav_duration, write_wav_file, AudioFormat};
+    use crate::recording::fixtures;
+    use std::fs;
+
+    fn session(id: &str, duration: f64) -> Session {
+        Session {
+            duration,
+            ..fixtures::session(id)
+        }
+    }
+
+    #[test]
+    fn test_finds_session_with_same_audio() {
+        let storage = fixtures::temp_dir("duplicates");
+        fs::create_dir_all(storage.join("audio")).unwrap();
+
+        write_wav_file(
+            &[0.1, 0.2, 0.3],
+            AudioFormat::default(),
+            &storage.join("audio/original.wav"),
+        )
+        .unwrap();
+        write_wav_file(
+            &[0.3, 0.2, 0.1],
+            AudioFormat::default(),
+            &storage.join("audio/other.wav"),
+        )
+        .unwrap();
+        let incoming = storage.join("renamed-copy.wav");
+        write_wav_file(&[0.1, 0.2, 0.3], AudioFormat::default(), &incoming).unwrap();
+        let duration = wav_duration(&incoming).unwrap();
+
+        let sessions = vec![session("other", duration), session("original", duration)];
+        assert_eq!(
+            find_duplicate_session(&incoming, duration, &sessions, &storage),
+            Some("original".to_string())
+        );
+
+        // A session of another length is never compared
+        let sessions = vec![session("original", duration + 1.0)];
+        assert_eq!(
+            find_duplicate_session(&incoming, duration, &sessions, &storage),
+            None
+        );
+
+        let _ = fs::remove_dir_all(&storage);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::recording::audio::calculate_rms_amplitude;
+use crate::recording::models::EmptyRecordingGuard;
+
+/// Check whether a recording is trivially empty
+///
+/// Returns the reason the recording should be discarded, or None to keep it
+pub fn empty_recording_reason(
+    guard: &EmptyRecordingGuard,
+    duration_seconds: f64,
+    samples: &[f32],
+) -> Option<String> {
+    if duration_seconds < guard.min_duration_seconds {
+        return Some(format!(
+            "Recording discarded: {:.1}s is shorter than the {:.1}s minimum.",
+            duration_seconds, guard.min_duration_seconds
+        ));
+    }
+
+    if guard.min_level > 0.0 && calculate_rms_amplitude(samples) < guard.min_level {
+        return Some("Recording discarded: no audio was detected.".to_string());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speech_like(len: usize) -> Vec<f32> {
+        (0..len).map(|i| 0.1 * (i as f32 * 0.05).sin()).collect()
+    }
+
+    #[test]
+    fn test_short_recording_is_discarded() {
+        let guard = EmptyRecordingGuard::default();
+        let reason = empty_recording_reason(&guard, 0.4, &speech_like(1000));
+
+        assert!(reason.unwrap().contains("0.4s"));
+    }
+
+    #[test]
+    fn test_silent_recording_is_discarded() {
+        let guard = EmptyRecordingGuard::default();
+        assert!(empty_recording_reason(&guard, 5.0, &vec![0.0001; 1000]).is_some());
+    }
+
+    #[test]
+    fn test_normal_recording_is_kept() {
+        let guard = EmptyRecordingGuard::default();
+        assert!(empty_recording_reason(&guard, 5.0, &speech_like(1000)).is_none());
+    }
+
+    #[test]
+    fn test_zero_thresholds_disable_guard() {
+        let guard = EmptyRecordingGuard {
+            min_duration_seconds: 0.0,
+            min_level: 0.0,
+        };
+        assert!(empty_recording_reason(&guard, 0.1, &[]).is_none());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::recording::models::{DragExportBundle, Session};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::storage::get_session;
+use crate::recording::transcription::romanize::romanize;
+use crate::recording::utils::get_storage_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Temp subdirectory holding prepared drag-export bundles
+const EXPORT_TEMP_DIR: &str = "ThoughtCast-export";
+
+/// Bundles older than this are removed on the next prepare/cleanup
+const STALE_BUNDLE_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Prepare a temporary export bundle for dragging a session out of the app
+///
+/// Copies the session's audio and transcript into a per-session temp folder
+/// so the OS drag operation works on throwaway files rather than the archive.
+/// Stale bundles from earlier drags are cleaned up first. With `romanized`,
+/// the bundle holds the romanized transcript instead of the original.
+pub fn prepare_drag_export(session_id: &str, romanized: bool) -> Result<DragExportBundle, String> {
+    let export_root = std::env::temp_dir().join(EXPORT_TEMP_DIR);
+    cleanup_stale_bundles(&export_root, STALE_BUNDLE_AGE);
+
+    let session = get_session(session_id)?;
+    build_bundle(&session, &get_storage_dir()?, &export_root, romanized)
+}
+
+/// Remove all prepared drag-export bundles
+///
+/// Called by the frontend once a drag completes, and on app startup
+pub fn cleanup_drag_exports() -> Result<(), String> {
+    let export_root = std::env::temp_dir().join(EXPORT_TEMP_DIR);
+    if !export_root.exists() {
+        return Ok(());
+    }
+
+    fs::remove_dir_all(&export_root)
+        .map_err(|e| format!("Failed to clean up export files: {}", e))
+}
+
+/// Copy a session's files from `storage_dir` into a bundle under `export_root`
+pub(crate) fn build_bundle(
+    session: &Session,
+    storage_dir: &Path,
+    export_root: &Path,
+    romanized: bool,
+) -> Result<DragExportBundle, String> {
+    let bundle_dir = export_root.join(&session.id);
+    if bundle_dir.exists() {
+        fs::remove_dir_all(&bundle_dir)
+            .map_err(|e| format!("Failed to reset export folder: {}", e))?;
+    }
+    fs::create_dir_all(&bundle_dir)
+        .map_err(|e| format!("Failed to create export folder: {}", e))?;
+
+    let mut files = Vec::new();
+
+    let audio_source = storage_dir.join(&session.audio_path);
+    if audio_source.is_file() {
+        files.push(copy_into_bundle(&audio_source, &bundle_dir)?);
+    }
+
+    if !session.transcript_path.is_empty() {
+        let transcript_source = storage_dir.join(&session.transcript_path);
+        if transcript_source.is_file() && romanized {
+            files.push(write_romanized_transcript(
+                session,
+                &transcript_source,
+                &bundle_dir,
+            )?);
+        } else if transcript_source.is_file() {
+            files.push(write_transcript(&transcript_source, &bundle_dir)?);
+        }
+    }
+
+    if files.is_empty() {
+        return Err(format!("No files to export for session {}", session.id));
+    }
+
+    Ok(DragExportBundle {
+        directory: bundle_dir.to_string_lossy().to_string(),
+        files,
+    })
+}
+
+/// Copy a file into the bundle folder, returning its absolute path
+fn copy_into_bundle(source: &Path, bundle_dir: &Path) -> Result<String, String> {
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", source.display()))?;
+    let target: PathBuf = bundle_dir.join(file_name);
+
+    fs::copy(source, &target)
+        .map_err(|e| format!("Failed to copy {} for export: {}", source.display(), e))?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+/// Write the transcript into the bundle folder, redacted as set in
+/// `privacy.redaction`, returning its absolute path
+fn write_transcript(source: &Path, bundle_dir: &Path) -> Result<String, String> {
+    let text = fs::read_to_string(source)
+        .map_err(|e| format!("Failed to read transcript for export: {}", e))?;
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", source.display()))?;
+    let target = bundle_dir.join(file_name);
+
+    fs::write(&target, redact_outgoing(&text))
+        .map_err(|e| format!("Failed to write transcript for export: {}", e))?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+/// Write the romanized transcript into the bundle folder, returning its
+/// absolute path
+fn write_romanized_transcript(
+    session: &Session,
+    transcript_source: &Path,
+    bundle_dir: &Path,
+) -> Result<String, String> {
+    let text = fs::read_to_string(transcript_source)
+        .map_err(|e| format!("Failed to read transcript for export: {}", e))?;
+    let target = bundle_dir.join(format!("{}.romanized.txt", session.id));
+
+    fs::write(&target, romanize(&redact_outgoing(&text)))
+        .map_err(|e| format!("Failed to write romanized transcript: {}", e))?;
+
+    Ok(target.to_string_lossy().to_string())
+}
+
+/// Remove bundle folders older than `max_age`
+///
+/// Best-effort: failures are logged and ignored
+fn cleanup_stale_bundles(export_root: &Path, max_age: Duration) {
+    let Ok(entries) = fs::read_dir(export_root) else {
+        return;
+    };
+
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        let is_stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+
+        if is_stale {
+            if let Err(e) = fs::remove_dir_all(entry.path()) {
+                log::warn!("Failed to remove stale export {}: {}", entry.path().display(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = fixtures::temp_dir(&format!("export-{}", name));
+        fs::create_dir_all(dir.join("audio")).unwrap();
+        fs::create_dir_all(dir.join("text")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_bundle_contains_audio_and_transcript() {
+        let storage = temp_dir("storage");
+        let export_root = temp_dir("root");
+        fs::write(storage.join("audio/s1.wav"), b"wav").unwrap();
+        fs::write(storage.join("text/s1.txt"), "hello").unwrap();
+
+        let bundle = build_bundle(&fixtures::session("s1"), &storage, &export_root, false).unwrap();
+
+        assert_eq!(bundle.files.len(), 2);
+        assert!(bundle.files.iter().all(|file| Path::new(file).exists()));
+        assert!(bundle.directory.ends_with("s1"));
+
+        fs::write(storage.join("text/s1.txt"), "привет").unwrap();
+        let bundle = build_bundle(&fixtures::session("s1"), &storage, &export_root, true).unwrap();
+        assert!(bundle.files[1].ends_with("s1.romanized.txt"));
+        assert_eq!(fs::read_to_string(&bundle.files[1]).unwrap(), "privet");
+
+        let _ = fs::remove_dir_all(&storage);
+        let _ = fs::remove_dir_all(&export_root);
+    }
+
+    #[test]
+    fn test_bundle_errors_without_files() {
+        let storage = temp_dir("empty-storage");
+        let export_root = temp_dir("empty-root");
+
+        assert!(
+            build_bundle(&fixtures::session("missing"), &storage, &export_root, false).is_err()
+        );
+
+        let _ = fs::remove_dir_all(&storage);
+        let _ = fs::remove_dir_all(&export_root);
+    }
+
+    #[test]
+    fn test_cleanup_keeps_fresh_bundles() {
+        let export_root = temp_dir("fresh");
+        fs::create_dir_all(export_root.join("bundle")).unwrap();
+
+        cleanup_stale_bundles(&export_root, Duration::from_secs(3600));
+        assert!(export_root.join("bundle").exists());
+
+        std::thread::sleep(Duration::from_millis(10));
+        cleanup_stale_bundles(&export_root, Duration::ZERO);
+        assert!(!export_root.join("bundle").exists());
+
+        let _ = fs::remove_dir_all(&export_root);
+    }
+}
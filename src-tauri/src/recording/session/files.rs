@@ -0,0 +1,88 @@
+use crate::recording::models::Session;
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::storage::{get_session, load_sessions, load_transcript};
+use crate::recording::utils::{
+    copy_files_to_clipboard, copy_to_clipboard, get_storage_dir, open_with_default_app,
+    reveal_in_file_manager, ClipboardMechanism,
+};
+use std::path::PathBuf;
+
+/// Absolute path of a session's audio file
+pub fn session_audio_path(session: &Session) -> Result<PathBuf, String> {
+    Ok(get_storage_dir()?.join(&session.audio_path))
+}
+
+/// Absolute path of a session's transcript file
+///
+/// Errors if the session has not been transcribed yet
+pub fn session_transcript_path(session: &Session) -> Result<PathBuf, String> {
+    if session.transcript_path.is_empty() {
+        return Err("No transcript available for this session".to_string());
+    }
+    Ok(get_storage_dir()?.join(&session.transcript_path))
+}
+
+/// Reveal a session's audio file in Finder/Explorer
+pub fn reveal_session_audio(session_id: &str) -> Result<(), String> {
+    let session = get_session(session_id)?;
+    let audio_path = session_audio_path(&session)?;
+
+    if !audio_path.exists() {
+        return Err(format!("Audio file not found: {}", audio_path.display()));
+    }
+
+    reveal_in_file_manager(&audio_path)
+}
+
+/// Open a session's transcript in the default text editor
+pub fn open_transcript_in_editor(session_id: &str) -> Result<(), String> {
+    let session = get_session(session_id)?;
+    let transcript_path = session_transcript_path(&session)?;
+
+    if !transcript_path.exists() {
+        return Err(format!(
+            "Transcript file not found: {}",
+            transcript_path.display()
+        ));
+    }
+
+    open_with_default_app(&transcript_path)
+}
+
+/// Place a session's audio file on the clipboard as a file reference
+///
+/// Lets the recording itself be pasted into chat or mail apps when the
+/// transcript isn't enough
+pub fn copy_session_audio_to_clipboard(session_id: &str) -> Result<ClipboardMechanism, String> {
+    let session = get_session(session_id)?;
+    let audio_path = session_audio_path(&session)?;
+
+    if !audio_path.exists() {
+        return Err(format!("Audio file not found: {}", audio_path.display()));
+    }
+
+    copy_files_to_clipboard(&[audio_path])
+}
+
+/// Most recently recorded session, or none before the first recording
+pub fn get_last_session() -> Result<Option<Session>, String> {
+    Ok(load_sessions()?.sessions.into_iter().next())
+}
+
+/// Copy the transcript of the most recently transcribed session to the
+/// clipboard, returning that session
+pub fn copy_last_transcript() -> Result<Session, String> {
+    let session = load_sessions()?
+        .sessions
+        .into_iter()
+        .find(|session| !session.transcript_path.is_empty())
+        .ok_or_else(|| "No transcribed sessions yet".to_string())?;
+
+    let transcript = load_transcript(&session.id)?;
+    if transcript.is_empty() {
+        return Err("No transcript available for this session".to_string());
+    }
+    copy_to_clipboard(&redact_outgoing(&transcript))?;
+
+    Ok(session)
+}
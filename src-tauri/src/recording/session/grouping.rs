@@ -0,0 +1,164 @@
+use crate::recording::models::{DayActivity, Session, SessionDayGroup};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate};
+use std::collections::BTreeMap;
+
+/// Group sessions by the local calendar day they were recorded on
+///
+/// Each session is placed using its own stored UTC offset, so a 7 pm
+/// recording stays on the same day even after travelling or a DST change.
+/// Legacy sessions without a stored offset use `fallback_offset_minutes`.
+/// Sessions with unparseable timestamps are skipped.
+///
+/// Returns groups newest day first; session order within a day is preserved.
+pub fn group_sessions_by_day(
+    sessions: &[Session],
+    fallback_offset_minutes: i32,
+) -> Vec<SessionDayGroup> {
+    let mut groups: Vec<(NaiveDate, SessionDayGroup)> = Vec::new();
+
+    for session in sessions {
+        let offset_minutes = session.utc_offset_minutes.unwrap_or(fallback_offset_minutes);
+        let Some(date) = local_date(&session.timestamp, offset_minutes) else {
+            continue;
+        };
+
+        match groups.iter_mut().find(|(group_date, _)| *group_date == date) {
+            Some((_, group)) => {
+                group.total_duration += session.duration;
+                group.sessions.push(session.clone());
+            }
+            None => groups.push((
+                date,
+                SessionDayGroup {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    total_duration: session.duration,
+                    sessions: vec![session.clone()],
+                },
+            )),
+        }
+    }
+
+    groups.sort_by(|(a, _), (b, _)| b.cmp(a));
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// Number of recordings and minutes recorded on each local day of `year`
+///
+/// Days are placed like in `group_sessions_by_day`. Only days with
+/// recordings are returned, oldest first. Uses the session index only, so
+/// no transcript is read.
+pub fn recording_activity(
+    sessions: &[Session],
+    year: i32,
+    fallback_offset_minutes: i32,
+) -> Vec<DayActivity> {
+    let mut days: BTreeMap<NaiveDate, (usize, f64)> = BTreeMap::new();
+
+    for session in sessions {
+        let offset_minutes = session.utc_offset_minutes.unwrap_or(fallback_offset_minutes);
+        let Some(date) = local_date(&session.timestamp, offset_minutes) else {
+            continue;
+        };
+        if date.year() == year {
+            let (count, seconds) = days.entry(date).or_default();
+            *count += 1;
+            *seconds += session.duration;
+        }
+    }
+
+    days.into_iter()
+        .map(|(date, (count, seconds))| DayActivity {
+            date: date.format("%Y-%m-%d").to_string(),
+            count,
+            minutes: seconds / 60.0,
+        })
+        .collect()
+}
+
+/// Local calendar date of an RFC 3339 timestamp at the given UTC offset
+fn local_date(timestamp: &str, offset_minutes: i32) -> Option<NaiveDate> {
+    let offset = FixedOffset::east_opt(offset_minutes * 60)?;
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|time| time.with_timezone(&offset).date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn create_session(id: &str, timestamp: &str, offset: Option<i32>) -> Session {
+        Session {
+            timestamp: timestamp.to_string(),
+            utc_offset_minutes: offset,
+            ..fixtures::session(id)
+        }
+    }
+
+    #[test]
+    fn test_evening_recording_stays_on_local_day() {
+        // 7 pm in New York (UTC-5) is midnight UTC the next day
+        let sessions = vec![create_session("a", "2024-11-03T00:00:00+00:00", Some(-300))];
+
+        let groups = group_sessions_by_day(&sessions, 0);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].date, "2024-11-02");
+    }
+
+    #[test]
+    fn test_fallback_offset_for_legacy_sessions() {
+        let sessions = vec![create_session("a", "2024-11-03T00:00:00+00:00", None)];
+
+        assert_eq!(group_sessions_by_day(&sessions, 0)[0].date, "2024-11-03");
+        assert_eq!(group_sessions_by_day(&sessions, -300)[0].date, "2024-11-02");
+    }
+
+    #[test]
+    fn test_groups_newest_day_first_with_totals() {
+        let sessions = vec![
+            create_session("c", "2024-11-03T12:00:00+00:00", Some(0)),
+            create_session("b", "2024-11-02T18:00:00+00:00", Some(0)),
+            create_session("a", "2024-11-02T09:00:00+00:00", Some(0)),
+        ];
+
+        let groups = group_sessions_by_day(&sessions, 0);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].date, "2024-11-03");
+        assert_eq!(groups[1].date, "2024-11-02");
+        assert_eq!(groups[1].sessions.len(), 2);
+        assert_eq!(groups[1].sessions[0].id, "b");
+        assert_eq!(groups[1].total_duration, 20.0);
+    }
+
+    #[test]
+    fn test_activity_counts_days_of_the_year() {
+        let sessions = vec![
+            create_session("d", "2025-01-01T00:30:00+00:00", Some(-300)),
+            create_session("c", "2024-11-03T12:00:00+00:00", Some(0)),
+            create_session("b", "2024-11-02T18:00:00+00:00", Some(0)),
+            create_session("a", "2024-11-02T09:00:00+00:00", Some(0)),
+            create_session("z", "2023-11-02T09:00:00+00:00", Some(0)),
+        ];
+
+        let activity = recording_activity(&sessions, 2024, 0);
+
+        let days: Vec<(&str, usize)> = activity
+            .iter()
+            .map(|day| (day.date.as_str(), day.count))
+            .collect();
+        assert_eq!(
+            days,
+            vec![("2024-11-02", 2), ("2024-11-03", 1), ("2024-12-31", 1)]
+        );
+        assert!((activity[0].minutes - 20.0 / 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_timestamps_are_skipped() {
+        let sessions = vec![create_session("a", "not-a-date", None)];
+        assert!(group_sessions_by_day(&sessions, 0).is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::recording::models::SessionIdScheme;
+use chrono::{DateTime, Local, Utc};
+
+/// Format used for the timestamp part of every session id
+const ID_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// Generate a session id that doesn't collide with existing sessions
+///
+/// The id always starts with the recording timestamp so files sort
+/// chronologically. Depending on the scheme a short random suffix is appended,
+/// and in every scheme a numeric counter is added if the id is still taken
+/// (e.g. two stops within the same second).
+///
+/// # Arguments
+/// * `scheme` - Configured id scheme
+/// * `timestamp` - Time the recording was stopped
+/// * `is_taken` - Returns true if an id is already used by a session or file
+pub fn generate_session_id<F>(scheme: SessionIdScheme, timestamp: DateTime<Utc>, is_taken: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    let base = match scheme {
+        SessionIdScheme::Timestamp => timestamp.format(ID_TIMESTAMP_FORMAT).to_string(),
+        SessionIdScheme::TimestampSuffix => format!(
+            "{}_{}",
+            timestamp.format(ID_TIMESTAMP_FORMAT),
+            random_suffix()
+        ),
+    };
+
+    if !is_taken(&base) {
+        return base;
+    }
+
+    (2..)
+        .map(|counter| format!("{}-{}", base, counter))
+        .find(|candidate| !is_taken(candidate))
+        .expect("unbounded counter always yields a free id")
+}
+
+/// Name (without extension) of a new session's audio and transcript files
+///
+/// Fills in the configured filename pattern (see `storage.filenamePattern`)
+/// with the local recording date and time, the title and the session id.
+/// The id is appended when the pattern lacks it, so names stay unique.
+/// Without a pattern, files are named after the session id.
+pub fn session_file_stem(
+    pattern: Option<&str>,
+    session_id: &str,
+    recorded_at: DateTime<Local>,
+    title: Option<&str>,
+) -> String {
+    let Some(pattern) = pattern.filter(|pattern| !pattern.trim().is_empty()) else {
+        return session_id.to_string();
+    };
+
+    let pattern = if pattern.contains("{id}") {
+        pattern.to_string()
+    } else {
+        format!("{}_{{id}}", pattern)
+    };
+
+    let filled = pattern
+        .replace("{date}", &recorded_at.format("%Y-%m-%d").to_string())
+        .replace("{time}", &recorded_at.format("%H-%M-%S").to_string())
+        .replace("{title}", &file_name_part(title.unwrap_or_default()))
+        .replace("{id}", session_id);
+
+    tidy_separators(&file_name_part(&filled))
+}
+
+/// Text made safe for a file name: characters that are invalid on some
+/// file system become `-`, whitespace becomes `-`
+fn file_name_part(text: &str) -> String {
+    text.trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_whitespace() || c.is_control() => '-',
+            c => c,
+        })
+        .collect()
+}
+
+/// Collapse runs of separators left by empty placeholders (e.g. an untitled
+/// session's `{date}_{title}_{id}`) and trim them from the ends
+fn tidy_separators(name: &str) -> String {
+    let mut tidy = String::with_capacity(name.len());
+    for c in name.chars() {
+        let is_separator = matches!(c, '_' | '-' | '.');
+        if is_separator && tidy.ends_with(['_', '-', '.']) {
+            continue;
+        }
+        tidy.push(c);
+    }
+
+    tidy.trim_matches(['_', '-', '.']).to_string()
+}
+
+/// Short random hex suffix (4 characters), from the OS random source so
+/// machines syncing the same folder don't pick the same one
+fn random_suffix() -> String {
+    let mut bytes = [0u8; 2];
+    // The caller's existence check still keeps the id unique on this machine
+    if let Err(e) = getrandom::getrandom(&mut bytes) {
+        log::warn!("Failed to generate session id suffix: {}", e);
+    }
+    format!("{:02x}{:02x}", bytes[0], bytes[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn test_timestamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 11, 2, 15, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_timestamp_scheme_uses_plain_timestamp() {
+        let id = generate_session_id(SessionIdScheme::Timestamp, test_timestamp(), |_| false);
+        assert_eq!(id, "2024-11-02_15-30-00");
+    }
+
+    #[test]
+    fn test_timestamp_scheme_adds_counter_on_collision() {
+        let id = generate_session_id(SessionIdScheme::Timestamp, test_timestamp(), |candidate| {
+            candidate == "2024-11-02_15-30-00" || candidate == "2024-11-02_15-30-00-2"
+        });
+        assert_eq!(id, "2024-11-02_15-30-00-3");
+    }
+
+    #[test]
+    fn test_suffix_scheme_appends_random_hex() {
+        let id = generate_session_id(SessionIdScheme::TimestampSuffix, test_timestamp(), |_| false);
+        assert!(id.starts_with("2024-11-02_15-30-00_"));
+
+        let suffix = id.rsplit('_').next().unwrap();
+        assert_eq!(suffix.len(), 4);
+        assert!(suffix.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_file_stem_fills_pattern() {
+        let recorded_at = test_timestamp().with_timezone(&Local);
+        let date = recorded_at.format("%Y-%m-%d").to_string();
+        let id = "2024-11-02_15-30-00_a3f9";
+
+        assert_eq!(
+            session_file_stem(None, id, recorded_at, Some("Standup")),
+            id
+        );
+        assert_eq!(
+            session_file_stem(
+                Some("{date}_{title}_{id}"),
+                id,
+                recorded_at,
+                Some("Q4 plan: draft/2")
+            ),
+            format!("{}_Q4-plan-draft-2_{}", date, id)
+        );
+        // Untitled sessions don't leave a double separator
+        assert_eq!(
+            session_file_stem(Some("{date}_{title}_{id}"), id, recorded_at, None),
+            format!("{}_{}", date, id)
+        );
+        // The id is always part of the name
+        assert_eq!(
+            session_file_stem(Some("{title}"), id, recorded_at, Some("Standup")),
+            format!("Standup_{}", id)
+        );
+    }
+
+    #[test]
+    fn test_suffix_scheme_never_returns_taken_id() {
+        let taken = generate_session_id(SessionIdScheme::TimestampSuffix, test_timestamp(), |_| false);
+        let id = generate_session_id(SessionIdScheme::TimestampSuffix, test_timestamp(), |candidate| {
+            candidate == taken
+        });
+        assert_ne!(id, taken);
+    }
+}
@@ -0,0 +1,291 @@
+use crate::recording::models::{ImportSummary, Session};
+use crate::recording::session::duplicates::find_duplicate_session;
+use crate::recording::session::storage::{
+    read_sessions_from, session_record_path, write_session_record, SESSIONS_DIR,
+};
+use crate::recording::utils::get_storage_dir;
+use std::fs;
+use std::path::{Component, Path};
+
+/// Import sessions from another ThoughtCast storage folder
+///
+/// Merges sessions from `source_dir` (e.g. a copy of another machine's
+/// `Documents/ThoughtCast`) into the active storage directory.
+/// Sessions whose id already exists locally are skipped, as are sessions whose
+/// audio is already in the archive under another id; audio and transcript
+/// files are copied alongside new sessions. Sessions with an unsafe id or
+/// file path are skipped. The source folder is never modified.
+pub fn import_from_directory(source_dir: &Path) -> Result<ImportSummary, String> {
+    let storage_dir = get_storage_dir()?;
+    import_sessions(source_dir, &storage_dir)
+}
+
+/// Import sessions from `source_dir` into `target_dir`
+fn import_sessions(source_dir: &Path, target_dir: &Path) -> Result<ImportSummary, String> {
+    validate_source_dir(source_dir, target_dir)?;
+
+    let foreign_sessions = read_sessions_from(source_dir)?;
+    let mut archived_sessions = read_sessions_from(target_dir)?;
+    let target_sessions_dir = target_dir.join(SESSIONS_DIR);
+
+    let mut summary = ImportSummary::default();
+
+    for session in foreign_sessions {
+        if !is_valid_session(&session) {
+            log::warn!(
+                "Skipping imported session with unsafe id or paths: {}",
+                session.id
+            );
+            summary.skipped_invalid.push(session.id);
+            continue;
+        }
+
+        if session_record_path(&target_sessions_dir, &session.id).exists() {
+            summary.skipped_duplicates += 1;
+            continue;
+        }
+
+        if !session.audio_path.is_empty() {
+            let audio = source_dir.join(&session.audio_path);
+            let duplicate =
+                find_duplicate_session(&audio, session.duration, &archived_sessions, target_dir);
+            if let Some(existing_id) = duplicate {
+                summary.linked_duplicates.insert(session.id, existing_id);
+                continue;
+            }
+        }
+
+        // Paths of files missing in the source are cleared, so the imported
+        // session doesn't point at nothing
+        let mut imported = session.clone();
+        for path in [&mut imported.audio_path, &mut imported.transcript_path] {
+            if !path.is_empty() && !copy_session_file(source_dir, target_dir, path)? {
+                summary.missing_files.push(std::mem::take(path));
+            }
+        }
+
+        write_session_record(&target_sessions_dir, &imported)?;
+        archived_sessions.push(imported);
+        summary.imported += 1;
+    }
+
+    Ok(summary)
+}
+
+/// Whether a session's id and file paths stay inside the storage root
+fn is_valid_session(session: &Session) -> bool {
+    let id_is_valid = !session.id.is_empty()
+        && session
+            .id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let path_is_valid = |path: &str| path.is_empty() || is_safe_relative_path(path);
+
+    id_is_valid && path_is_valid(&session.audio_path) && path_is_valid(&session.transcript_path)
+}
+
+/// Ensure the source is a different folder that looks like ThoughtCast storage
+fn validate_source_dir(source_dir: &Path, target_dir: &Path) -> Result<(), String> {
+    if !source_dir.is_dir() {
+        return Err(format!("Import folder not found: {}", source_dir.display()));
+    }
+
+    let same_dir = match (source_dir.canonicalize(), target_dir.canonicalize()) {
+        (Ok(source), Ok(target)) => source == target,
+        _ => false,
+    };
+    if same_dir {
+        return Err("Cannot import from the current ThoughtCast folder.".to_string());
+    }
+
+    if !source_dir.join(SESSIONS_DIR).is_dir() && !source_dir.join("sessions.json").is_file() {
+        return Err(format!(
+            "{} does not look like a ThoughtCast folder (no sessions found).",
+            source_dir.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Copy a session file (audio or transcript) between storage roots
+///
+/// Returns false if the source file doesn't exist. Existing target files are
+/// left untouched. The path must have been checked by `is_safe_relative_path`.
+fn copy_session_file(
+    source_dir: &Path,
+    target_dir: &Path,
+    relative_path: &str,
+) -> Result<bool, String> {
+    let source = source_dir.join(relative_path);
+    if !source.is_file() {
+        return Ok(false);
+    }
+
+    let target = target_dir.join(relative_path);
+    if target.exists() {
+        return Ok(true);
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+    }
+
+    fs::copy(&source, &target)
+        .map_err(|e| format!("Failed to copy {}: {}", relative_path, e))?;
+
+    Ok(true)
+}
+
+/// Whether a stored relative path stays inside the storage root
+fn is_safe_relative_path(relative_path: &str) -> bool {
+    let path = Path::new(relative_path);
+    !relative_path.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::audio::{write_wav_file, AudioFormat};
+    use crate::recording::fixtures;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = fixtures::temp_dir(&format!("import-{}", name));
+        for sub in [SESSIONS_DIR, "audio", "text"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_safe_relative_paths() {
+        assert!(is_safe_relative_path("audio/a.wav"));
+        assert!(!is_safe_relative_path("../audio/a.wav"));
+        assert!(!is_safe_relative_path("/etc/passwd"));
+        assert!(!is_safe_relative_path(""));
+    }
+
+    #[test]
+    fn test_import_copies_new_sessions_and_skips_duplicates() {
+        let source = temp_dir("source");
+        let target = temp_dir("target");
+
+        for id in ["shared", "laptop-only"] {
+            write_session_record(&source.join(SESSIONS_DIR), &fixtures::session(id)).unwrap();
+            fs::write(source.join(format!("audio/{}.wav", id)), b"wav").unwrap();
+            fs::write(source.join(format!("text/{}.txt", id)), "text").unwrap();
+        }
+        write_session_record(&target.join(SESSIONS_DIR), &fixtures::session("shared")).unwrap();
+
+        let summary = import_sessions(&source, &target).unwrap();
+
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped_duplicates, 1);
+        assert!(summary.missing_files.is_empty());
+        assert!(target.join("audio/laptop-only.wav").exists());
+        assert!(target.join("text/laptop-only.txt").exists());
+        assert!(target.join(SESSIONS_DIR).join("laptop-only.json").exists());
+
+        let _ = fs::remove_dir_all(&source);
+        let _ = fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn test_import_reports_missing_files() {
+        let source = temp_dir("missing-source");
+        let target = temp_dir("missing-target");
+
+        write_session_record(&source.join(SESSIONS_DIR), &fixtures::session("no-files")).unwrap();
+
+        let summary = import_sessions(&source, &target).unwrap();
+
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.missing_files.len(), 2);
+        let imported = read_sessions_from(&target).unwrap();
+        assert_eq!(imported[0].audio_path, "");
+        assert_eq!(imported[0].transcript_path, "");
+
+        let _ = fs::remove_dir_all(&source);
+        let _ = fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn test_import_links_sessions_with_archived_audio() {
+        let source = temp_dir("linked-source");
+        let target = temp_dir("linked-target");
+
+        let mut renamed = fixtures::session("renamed");
+        renamed.duration = 1.0;
+        write_session_record(&source.join(SESSIONS_DIR), &renamed).unwrap();
+        write_wav_file(
+            &[0.2; 100],
+            AudioFormat::default(),
+            &source.join("audio/renamed.wav"),
+        )
+        .unwrap();
+
+        let mut archived = fixtures::session("archived");
+        archived.duration = 1.0;
+        write_session_record(&target.join(SESSIONS_DIR), &archived).unwrap();
+        write_wav_file(
+            &[0.2; 100],
+            AudioFormat::default(),
+            &target.join("audio/archived.wav"),
+        )
+        .unwrap();
+
+        let summary = import_sessions(&source, &target).unwrap();
+
+        assert_eq!(summary.imported, 0);
+        assert_eq!(summary.linked_duplicates["renamed"], "archived");
+        assert!(!target.join(SESSIONS_DIR).join("renamed.json").exists());
+
+        let _ = fs::remove_dir_all(&source);
+        let _ = fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn test_import_skips_unsafe_sessions() {
+        let source = temp_dir("unsafe-source");
+        let target = temp_dir("unsafe-target");
+
+        let mut escaping_id = fixtures::session("escaping-id");
+        escaping_id.id = "../../escaped".to_string();
+        let mut escaping_audio = fixtures::session("escaping-audio");
+        escaping_audio.audio_path = "../outside.wav".to_string();
+        // Records are named after ids, so write them directly
+        for (name, session) in [("a", &escaping_id), ("b", &escaping_audio)] {
+            fs::write(
+                source.join(SESSIONS_DIR).join(format!("{}.json", name)),
+                serde_json::to_string(session).unwrap(),
+            )
+            .unwrap();
+        }
+        write_session_record(&source.join(SESSIONS_DIR), &fixtures::session("fine")).unwrap();
+
+        let summary = import_sessions(&source, &target).unwrap();
+
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.skipped_invalid.len(), 2);
+        assert!(!target
+            .join(SESSIONS_DIR)
+            .join("../../escaped.json")
+            .exists());
+        assert!(target.join(SESSIONS_DIR).join("fine.json").exists());
+
+        let _ = fs::remove_dir_all(&source);
+        let _ = fs::remove_dir_all(&target);
+    }
+
+    #[test]
+    fn test_import_rejects_same_directory() {
+        let dir = temp_dir("same");
+        assert!(import_sessions(&dir, &dir).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
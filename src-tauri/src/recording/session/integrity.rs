@@ -0,0 +1,248 @@
+use crate::recording::audio::{wav_duration, wav_speech_seconds};
+use crate::recording::models::{IntegrityFix, Session, StorageIntegrityReport, UndoableOperation};
+use crate::recording::session::journal::{journal_move, session_files};
+use crate::recording::session::legacy::normalize_session;
+use crate::recording::session::lifecycle::generate_unique_session_id;
+use crate::recording::session::recovery::{claim_transcription, mark_transcription_pending};
+use crate::recording::session::storage::{
+    add_session, load_sessions, session_record_path, write_session_record, SESSIONS_DIR,
+};
+use crate::recording::utils::get_storage_dir;
+use chrono::{DateTime, Local, Utc};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Folders (relative to the storage root) holding session audio and
+/// transcripts
+const SESSION_FILE_DIRS: [&str; 2] = ["audio", "text"];
+
+/// Check session records against the files in the storage folder, after
+/// resolving the problems with `fix` if given
+///
+/// Reports sessions whose audio or transcript is missing and files no
+/// session refers to.
+pub fn scan_storage_integrity(fix: Option<IntegrityFix>) -> Result<StorageIntegrityReport, String> {
+    let storage_dir = get_storage_dir()?;
+
+    if let Some(fix) = fix {
+        let sessions = load_sessions()?.sessions;
+        let fixed = match fix {
+            IntegrityFix::Repair => repair_sessions(sessions, &storage_dir)?,
+            IntegrityFix::Reindex => reindex_orphaned_audio(&sessions, &storage_dir)?,
+            IntegrityFix::Delete => delete_orphaned_files(&sessions, &storage_dir)?,
+        };
+        log::info!("Storage integrity fix {:?} changed {} items", fix, fixed);
+    }
+
+    Ok(check_integrity(&load_sessions()?.sessions, &storage_dir))
+}
+
+fn check_integrity(sessions: &[Session], storage_dir: &Path) -> StorageIntegrityReport {
+    let sessions_missing = |path: fn(&Session) -> &str| {
+        sessions
+            .iter()
+            .filter(|session| is_missing(storage_dir, path(session)))
+            .map(|session| session.id.clone())
+            .collect()
+    };
+
+    StorageIntegrityReport {
+        missing_audio: sessions_missing(|session| &session.audio_path),
+        missing_transcripts: sessions_missing(|session| &session.transcript_path),
+        orphaned_files: orphaned_files(sessions, storage_dir),
+    }
+}
+
+/// Whether a session refers to a file (relative to the storage root) that
+/// doesn't exist
+fn is_missing(storage_dir: &Path, relative_path: &str) -> bool {
+    !relative_path.is_empty() && !storage_dir.join(relative_path).is_file()
+}
+
+/// Files in the audio and text folders that no session refers to, sorted;
+/// hidden files (e.g. `.DS_Store`) are left out
+fn orphaned_files(sessions: &[Session], storage_dir: &Path) -> Vec<String> {
+    let referenced: HashSet<String> = sessions.iter().flat_map(session_files).collect();
+
+    let mut orphaned: Vec<String> = SESSION_FILE_DIRS
+        .iter()
+        .flat_map(|dir| {
+            fs::read_dir(storage_dir.join(dir))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| !name.starts_with('.'))
+                .map(move |name| format!("{}/{}", dir, name))
+        })
+        .filter(|file| !referenced.contains(file))
+        .collect();
+    orphaned.sort();
+    orphaned
+}
+
+/// Relink transcripts and clear paths of missing files, returning the number
+/// of sessions changed
+///
+/// Sessions left with audio but no transcript are queued for transcription.
+/// Sessions being transcribed are left for the next repair.
+fn repair_sessions(sessions: Vec<Session>, storage_dir: &Path) -> Result<usize, String> {
+    let sessions_dir = storage_dir.join(SESSIONS_DIR);
+    let mut repaired = 0;
+
+    for mut session in sessions {
+        let Some(_claim) = claim_transcription(&session.id) else {
+            continue;
+        };
+
+        let mut changed = normalize_session(&mut session, storage_dir);
+        if is_missing(storage_dir, &session.audio_path) {
+            session.audio_path.clear();
+            changed = true;
+        }
+        let lost_transcript = is_missing(storage_dir, &session.transcript_path);
+        if lost_transcript {
+            session.transcript_path.clear();
+            changed = true;
+        }
+
+        if changed {
+            write_session_record(&sessions_dir, &session)?;
+            repaired += 1;
+        }
+        if lost_transcript && !session.audio_path.is_empty() {
+            mark_transcription_pending(&session.id)?;
+        }
+    }
+
+    Ok(repaired)
+}
+
+/// Create a session for each orphaned audio file, returning the number
+/// created
+///
+/// The transcript named after the audio is linked when there is one;
+/// otherwise the session is queued for transcription.
+fn reindex_orphaned_audio(sessions: &[Session], storage_dir: &Path) -> Result<usize, String> {
+    let mut added = 0;
+
+    for audio_path in orphaned_files(sessions, storage_dir) {
+        if !audio_path.ends_with(".wav") {
+            continue;
+        }
+
+        let session = match orphan_session(&audio_path, storage_dir) {
+            Ok(session) => session,
+            Err(e) => {
+                log::warn!("Skipping orphaned audio {}: {}", audio_path, e);
+                continue;
+            }
+        };
+        add_session(session.clone())?;
+        if session.transcript_path.is_empty() {
+            mark_transcription_pending(&session.id)?;
+        }
+        added += 1;
+    }
+
+    Ok(added)
+}
+
+/// Session for an audio file no session refers to, dated when the file was
+/// last written
+pub(crate) fn orphan_session(audio_path: &str, storage_dir: &Path) -> Result<Session, String> {
+    let path = storage_dir.join(audio_path);
+    let duration = wav_duration(&path)?;
+    let recorded_at: DateTime<Utc> = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("Failed to read file date: {}", e))?
+        .into();
+
+    let stem = Path::new(audio_path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let id = if stem.is_empty()
+        || session_record_path(&storage_dir.join(SESSIONS_DIR), &stem).exists()
+    {
+        generate_unique_session_id(recorded_at)?
+    } else {
+        stem
+    };
+
+    let mut session = Session {
+        id,
+        timestamp: recorded_at.to_rfc3339(),
+        audio_path: audio_path.to_string(),
+        duration,
+        preview: "Processing...".to_string(),
+        utc_offset_minutes: Some(recorded_at.with_timezone(&Local).offset().local_minus_utc() / 60),
+        speech_seconds: wav_speech_seconds(&path),
+        ..Session::default()
+    };
+    normalize_session(&mut session, storage_dir);
+
+    Ok(session)
+}
+
+/// Delete orphaned files as one undoable operation, returning the number
+/// deleted
+fn delete_orphaned_files(sessions: &[Session], storage_dir: &Path) -> Result<usize, String> {
+    let orphaned = orphaned_files(sessions, storage_dir);
+    let count = orphaned.len();
+    if count > 0 {
+        journal_move(UndoableOperation::Delete, &[], orphaned)?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn session(id: &str, transcript_path: &str) -> Session {
+        Session {
+            transcript_path: transcript_path.to_string(),
+            ..fixtures::session(id)
+        }
+    }
+
+    #[test]
+    fn test_reports_missing_and_orphaned_files() {
+        let storage_dir = fixtures::temp_dir("integrity");
+        fs::create_dir_all(storage_dir.join("audio")).unwrap();
+        fs::create_dir_all(storage_dir.join("text")).unwrap();
+        for file in [
+            "audio/complete.wav",
+            "text/complete.txt",
+            "text/complete.json",
+            "text/no-audio.txt",
+            "audio/no-transcript.wav",
+            "audio/stray.wav",
+            "text/stray.txt",
+            "audio/.DS_Store",
+        ] {
+            fs::write(storage_dir.join(file), "").unwrap();
+        }
+        let sessions = vec![
+            session("complete", "text/complete.txt"),
+            session("no-audio", "text/no-audio.txt"),
+            session("no-transcript", "text/no-transcript.txt"),
+            session("pending", ""),
+        ];
+
+        assert_eq!(
+            check_integrity(&sessions, &storage_dir),
+            StorageIntegrityReport {
+                missing_audio: vec!["no-audio".to_string(), "pending".to_string()],
+                missing_transcripts: vec!["no-transcript".to_string()],
+                orphaned_files: vec!["audio/stray.wav".to_string(), "text/stray.txt".to_string()],
+            }
+        );
+
+        let _ = fs::remove_dir_all(&storage_dir);
+    }
+}
@@ -0,0 +1,416 @@
+use crate::recording::models::{JournaledOperation, Session, UndoableOperation};
+use crate::recording::session::storage::{invalidate_session_cache, SESSIONS_DIR};
+use crate::recording::session::transcript_versions::transcript_version_files;
+use crate::recording::utils::{
+    get_local_state_dir, get_storage_dir, romanized_transcript_relative_path,
+    structured_transcript_relative_path, transcription_log_relative_path,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of operations that can be undone
+const MAX_UNDO_ENTRIES: usize = 10;
+
+/// Directory (relative to the local state dir) holding the undo and redo
+/// stacks, one folder per operation
+const JOURNAL_DIR: &str = "journal";
+const UNDO_STACK: &str = "undo";
+const REDO_STACK: &str = "redo";
+
+const ENTRY_FILE: &str = "entry.json";
+const FILES_DIR: &str = "files";
+
+/// Journal entry metadata, stored as `entry.json` next to the saved files
+#[derive(Serialize, Deserialize)]
+struct EntryRecord {
+    #[serde(flatten)]
+    operation: JournaledOperation,
+    /// Files (relative to the storage root) saved in the entry
+    files: Vec<String>,
+    /// Files that didn't exist before the operation; restoring the entry
+    /// removes them
+    absent: Vec<String>,
+}
+
+/// Journal entry whose files were copied before an operation ran
+pub(crate) struct JournalEntry(PathBuf);
+
+impl JournalEntry {
+    /// Drop the entry of an operation that failed and left nothing to undo
+    pub(crate) fn discard(self) {
+        if let Err(e) = fs::remove_dir_all(&self.0) {
+            log::warn!(
+                "Failed to discard journal entry {}: {}",
+                self.0.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Copy the files an operation is about to overwrite into the journal
+///
+/// Starts a new history, so operations undone earlier can no longer be redone.
+pub(crate) fn journal_copy(
+    operation: UndoableOperation,
+    session_ids: &[String],
+    files: Vec<String>,
+) -> Result<JournalEntry, String> {
+    let journal_dir = journal_dir()?;
+    clear_stack(&journal_dir.join(REDO_STACK));
+
+    write_entry(
+        &journal_dir.join(UNDO_STACK),
+        &get_storage_dir()?,
+        new_operation(operation, session_ids),
+        files,
+        false,
+    )
+    .map(JournalEntry)
+}
+
+/// Move files out of the storage folder into the journal, deleting them in
+/// a way that can be undone
+///
+/// Either every file is moved or, if one can't be, none is.
+pub(crate) fn journal_move(
+    operation: UndoableOperation,
+    session_ids: &[String],
+    files: Vec<String>,
+) -> Result<(), String> {
+    let journal_dir = journal_dir()?;
+    clear_stack(&journal_dir.join(REDO_STACK));
+
+    write_entry(
+        &journal_dir.join(UNDO_STACK),
+        &get_storage_dir()?,
+        new_operation(operation, session_ids),
+        files,
+        true,
+    )?;
+    invalidate_session_cache();
+    Ok(())
+}
+
+/// Revert the most recent journaled operation
+///
+/// The files it changed are put back as they were before it ran; their
+/// current versions are kept so the operation can be redone.
+pub fn undo_last_operation() -> Result<JournaledOperation, String> {
+    let journal_dir = journal_dir()?;
+    let operation = restore_latest(
+        &journal_dir.join(UNDO_STACK),
+        &journal_dir.join(REDO_STACK),
+        &get_storage_dir()?,
+    )?
+    .ok_or_else(|| "Nothing to undo".to_string())?;
+
+    log::info!(
+        "Undid {:?} of {} sessions",
+        operation.operation,
+        operation.session_ids.len()
+    );
+    Ok(operation)
+}
+
+/// Reapply the most recently undone operation
+pub fn redo_last_operation() -> Result<JournaledOperation, String> {
+    let journal_dir = journal_dir()?;
+    let operation = restore_latest(
+        &journal_dir.join(REDO_STACK),
+        &journal_dir.join(UNDO_STACK),
+        &get_storage_dir()?,
+    )?
+    .ok_or_else(|| "Nothing to redo".to_string())?;
+
+    log::info!(
+        "Redid {:?} of {} sessions",
+        operation.operation,
+        operation.session_ids.len()
+    );
+    Ok(operation)
+}
+
+/// Path (relative to the storage root) of a session's metadata file
+pub(crate) fn session_record_file(session_id: &str) -> String {
+    format!("{}/{}.json", SESSIONS_DIR, session_id)
+}
+
+/// Paths (relative to the storage root) of a session's metadata and every
+/// file written by transcribing it
+pub(crate) fn transcript_files(session: &Session) -> Vec<String> {
+    let mut files = vec![
+        session_record_file(&session.id),
+        session.transcript_path.clone(),
+        structured_transcript_relative_path(&session.id),
+        romanized_transcript_relative_path(&session.id),
+        transcription_log_relative_path(&session.id),
+    ];
+    files.retain(|file| !file.is_empty());
+
+    files
+}
+
+/// Paths (relative to the storage root) of every file belonging to a session
+pub(crate) fn session_files(session: &Session) -> Vec<String> {
+    let mut files = transcript_files(session);
+    files.extend(transcript_version_files(&session.id));
+    if !session.audio_path.is_empty() {
+        files.push(session.audio_path.clone());
+    }
+
+    files
+}
+
+fn new_operation(operation: UndoableOperation, session_ids: &[String]) -> JournaledOperation {
+    JournaledOperation {
+        operation,
+        session_ids: session_ids.to_vec(),
+        created_at: Utc::now().to_rfc3339(),
+    }
+}
+
+/// Save `files` from `storage_dir` in a new entry on top of `stack`, moving
+/// them out of the storage folder with `move_files`
+///
+/// Returns the entry's folder. Only the newest `MAX_UNDO_ENTRIES` entries
+/// are kept.
+fn write_entry(
+    stack: &Path,
+    storage_dir: &Path,
+    operation: JournaledOperation,
+    mut files: Vec<String>,
+    move_files: bool,
+) -> Result<PathBuf, String> {
+    files.sort();
+    files.dedup();
+    let (present, absent): (Vec<String>, Vec<String>) = files
+        .into_iter()
+        .partition(|file| storage_dir.join(file).exists());
+
+    let entry_dir = stack.join(next_entry_name(stack));
+    let files_dir = entry_dir.join(FILES_DIR);
+    fs::create_dir_all(&files_dir).map_err(|e| format!("Failed to create journal entry: {}", e))?;
+
+    let record = EntryRecord {
+        operation,
+        files: present,
+        absent,
+    };
+    let content = serde_json::to_string_pretty(&record)
+        .map_err(|e| format!("Failed to serialize journal entry: {}", e))?;
+    fs::write(entry_dir.join(ENTRY_FILE), content)
+        .map_err(|e| format!("Failed to write journal entry: {}", e))?;
+
+    for (i, file) in record.files.iter().enumerate() {
+        let source = storage_dir.join(file);
+        let target = files_dir.join(file);
+        let saved = if move_files {
+            move_file(&source, &target)
+        } else {
+            copy_file(&source, &target)
+        };
+
+        if let Err(e) = saved {
+            if move_files {
+                for file in record.files[..i].iter().rev() {
+                    if let Err(e) = move_file(&files_dir.join(file), &storage_dir.join(file)) {
+                        log::error!("Failed to put back {}: {}", file, e);
+                    }
+                }
+            }
+            let _ = fs::remove_dir_all(&entry_dir);
+            return Err(format!("Failed to save {} for undo: {}", file, e));
+        }
+    }
+
+    prune_stack(stack);
+    Ok(entry_dir)
+}
+
+/// Put the files of the newest entry in `from` back into `storage_dir`,
+/// first saving their current versions as an entry in `to`
+///
+/// Returns the restored operation, or None when `from` is empty.
+fn restore_latest(
+    from: &Path,
+    to: &Path,
+    storage_dir: &Path,
+) -> Result<Option<JournaledOperation>, String> {
+    let Some(entry_dir) = stack_entries(from).pop() else {
+        return Ok(None);
+    };
+    let content = fs::read_to_string(entry_dir.join(ENTRY_FILE))
+        .map_err(|e| format!("Failed to read journal entry: {}", e))?;
+    let record: EntryRecord = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse journal entry: {}", e))?;
+
+    let current_files = record.files.iter().chain(&record.absent).cloned().collect();
+    write_entry(
+        to,
+        storage_dir,
+        record.operation.clone(),
+        current_files,
+        true,
+    )?;
+
+    let files_dir = entry_dir.join(FILES_DIR);
+    let mut failed = Vec::new();
+    for file in &record.files {
+        if let Err(e) = move_file(&files_dir.join(file), &storage_dir.join(file)) {
+            log::error!("Failed to restore {}: {}", file, e);
+            failed.push(file.as_str());
+        }
+    }
+    invalidate_session_cache();
+
+    if !failed.is_empty() {
+        return Err(format!("Failed to restore {}", failed.join(", ")));
+    }
+    if let Err(e) = fs::remove_dir_all(&entry_dir) {
+        log::warn!(
+            "Failed to remove journal entry {}: {}",
+            entry_dir.display(),
+            e
+        );
+    }
+
+    Ok(Some(record.operation))
+}
+
+/// Entry folders of a stack, oldest first
+fn stack_entries(stack: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(stack)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.join(ENTRY_FILE).is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort();
+
+    entries
+}
+
+/// Folder name sorting after every existing entry of the stack
+fn next_entry_name(stack: &Path) -> String {
+    let latest = stack_entries(stack)
+        .last()
+        .and_then(|path| path.file_name()?.to_str()?.parse::<i64>().ok())
+        .unwrap_or(0);
+
+    format!("{:020}", Utc::now().timestamp_millis().max(latest + 1))
+}
+
+fn prune_stack(stack: &Path) {
+    let entries = stack_entries(stack);
+    let excess = entries.len().saturating_sub(MAX_UNDO_ENTRIES);
+
+    for entry in &entries[..excess] {
+        if let Err(e) = fs::remove_dir_all(entry) {
+            log::warn!("Failed to prune journal entry {}: {}", entry.display(), e);
+        }
+    }
+}
+
+fn clear_stack(stack: &Path) {
+    if stack.exists() {
+        if let Err(e) = fs::remove_dir_all(stack) {
+            log::warn!("Failed to clear {}: {}", stack.display(), e);
+        }
+    }
+}
+
+/// Move a file, copying it when the journal and storage folder are on
+/// different drives
+pub(crate) fn move_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    fs::copy(from, to).map_err(|e| e.to_string())?;
+    fs::remove_file(from).map_err(|e| e.to_string())
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::copy(from, to).map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn journal_dir() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(JOURNAL_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    #[test]
+    fn test_undo_and_redo_swap_file_versions() {
+        let storage = fixtures::temp_dir("journal-storage");
+        let journal = fixtures::temp_dir("journal-stacks");
+        let (undo, redo) = (journal.join(UNDO_STACK), journal.join(REDO_STACK));
+        fs::create_dir_all(storage.join("text")).unwrap();
+        fs::write(storage.join("text/a.txt"), "original").unwrap();
+
+        // Retranscribing overwrites a.txt and creates a.json
+        let files = vec!["text/a.txt".to_string(), "text/a.json".to_string()];
+        let operation = new_operation(UndoableOperation::Retranscribe, &["a".to_string()]);
+        write_entry(&undo, &storage, operation, files, false).unwrap();
+        fs::write(storage.join("text/a.txt"), "retranscribed").unwrap();
+        fs::write(storage.join("text/a.json"), "{}").unwrap();
+
+        let undone = restore_latest(&undo, &redo, &storage).unwrap().unwrap();
+        assert_eq!(undone.operation, UndoableOperation::Retranscribe);
+        assert_eq!(
+            fs::read_to_string(storage.join("text/a.txt")).unwrap(),
+            "original"
+        );
+        assert!(!storage.join("text/a.json").exists());
+        assert!(stack_entries(&undo).is_empty());
+
+        restore_latest(&redo, &undo, &storage).unwrap().unwrap();
+        assert_eq!(
+            fs::read_to_string(storage.join("text/a.txt")).unwrap(),
+            "retranscribed"
+        );
+        assert!(storage.join("text/a.json").exists());
+        assert!(restore_latest(&redo, &undo, &storage).unwrap().is_none());
+
+        let _ = fs::remove_dir_all(&storage);
+        let _ = fs::remove_dir_all(&journal);
+    }
+
+    #[test]
+    fn test_moving_files_empties_storage() {
+        let storage = fixtures::temp_dir("journal-move");
+        let stack = storage.join("undo");
+        fs::create_dir_all(storage.join("audio")).unwrap();
+        fs::write(storage.join("audio/a.wav"), "audio").unwrap();
+
+        let operation = new_operation(UndoableOperation::Delete, &["a".to_string()]);
+        write_entry(
+            &stack,
+            &storage,
+            operation,
+            vec!["audio/a.wav".to_string()],
+            true,
+        )
+        .unwrap();
+
+        assert!(!storage.join("audio/a.wav").exists());
+        assert_eq!(stack_entries(&stack).len(), 1);
+
+        let _ = fs::remove_dir_all(&storage);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::recording::audio::wav_duration;
+use crate::recording::models::Session;
+use crate::recording::session::storage::{load_sessions, write_session_record, SESSIONS_DIR};
+use crate::recording::transcription::text_processor::{clean_transcript, generate_preview};
+use crate::recording::utils::{get_storage_dir, transcript_relative_path};
+use std::fs;
+use std::path::Path;
+
+/// Preview of sessions saved before their transcription finished
+const PENDING_PREVIEW: &str = "Processing...";
+
+/// Extension Whisper.cpp's text output is written with, next to the audio
+const WHISPER_TEXT_SUFFIX: &str = ".wav.txt";
+
+/// Normalize sessions written by the original single-module recorder
+///
+/// Relinks transcripts (including raw Whisper.cpp output the old recorder
+/// left next to the audio), fills in previews and durations it didn't
+/// record, then removes stray `.wav.txt` files from the audio folder. Runs
+/// on startup, before queued transcriptions resume, and changes nothing
+/// once sessions are normalized. Returns the number of sessions updated.
+pub fn migrate_legacy_sessions() -> Result<usize, String> {
+    let storage_dir = get_storage_dir()?;
+    let sessions_dir = storage_dir.join(SESSIONS_DIR);
+    let mut updated = 0;
+
+    for mut session in load_sessions()?.sessions {
+        if normalize_session(&mut session, &storage_dir) {
+            write_session_record(&sessions_dir, &session)?;
+            updated += 1;
+        }
+    }
+
+    let removed = remove_stray_whisper_output(&storage_dir);
+    if updated > 0 || removed > 0 {
+        log::info!(
+            "Migrated {} legacy sessions and removed {} stray Whisper.cpp files",
+            updated,
+            removed
+        );
+    }
+    Ok(updated)
+}
+
+/// Fill in what an older version didn't record; returns whether anything
+/// changed
+pub(crate) fn normalize_session(session: &mut Session, storage_dir: &Path) -> bool {
+    let mut changed = false;
+
+    if !transcript_exists(session, storage_dir) {
+        if let Some(path) = relink_transcript(session, storage_dir) {
+            session.transcript_path = path;
+            changed = true;
+        }
+    }
+
+    let needs_preview = session.preview.is_empty() || session.preview == PENDING_PREVIEW;
+    if needs_preview && transcript_exists(session, storage_dir) {
+        if let Ok(text) = fs::read_to_string(storage_dir.join(&session.transcript_path)) {
+            session.preview = generate_preview(&text);
+            changed = true;
+        }
+    }
+
+    if session.duration <= 0.0 && !session.audio_path.is_empty() {
+        if let Ok(duration) = wav_duration(&storage_dir.join(&session.audio_path)) {
+            session.duration = duration;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+fn transcript_exists(session: &Session, storage_dir: &Path) -> bool {
+    !session.transcript_path.is_empty() && storage_dir.join(&session.transcript_path).is_file()
+}
+
+/// Relative path of the session's transcript: the one named after its
+/// audio, or one saved from Whisper.cpp output left next to the audio
+fn relink_transcript(session: &Session, storage_dir: &Path) -> Option<String> {
+    let stem = Path::new(&session.audio_path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| session.id.clone());
+    let relative = transcript_relative_path(&stem);
+    let target = storage_dir.join(&relative);
+    if target.is_file() {
+        return Some(relative);
+    }
+
+    if session.audio_path.is_empty() {
+        return None;
+    }
+    let leftover = storage_dir.join(format!("{}.txt", session.audio_path));
+    let raw = fs::read_to_string(&leftover).ok()?;
+    let saved = target
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&target, clean_transcript(&raw)));
+    match saved {
+        Ok(()) => {
+            let _ = fs::remove_file(&leftover);
+            Some(relative)
+        }
+        Err(e) => {
+            log::warn!("Failed to save transcript of session {}: {}", session.id, e);
+            None
+        }
+    }
+}
+
+/// Delete Whisper.cpp text output left in the audio folder, returning how
+/// many files were removed
+fn remove_stray_whisper_output(storage_dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(storage_dir.join("audio")) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.to_string_lossy().ends_with(WHISPER_TEXT_SUFFIX))
+        .filter(|path| match fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Failed to remove {}: {}", path.display(), e);
+                false
+            }
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn legacy_session(id: &str) -> Session {
+        Session {
+            preview: PENDING_PREVIEW.to_string(),
+            transcript_path: String::new(),
+            ..fixtures::session(id)
+        }
+    }
+
+    #[test]
+    fn test_relinks_transcripts_and_removes_leftovers() {
+        let storage_dir = fixtures::temp_dir("legacy");
+        fs::create_dir_all(storage_dir.join("audio")).unwrap();
+        fs::create_dir_all(storage_dir.join("text")).unwrap();
+        fs::write(storage_dir.join("text/saved.txt"), "Already saved.").unwrap();
+        fs::write(
+            storage_dir.join("audio/leftover.wav.txt"),
+            " Left next to the audio.\n",
+        )
+        .unwrap();
+        fs::write(storage_dir.join("audio/orphan.wav.txt"), "No session.").unwrap();
+
+        let mut saved = legacy_session("saved");
+        assert!(normalize_session(&mut saved, &storage_dir));
+        assert_eq!(saved.transcript_path, "text/saved.txt");
+        assert_eq!(saved.preview, "Already saved.");
+
+        let mut leftover = legacy_session("leftover");
+        assert!(normalize_session(&mut leftover, &storage_dir));
+        assert_eq!(leftover.transcript_path, "text/leftover.txt");
+        assert!(storage_dir.join("text/leftover.txt").is_file());
+        assert!(!normalize_session(&mut leftover, &storage_dir));
+
+        assert_eq!(remove_stray_whisper_output(&storage_dir), 1);
+        assert!(!storage_dir.join("audio/orphan.wav.txt").exists());
+
+        let _ = fs::remove_dir_all(&storage_dir);
+    }
+}
@@ -1,12 +1,52 @@
-use crate::recording::audio::{start_capture, write_wav_file};
-use crate::recording::models::Session;
-use crate::recording::session::storage::add_session;
-use crate::recording::state::{RecordingStatus, SharedRecordingState};
-use crate::recording::transcription::transcribe_with_whisper;
-use crate::recording::utils::{copy_to_clipboard, get_storage_dir};
-use chrono::Utc;
-use std::thread;
-use std::time::Instant;
+use crate::recording::audio::{
+    capture_gap, speech_seconds, start_capture, wav_duration, wav_speech_seconds, write_wav_file,
+    AudioFormat,
+};
+use crate::recording::models::{
+    CalendarEvent, DecodingConfig, PostprocessStep, Session, UndoableOperation,
+};
+use crate::recording::postprocess::step_enabled;
+use crate::recording::power::{keep_awake, transcription_deferral};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::auto_copy::{auto_copy_text, TemplateContext};
+use crate::recording::session::checkpoint::{clear_checkpoint, write_checkpoint};
+use crate::recording::session::completion::on_transcription_complete;
+use crate::recording::session::duplicates::find_duplicate_session;
+use crate::recording::session::empty_guard::empty_recording_reason;
+use crate::recording::session::ids::{generate_session_id, session_file_stem};
+use crate::recording::session::journal::{journal_copy, transcript_files};
+use crate::recording::session::recovery::{
+    claim_transcription, clear_transcription_pending, mark_transcription_pending,
+};
+use crate::recording::session::retention::delete_session_audio;
+use crate::recording::session::search::index_transcript;
+use crate::recording::session::storage::{
+    add_session, get_session, load_sessions, session_record_path, update_session, SESSIONS_DIR,
+};
+use crate::recording::session::tasks::{
+    spawn_transcription_task, transcription_backlog, transcription_cancelled,
+};
+use crate::recording::session::transcript_versions::{
+    discard_transcript_version, save_transcript_version,
+};
+use crate::recording::session::versioning::commit_transcripts;
+use crate::recording::state::{RecordingState, RecordingStatus, SharedRecordingState};
+use crate::recording::statistics::{
+    estimate_for_duration, record_estimate_accuracy, record_throughput, TranscriptionEstimate,
+};
+use crate::recording::transcription::{
+    generate_preview, transcribe_with_model, transcribe_with_whisper, transcription_log_path,
+};
+use crate::recording::utils::{audio_relative_path, copy_to_clipboard, get_storage_dir};
+use chrono::{DateTime, Local, Utc};
+use std::fs;
+use std::io::{self, Read};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Longest wait for the capture thread to close its stream when stopping
+const CAPTURE_STOP_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Start a new recording session
 ///
@@ -15,19 +55,142 @@ pub fn start_recording(state: SharedRecordingState) -> Result<(), String> {
     start_capture(state)
 }
 
+/// Start recording, or stop the active recording and transcribe it
+///
+/// Used by global hotkeys and media keys. Returns the saved session when a
+/// recording was stopped.
+pub fn toggle_recording<F>(
+    state: SharedRecordingState,
+    event_emitter: F,
+) -> Result<Option<Session>, String>
+where
+    F: Fn(TranscriptionResult) + Send + 'static,
+{
+    if !state.lock().unwrap().is_active() {
+        start_recording(state)?;
+        return Ok(None);
+    }
+
+    let session = stop_recording(state.clone())?;
+    let audio_path = get_storage_dir()?.join(&session.audio_path);
+    orchestrate_async_transcription(
+        state.clone(),
+        session.id.clone(),
+        audio_path,
+        event_emitter,
+    );
+    continue_rapid_capture(state);
+
+    Ok(Some(session))
+}
+
+/// In rapid capture mode (`audio.rapidCapture`), start the next recording
+/// once a stopped one is saved
+///
+/// The saved recording is transcribed in the background meanwhile. Returns
+/// whether a new recording started.
+pub fn continue_rapid_capture(state: SharedRecordingState) -> bool {
+    let rapid_capture = crate::recording::load_config()
+        .map(|config| config.audio.rapid_capture)
+        .unwrap_or(false);
+    if !rapid_capture {
+        return false;
+    }
+
+    // The new recording doesn't wait for the transcription
+    state.lock().unwrap().finish_processing();
+    match start_recording(state) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Rapid capture could not start the next recording: {}", e);
+            false
+        }
+    }
+}
+
+/// Stop the active recording and immediately start a new one
+///
+/// For fast back-to-back capture: the stopped recording is transcribed in
+/// the background while the new one records. Returns the saved session, or
+/// None when nothing was recording (or it was discarded as empty).
+pub fn restart_recording<F>(
+    state: SharedRecordingState,
+    event_emitter: F,
+) -> Result<Option<Session>, String>
+where
+    F: Fn(TranscriptionResult) + Send + 'static,
+{
+    let mut stopped = None;
+
+    if state.lock().unwrap().is_active() {
+        match stop_recording(state.clone()) {
+            Ok(session) => {
+                let audio_path = get_storage_dir()?.join(&session.audio_path);
+                orchestrate_async_transcription(
+                    state.clone(),
+                    session.id.clone(),
+                    audio_path,
+                    event_emitter,
+                );
+                stopped = Some(session);
+            }
+            Err(e) if !state.lock().unwrap().is_active() => {
+                log::warn!("Previous recording not saved: {}", e);
+            }
+            Err(e) => return Err(e),
+        }
+        // The new recording doesn't wait for the transcription
+        state.lock().unwrap().finish_processing();
+    }
+
+    start_recording(state)?;
+    Ok(stopped)
+}
+
+/// Start recording a calendar meeting
+///
+/// The event is stored on the session when recording stops, and its
+/// title becomes the session title
+pub fn start_meeting_recording(
+    state: SharedRecordingState,
+    meeting: CalendarEvent,
+) -> Result<(), String> {
+    start_capture(state.clone())?;
+    state.lock().unwrap().meeting = Some(meeting);
+    Ok(())
+}
+
+/// Seconds of speech in the recording being transcribed, while its audio is
+/// still in memory
+pub fn processing_speech_seconds(state: &SharedRecordingState) -> Option<f64> {
+    let (samples, format) = {
+        let state_guard = state.lock().unwrap();
+        if state_guard.status() != RecordingStatus::Processing {
+            return None;
+        }
+        (Arc::clone(&state_guard.samples), state_guard.audio_format)
+    };
+
+    let samples = samples.lock().unwrap();
+    Some(speech_seconds(&samples, format.samples_per_second()))
+}
+
 /// Pause the current recording session
 ///
 /// Stops audio capture while preserving existing recording.
-/// Recording can be resumed to continue from this point.
+/// Recording can be resumed to continue from this point. The audio so far is
+/// written to disk, so it survives a crash during the pause.
 pub fn pause_recording(state: SharedRecordingState) -> Result<(), String> {
     let mut state_guard = state.lock().unwrap();
 
-    if state_guard.status != RecordingStatus::Recording {
+    if state_guard.status() != RecordingStatus::Recording {
         return Err("No active recording to pause.".to_string());
     }
 
-    state_guard.status = RecordingStatus::Paused;
-    state_guard.pause_start_time = Some(Utc::now());
+    state_guard.transition(RecordingStatus::Paused)?;
+    state_guard.timer.pause();
+
+    write_checkpoint(&mut state_guard);
 
     Ok(())
 }
@@ -38,19 +201,13 @@ pub fn pause_recording(state: SharedRecordingState) -> Result<(), String> {
 pub fn resume_recording(state: SharedRecordingState) -> Result<(), String> {
     let mut state_guard = state.lock().unwrap();
 
-    if state_guard.status != RecordingStatus::Paused {
+    if state_guard.status() != RecordingStatus::Paused {
         return Err("No paused recording to resume.".to_string());
     }
 
-    // Calculate duration of this pause and add to total
-    if let Some(pause_start) = state_guard.pause_start_time {
-        let pause_end = Utc::now();
-        let pause_duration = (pause_end - pause_start).num_milliseconds();
-        state_guard.total_paused_duration_ms += pause_duration;
-    }
-
-    state_guard.status = RecordingStatus::Recording;
-    state_guard.pause_start_time = None;
+    state_guard.timer.resume();
+    state_guard.reset_input_health();
+    state_guard.transition(RecordingStatus::Recording)?;
 
     Ok(())
 }
@@ -65,28 +222,52 @@ pub fn cancel_recording(state: SharedRecordingState) -> Result<(), String> {
         return Err("No active recording to cancel.".to_string());
     }
 
-    // Reset to idle state
-    state_guard.status = RecordingStatus::Idle;
-    state_guard.start_time = None;
-    state_guard.pause_start_time = None;
-    state_guard.total_paused_duration_ms = 0;
+    reset_to_idle(&mut state_guard);
+
+    Ok(())
+}
+
+/// Wait until the capture thread signals that its stream is closed, so no
+/// samples arrive after the recording is saved
+fn wait_for_capture_stop(capture_stopped: Option<Receiver<()>>) {
+    let Some(capture_stopped) = capture_stopped else {
+        return;
+    };
+
+    // A disconnected channel means the thread has already exited
+    if let Err(RecvTimeoutError::Timeout) = capture_stopped.recv_timeout(CAPTURE_STOP_TIMEOUT) {
+        log::warn!(
+            "Audio capture did not stop within {} seconds",
+            CAPTURE_STOP_TIMEOUT.as_secs()
+        );
+    }
+}
+
+/// Reset recording state to idle and clear recorded samples
+fn reset_to_idle(state: &mut RecordingState) {
+    if let Err(e) = state.transition(RecordingStatus::Idle) {
+        log::warn!("{}", e);
+    }
+    state.timer.reset();
+    state.meeting = None;
+    state.previous_segment_id = None;
 
     // Clear samples
     {
-        let mut samples = state_guard.samples.lock().unwrap();
+        let mut samples = state.samples.lock().unwrap();
         samples.clear();
     }
-
-    Ok(())
+    clear_checkpoint(state);
 }
 
 /// Stop the current recording session and save the audio
 ///
 /// This is the first phase of the stop workflow:
 /// 1. Stops audio capture
-/// 2. Saves audio to WAV file
-/// 3. Creates initial session record (without transcription)
-/// 4. Returns session info for async transcription
+/// 2. Discards trivially empty recordings (see `EmptyRecordingGuard`)
+/// 3. Saves audio to WAV file
+/// 4. Creates initial session record (without transcription)
+/// 5. Returns session info for async transcription
 ///
 /// Transcription happens asynchronously via process_transcription_async
 ///
@@ -98,44 +279,146 @@ pub fn stop_recording(state: SharedRecordingState) -> Result<Session, String> {
         return Err("No active recording to stop.".to_string());
     }
 
-    // If currently paused, finalize the pause duration
-    if state_guard.status == RecordingStatus::Paused {
-        if let Some(pause_start) = state_guard.pause_start_time {
-            let pause_end = Utc::now();
-            let pause_duration = (pause_end - pause_start).num_milliseconds();
-            state_guard.total_paused_duration_ms += pause_duration;
-        }
-    }
-
-    // Calculate duration (excluding paused time)
-    let duration = calculate_duration(&state_guard);
+    // Duration excluding paused time (including a pause still in progress)
+    let duration = state_guard.timer.elapsed_seconds();
 
     // Mark as processing (this will stop the recording thread)
-    state_guard.status = RecordingStatus::Processing;
+    state_guard.transition(RecordingStatus::Processing)?;
 
-    // Wait a bit for the recording thread to finish collecting samples
+    // Wait for the recording thread to close its stream
+    let capture_stopped = state_guard.capture_stopped.take();
     drop(state_guard);
-    thread::sleep(std::time::Duration::from_millis(200));
-    let state_guard = state.lock().unwrap();
+    wait_for_capture_stop(capture_stopped);
+    let mut state_guard = state.lock().unwrap();
 
-    // Generate timestamp-based ID
-    let timestamp = Utc::now();
-    let id = timestamp.format("%Y-%m-%d_%H-%M-%S").to_string();
+    // Discard accidental recordings instead of saving and transcribing them
+    let guard = crate::recording::load_config()
+        .map(|config| config.audio.empty_recording_guard)
+        .unwrap_or_default();
+    let empty_reason = {
+        let samples = state_guard.samples.lock().unwrap();
+        empty_recording_reason(&guard, duration, &samples)
+    };
+    if let Some(reason) = empty_reason {
+        reset_to_idle(&mut state_guard);
+        return Err(reason);
+    }
+
+    // Calendar event this recording was started for (if any)
+    let meeting = state_guard.meeting.take();
+    let previous_session_id = state_guard.previous_segment_id.take();
+    let input_device = state_guard.input_device.take();
+
+    let session = {
+        let samples = state_guard.samples.lock().unwrap();
+        create_session(
+            &samples,
+            state_guard.audio_format,
+            duration,
+            meeting,
+            previous_session_id,
+            input_device,
+        )
+    };
+    // Nothing is left to transcribe, so don't block the next recording; the
+    // checkpoint is kept when the audio couldn't be saved
+    match session {
+        Ok(_) => clear_checkpoint(&mut state_guard),
+        Err(_) => state_guard.finish_processing(),
+    }
+    session
+}
+
+/// Close the current segment and continue recording into a new one
+///
+/// Used for auto-segmentation of long recordings: the audio captured so far
+/// is saved as its own session (ready for transcription) while capture
+/// continues without a gap. The next segment links back to this session.
+pub fn split_recording(state: SharedRecordingState) -> Result<Session, String> {
+    let mut state_guard = state.lock().unwrap();
+
+    if !state_guard.is_recording() {
+        return Err("No active recording to split.".to_string());
+    }
+
+    let duration = state_guard.timer.elapsed_seconds();
+    let samples = std::mem::take(&mut *state_guard.samples.lock().unwrap());
+    clear_checkpoint(&mut state_guard);
+
+    // The next segment starts now
+    state_guard.timer.start(chrono::Duration::zero());
+
+    let meeting = state_guard.meeting.clone();
+    let previous_session_id = state_guard.previous_segment_id.take();
+    let input_device = state_guard.input_device.clone();
+
+    let session = create_session(
+        &samples,
+        state_guard.audio_format,
+        duration,
+        meeting,
+        previous_session_id,
+        input_device,
+    )?;
+    state_guard.previous_segment_id = Some(session.id.clone());
+
+    Ok(session)
+}
 
-    // Save audio file (returned for Tauri command to use for async transcription)
-    let _audio_path = save_audio_file(&id, &state_guard)?;
+/// Save recorded audio and create the initial session record
+///
+/// The record has no transcript yet; transcription is added later
+fn create_session(
+    samples: &[f32],
+    format: AudioFormat,
+    duration: f64,
+    meeting: Option<CalendarEvent>,
+    previous_session_id: Option<String>,
+    input_device: Option<String>,
+) -> Result<Session, String> {
+    // Generate collision-free timestamp-based ID
+    let timestamp = Utc::now();
+    let id = generate_unique_session_id(timestamp)?;
+    let title = meeting.as_ref().map(|meeting| meeting.title.clone());
+    let audio_relative =
+        audio_relative_path(&new_session_file_stem(&id, timestamp, title.as_deref()));
+
+    // Save audio file
+    let audio_path = get_storage_dir()?.join(&audio_relative);
+    write_wav_file(samples, format, &audio_path)?;
+
+    // The clock keeps running when capture stalls, so the audio is the truth
+    let audio_duration = wav_duration(&audio_path).unwrap_or_else(|e| {
+        log::warn!("{}", e);
+        duration
+    });
+    let capture_gap_seconds = capture_gap(duration, audio_duration);
+    if let Some(gap) = capture_gap_seconds {
+        log::warn!(
+            "Recording {} is missing {:.1}s of audio ({:.1}s recorded, {:.1}s captured)",
+            id,
+            gap,
+            duration,
+            audio_duration
+        );
+    }
+    let speech_seconds = Some(speech_seconds(samples, format.samples_per_second()));
 
-    // Create initial session record (transcription will be added later)
     let session = Session {
         id: id.clone(),
         timestamp: timestamp.to_rfc3339(),
-        audio_path: format!("audio/{}.wav", id),
-        duration,
+        audio_path: audio_relative,
+        duration: audio_duration,
         preview: "Processing...".to_string(),
-        transcript_path: String::new(),
-        clipboard_copied: false,
-        transcription_time_seconds: None,
-        model_path: None,
+        utc_offset_minutes: Some(Local::now().offset().local_minus_utc() / 60),
+        title,
+        meeting,
+        previous_session_id,
+        wall_clock_duration: Some(duration),
+        capture_gap_seconds,
+        input_device,
+        speech_seconds,
+        ..Session::default()
     };
 
     // Persist initial session to index
@@ -144,13 +427,82 @@ pub fn stop_recording(state: SharedRecordingState) -> Result<Session, String> {
     Ok(session)
 }
 
+/// Save a WAV recording made on another device and create its session record
+///
+/// The audio is streamed to the session's audio file. The session is dated
+/// `recorded_at` and, like a fresh recording, has no transcript yet. Audio
+/// that isn't a readable WAV file is rejected.
+///
+/// Returns the session and whether it is new: audio already in the archive
+/// (e.g. sent twice) is discarded in favor of the existing session.
+pub(crate) fn add_received_recording(
+    audio: &mut dyn Read,
+    recorded_at: DateTime<Utc>,
+    title: Option<String>,
+    source_device: &str,
+) -> Result<(Session, bool), String> {
+    let id = generate_unique_session_id(recorded_at)?;
+    let audio_relative =
+        audio_relative_path(&new_session_file_stem(&id, recorded_at, title.as_deref()));
+
+    let audio_path = get_storage_dir()?.join(&audio_relative);
+    fs::File::create(&audio_path)
+        .and_then(|mut file| io::copy(audio, &mut file))
+        .map_err(|e| {
+            let _ = fs::remove_file(&audio_path);
+            format!("Failed to save audio: {}", e)
+        })?;
+
+    let duration = match wav_duration(&audio_path) {
+        Ok(duration) => duration,
+        Err(e) => {
+            let _ = fs::remove_file(&audio_path);
+            return Err(e);
+        }
+    };
+
+    let speech_seconds = wav_speech_seconds(&audio_path);
+
+    let index = load_sessions()?;
+    if let Some(existing_id) =
+        find_duplicate_session(&audio_path, duration, &index.sessions, &get_storage_dir()?)
+    {
+        let _ = fs::remove_file(&audio_path);
+        let existing = index.sessions.into_iter().find(|s| s.id == existing_id);
+        return existing
+            .map(|session| (session, false))
+            .ok_or_else(|| format!("Session not found: {}", existing_id));
+    }
+
+    let session = Session {
+        id: id.clone(),
+        timestamp: recorded_at.to_rfc3339(),
+        audio_path: audio_relative,
+        duration,
+        preview: "Processing...".to_string(),
+        utc_offset_minutes: Some(recorded_at.with_timezone(&Local).offset().local_minus_utc() / 60),
+        title,
+        source_device: Some(source_device.to_string()),
+        speech_seconds,
+        ..Session::default()
+    };
+
+    add_session(session.clone())?;
+
+    Ok((session, true))
+}
+
 /// Orchestrate async transcription in background thread
 ///
-/// This function spawns a background thread that:
-/// 1. Processes transcription
+/// When too many transcriptions are already running, the session is left to
+/// the transcription queue like a deferred one. Otherwise this function
+/// spawns a tracked, cancellable transcription task (see `tasks`) that:
+/// 1. Processes transcription (with the draft model in two-pass mode)
 /// 2. Updates session with results
 /// 3. Updates recording state to idle
 /// 4. Emits Tauri event with results
+/// 5. In two-pass mode, re-transcribes with the regular model and emits
+///    a refined result that replaces the draft
 ///
 /// This is domain orchestration logic extracted from the Tauri command layer.
 ///
@@ -167,29 +519,163 @@ pub fn orchestrate_async_transcription<F>(
 ) where
     F: Fn(TranscriptionResult) + Send + 'static,
 {
-    thread::spawn(move || {
-        let result = process_transcription_async(audio_path, session_id.clone());
+    if let Some(reason) = transcription_backlog() {
+        defer_transcription(&state, session_id, reason, &event_emitter);
+        return;
+    }
 
-        // Update state to idle regardless of success/failure
-        if let Ok(mut state_guard) = state.lock() {
-            state_guard.status = RecordingStatus::Idle;
+    let task_session_id = session_id.clone();
+    let spawned = spawn_transcription_task(&task_session_id, move || {
+        if let Some(reason) = transcription_deferral(&state) {
+            defer_transcription(&state, session_id, reason, &event_emitter);
+            return;
         }
 
-        // Emit event via injected callback
-        match result {
-            Ok(session) => event_emitter(TranscriptionResult::Success(session)),
-            Err(error) => event_emitter(TranscriptionResult::Error {
-                session_id,
+        run_transcription_workflow(&state, &session_id, audio_path, &event_emitter);
+    });
+
+    // The session is already saved; the transcription queue picks it up
+    if let Err(e) = spawned {
+        log::error!("{}", e);
+        if let Err(e) = mark_transcription_pending(&task_session_id) {
+            log::error!("{}", e);
+        }
+    }
+}
+
+/// Queue a transcription for the transcription queue to pick up later
+fn defer_transcription<F>(
+    state: &SharedRecordingState,
+    session_id: String,
+    reason: String,
+    event_emitter: &F,
+) where
+    F: Fn(TranscriptionResult),
+{
+    log::info!("Deferring transcription of {}: {}", session_id, reason);
+
+    if let Err(e) = mark_transcription_pending(&session_id) {
+        log::error!("{}", e);
+    }
+
+    if let Ok(mut state_guard) = state.lock() {
+        state_guard.finish_processing();
+    }
+
+    event_emitter(TranscriptionResult::Deferred { session_id, reason });
+}
+
+/// Transcribe a saved session and emit the results (blocking)
+///
+/// The session stays marked as pending until the workflow finishes, so a
+/// transcription cut short by quitting the app is resumed on next launch.
+/// Does nothing if the session is already being transcribed.
+pub(crate) fn run_transcription_workflow<F>(
+    state: &SharedRecordingState,
+    session_id: &str,
+    audio_path: std::path::PathBuf,
+    event_emitter: &F,
+) where
+    F: Fn(TranscriptionResult),
+{
+    let Some(_claim) = claim_transcription(session_id) else {
+        log::info!("Transcription of {} already in progress", session_id);
+        return;
+    };
+    let _awake = keep_awake();
+
+    if let Err(e) = mark_transcription_pending(session_id) {
+        log::warn!("{}", e);
+    }
+
+    let draft_model = crate::recording::load_config()
+        .ok()
+        .and_then(|config| config.transcription.draft_model_path);
+
+    let result =
+        process_transcription_async(audio_path, session_id.to_string(), draft_model.as_deref());
+    let refine = draft_model.is_some() && result.is_ok() && !transcription_cancelled();
+
+    // Update state to idle regardless of success/failure (unless this was
+    // an auto-segment of a recording that is still in progress)
+    if let Ok(mut state_guard) = state.lock() {
+        state_guard.finish_processing();
+    }
+
+    // Emit event via injected callback (the audio is still needed for refinement)
+    let mut transcribed = match result {
+        Ok(session) if refine => {
+            event_emitter(TranscriptionResult::Success(session.clone()));
+            Some(session)
+        }
+        Ok(session) => {
+            let session = discard_audio_if_configured(session);
+            event_emitter(TranscriptionResult::Success(session.clone()));
+            Some(session)
+        }
+        Err(error) => {
+            event_emitter(TranscriptionResult::Error {
+                session_id: session_id.to_string(),
                 error,
-            }),
+            });
+            None
         }
-    });
+    };
+
+    // Second pass: the draft stays in place if refinement fails
+    if refine {
+        match transcribe_session(session_id, None) {
+            Ok((session, _)) => {
+                let session = discard_audio_if_configured(session);
+                event_emitter(TranscriptionResult::Refined(session.clone()));
+                transcribed = Some(session);
+            }
+            Err(e) => log::warn!("Refined transcription failed, keeping draft: {}", e),
+        }
+    }
+
+    if let Some(session) = transcribed {
+        on_transcription_complete(&session);
+    }
+
+    if let Err(e) = clear_transcription_pending(session_id) {
+        log::warn!("{}", e);
+    }
+}
+
+/// Delete the audio file after a successful transcription if `keepAudio` is off
+///
+/// Returns the session with its audio path cleared when the audio was removed
+fn discard_audio_if_configured(session: Session) -> Session {
+    let keep_audio = crate::recording::load_config()
+        .map(|config| config.storage.keep_audio)
+        .unwrap_or(true);
+
+    if keep_audio || session.transcript_path.is_empty() || session.audio_path.is_empty() {
+        return session;
+    }
+
+    match delete_session_audio(&session) {
+        Ok(()) => Session {
+            audio_path: String::new(),
+            ..session
+        },
+        Err(e) => {
+            log::warn!("{}", e);
+            session
+        }
+    }
 }
 
 /// Result of async transcription for event emission
 pub enum TranscriptionResult {
     Success(Session),
+    /// Second-pass transcript replacing the draft (two-pass mode)
+    Refined(Session),
     Error { session_id: String, error: String },
+    /// Queued until power conditions allow it (see `PowerConfig`) or other
+    /// transcriptions finish
+    Deferred { session_id: String, reason: String },
 }
 
 /// Process transcription asynchronously and update session
@@ -200,112 +686,183 @@ pub enum TranscriptionResult {
 /// 3. Updates session record with transcription results
 /// 4. Records transcription timing statistics for future estimates
 ///
+/// `model_path` overrides model selection (used for two-pass drafts).
+///
 /// Returns updated session on success, or error message on failure
 pub fn process_transcription_async(
     audio_path: std::path::PathBuf,
     session_id: String,
+    model_path: Option<&str>,
 ) -> Result<Session, String> {
-    use crate::recording::session::storage::{load_sessions, save_sessions};
-
-    // Load sessions to get audio duration before transcription
-    let mut index = load_sessions()?;
-    let audio_duration = index
-        .sessions
-        .iter()
-        .find(|s| s.id == session_id)
-        .map(|s| s.duration)
-        .unwrap_or(0.0);
+    // Load the session to get audio duration before transcription
+    let (audio_duration, session_timestamp, speech_seconds) = get_session(&session_id)
+        .map(|s| (s.duration, s.timestamp, s.speech_seconds))
+        .unwrap_or((0.0, String::new(), None));
+    // Sessions recorded before speech was measured get it now
+    let speech_seconds = speech_seconds.or_else(|| wav_speech_seconds(&audio_path));
+
+    // Estimates are for the selected model, so overrides aren't compared;
+    // transcription time follows the speech, not the silence
+    let estimate = match model_path {
+        Some(_) => None,
+        None => estimate_for_duration(audio_duration, speech_seconds)
+            .ok()
+            .flatten(),
+    };
 
     // Time the transcription process
     let transcription_start = Instant::now();
 
     // Attempt transcription
-    let (transcript_path, preview, clipboard_copied) =
-        process_transcription(&audio_path, &session_id);
+    let copy_context = TemplateContext {
+        session_id: &session_id,
+        timestamp: &session_timestamp,
+        duration_seconds: audio_duration,
+    };
+    let (transcript_path, preview, clipboard_copied, model_path, language) =
+        process_transcription(&audio_path, &copy_context, model_path);
 
     let transcription_elapsed = transcription_start.elapsed().as_secs_f64();
 
-    // Get model path for tracking
-    let model_path = crate::recording::load_config()
-        .ok()
-        .map(|config| config.model_path);
-
-    // Find and update the session
-    let updated_session = {
-        let session = index
-            .sessions
-            .iter_mut()
-            .find(|s| s.id == session_id)
-            .ok_or_else(|| format!("Session not found: {}", session_id))?;
-
+    // Update the record as it is now, not as it was before the (possibly
+    // long) Whisper.cpp run, so edits and synced changes made meanwhile stay
+    let log_path = transcription_log_path(&session_id);
+    update_session(&session_id, |session| {
         session.transcript_path = transcript_path.clone();
         session.preview = preview;
         session.clipboard_copied = clipboard_copied;
+        session.transcription_log_path = log_path;
+        session.speech_seconds = speech_seconds;
+        if !transcript_path.is_empty() {
+            session.language = language;
+            session.decoding = recorded_decoding(None);
+        }
 
         // Store transcription metadata for progress estimation
         if !transcript_path.is_empty() && audio_duration > 0.0 {
             session.transcription_time_seconds = Some(transcription_elapsed);
+            record_accuracy(estimate, audio_duration, transcription_elapsed);
+            if let Some(model_path) = &model_path {
+                record_speed(model_path, audio_duration, transcription_elapsed);
+            }
             session.model_path = model_path;
         }
+    })?;
 
-        session.clone()
-    };
-
-    // Save updated sessions
-    save_sessions(&index)?;
+    get_session(&session_id)
+}
 
-    Ok(updated_session)
+/// Compare a transcription's time with its estimate, so the estimator's
+/// confidence reflects how accurate it has been
+fn record_accuracy(
+    estimate: Option<TranscriptionEstimate>,
+    audio_duration: f64,
+    transcription_elapsed: f64,
+) {
+    let Some(estimate) = estimate else {
+        return;
+    };
+    if let Err(e) = record_estimate_accuracy(
+        audio_duration,
+        estimate.estimated_seconds,
+        transcription_elapsed,
+    ) {
+        log::warn!("Failed to record estimate accuracy: {}", e);
+    }
 }
 
-/// Calculate recording duration from start time, excluding paused time
-fn calculate_duration(state: &crate::recording::state::RecordingState) -> f64 {
-    if let Some(start_time) = state.start_time {
-        let end_time = Utc::now();
-        let total_elapsed_ms = (end_time - start_time).num_milliseconds();
-        let active_recording_ms = total_elapsed_ms - state.total_paused_duration_ms;
-        active_recording_ms as f64 / 1000.0
-    } else {
-        0.0
+/// Keep the transcription's speed, so slowdowns of this machine show in its
+/// throughput history
+fn record_speed(model_path: &str, audio_duration: f64, transcription_elapsed: f64) {
+    if let Err(e) = record_throughput(model_path, audio_duration, transcription_elapsed) {
+        log::warn!("Failed to record throughput: {}", e);
     }
 }
 
-/// Save recorded audio samples to a WAV file
-fn save_audio_file(
-    id: &str,
-    state: &crate::recording::state::RecordingState,
-) -> Result<std::path::PathBuf, String> {
+/// Generate a session id that isn't used by any session record or audio file
+pub(crate) fn generate_unique_session_id(timestamp: chrono::DateTime<Utc>) -> Result<String, String> {
     let storage_dir = get_storage_dir()?;
-    let audio_filename = format!("{}.wav", id);
-    let audio_path = storage_dir.join("audio").join(&audio_filename);
+    let sessions_dir = storage_dir.join(SESSIONS_DIR);
+    let scheme = crate::recording::load_config()
+        .map(|config| config.storage.session_id_scheme)
+        .unwrap_or_default();
+
+    Ok(generate_session_id(scheme, timestamp, |candidate| {
+        session_record_path(&sessions_dir, candidate).exists()
+            || storage_dir.join(audio_relative_path(candidate)).exists()
+    }))
+}
 
-    // Copy samples from state
-    let samples = state.samples.lock().unwrap();
-    write_wav_file(&samples, &audio_path)?;
+/// Name of a new session's files, following `storage.filenamePattern`
+fn new_session_file_stem(
+    session_id: &str,
+    recorded_at: DateTime<Utc>,
+    title: Option<&str>,
+) -> String {
+    let pattern = crate::recording::load_config()
+        .ok()
+        .and_then(|config| config.storage.filename_pattern);
+
+    session_file_stem(
+        pattern.as_deref(),
+        session_id,
+        recorded_at.with_timezone(&Local),
+        title,
+    )
+}
 
-    Ok(audio_path)
+/// Decoding settings a transcription ran with: `decoding` when given,
+/// otherwise the configured ones; None for Whisper.cpp's defaults
+fn recorded_decoding(decoding: Option<DecodingConfig>) -> Option<DecodingConfig> {
+    decoding
+        .or_else(|| {
+            crate::recording::load_config()
+                .ok()
+                .map(|config| config.transcription.decoding)
+        })
+        .filter(|decoding| *decoding != DecodingConfig::default())
 }
 
 /// Process transcription and handle result
 ///
-/// Returns (transcript_path, preview, clipboard_copied)
+/// Returns (transcript_path, preview, clipboard_copied, model_path, language)
 fn process_transcription(
     audio_path: &std::path::Path,
-    id: &str,
-) -> (String, String, bool) {
-    match transcribe_with_whisper(audio_path, id) {
-        Ok((path, text)) => {
+    copy_context: &TemplateContext,
+    model_path: Option<&str>,
+) -> (String, String, bool, Option<String>, Option<String>) {
+    let transcription = match model_path {
+        Some(model_path) => transcribe_with_model(audio_path, copy_context.session_id, model_path),
+        None => transcribe_with_whisper(
+            audio_path,
+            copy_context.session_id,
+            copy_context.duration_seconds,
+            None,
+        ),
+    };
+
+    match transcription {
+        Ok((path, text, model_path, language)) => {
+            index_transcript(copy_context.session_id, &path, &text);
+
             // Generate preview from transcript
             let preview = generate_preview(&text);
 
-            // Attempt automatic clipboard copy
-            let clipboard_copied = if !text.is_empty() {
-                match copy_to_clipboard(&text) {
-                    Ok(_) => {
-                        println!("Transcript copied to clipboard");
+            // Attempt automatic clipboard copy (as configured)
+            let auto_copy_config = crate::recording::load_config()
+                .map(|config| config.integrations.auto_copy)
+                .unwrap_or_default();
+
+            let copy_text = auto_copy_text(&auto_copy_config, &text, copy_context)
+                .filter(|_| step_enabled(PostprocessStep::Clipboard));
+            let clipboard_copied = if let Some(copy_text) = copy_text {
+                match copy_to_clipboard(&redact_outgoing(&copy_text)) {
+                    Ok(mechanism) => {
+                        log::info!("Transcript copied to clipboard ({:?})", mechanism);
                         true
                     }
                     Err(e) => {
-                        eprintln!("Failed to copy to clipboard: {}", e);
+                        log::warn!("Failed to copy to clipboard: {}", e);
                         false
                     }
                 }
@@ -313,44 +870,60 @@ fn process_transcription(
                 false
             };
 
-            (path, preview, clipboard_copied)
+            (path, preview, clipboard_copied, Some(model_path), language)
         }
         Err(e) => {
             // Log error but don't fail the recording
-            eprintln!("Transcription failed: {}", e);
-            (String::new(), format!("Transcription failed: {}", e), false)
+            log::error!("Transcription failed: {}", e);
+            (
+                String::new(),
+                format!("Transcription failed: {}", e),
+                false,
+                None,
+                None,
+            )
         }
     }
 }
 
-/// Generate a preview string from transcript text
-fn generate_preview(text: &str) -> String {
-    if text.len() > 100 {
-        format!("{}...", &text[..100])
-    } else if text.is_empty() {
-        "No transcript".to_string()
-    } else {
-        text.to_string()
+/// Re-transcribe an existing audio session
+///
+/// The previous transcript is kept as a numbered version (see
+/// `list_transcript_versions`) and in the undo journal. `decoding`
+/// replaces the configured decoding settings for this run.
+pub fn retranscribe_session(
+    session_id: &str,
+    decoding: Option<DecodingConfig>,
+) -> Result<String, String> {
+    let session = get_session(session_id)?;
+    let entry = journal_copy(
+        UndoableOperation::Retranscribe,
+        std::slice::from_ref(&session.id),
+        transcript_files(&session),
+    )?;
+
+    match transcribe_session(session_id, decoding) {
+        Ok((_, transcript_text)) => Ok(transcript_text),
+        Err(e) => {
+            entry.discard();
+            Err(e)
+        }
     }
 }
 
-/// Re-transcribe an existing audio session
+/// Transcribe a stored session's audio and update its record
 ///
-/// This will overwrite any existing transcript for this session
-pub fn retranscribe_session(session_id: &str) -> Result<String, String> {
-    use crate::recording::session::storage::{load_sessions, save_sessions};
-
+/// Only this session's transcript fields are written, to the record as it
+/// is once Whisper.cpp is done, so several sessions can be transcribed in
+/// parallel (see `batch`) while the user edits them.
+///
+/// Returns the updated session and the transcript text
+pub(crate) fn transcribe_session(
+    session_id: &str,
+    decoding: Option<DecodingConfig>,
+) -> Result<(Session, String), String> {
     let storage_dir = get_storage_dir()?;
-
-    // Load sessions to find the audio file
-    let mut index = load_sessions()?;
-
-    // Find the session
-    let session = index
-        .sessions
-        .iter_mut()
-        .find(|s| s.id == session_id)
-        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    let session = get_session(session_id)?;
 
     // Get the full path to the audio file
     let audio_path = storage_dir.join(&session.audio_path);
@@ -362,31 +935,201 @@ pub fn retranscribe_session(session_id: &str) -> Result<String, String> {
     // Get audio duration for metadata
     let audio_duration = session.duration;
 
+    // Keep the transcript being replaced as a version
+    let saved_version = save_transcript_version(&session).unwrap_or_else(|e| {
+        log::warn!("Failed to save transcript version of {}: {}", session_id, e);
+        None
+    });
+
+    // Sessions recorded before speech was measured get it now
+    let speech_seconds = session
+        .speech_seconds
+        .or_else(|| wav_speech_seconds(&audio_path));
+    let estimate = estimate_for_duration(audio_duration, speech_seconds)
+        .ok()
+        .flatten();
+
     // Time the transcription process
     let transcription_start = Instant::now();
 
-    // Run transcription
-    let (transcript_path, transcript_text) = transcribe_with_whisper(&audio_path, session_id)?;
+    // Run transcription, keeping the run log even when it fails
+    let transcription =
+        transcribe_with_whisper(&audio_path, session_id, audio_duration, decoding.clone());
+    let log_path = transcription_log_path(session_id);
+    let (transcript_path, transcript_text, model_path, language) = match transcription {
+        Ok(transcription) => transcription,
+        Err(e) => {
+            if let Some(version) = saved_version {
+                discard_transcript_version(session_id, version);
+            }
+            update_session(session_id, |session| {
+                session.transcription_log_path = log_path;
+                session.speech_seconds = speech_seconds;
+            })?;
+            return Err(e);
+        }
+    };
 
     let transcription_elapsed = transcription_start.elapsed().as_secs_f64();
+    index_transcript(session_id, &transcript_path, &transcript_text);
 
-    // Get model path for tracking
-    let model_path = crate::recording::load_config()
-        .ok()
-        .map(|config| config.model_path);
+    // Store transcription metadata for progress estimation
+    let timed = !transcript_path.is_empty() && audio_duration > 0.0;
+    if timed {
+        record_accuracy(estimate, audio_duration, transcription_elapsed);
+        record_speed(&model_path, audio_duration, transcription_elapsed);
+    }
 
-    // Update session with new transcript info
-    session.transcript_path = transcript_path.clone();
-    session.preview = generate_preview(&transcript_text);
+    // Re-read the record, so edits made during the run are kept
+    let preview = generate_preview(&transcript_text);
+    update_session(session_id, |session| {
+        session.transcript_path = transcript_path;
+        session.preview = preview;
+        session.language = language;
+        session.decoding = recorded_decoding(decoding);
+        session.transcription_log_path = log_path;
+        session.speech_seconds = speech_seconds;
+        if timed {
+            session.transcription_time_seconds = Some(transcription_elapsed);
+            session.model_path = Some(model_path);
+        }
+    })?;
+    let updated_session = get_session(session_id)?;
 
-    // Store transcription metadata for progress estimation
-    if !transcript_path.is_empty() && audio_duration > 0.0 {
-        session.transcription_time_seconds = Some(transcription_elapsed);
-        session.model_path = model_path;
+    if let Err(e) = commit_transcripts("Transcribe") {
+        log::warn!("Failed to commit transcript: {}", e);
+    }
+
+    Ok((updated_session, transcript_text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::audio::{read_wav_samples, WAV_SAMPLE_RATE};
+    use crate::recording::fixtures::temp_dir;
+    use crate::recording::session::storage::load_sessions_from;
+    use crate::recording::timing::ManualClock;
+    use crate::recording::utils::storage::use_test_storage_root;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    #[test]
+    fn test_pause_and_resume_exclude_paused_time() {
+        let clock = Arc::new(ManualClock::new());
+        let state = Arc::new(Mutex::new(RecordingState::with_clock(clock.clone())));
+        {
+            let mut state_guard = state.lock().unwrap();
+            state_guard.transition(RecordingStatus::Recording).unwrap();
+            state_guard.timer.start(chrono::Duration::zero());
+        }
+
+        clock.advance(30);
+        pause_recording(Arc::clone(&state)).unwrap();
+        assert!(pause_recording(Arc::clone(&state)).is_err());
+        clock.advance(600);
+        resume_recording(Arc::clone(&state)).unwrap();
+        clock.advance(15);
+        assert_eq!(state.lock().unwrap().timer.elapsed_seconds(), 45.0);
+
+        cancel_recording(Arc::clone(&state)).unwrap();
+        let state_guard = state.lock().unwrap();
+        assert_eq!(state_guard.status(), RecordingStatus::Idle);
+        assert_eq!(state_guard.timer.elapsed_seconds(), 0.0);
     }
 
-    // Save updated sessions
-    save_sessions(&index)?;
+    #[test]
+    fn test_waits_for_capture_to_stop() {
+        let (stopped_tx, stopped_rx) = std::sync::mpsc::channel();
+        let flushed = Arc::new(Mutex::new(false));
+        let flushed_by_capture = Arc::clone(&flushed);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(300));
+            *flushed_by_capture.lock().unwrap() = true;
+            let _ = stopped_tx.send(());
+        });
+
+        wait_for_capture_stop(Some(stopped_rx));
+        assert!(*flushed.lock().unwrap());
+
+        // A capture thread that already exited doesn't hold up stopping
+        let (stopped_tx, stopped_rx) = std::sync::mpsc::channel::<()>();
+        drop(stopped_tx);
+        let started = Instant::now();
+        wait_for_capture_stop(Some(stopped_rx));
+        assert!(started.elapsed() < CAPTURE_STOP_TIMEOUT);
+    }
 
-    Ok(transcript_text)
+    /// Stand-in for Whisper.cpp: writes a fixed transcript next to the audio
+    #[cfg(unix)]
+    const STUB_WHISPER: &str = r#"#!/bin/sh
+while [ $# -gt 0 ]; do
+    if [ "$1" = "-f" ]; then audio="$2"; fi
+    shift
+done
+echo "Hello from the stub engine." > "$audio.txt"
+"#;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_records_saves_and_transcribes_from_virtual_microphone() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = temp_dir("record-save-transcribe");
+        let whisper = root.join("whisper-stub");
+        fs::write(&whisper, STUB_WHISPER).unwrap();
+        fs::set_permissions(&whisper, fs::Permissions::from_mode(0o755)).unwrap();
+        let model = root.join("ggml-stub.bin");
+        fs::write(&model, b"").unwrap();
+        let config = serde_json::json!({
+            "version": 1,
+            "transcription": {
+                "whisperPath": whisper,
+                "modelPath": model,
+            },
+            "audio": { "virtualInput": { "type": "tone", "frequency": 440 } },
+            "integrations": { "autoCopy": { "mode": "off" } },
+        });
+        fs::write(root.join("config.json"), config.to_string()).unwrap();
+        let storage = use_test_storage_root(&root);
+
+        // Record through the virtual microphone
+        let state: SharedRecordingState = Arc::new(Mutex::new(RecordingState::new()));
+        start_recording(Arc::clone(&state)).unwrap();
+        thread::sleep(Duration::from_millis(1500));
+        let session = stop_recording(Arc::clone(&state)).unwrap();
+
+        // The tone is saved as a WAV file of the recorded length
+        let audio_path = root.join(&session.audio_path);
+        let (samples, samples_per_second) = read_wav_samples(&audio_path).unwrap();
+        assert_eq!(samples_per_second, WAV_SAMPLE_RATE as usize);
+        assert!((1.0..3.0).contains(&session.duration));
+        assert!(samples.iter().any(|sample| sample.abs() > 0.4));
+
+        // The session record is written before transcription
+        let index = load_sessions_from(&root).unwrap();
+        let record = index.sessions.iter().find(|s| s.id == session.id).unwrap();
+        assert_eq!(record.audio_path, session.audio_path);
+        assert_eq!(record.preview, "Processing...");
+        assert!(record.transcript_path.is_empty());
+        assert!(record.speech_seconds.is_some());
+
+        // Transcription updates the record with the engine's transcript
+        let transcribed =
+            process_transcription_async(audio_path.clone(), session.id.clone(), None).unwrap();
+        assert!(transcribed.preview.contains("Hello from the stub engine"));
+        assert_eq!(transcribed.model_path.as_deref(), model.to_str());
+        assert!(!transcribed.clipboard_copied);
+        let transcript = fs::read_to_string(root.join(&transcribed.transcript_path)).unwrap();
+        assert!(transcript.contains("Hello from the stub engine"));
+        assert!(!audio_path.with_extension("wav.txt").exists());
+
+        let index = load_sessions_from(&root).unwrap();
+        let record = index.sessions.iter().find(|s| s.id == session.id).unwrap();
+        assert_eq!(record.transcript_path, transcribed.transcript_path);
+        assert_eq!(record.preview, transcribed.preview);
+
+        drop(storage);
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -1,8 +1,81 @@
+pub mod access;
+pub mod action_items;
+pub mod auto_copy;
+pub mod auto_pause;
+pub mod batch;
+pub mod bulk;
+pub mod cache;
+pub mod checkpoint;
+pub mod clips;
+pub mod completion;
+pub mod duplicates;
+pub mod empty_guard;
+pub mod export;
+pub mod files;
+pub mod grouping;
+pub mod ids;
+pub mod import;
+pub mod integrity;
+pub mod journal;
+pub mod legacy;
 pub mod lifecycle;
+pub mod model_comparison;
+pub mod pause_reminder;
+pub mod quality;
+pub mod recovery;
+pub mod reports;
+pub mod retention;
+pub mod search;
+pub mod segmentation;
 pub mod storage;
+pub mod tasks;
+pub mod titles;
+pub mod transcript_diff;
+pub mod transcript_versions;
+pub mod versioning;
+pub mod waveform;
 
+pub use access::{
+    get_frequently_viewed, get_recently_viewed, get_session_access, record_session_access,
+};
+pub use action_items::get_action_items;
+pub use auto_pause::{start_auto_pause_monitor, AutoPauseEvent};
+pub use batch::start_batch_transcription;
+pub use bulk::{delete_sessions, export_sessions, retranscribe_sessions, tag_sessions};
+pub use checkpoint::recover_checkpoint;
+pub use clips::export_clip;
+pub use export::{cleanup_drag_exports, prepare_drag_export};
+pub use files::{
+    copy_last_transcript, copy_session_audio_to_clipboard, get_last_session,
+    open_transcript_in_editor, reveal_session_audio,
+};
+pub use grouping::{group_sessions_by_day, recording_activity};
+pub use import::import_from_directory;
+pub use integrity::scan_storage_integrity;
+pub use journal::{redo_last_operation, undo_last_operation};
+pub use legacy::migrate_legacy_sessions;
 pub use lifecycle::{
-    cancel_recording, orchestrate_async_transcription, pause_recording, resume_recording,
-    retranscribe_session, start_recording, stop_recording, TranscriptionResult,
+    cancel_recording, continue_rapid_capture, orchestrate_async_transcription, pause_recording,
+    processing_speech_seconds, restart_recording, resume_recording, retranscribe_session,
+    start_meeting_recording, start_recording, stop_recording, toggle_recording,
+    TranscriptionResult,
+};
+pub use model_comparison::compare_models;
+pub use pause_reminder::start_pause_reminder;
+pub use quality::{filter_sessions, set_session_quality_flag, set_session_rating};
+pub use recovery::{finalize_for_exit, process_pending_now, start_transcription_queue};
+pub use reports::{generate_weekly_report, start_report_scheduler};
+pub use retention::{apply_retention_policy, get_retention_candidates, start_retention_task};
+pub use search::search_sessions;
+pub use segmentation::start_segment_timer;
+pub use cache::{SessionCache, SharedSessionCache};
+pub use storage::{
+    load_romanized_transcript, load_sessions, load_transcript, regenerate_previews, sort_sessions,
+    update_session_notes, use_session_cache,
 };
-pub use storage::{load_sessions, load_transcript};
+pub use tasks::{cancel_transcription, wait_for_transcriptions};
+pub use titles::regenerate_titles;
+pub use transcript_diff::diff_transcripts;
+pub use transcript_versions::{list_transcript_versions, restore_transcript_version};
+pub use versioning::{get_transcript_diff, get_transcript_history, start_transcript_versioning};
+pub use waveform::export_waveform_image;
@@ -0,0 +1,93 @@
+use crate::recording::models::{ModelComparison, ModelRun};
+use crate::recording::power::keep_awake;
+use crate::recording::session::storage::get_session;
+use crate::recording::session::transcript_diff::diff_texts;
+use crate::recording::transcription::transcribe_file_with_model;
+use crate::recording::utils::get_storage_dir;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// Transcribe a session's audio with two models, one after the other, and
+/// compare their speed and output side by side
+///
+/// The session's own transcript is left untouched.
+pub fn compare_models(
+    session_id: &str,
+    model_a: &str,
+    model_b: &str,
+) -> Result<ModelComparison, String> {
+    let session = get_session(session_id)?;
+    let audio_path = get_storage_dir()?.join(&session.audio_path);
+    if session.audio_path.is_empty() || !audio_path.exists() {
+        return Err(format!("Audio file not found: {}", audio_path.display()));
+    }
+
+    // Whisper.cpp writes its output next to the audio, so work on a copy
+    let work_path = std::env::temp_dir().join(format!("thoughtcast-compare-{}.wav", session.id));
+    fs::copy(&audio_path, &work_path).map_err(|e| format!("Failed to copy audio: {}", e))?;
+
+    let _awake = keep_awake();
+    let run_a = run_model(&work_path, model_a, session.duration);
+    let run_b = run_a
+        .as_ref()
+        .map_err(Clone::clone)
+        .and_then(|_| run_model(&work_path, model_b, session.duration));
+    let _ = fs::remove_file(&work_path);
+    let (run_a, run_b) = (run_a?, run_b?);
+
+    Ok(ModelComparison {
+        session_id: session.id,
+        audio_duration: session.duration,
+        diff: diff_texts(
+            &run_a.transcript,
+            &run_b.transcript,
+            Some(run_a.model_path.clone()),
+            Some(run_b.model_path.clone()),
+        ),
+        model_a: run_a,
+        model_b: run_b,
+    })
+}
+
+fn run_model(audio_path: &Path, model_path: &str, audio_duration: f64) -> Result<ModelRun, String> {
+    let start = Instant::now();
+    let (transcript, model_path) = transcribe_file_with_model(audio_path, model_path)
+        .map_err(|e| format!("{}: {}", model_path, e))?;
+    let elapsed_seconds = start.elapsed().as_secs_f64();
+
+    log::info!(
+        "Compared {}: {:.1}s for {:.1}s of audio",
+        model_path,
+        elapsed_seconds,
+        audio_duration
+    );
+
+    Ok(ModelRun {
+        word_count: transcript.split_whitespace().count(),
+        realtime_factor: realtime_factor(elapsed_seconds, audio_duration),
+        model_path,
+        transcript,
+        elapsed_seconds,
+    })
+}
+
+/// Processing time / audio duration, or 0 for audio of unknown length
+fn realtime_factor(elapsed_seconds: f64, audio_duration: f64) -> f64 {
+    if audio_duration > 0.0 {
+        elapsed_seconds / audio_duration
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_realtime_factor() {
+        assert_eq!(realtime_factor(15.0, 60.0), 0.25);
+        assert_eq!(realtime_factor(3.0, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::recording::audio::play_chime;
+use crate::recording::config::load_config;
+use crate::recording::models::PausedTooLongEvent;
+use crate::recording::state::{RecordingStatus, SharedRecordingState};
+use std::thread;
+use std::time::Duration;
+
+/// How often the pause length is checked
+const TICK: Duration = Duration::from_secs(1);
+
+/// Start the background thread that reminds about a recording left paused
+///
+/// While `audio.pauseReminder.afterMinutes` is set, each time the pause in
+/// progress reaches another multiple of it a reminder is emitted (with a
+/// chime if enabled), whether the user or `auto_pause` paused the recording.
+///
+/// # Arguments
+/// * `state` - Shared recording state
+/// * `event_emitter` - Callback to emit Tauri events (injected dependency)
+pub fn start_pause_reminder<F>(state: SharedRecordingState, event_emitter: F)
+where
+    F: Fn(PausedTooLongEvent) + Send + 'static,
+{
+    thread::spawn(move || {
+        // Reminders already given for the pause in progress
+        let mut reminded = 0;
+
+        loop {
+            thread::sleep(TICK);
+
+            let Some(paused_seconds) = current_pause_seconds(&state) else {
+                reminded = 0;
+                continue;
+            };
+
+            let reminder = match load_config() {
+                Ok(config) => config.audio.pause_reminder,
+                Err(_) => continue,
+            };
+            let Some(after_minutes) = reminder.after_minutes.filter(|m| *m > 0) else {
+                continue;
+            };
+
+            let due = reminders_due(paused_seconds, after_minutes);
+            if due == 0 {
+                // A new pause started since the last reminder
+                reminded = 0;
+            }
+            if due <= reminded {
+                continue;
+            }
+            reminded = due;
+
+            log::info!("Recording has been paused for {:.0}s", paused_seconds);
+            if reminder.chime {
+                play_chime();
+            }
+            event_emitter(PausedTooLongEvent { paused_seconds });
+        }
+    });
+}
+
+/// Length of the current pause in seconds, if the recording is paused
+fn current_pause_seconds(state: &SharedRecordingState) -> Option<f64> {
+    let state_guard = state.lock().ok()?;
+
+    if state_guard.status() != RecordingStatus::Paused {
+        return None;
+    }
+
+    state_guard.timer.current_pause_seconds()
+}
+
+/// Number of reminders a pause of `paused_seconds` has earned
+fn reminders_due(paused_seconds: f64, after_minutes: u32) -> u32 {
+    (paused_seconds / (after_minutes as f64 * 60.0)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reminds_every_interval() {
+        assert_eq!(reminders_due(0.0, 10), 0);
+        assert_eq!(reminders_due(599.0, 10), 0);
+        assert_eq!(reminders_due(600.0, 10), 1);
+        assert_eq!(reminders_due(1250.0, 10), 2);
+    }
+}
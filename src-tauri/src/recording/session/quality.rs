@@ -0,0 +1,170 @@
+use crate::recording::audio::read_wav_samples;
+use crate::recording::models::{QualityFlag, Session, SessionFilter};
+use crate::recording::session::bulk::contains_tag;
+use crate::recording::session::storage::{get_session, update_session};
+use crate::recording::transcription::{load_structured_transcript, mean_confidence};
+use crate::recording::utils::get_storage_dir;
+use std::thread;
+
+/// Mean segment confidence below which a transcript is flagged
+const LOW_CONFIDENCE_THRESHOLD: f64 = 0.5;
+
+/// Sample level treated as clipped
+const CLIPPING_LEVEL: f32 = 0.999;
+
+/// Share of clipped samples above which a recording is flagged
+const MAX_CLIPPED_RATIO: f64 = 0.001;
+
+/// Set or clear a session's star rating (1 to 5)
+///
+/// Returns the updated session
+pub fn set_session_rating(session_id: &str, rating: Option<u8>) -> Result<Session, String> {
+    if rating.is_some_and(|rating| !(1..=5).contains(&rating)) {
+        return Err("Ratings go from 1 to 5 stars".to_string());
+    }
+
+    update_session(session_id, |session| session.rating = rating)?;
+    get_session(session_id)
+}
+
+/// Set or clear a session's quality flag
+///
+/// Returns the updated session
+pub fn set_session_quality_flag(
+    session_id: &str,
+    flag: Option<QualityFlag>,
+) -> Result<Session, String> {
+    update_session(session_id, |session| session.quality_flag = flag)?;
+    get_session(session_id)
+}
+
+/// Flag a newly transcribed session whose transcript is mostly uncertain or
+/// whose audio clipped, in the background
+///
+/// Sessions the user already rated or flagged are left alone.
+pub fn auto_flag_quality(session: &Session) {
+    if session.rating.is_some() || session.quality_flag.is_some() {
+        return;
+    }
+
+    let session = session.clone();
+    thread::spawn(move || match assess_quality(&session) {
+        Ok(Some(flag)) => {
+            log::info!("Flagging session {} as {:?}", session.id, flag);
+            if let Err(e) = update_session(&session.id, |s| s.quality_flag = Some(flag)) {
+                log::warn!("Failed to flag session {}: {}", session.id, e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to assess quality of {}: {}", session.id, e),
+    });
+}
+
+/// Sessions matching every criterion of the filter
+pub fn filter_sessions(sessions: Vec<Session>, filter: &SessionFilter) -> Vec<Session> {
+    sessions
+        .into_iter()
+        .filter(|session| matches_filter(session, filter))
+        .collect()
+}
+
+fn matches_filter(session: &Session, filter: &SessionFilter) -> bool {
+    let rated = filter.min_rating.is_some() || filter.max_rating.is_some();
+    if rated {
+        // Unrated sessions match no rating bound
+        let Some(rating) = session.rating else {
+            return false;
+        };
+        let min = filter.min_rating.unwrap_or(1);
+        let max = filter.max_rating.unwrap_or(5);
+        if !(min..=max).contains(&rating) {
+            return false;
+        }
+    }
+
+    if let Some(tag) = &filter.tag {
+        if !contains_tag(&session.tags, tag.trim()) {
+            return false;
+        }
+    }
+
+    match filter.flagged {
+        Some(flagged) => session.quality_flag.is_some() == flagged,
+        None => true,
+    }
+}
+
+/// Quality problem with a session's transcript or audio, if any
+fn assess_quality(session: &Session) -> Result<Option<QualityFlag>, String> {
+    if let Ok(transcript) = load_structured_transcript(&session.id) {
+        if mean_confidence(&transcript).is_some_and(|c| c < LOW_CONFIDENCE_THRESHOLD) {
+            return Ok(Some(QualityFlag::LowConfidence));
+        }
+    }
+
+    if session.audio_path.is_empty() {
+        return Ok(None);
+    }
+    let audio_path = get_storage_dir()?.join(&session.audio_path);
+    if !audio_path.exists() {
+        return Ok(None);
+    }
+
+    let (samples, _) = read_wav_samples(&audio_path)?;
+    Ok(is_clipped(&samples).then_some(QualityFlag::Clipped))
+}
+
+fn is_clipped(samples: &[f32]) -> bool {
+    let clipped = samples
+        .iter()
+        .filter(|sample| sample.abs() >= CLIPPING_LEVEL)
+        .count();
+    !samples.is_empty() && clipped as f64 / samples.len() as f64 > MAX_CLIPPED_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn session(rating: Option<u8>, quality_flag: Option<QualityFlag>) -> Session {
+        Session {
+            rating,
+            quality_flag,
+            ..fixtures::session("2024-11-02_15-30-00")
+        }
+    }
+
+    #[test]
+    fn test_filters_by_rating_and_flag() {
+        let unrated = session(None, None);
+        let low = session(Some(2), Some(QualityFlag::Clipped));
+        let high = session(Some(5), None);
+
+        let cleanup = SessionFilter {
+            max_rating: Some(2),
+            ..Default::default()
+        };
+        assert!(matches_filter(&low, &cleanup));
+        assert!(!matches_filter(&high, &cleanup));
+        assert!(!matches_filter(&unrated, &cleanup));
+
+        let flagged = SessionFilter {
+            flagged: Some(true),
+            ..Default::default()
+        };
+        assert!(matches_filter(&low, &flagged));
+        assert!(!matches_filter(&high, &flagged));
+
+        assert!(matches_filter(&unrated, &SessionFilter::default()));
+    }
+
+    #[test]
+    fn test_detects_clipping() {
+        let mut samples = vec![0.2; 1000];
+        assert!(!is_clipped(&samples));
+        samples[0] = 1.0;
+        samples[1] = -1.0;
+        assert!(is_clipped(&samples));
+    }
+}
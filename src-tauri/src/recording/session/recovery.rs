@@ -0,0 +1,250 @@
+use crate::recording::file_store::{DiskStorage, Storage};
+use crate::recording::power::transcription_deferral;
+use crate::recording::session::lifecycle::{
+    run_transcription_workflow, stop_recording, TranscriptionResult,
+};
+use crate::recording::session::storage::load_sessions;
+use crate::recording::state::SharedRecordingState;
+use crate::recording::utils::{get_local_state_dir, get_storage_dir};
+use std::path::{Path, PathBuf};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+/// Sessions saved but not yet transcribed, kept outside the synced storage
+/// folder so only this machine resumes them
+const PENDING_FILE: &str = "pending-transcriptions.json";
+
+/// Serializes read-modify-write access to the pending file
+static PENDING_LOCK: Mutex<()> = Mutex::new(());
+
+/// How often the queue re-checks deferred transcriptions
+const QUEUE_TICK: Duration = Duration::from_secs(5);
+
+/// Queue thread, woken early by `process_pending_now`
+static QUEUE_THREAD: OnceLock<Thread> = OnceLock::new();
+
+/// Set by `process_pending_now` to bypass deferral for one queue run
+static PROCESS_NOW: AtomicBool = AtomicBool::new(false);
+
+/// Sessions currently being transcribed, so a session queued twice
+/// (e.g. deferred, then picked up by the queue) runs only once
+static IN_PROGRESS: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+/// Marks a session as being transcribed until dropped
+pub(crate) struct TranscriptionClaim(String);
+
+impl Drop for TranscriptionClaim {
+    fn drop(&mut self) {
+        in_progress().lock().unwrap().remove(&self.0);
+    }
+}
+
+/// Claim a session for transcription, or None if it is already claimed
+pub(crate) fn claim_transcription(session_id: &str) -> Option<TranscriptionClaim> {
+    in_progress()
+        .lock()
+        .unwrap()
+        .insert(session_id.to_string())
+        .then(|| TranscriptionClaim(session_id.to_string()))
+}
+
+/// Whether any session is being transcribed
+pub(crate) fn is_transcribing() -> bool {
+    !in_progress().lock().unwrap().is_empty()
+}
+
+fn in_progress() -> &'static Mutex<HashSet<String>> {
+    IN_PROGRESS.get_or_init(Default::default)
+}
+
+/// Finalize an active recording before the app exits
+///
+/// Runs the stop workflow so the audio and session are saved instead of
+/// discarded with the in-memory samples; the session is transcribed on the
+/// next launch (see `start_transcription_queue`).
+pub fn finalize_for_exit(state: SharedRecordingState) {
+    if !state.lock().unwrap().is_active() {
+        return;
+    }
+
+    match stop_recording(state) {
+        Ok(session) => {
+            log::info!("Saved recording {} before exit", session.id);
+            if let Err(e) = mark_transcription_pending(&session.id) {
+                log::error!("{}", e);
+            }
+        }
+        Err(e) => log::warn!("Recording not saved on exit: {}", e),
+    }
+}
+
+/// Start the background transcription queue
+///
+/// Transcribes pending sessions one at a time: those interrupted when the app
+/// last quit and those deferred by the power settings (see
+/// `transcription_deferral`). Checks at startup and every few seconds after;
+/// `process_pending_now` runs the queue immediately regardless of deferral.
+pub fn start_transcription_queue<F>(state: SharedRecordingState, event_emitter: F)
+where
+    F: Fn(TranscriptionResult) + Send + 'static,
+{
+    let handle = thread::spawn(move || loop {
+        let forced = PROCESS_NOW.swap(false, Ordering::SeqCst);
+        run_pending_transcriptions(&state, forced, &event_emitter);
+        thread::park_timeout(QUEUE_TICK);
+    });
+
+    let _ = QUEUE_THREAD.set(handle.thread().clone());
+}
+
+/// Transcribe all pending sessions now, ignoring power-based deferral
+///
+/// Returns the number of sessions queued.
+pub fn process_pending_now() -> Result<usize, String> {
+    let queue = QUEUE_THREAD
+        .get()
+        .ok_or_else(|| "Transcription queue is not running".to_string())?;
+    let pending = pending_transcriptions()?.len();
+
+    PROCESS_NOW.store(true, Ordering::SeqCst);
+    queue.unpark();
+    Ok(pending)
+}
+
+fn run_pending_transcriptions<F>(state: &SharedRecordingState, forced: bool, event_emitter: &F)
+where
+    F: Fn(TranscriptionResult),
+{
+    let pending = match pending_transcriptions() {
+        Ok(pending) => pending,
+        Err(e) => {
+            log::warn!("{}", e);
+            return;
+        }
+    };
+
+    for session_id in pending {
+        if !forced {
+            if let Some(reason) = transcription_deferral(state) {
+                log::debug!("Transcription queue waiting: {}", reason);
+                return;
+            }
+        }
+
+        match pending_audio_path(&session_id) {
+            Some(audio_path) => {
+                log::info!("Transcribing queued session {}", session_id);
+                run_transcription_workflow(state, &session_id, audio_path, event_emitter);
+            }
+            None => {
+                log::warn!(
+                    "Dropping pending transcription of {}: audio not found",
+                    session_id
+                );
+                let _ = clear_transcription_pending(&session_id);
+            }
+        }
+    }
+}
+
+/// Audio file of a pending session, if the session and its audio still exist
+fn pending_audio_path(session_id: &str) -> Option<PathBuf> {
+    let index = load_sessions().ok()?;
+    let session = index.sessions.iter().find(|s| s.id == session_id)?;
+    let audio_path = get_storage_dir().ok()?.join(&session.audio_path);
+
+    (!session.audio_path.is_empty() && audio_path.exists()).then_some(audio_path)
+}
+
+/// Record that a session still needs transcribing
+pub fn mark_transcription_pending(session_id: &str) -> Result<(), String> {
+    update_pending(|pending| add_pending(pending, session_id))
+}
+
+/// Record that a session's transcription finished (successfully or not)
+pub fn clear_transcription_pending(session_id: &str) -> Result<(), String> {
+    update_pending(|pending| pending.retain(|id| id != session_id))
+}
+
+fn add_pending(pending: &mut Vec<String>, session_id: &str) {
+    if !pending.iter().any(|id| id == session_id) {
+        pending.push(session_id.to_string());
+    }
+}
+
+fn pending_transcriptions() -> Result<Vec<String>, String> {
+    let _guard = PENDING_LOCK.lock().unwrap();
+    read_pending(&DiskStorage, &pending_file_path()?)
+}
+
+fn update_pending(update: impl FnOnce(&mut Vec<String>)) -> Result<(), String> {
+    let _guard = PENDING_LOCK.lock().unwrap();
+    update_pending_in(&DiskStorage, &pending_file_path()?, update)
+}
+
+fn update_pending_in(
+    storage: &dyn Storage,
+    path: &Path,
+    update: impl FnOnce(&mut Vec<String>),
+) -> Result<(), String> {
+    let mut pending = read_pending(storage, path)?;
+    update(&mut pending);
+
+    let content = serde_json::to_string_pretty(&pending)
+        .map_err(|e| format!("Failed to serialize pending transcriptions: {}", e))?;
+    storage
+        .write(path, &content)
+        .map_err(|e| format!("Failed to write pending transcriptions: {}", e))
+}
+
+fn read_pending(storage: &dyn Storage, path: &Path) -> Result<Vec<String>, String> {
+    if !storage.exists(path) {
+        return Ok(Vec::new());
+    }
+
+    let content = storage
+        .read_to_string(path)
+        .map_err(|e| format!("Failed to read pending transcriptions: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse pending transcriptions: {}", e))
+}
+
+fn pending_file_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(PENDING_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::file_store::MemoryStorage;
+
+    #[test]
+    fn test_pending_list_survives_restart() {
+        let storage = MemoryStorage::new();
+        let path = Path::new("/state").join(PENDING_FILE);
+        assert!(read_pending(&storage, &path).unwrap().is_empty());
+
+        update_pending_in(&storage, &path, |pending| add_pending(pending, "a")).unwrap();
+        update_pending_in(&storage, &path, |pending| add_pending(pending, "b")).unwrap();
+        update_pending_in(&storage, &path, |pending| add_pending(pending, "a")).unwrap();
+        assert_eq!(read_pending(&storage, &path).unwrap(), vec!["a", "b"]);
+
+        update_pending_in(&storage, &path, |pending| pending.retain(|id| id != "a")).unwrap();
+        assert_eq!(read_pending(&storage, &path).unwrap(), vec!["b"]);
+
+        storage.write(&path, "{ not json").unwrap();
+        assert!(read_pending(&storage, &path).is_err());
+    }
+
+    #[test]
+    fn test_claims_are_exclusive() {
+        let claim = claim_transcription("recovery-test-session").unwrap();
+        assert!(claim_transcription("recovery-test-session").is_none());
+
+        drop(claim);
+        assert!(claim_transcription("recovery-test-session").is_some());
+    }
+}
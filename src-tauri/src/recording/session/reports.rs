@@ -0,0 +1,332 @@
+use crate::recording::config::load_config;
+use crate::recording::integrations::email::send_text_email;
+use crate::recording::models::{ReportConfig, Session, WeeklyReport};
+use crate::recording::redaction::redact_outgoing;
+use crate::recording::session::action_items::extract_action_items;
+use crate::recording::session::auto_copy::format_duration;
+use crate::recording::session::grouping::group_sessions_by_day;
+use crate::recording::session::storage::{load_sessions, load_transcript};
+use crate::recording::transcription::text_processor::extract_summary;
+use crate::recording::utils::get_storage_dir;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
+};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+/// Folder in the storage directory where reports are written by default
+const REPORTS_DIR: &str = "reports";
+
+/// Sentences of each transcript quoted as its summary
+const SUMMARY_SENTENCES: usize = 2;
+
+/// How often the report scheduler checks whether a report is due
+const TICK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// A session as it appears in the report
+struct ReportEntry {
+    session: Session,
+    time: String,
+    summary: Option<String>,
+    action_items: Vec<String>,
+}
+
+/// Markdown report of a week's sessions, summaries and action items
+///
+/// `week` is an ISO week such as `2024-W45`; the current week when None.
+/// Sessions are placed on the day they were recorded in their own time zone.
+pub fn generate_weekly_report(week: Option<&str>) -> Result<WeeklyReport, String> {
+    let monday = match week {
+        Some(week) => parse_week(week)?,
+        None => week_start(Local::now().date_naive()),
+    };
+    let sunday = monday + Duration::days(6);
+
+    let sessions = load_sessions()?.sessions;
+    let fallback_offset = Local::now().offset().local_minus_utc() / 60;
+    let mut days: Vec<(NaiveDate, Vec<ReportEntry>)> = Vec::new();
+
+    for group in group_sessions_by_day(&sessions, fallback_offset) {
+        let Ok(date) = NaiveDate::parse_from_str(&group.date, "%Y-%m-%d") else {
+            continue;
+        };
+        if date < monday || date > sunday {
+            continue;
+        }
+
+        let mut entries: Vec<ReportEntry> = group
+            .sessions
+            .into_iter()
+            .map(|session| report_entry(session, fallback_offset))
+            .collect();
+        entries.sort_by(|a, b| a.time.cmp(&b.time));
+        days.push((date, entries));
+    }
+    days.sort_by_key(|(date, _)| *date);
+
+    Ok(build_report(monday, &days))
+}
+
+/// Write a report to the configured export folder, returning its path
+pub fn export_weekly_report(
+    report: &WeeklyReport,
+    config: &ReportConfig,
+) -> Result<PathBuf, String> {
+    let dir = match &config.export_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => get_storage_dir()?.join(REPORTS_DIR),
+    };
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create reports folder: {}", e))?;
+
+    let path = dir.join(format!("{}.md", report.week));
+    fs::write(&path, redact_outgoing(&report.markdown))
+        .map_err(|e| format!("Failed to write weekly report: {}", e))?;
+    Ok(path)
+}
+
+/// Start the background thread writing (and e-mailing) the weekly report
+/// at the configured time (see `ReportConfig`)
+///
+/// The config is reloaded on every check, so edits apply without a restart.
+pub fn start_report_scheduler() {
+    thread::spawn(|| {
+        let mut last_check = Local::now().naive_local();
+
+        loop {
+            thread::sleep(TICK);
+            let now = Local::now().naive_local();
+
+            if let Ok(config) = load_config().map(|config| config.reports) {
+                if config.auto_generate && report_due(&config, last_check, now) {
+                    if let Err(e) = run_scheduled_report(&config) {
+                        log::warn!("Weekly report failed: {}", e);
+                    }
+                }
+            }
+
+            last_check = now;
+        }
+    });
+}
+
+fn run_scheduled_report(config: &ReportConfig) -> Result<(), String> {
+    let report = generate_weekly_report(None)?;
+    let path = export_weekly_report(&report, config)?;
+    log::info!("Wrote weekly report {}", path.display());
+
+    if !config.email_to.is_empty() {
+        let subject = format!("ThoughtCast weekly report {}", report.week);
+        send_text_email(&config.email_to, &subject, &report.markdown)?;
+        log::info!("E-mailed weekly report to {}", config.email_to.join(", "));
+    }
+    Ok(())
+}
+
+/// Whether the report time fell in the window `(since, until]`
+fn report_due(config: &ReportConfig, since: NaiveDateTime, until: NaiveDateTime) -> bool {
+    let Ok(at) = NaiveTime::parse_from_str(&config.at, "%H:%M") else {
+        log::warn!("Invalid report time '{}'", config.at);
+        return false;
+    };
+
+    since
+        .date()
+        .iter_days()
+        .take_while(|date| *date <= until.date())
+        .filter(|date| date.weekday() == config.day)
+        .map(|date| date.and_time(at))
+        .any(|time| since < time && time <= until)
+}
+
+/// Monday of an ISO week written as `2024-W45`
+fn parse_week(week: &str) -> Result<NaiveDate, String> {
+    let invalid = || format!("Invalid week '{}', expected e.g. 2024-W45", week);
+    let (year, number) = week.split_once("-W").ok_or_else(invalid)?;
+    let year = year.parse().map_err(|_| invalid())?;
+    let number = number.parse().map_err(|_| invalid())?;
+
+    NaiveDate::from_isoywd_opt(year, number, chrono::Weekday::Mon).ok_or_else(invalid)
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn report_entry(session: Session, fallback_offset: i32) -> ReportEntry {
+    let offset = session.utc_offset_minutes.unwrap_or(fallback_offset);
+    let time = FixedOffset::east_opt(offset * 60)
+        .and_then(|offset| {
+            DateTime::parse_from_rfc3339(&session.timestamp)
+                .ok()
+                .map(|time| time.with_timezone(&offset).format("%H:%M").to_string())
+        })
+        .unwrap_or_default();
+
+    let transcript = if session.transcript_path.is_empty() {
+        None
+    } else {
+        load_transcript(&session.id)
+            .map_err(|e| log::warn!("Report skips transcript of {}: {}", session.id, e))
+            .ok()
+    };
+
+    ReportEntry {
+        time,
+        summary: transcript
+            .as_deref()
+            .map(|text| extract_summary(text, SUMMARY_SENTENCES))
+            .filter(|summary| !summary.is_empty()),
+        action_items: transcript
+            .as_deref()
+            .map(extract_action_items)
+            .unwrap_or_default(),
+        session,
+    }
+}
+
+fn build_report(monday: NaiveDate, days: &[(NaiveDate, Vec<ReportEntry>)]) -> WeeklyReport {
+    let week = monday.iso_week();
+    let entries = || {
+        days.iter()
+            .flat_map(|(date, entries)| entries.iter().map(move |e| (date, e)))
+    };
+    let session_count = entries().count();
+    let total_duration: f64 = entries().map(|(_, entry)| entry.session.duration).sum();
+    let action_item_count = entries().map(|(_, entry)| entry.action_items.len()).sum();
+
+    let mut markdown = format!(
+        "# Week {}, {} ({} – {})\n\n",
+        week.week(),
+        week.year(),
+        monday.format("%b %-d"),
+        (monday + Duration::days(6)).format("%b %-d")
+    );
+    let _ = writeln!(
+        markdown,
+        "Recordings: {} ({} in total). Action items: {}.",
+        session_count,
+        format_duration(total_duration),
+        action_item_count
+    );
+
+    if action_item_count > 0 {
+        markdown.push_str("\n## Action items\n\n");
+        for (date, entry) in entries() {
+            for item in &entry.action_items {
+                let _ = writeln!(
+                    markdown,
+                    "- [ ] {} ({} {})",
+                    item,
+                    date.format("%A"),
+                    entry.time
+                );
+            }
+        }
+    }
+
+    for (date, entries) in days {
+        let _ = write!(markdown, "\n## {}\n", date.format("%A, %b %-d"));
+        for entry in entries {
+            let title = match &entry.session.title {
+                Some(title) => format!(" {}", title),
+                None => String::new(),
+            };
+            let _ = write!(
+                markdown,
+                "\n### {}{} ({})\n\n{}\n",
+                entry.time,
+                title,
+                format_duration(entry.session.duration),
+                entry.summary.as_deref().unwrap_or("_Not transcribed yet._")
+            );
+        }
+    }
+
+    WeeklyReport {
+        week: format!("{}-W{:02}", week.year(), week.week()),
+        session_count,
+        total_duration,
+        action_item_count,
+        markdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time: &str, duration: f64, summary: Option<&str>, items: &[&str]) -> ReportEntry {
+        let session: Session = serde_json::from_value(serde_json::json!({
+            "id": format!("2024-11-04_{}", time),
+            "timestamp": "2024-11-04T09:12:00Z",
+            "audio_path": "",
+            "duration": duration,
+            "preview": "",
+        }))
+        .unwrap();
+        ReportEntry {
+            session,
+            time: time.to_string(),
+            summary: summary.map(str::to_string),
+            action_items: items.iter().map(|item| item.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_builds_markdown_report() {
+        let monday = parse_week("2024-W45").unwrap();
+        assert_eq!(monday, NaiveDate::from_ymd_opt(2024, 11, 4).unwrap());
+
+        let days = vec![(
+            monday,
+            vec![
+                entry(
+                    "09:12",
+                    60.0,
+                    Some("Plan the launch."),
+                    &["Call the printer"],
+                ),
+                entry("17:40", 30.0, None, &[]),
+            ],
+        )];
+        let report = build_report(monday, &days);
+
+        assert_eq!(report.week, "2024-W45");
+        assert_eq!(report.session_count, 2);
+        assert_eq!(report.action_item_count, 1);
+        assert!(report
+            .markdown
+            .starts_with("# Week 45, 2024 (Nov 4 – Nov 10)"));
+        assert!(report
+            .markdown
+            .contains("Recordings: 2 (1:30 in total). Action items: 1."));
+        assert!(report
+            .markdown
+            .contains("- [ ] Call the printer (Monday 09:12)"));
+        assert!(report.markdown.contains("## Monday, Nov 4"));
+        assert!(report
+            .markdown
+            .contains("### 09:12 (1:00)\n\nPlan the launch."));
+        assert!(report.markdown.contains("_Not transcribed yet._"));
+    }
+
+    #[test]
+    fn test_report_due_once_at_configured_time() {
+        let config = ReportConfig::default();
+        // 2024-11-08 is a Friday
+        let at = |day, hour, minute| {
+            NaiveDate::from_ymd_opt(2024, 11, day)
+                .unwrap()
+                .and_hms_opt(hour, minute, 0)
+                .unwrap()
+        };
+
+        assert!(report_due(&config, at(8, 16, 59), at(8, 17, 0)));
+        assert!(!report_due(&config, at(8, 17, 0), at(8, 17, 1)));
+        assert!(!report_due(&config, at(7, 16, 59), at(7, 17, 0)));
+        assert!(report_due(&config, at(7, 12, 0), at(9, 12, 0)));
+        assert!(parse_week("2024-45").is_err());
+    }
+}
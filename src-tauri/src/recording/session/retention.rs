@@ -0,0 +1,275 @@
+use crate::recording::config::load_config;
+use crate::recording::models::{RetentionAction, RetentionCandidate, RetentionConfig, Session};
+use crate::recording::session::journal::{session_files, session_record_file};
+use crate::recording::session::recovery::claim_transcription;
+use crate::recording::session::storage::{load_sessions, remove_session, update_session};
+use crate::recording::utils::{get_local_state_dir, get_storage_dir};
+use chrono::{DateTime, Duration, Utc};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// The retention policy the user last confirmed, kept on this machine only;
+/// the background task applies the policy only while it matches, so a
+/// synced or edited config never deletes anything unconfirmed
+const ACKNOWLEDGED_FILE: &str = "retention-acknowledged.json";
+
+/// Wait after startup before the first run, so startup isn't slowed down
+const STARTUP_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How often the retention policy is applied
+const TICK: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// What the retention policy (`storage.retention`) would remove now
+///
+/// Shown as a preview; nothing is removed.
+pub fn get_retention_candidates() -> Result<Vec<RetentionCandidate>, String> {
+    let config = load_config()?.storage.retention;
+    let storage_dir = get_storage_dir()?;
+    let sessions = load_sessions()?.sessions;
+
+    Ok(due_for_removal(&sessions, &config, Utc::now())
+        .into_iter()
+        .map(|(session, action)| candidate(session, action, &storage_dir))
+        .collect())
+}
+
+/// Confirm the current retention policy and apply it now, returning what
+/// was removed
+///
+/// Once confirmed, the background task keeps applying the policy until it
+/// is changed.
+pub fn apply_retention_policy() -> Result<Vec<RetentionCandidate>, String> {
+    let policy = load_config()?.storage.retention;
+    let content = serde_json::to_string(&policy)
+        .map_err(|e| format!("Failed to serialize retention policy: {}", e))?;
+    fs::write(acknowledged_path()?, content)
+        .map_err(|e| format!("Failed to save retention policy: {}", e))?;
+
+    apply_retention()
+}
+
+/// Remove what the retention policy is due to remove, returning what was
+/// removed
+///
+/// Expired sessions are deleted permanently, not moved to the undo history,
+/// so a policy run doesn't take the place of the user's last undoable
+/// operation. Sessions being transcribed are left for the next run.
+fn apply_retention() -> Result<Vec<RetentionCandidate>, String> {
+    let candidates = get_retention_candidates()?;
+    let sessions = load_sessions()?.sessions;
+    let storage_dir = get_storage_dir()?;
+
+    let mut removed = Vec::new();
+    for candidate in candidates {
+        let Some(session) = sessions.iter().find(|s| s.id == candidate.session_id) else {
+            continue;
+        };
+        let Some(_claim) = claim_transcription(&session.id) else {
+            continue;
+        };
+
+        let result = match candidate.action {
+            RetentionAction::DeleteSession => delete_expired_session(session, &storage_dir),
+            RetentionAction::DeleteAudio => delete_session_audio(session),
+        };
+        match result {
+            Ok(()) => removed.push(candidate),
+            Err(e) => log::warn!("Retention failed for session {}: {}", session.id, e),
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Apply the confirmed retention policy hourly in a background thread
+///
+/// `on_applied` receives what was removed, when anything was. A policy that
+/// hasn't been confirmed with `apply_retention_policy` (the first one, or
+/// one changed since) isn't applied; `on_confirmation_needed` receives what
+/// it would remove, once per policy. The config is reloaded on every run,
+/// so edits apply without a restart.
+pub fn start_retention_task<F, G>(on_applied: F, on_confirmation_needed: G)
+where
+    F: Fn(Vec<RetentionCandidate>) + Send + 'static,
+    G: Fn(Vec<RetentionCandidate>) + Send + 'static,
+{
+    thread::spawn(move || {
+        thread::sleep(STARTUP_DELAY);
+
+        // Unconfirmed policy the user was last asked about
+        let mut asked_about: Option<RetentionConfig> = None;
+
+        loop {
+            let policy = load_config().map(|config| config.storage.retention);
+            match policy {
+                Ok(policy) if policy == RetentionConfig::default() => {}
+                Ok(policy) if read_acknowledged().as_ref() == Some(&policy) => {
+                    match apply_retention() {
+                        Ok(removed) if !removed.is_empty() => {
+                            log::info!("Retention policy removed {} recordings", removed.len());
+                            on_applied(removed);
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::warn!("Retention policy failed: {}", e),
+                    }
+                }
+                Ok(policy) if asked_about.as_ref() != Some(&policy) => {
+                    match get_retention_candidates() {
+                        Ok(candidates) if !candidates.is_empty() => {
+                            log::info!("Retention policy awaits confirmation");
+                            on_confirmation_needed(candidates);
+                            asked_about = Some(policy);
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::warn!("Retention preview failed: {}", e),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Retention policy failed: {}", e),
+            }
+
+            thread::sleep(TICK);
+        }
+    });
+}
+
+/// Delete every file of an expired session, its metadata last so a failed
+/// run is retried
+fn delete_expired_session(session: &Session, storage_dir: &Path) -> Result<(), String> {
+    let record = session_record_file(&session.id);
+    for file in session_files(session) {
+        if file == record {
+            continue;
+        }
+        match fs::remove_file(storage_dir.join(&file)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                return Err(format!("Failed to delete {}: {}", file, e))
+            }
+            _ => {}
+        }
+    }
+
+    remove_session(&session.id)
+}
+
+/// Delete a session's audio file (if still there) and clear its audio path
+pub(crate) fn delete_session_audio(session: &Session) -> Result<(), String> {
+    let path = get_storage_dir()?.join(&session.audio_path);
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => {
+            return Err(format!("Failed to delete audio file: {}", e))
+        }
+        _ => {}
+    }
+    update_session(&session.id, |s| s.audio_path.clear())
+}
+
+/// Sessions old enough for removal, and what to remove from each
+///
+/// A session past `transcriptDays` is deleted as a whole; otherwise its
+/// audio is deleted once past `audioDays`, if the session was transcribed.
+fn due_for_removal<'a>(
+    sessions: &'a [Session],
+    config: &RetentionConfig,
+    now: DateTime<Utc>,
+) -> Vec<(&'a Session, RetentionAction)> {
+    let expired = |days: Option<u32>, recorded: DateTime<Utc>| {
+        days.is_some_and(|days| now - recorded > Duration::days(days.into()))
+    };
+
+    sessions
+        .iter()
+        .filter_map(|session| {
+            let recorded = DateTime::parse_from_rfc3339(&session.timestamp).ok()?;
+            let recorded = recorded.with_timezone(&Utc);
+
+            if expired(config.transcript_days, recorded) {
+                Some((session, RetentionAction::DeleteSession))
+            } else if expired(config.audio_days, recorded)
+                && !session.audio_path.is_empty()
+                && !session.transcript_path.is_empty()
+            {
+                Some((session, RetentionAction::DeleteAudio))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The policy last confirmed with `apply_retention_policy`
+fn read_acknowledged() -> Option<RetentionConfig> {
+    let content = fs::read_to_string(acknowledged_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn acknowledged_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(ACKNOWLEDGED_FILE))
+}
+
+fn candidate(session: &Session, action: RetentionAction, storage_dir: &Path) -> RetentionCandidate {
+    let files = match action {
+        RetentionAction::DeleteAudio => vec![session.audio_path.clone()],
+        RetentionAction::DeleteSession => session_files(session),
+    };
+    let bytes = files
+        .iter()
+        .filter_map(|file| fs::metadata(storage_dir.join(file)).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    RetentionCandidate {
+        session_id: session.id.clone(),
+        timestamp: session.timestamp.clone(),
+        title: session.title.clone(),
+        action,
+        bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn session(id: &str, timestamp: &str, transcript_path: &str) -> Session {
+        Session {
+            timestamp: timestamp.to_string(),
+            transcript_path: transcript_path.to_string(),
+            ..fixtures::session(id)
+        }
+    }
+
+    #[test]
+    fn test_deletes_audio_and_sessions_by_age() {
+        let now = DateTime::parse_from_rfc3339("2024-12-31T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let sessions = vec![
+            session("recent", "2024-12-30T12:00:00Z", "text/recent.txt"),
+            session("month", "2024-11-20T12:00:00Z", "text/month.txt"),
+            session("untranscribed", "2024-11-20T12:00:00Z", ""),
+            session("year", "2023-12-01T12:00:00Z", "text/year.txt"),
+        ];
+        let config = RetentionConfig {
+            audio_days: Some(30),
+            transcript_days: Some(365),
+        };
+
+        let due: Vec<(&str, RetentionAction)> = due_for_removal(&sessions, &config, now)
+            .into_iter()
+            .map(|(session, action)| (session.id.as_str(), action))
+            .collect();
+        assert_eq!(
+            due,
+            vec![
+                ("month", RetentionAction::DeleteAudio),
+                ("year", RetentionAction::DeleteSession),
+            ]
+        );
+
+        assert!(due_for_removal(&sessions, &RetentionConfig::default(), now).is_empty());
+    }
+}
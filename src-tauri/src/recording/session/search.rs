@@ -0,0 +1,343 @@
+use crate::recording::models::Session;
+use crate::recording::session::storage::load_sessions;
+use crate::recording::transcription::text_processor::normalize_language;
+use crate::recording::utils::{get_local_state_dir, get_storage_dir};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// Word index of all transcripts, kept on this machine only (it is rebuilt
+/// from the synced transcripts as needed)
+const INDEX_FILE: &str = "search-index.json";
+
+/// Wait after a change before writing the index file, so a burst of
+/// transcriptions (e.g. a batch) writes it once; a write lost on quit only
+/// means re-reading those transcripts on the next search
+const SAVE_DELAY: Duration = Duration::from_secs(5);
+
+/// How often searches check the transcripts on disk for changes made
+/// outside the app, e.g. by a sync tool
+const RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Words of each session's transcript, keyed by session id
+#[derive(Default, Serialize, Deserialize)]
+struct SearchIndex {
+    entries: BTreeMap<String, IndexEntry>,
+    /// When the transcripts on disk were last checked, and the sessions'
+    /// transcript paths then
+    #[serde(skip)]
+    checked: Option<(Instant, Vec<(String, String)>)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    /// Transcript modification time (ms since epoch) when it was indexed
+    modified: u64,
+    words: BTreeSet<String>,
+}
+
+/// Loaded index, shared between searches and transcript writes
+static INDEX: Mutex<Option<SearchIndex>> = Mutex::new(None);
+
+/// Whether a write of the index file is scheduled
+static SAVE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+/// Sessions whose transcript, title or notes contain every word of `query`,
+/// optionally only those in `language` (e.g. "de")
+///
+/// Words match by prefix, case-insensitively ("meet" finds "Meeting"). An
+/// empty query with a language lists all sessions in that language.
+/// Transcripts the app writes are indexed right away; ones changed on disk
+/// otherwise are re-read when the session list changes, and at most every
+/// `RECHECK_INTERVAL`, so searches don't check every transcript file.
+pub fn search_sessions(query: &str, language: Option<&str>) -> Result<Vec<Session>, String> {
+    let terms = tokenize(query);
+    if terms.is_empty() && language.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let sessions = load_sessions()?.sessions;
+    let storage_dir = get_storage_dir()?;
+
+    let mut guard = INDEX.lock().unwrap();
+    let index = guard.get_or_insert_with(load_index);
+    let transcripts = transcript_paths(&sessions);
+    if needs_recheck(index, &transcripts) {
+        if refresh_index(index, &sessions, &storage_dir) {
+            schedule_save();
+        }
+        index.checked = Some((Instant::now(), transcripts));
+    }
+
+    Ok(sessions
+        .into_iter()
+        .filter(|session| in_language(session, language))
+        .filter(|session| {
+            let title_words = tokenize(&format!(
+                "{} {}",
+                session.title.as_deref().unwrap_or_default(),
+                session.notes.as_deref().unwrap_or_default()
+            ));
+            terms.iter().all(|term| {
+                index
+                    .entries
+                    .get(&session.id)
+                    .is_some_and(|entry| contains_prefix(&entry.words, term))
+                    || contains_prefix(&title_words, term)
+            })
+        })
+        .collect())
+}
+
+/// Whether a session's transcript is in `language` (any when unset)
+fn in_language(session: &Session, language: Option<&str>) -> bool {
+    let Some(language) = language else {
+        return true;
+    };
+    session
+        .language
+        .as_deref()
+        .is_some_and(|code| normalize_language(code) == normalize_language(language))
+}
+
+/// Update a session's index entry after its transcript was written
+///
+/// `transcript_path` is relative to the storage folder. The index file is
+/// written shortly after, together with any other changes.
+pub fn index_transcript(session_id: &str, transcript_path: &str, text: &str) {
+    let modified = get_storage_dir()
+        .ok()
+        .and_then(|storage_dir| modified_millis(&storage_dir.join(transcript_path)));
+
+    let mut guard = INDEX.lock().unwrap();
+    let index = guard.get_or_insert_with(load_index);
+    index.entries.insert(
+        session_id.to_string(),
+        IndexEntry {
+            modified: modified.unwrap_or_default(),
+            words: tokenize(text),
+        },
+    );
+    drop(guard);
+
+    schedule_save();
+}
+
+/// Session ids and transcript paths of the sessions with a transcript
+fn transcript_paths(sessions: &[Session]) -> Vec<(String, String)> {
+    sessions
+        .iter()
+        .filter(|s| !s.transcript_path.is_empty())
+        .map(|s| (s.id.clone(), s.transcript_path.clone()))
+        .collect()
+}
+
+/// Whether the transcripts on disk should be checked before searching:
+/// the sessions' transcripts changed or the last check is too old
+fn needs_recheck(index: &SearchIndex, transcripts: &[(String, String)]) -> bool {
+    match &index.checked {
+        Some((checked_at, checked)) => {
+            checked_at.elapsed() >= RECHECK_INTERVAL || checked.as_slice() != transcripts
+        }
+        None => true,
+    }
+}
+
+/// Write the index file after `SAVE_DELAY`, unless a write is already due
+fn schedule_save() {
+    if SAVE_SCHEDULED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    thread::spawn(|| {
+        thread::sleep(SAVE_DELAY);
+        let content = {
+            let guard = INDEX.lock().unwrap();
+            SAVE_SCHEDULED.store(false, Ordering::SeqCst);
+            guard.as_ref().map(serde_json::to_string)
+        };
+
+        let result = match content {
+            Some(Ok(content)) => index_file_path().and_then(|path| {
+                fs::write(path, content).map_err(|e| format!("Failed to write search index: {}", e))
+            }),
+            Some(Err(e)) => Err(format!("Failed to serialize search index: {}", e)),
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            log::warn!("{}", e);
+        }
+    });
+}
+
+/// Bring the index in line with the session list
+///
+/// Re-indexes transcripts that are new or changed on disk and drops entries
+/// of sessions that no longer have one. Returns whether anything changed.
+fn refresh_index(index: &mut SearchIndex, sessions: &[Session], storage_dir: &Path) -> bool {
+    let mut changed = false;
+
+    let transcripts: BTreeMap<&str, PathBuf> = sessions
+        .iter()
+        .filter(|s| !s.transcript_path.is_empty())
+        .map(|s| (s.id.as_str(), storage_dir.join(&s.transcript_path)))
+        .collect();
+
+    index.entries.retain(|id, _| {
+        let keep = transcripts.contains_key(id.as_str());
+        changed |= !keep;
+        keep
+    });
+
+    for (session_id, path) in transcripts {
+        let Some(modified) = modified_millis(&path) else {
+            continue;
+        };
+        if index
+            .entries
+            .get(session_id)
+            .is_some_and(|entry| entry.modified == modified)
+        {
+            continue;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                let words = tokenize(&text);
+                index
+                    .entries
+                    .insert(session_id.to_string(), IndexEntry { modified, words });
+                changed = true;
+            }
+            Err(e) => log::warn!("Failed to index transcript {}: {}", path.display(), e),
+        }
+    }
+
+    changed
+}
+
+/// Lowercase alphanumeric words of a text
+fn tokenize(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Whether any word starts with `prefix`
+fn contains_prefix(words: &BTreeSet<String>, prefix: &str) -> bool {
+    words
+        .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+        .next()
+        .is_some_and(|word| word.starts_with(prefix))
+}
+
+fn modified_millis(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+fn load_index() -> SearchIndex {
+    index_file_path()
+        .and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string()))
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        .unwrap_or_default()
+}
+
+fn index_file_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(INDEX_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    #[test]
+    fn test_tokenize() {
+        let words = tokenize("Hello, world! Hello again: it's 2024.");
+        let expected: BTreeSet<String> = ["hello", "world", "again", "it", "s", "2024"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(words, expected);
+    }
+
+    #[test]
+    fn test_contains_prefix() {
+        let words = tokenize("Weekly meeting notes");
+
+        assert!(contains_prefix(&words, "meet"));
+        assert!(contains_prefix(&words, "notes"));
+        assert!(!contains_prefix(&words, "eting"));
+        assert!(!contains_prefix(&words, "zebra"));
+    }
+
+    fn create_session(id: &str, transcript_path: &str) -> Session {
+        Session {
+            transcript_path: transcript_path.to_string(),
+            ..fixtures::session(id)
+        }
+    }
+
+    #[test]
+    fn test_refresh_index_tracks_transcripts() {
+        let storage_dir = fixtures::temp_dir("search");
+        fs::create_dir_all(storage_dir.join("text")).unwrap();
+        fs::write(storage_dir.join("text/a.txt"), "Budget review").unwrap();
+
+        let mut index = SearchIndex::default();
+        index.entries.insert(
+            "deleted".to_string(),
+            IndexEntry {
+                modified: 0,
+                words: BTreeSet::new(),
+            },
+        );
+
+        let sessions = vec![create_session("a", "text/a.txt"), create_session("b", "")];
+        assert!(refresh_index(&mut index, &sessions, &storage_dir));
+        assert!(contains_prefix(&index.entries["a"].words, "budget"));
+        assert!(!index.entries.contains_key("b"));
+        assert!(!index.entries.contains_key("deleted"));
+
+        // Unchanged transcripts are not re-read
+        assert!(!refresh_index(&mut index, &sessions, &storage_dir));
+
+        let _ = fs::remove_dir_all(&storage_dir);
+    }
+
+    #[test]
+    fn test_rechecks_when_transcripts_change() {
+        let mut index = SearchIndex::default();
+        let sessions = vec![create_session("a", "text/a.txt")];
+        let transcripts = transcript_paths(&sessions);
+        assert!(needs_recheck(&index, &transcripts));
+
+        index.checked = Some((Instant::now(), transcripts.clone()));
+        assert!(!needs_recheck(&index, &transcripts));
+
+        let added = transcript_paths(&[
+            create_session("a", "text/a.txt"),
+            create_session("b", "text/b.txt"),
+        ]);
+        assert!(needs_recheck(&index, &added));
+    }
+
+    #[test]
+    fn test_in_language() {
+        let mut session = create_session("a", "text/a.txt");
+        assert!(in_language(&session, None));
+        assert!(!in_language(&session, Some("de")));
+
+        session.language = Some("de".to_string());
+        assert!(in_language(&session, Some("DE")));
+        assert!(!in_language(&session, Some("en")));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::recording::audio::play_chime;
+use crate::recording::config::load_config;
+use crate::recording::session::lifecycle::{
+    orchestrate_async_transcription, split_recording, TranscriptionResult,
+};
+use crate::recording::state::{RecordingStatus, SharedRecordingState};
+use crate::recording::utils::get_storage_dir;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How often the segment timer checks the recording length
+const TICK: Duration = Duration::from_secs(1);
+
+/// Start the auto-segmentation timer thread
+///
+/// While a recording is in progress and `segmentation.everyMinutes` is set,
+/// each time the current segment reaches that length it is split off into
+/// its own session and transcribed in the background (emitting the usual
+/// transcription events), while recording continues into the next segment.
+///
+/// # Arguments
+/// * `state` - Shared recording state
+/// * `event_emitter` - Callback to emit Tauri events (injected dependency)
+pub fn start_segment_timer<F>(state: SharedRecordingState, event_emitter: F)
+where
+    F: Fn(TranscriptionResult) + Send + Clone + 'static,
+{
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+
+        let elapsed_seconds = match active_segment_seconds(&state) {
+            Some(seconds) => seconds,
+            None => continue,
+        };
+
+        let segmentation = match load_config() {
+            Ok(config) => config.audio.segmentation,
+            Err(_) => continue,
+        };
+        let segment_seconds = match segmentation.every_minutes.filter(|m| *m > 0) {
+            Some(minutes) => minutes as f64 * 60.0,
+            None => continue,
+        };
+
+        if elapsed_seconds < segment_seconds {
+            continue;
+        }
+
+        match split_recording(Arc::clone(&state)) {
+            Ok(session) => {
+                if segmentation.chime {
+                    play_chime();
+                }
+
+                match get_storage_dir() {
+                    Ok(storage_dir) => orchestrate_async_transcription(
+                        Arc::clone(&state),
+                        session.id.clone(),
+                        storage_dir.join(&session.audio_path),
+                        event_emitter.clone(),
+                    ),
+                    Err(e) => log::error!("Failed to transcribe segment {}: {}", session.id, e),
+                }
+            }
+            Err(e) => log::error!("Failed to split recording: {}", e),
+        }
+    });
+}
+
+/// Recorded (non-paused) length of the current segment, if recording
+fn active_segment_seconds(state: &SharedRecordingState) -> Option<f64> {
+    let state_guard = state.lock().ok()?;
+
+    if state_guard.status() != RecordingStatus::Recording {
+        return None;
+    }
+
+    Some(state_guard.timer.elapsed_seconds())
+}
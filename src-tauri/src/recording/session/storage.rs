@@ -1,50 +1,158 @@
-use crate::recording::models::{Session, SessionIndex};
-use crate::recording::utils::get_storage_dir;
+use crate::recording::file_store::{DiskStorage, Storage};
+use crate::recording::models::{Session, SessionIndex, SessionSort, SessionSortField};
+use crate::recording::session::access::get_session_access;
+use crate::recording::session::cache::SharedSessionCache;
+use crate::recording::timing::{Clock, SystemClock};
+use crate::recording::transcription::generate_preview;
+use crate::recording::transcription::romanize::romanize;
+use crate::recording::utils::{
+    get_storage_dir, romanized_transcript_relative_path, transcript_relative_path,
+};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-/// Load all sessions from the sessions.json index file
+/// Directory (relative to storage root) holding one metadata file per session
+pub const SESSIONS_DIR: &str = "sessions";
+
+/// The app's session cache, registered at startup; sessions are read from
+/// disk on every load without one (e.g. in tests)
+static SESSION_CACHE: OnceLock<SharedSessionCache> = OnceLock::new();
+
+/// Legacy single-file index, migrated into per-session files on first load
+const LEGACY_INDEX_FILE: &str = "sessions.json";
+
+/// Load all sessions from the per-session metadata files
 ///
-/// Creates an empty index file if it doesn't exist
+/// Each session lives in its own `sessions/{id}.json` file so that sync tools
+/// (iCloud, Dropbox, Syncthing) only ever conflict on a single session rather
+/// than the whole history. Conflict copies are merged by session id and the
+/// winning record is written back to the canonical file.
+///
+/// The index is served from the registered `SessionCache` until a session
+/// is written or a session file changes on disk.
 pub fn load_sessions() -> Result<SessionIndex, String> {
     let storage_dir = get_storage_dir()?;
-    let sessions_file = storage_dir.join("sessions.json");
+    let load = || {
+        migrate_legacy_index(&DiskStorage, &SystemClock, &storage_dir)?;
+        load_sessions_from(&storage_dir)
+    };
+
+    match SESSION_CACHE.get() {
+        Some(cache) => cache.get_or_load(&storage_dir.join(SESSIONS_DIR), load),
+        None => load(),
+    }
+}
 
-    if !sessions_file.exists() {
-        // Create empty sessions file
-        let index = SessionIndex {
-            sessions: Vec::new(),
-        };
-        save_sessions(&index)?;
-        return Ok(index);
+/// Serve `load_sessions` from `cache`, the one held in the app's state
+///
+/// Only the first cache registered is used.
+pub fn use_session_cache(cache: SharedSessionCache) {
+    if SESSION_CACHE.set(cache).is_err() {
+        log::warn!("A session cache is already in use");
+    }
+}
+
+/// Drop the cached sessions after writing session records
+pub(crate) fn invalidate_session_cache() {
+    if let Some(cache) = SESSION_CACHE.get() {
+        cache.invalidate();
     }
+}
 
-    let content = fs::read_to_string(&sessions_file)
-        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+/// Load and merge all sessions stored under the given storage root
+///
+/// Does not require the directory to be the active storage dir, which allows
+/// reading another ThoughtCast folder (e.g. for imports)
+pub fn load_sessions_from(storage_dir: &Path) -> Result<SessionIndex, String> {
+    load_sessions_in(&DiskStorage, &SystemClock, storage_dir)
+}
 
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse sessions file: {}", e))
+/// `load_sessions_from` on the given storage
+fn load_sessions_in(
+    storage: &dyn Storage,
+    clock: &dyn Clock,
+    storage_dir: &Path,
+) -> Result<SessionIndex, String> {
+    let sessions_dir = storage_dir.join(SESSIONS_DIR);
+    let records = read_session_records(storage, &sessions_dir)?;
+
+    let conflict_copies: Vec<PathBuf> = records
+        .iter()
+        .filter(|(path, session)| !is_canonical_record_path(path, &session.id))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    let sessions = merge_session_records(records.into_iter().map(|(_, session)| session).collect());
+
+    // Collapse conflict copies into the canonical file for each merged session
+    if !conflict_copies.is_empty() {
+        for session in &sessions {
+            write_session_record_to(storage, clock, &sessions_dir, session)?;
+        }
+        for path in conflict_copies {
+            if let Err(e) = storage.remove_file(&path) {
+                log::warn!("Failed to remove conflict copy {}: {}", path.display(), e);
+            }
+        }
+        invalidate_session_cache();
+    }
+
+    Ok(SessionIndex { sessions })
 }
 
-/// Save the session index to disk
+/// Read all sessions from another ThoughtCast folder without modifying it
 ///
-/// Writes to sessions.json with pretty-printing for human readability
-pub fn save_sessions(index: &SessionIndex) -> Result<(), String> {
-    let storage_dir = get_storage_dir()?;
-    let sessions_file = storage_dir.join("sessions.json");
+/// Includes sessions from a legacy `sessions.json` index if the folder was
+/// never migrated to per-session files
+pub fn read_sessions_from(storage_dir: &Path) -> Result<Vec<Session>, String> {
+    let mut sessions: Vec<Session> =
+        read_session_records(&DiskStorage, &storage_dir.join(SESSIONS_DIR))?
+            .into_iter()
+            .map(|(_, session)| session)
+            .collect();
+
+    let legacy_file = storage_dir.join(LEGACY_INDEX_FILE);
+    if legacy_file.exists() {
+        let content = fs::read_to_string(&legacy_file)
+            .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+        let index: SessionIndex = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse sessions file: {}", e))?;
+        sessions.extend(index.sessions);
+    }
 
-    let content = serde_json::to_string_pretty(index)
-        .map_err(|e| format!("Failed to serialize sessions: {}", e))?;
+    Ok(merge_session_records(sessions))
+}
 
-    fs::write(&sessions_file, content)
-        .map_err(|e| format!("Failed to write sessions file: {}", e))
+/// Find a single session by id
+pub fn get_session(session_id: &str) -> Result<Session, String> {
+    load_sessions()?
+        .sessions
+        .into_iter()
+        .find(|s| s.id == session_id)
+        .ok_or_else(|| format!("Session not found: {}", session_id))
+}
+
+/// Relative path (from the storage root) of a session's transcript
+///
+/// Transcripts are named like the session's audio file, which follows the
+/// filename pattern in effect when it was recorded.
+pub fn session_transcript_relative_path(session_id: &str) -> String {
+    let file_stem = get_session(session_id).ok().and_then(|session| {
+        Path::new(&session.audio_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+    });
+
+    transcript_relative_path(file_stem.as_deref().unwrap_or(session_id))
 }
 
 /// Load transcript text for a specific session from disk
 pub fn load_transcript(session_id: &str) -> Result<String, String> {
     let storage_dir = get_storage_dir()?;
-    let transcript_path = storage_dir
-        .join("text")
-        .join(format!("{}.txt", session_id));
+    let transcript_path = storage_dir.join(session_transcript_relative_path(session_id));
 
     if !transcript_path.exists() {
         return Err(format!(
@@ -57,17 +165,61 @@ pub fn load_transcript(session_id: &str) -> Result<String, String> {
         .map_err(|e| format!("Failed to read transcript file: {}", e))
 }
 
+/// Load a session's transcript with Cyrillic, Greek and kana romanized
+///
+/// Uses the copy saved at transcription time when there is one (see
+/// `transcription.romanize`), otherwise romanizes the transcript now.
+pub fn load_romanized_transcript(session_id: &str) -> Result<String, String> {
+    let path = get_storage_dir()?.join(romanized_transcript_relative_path(session_id));
+
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(text),
+        Err(_) => load_transcript(session_id).map(|text| romanize(&text)),
+    }
+}
+
+/// Recompute every transcribed session's preview from its transcript
+///
+/// Repairs previews written by older versions (e.g. cut mid-character).
+/// Returns the number of sessions whose preview changed.
+pub fn regenerate_previews() -> Result<usize, String> {
+    let storage_dir = get_storage_dir()?;
+    let sessions_dir = storage_dir.join(SESSIONS_DIR);
+    let mut updated = 0;
+
+    for mut session in load_sessions()?.sessions {
+        if session.transcript_path.is_empty() {
+            continue;
+        }
+
+        let text = match fs::read_to_string(storage_dir.join(&session.transcript_path)) {
+            Ok(text) => text,
+            Err(e) => {
+                log::warn!("Skipping preview for {}: {}", session.id, e);
+                continue;
+            }
+        };
+
+        let preview = generate_preview(&text);
+        if session.preview != preview {
+            session.preview = preview;
+            write_session_record(&sessions_dir, &session)?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
 /// Add a new session to the index
 ///
-/// Inserts at the beginning so most recent sessions appear first
+/// Only the new session's metadata file is written
 pub fn add_session(session: Session) -> Result<(), String> {
-    let mut index = load_sessions()?;
-    index.sessions.insert(0, session);
-    save_sessions(&index)
+    let sessions_dir = get_storage_dir()?.join(SESSIONS_DIR);
+    write_session_record(&sessions_dir, &session)
 }
 
 /// Update an existing session in the index
-#[allow(dead_code)]
 pub fn update_session<F>(session_id: &str, updater: F) -> Result<(), String>
 where
     F: FnOnce(&mut Session),
@@ -82,24 +234,291 @@ where
 
     updater(session);
 
-    save_sessions(&index)
+    let sessions_dir = get_storage_dir()?.join(SESSIONS_DIR);
+    write_session_record(&sessions_dir, session)
+}
+
+/// Set a session's notes, clearing them when empty
+///
+/// Returns the updated session
+pub fn update_session_notes(session_id: &str, notes: Option<&str>) -> Result<Session, String> {
+    let notes = notes
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+        .map(String::from);
+
+    update_session(session_id, |session| session.notes = notes)?;
+    get_session(session_id)
+}
+
+/// Remove a session's metadata file from the index
+pub fn remove_session(session_id: &str) -> Result<(), String> {
+    let sessions_dir = get_storage_dir()?.join(SESSIONS_DIR);
+    let path = session_record_path(&sessions_dir, session_id);
+
+    if path.exists() {
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove session file: {}", e))?;
+        invalidate_session_cache();
+    }
+
+    Ok(())
+}
+
+/// Path of the canonical metadata file for a session
+pub fn session_record_path(sessions_dir: &Path, session_id: &str) -> PathBuf {
+    sessions_dir.join(format!("{}.json", session_id))
+}
+
+/// Whether a metadata file is the canonical file for the session it contains
+///
+/// Sync tools create copies like `{id} (conflicted copy).json` or
+/// `{id}.sync-conflict-*.json`, which still contain the original id
+fn is_canonical_record_path(path: &Path, session_id: &str) -> bool {
+    path.file_stem().and_then(|stem| stem.to_str()) == Some(session_id)
+}
+
+/// Read every session metadata file in a directory
+///
+/// Unreadable or corrupt files are skipped (and logged) so that a single bad
+/// sync copy cannot hide the rest of the history
+fn read_session_records(
+    storage: &dyn Storage,
+    sessions_dir: &Path,
+) -> Result<Vec<(PathBuf, Session)>, String> {
+    if !storage.exists(sessions_dir) {
+        return Ok(Vec::new());
+    }
+
+    let entries = storage
+        .list_dir(sessions_dir)
+        .map_err(|e| format!("Failed to read sessions directory: {}", e))?;
+
+    let mut records = Vec::new();
+    for path in entries {
+        let is_json = path.extension().and_then(|ext| ext.to_str()) == Some("json");
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+
+        if !is_json || is_hidden {
+            continue;
+        }
+
+        match storage
+            .read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<Session>(&content).map_err(|e| e.to_string())
+            }) {
+            Ok(session) => records.push((path, session)),
+            Err(e) => log::warn!("Skipping unreadable session file {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(records)
+}
+
+/// Write a session's metadata file if its content changed
+///
+/// Unchanged sessions are not rewritten so sync tools only see real edits.
+/// Changed sessions get a fresh `updated_at` and are written atomically via a
+/// hidden temp file + rename.
+pub fn write_session_record(sessions_dir: &Path, session: &Session) -> Result<(), String> {
+    if write_session_record_to(&DiskStorage, &SystemClock, sessions_dir, session)? {
+        invalidate_session_cache();
+    }
+    Ok(())
+}
+
+/// `write_session_record` on the given storage; returns whether the file
+/// was written
+fn write_session_record_to(
+    storage: &dyn Storage,
+    clock: &dyn Clock,
+    sessions_dir: &Path,
+    session: &Session,
+) -> Result<bool, String> {
+    storage
+        .create_dir_all(sessions_dir)
+        .map_err(|e| format!("Failed to create sessions directory: {}", e))?;
+
+    let path = session_record_path(sessions_dir, &session.id);
+
+    if let Some(existing) = storage
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Session>(&content).ok())
+    {
+        if same_content(&existing, session) {
+            return Ok(false);
+        }
+    }
+
+    let mut record = session.clone();
+    record.updated_at = Some(clock.now().to_rfc3339());
+
+    let content = serde_json::to_string_pretty(&record)
+        .map_err(|e| format!("Failed to serialize session: {}", e))?;
+
+    let temp_path = sessions_dir.join(format!(".{}.json.tmp", session.id));
+    storage
+        .write(&temp_path, &content)
+        .map_err(|e| format!("Failed to write session file: {}", e))?;
+    storage
+        .rename(&temp_path, &path)
+        .map_err(|e| format!("Failed to write session file: {}", e))?;
+
+    Ok(true)
+}
+
+/// Compare two session records ignoring their write timestamps
+fn same_content(a: &Session, b: &Session) -> bool {
+    let mut a = a.clone();
+    a.updated_at = None;
+    let mut b = b.clone();
+    b.updated_at = None;
+    a == b
+}
+
+/// Merge session records, resolving duplicates by id
+///
+/// When several records share an id (conflict copies), the most recently
+/// written one wins; records without `updated_at` lose to any timestamped one,
+/// and a transcribed record beats a still-processing one on ties.
+/// Result is ordered newest-first by recording timestamp.
+pub fn merge_session_records(records: Vec<Session>) -> Vec<Session> {
+    let mut by_id: HashMap<String, Session> = HashMap::new();
+
+    for record in records {
+        match by_id.get(&record.id) {
+            Some(existing) if !is_newer_record(&record, existing) => {}
+            _ => {
+                by_id.insert(record.id.clone(), record);
+            }
+        }
+    }
+
+    let mut sessions: Vec<Session> = by_id.into_values().collect();
+    sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.id.cmp(&a.id)));
+    sessions
+}
+
+/// Order sessions (as loaded, newest first) by `sort`
+///
+/// Sessions lacking what they're sorted by (a title, a transcript, a
+/// viewing) come last in either direction; ties keep the newest first.
+pub fn sort_sessions(sessions: &mut [Session], sort: SessionSort) -> Result<(), String> {
+    match sort.field {
+        SessionSortField::Recorded if sort.ascending => sessions.reverse(),
+        SessionSortField::Recorded => {}
+        SessionSortField::Duration => sort_by_key(sessions, sort, |s| Some(s.duration)),
+        SessionSortField::WordCount => {
+            let storage_dir = get_storage_dir()?;
+            let word_counts: HashMap<String, usize> = sessions
+                .iter()
+                .filter(|session| !session.transcript_path.is_empty())
+                .filter_map(|session| {
+                    let text = fs::read_to_string(storage_dir.join(&session.transcript_path));
+                    Some((session.id.clone(), text.ok()?.split_whitespace().count()))
+                })
+                .collect();
+            sort_by_key(sessions, sort, |s| word_counts.get(&s.id).copied())
+        }
+        SessionSortField::Title => {
+            sort_by_key(sessions, sort, |s| s.title.as_deref().map(str::to_lowercase))
+        }
+        SessionSortField::LastAccessed => {
+            let access = get_session_access();
+            sort_by_key(sessions, sort, |s| {
+                access.get(&s.id).map(|entry| entry.last_accessed.clone())
+            })
+        }
+    }
+    Ok(())
+}
+
+/// Stable sort by `key`, putting sessions without one last
+fn sort_by_key<K, F>(sessions: &mut [Session], sort: SessionSort, key: F)
+where
+    K: PartialOrd,
+    F: Fn(&Session) -> Option<K>,
+{
+    sessions.sort_by(|a, b| match (key(a), key(b)) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            if sort.ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+}
+
+/// Whether `candidate` should replace `current` when merging duplicates
+fn is_newer_record(candidate: &Session, current: &Session) -> bool {
+    match candidate.updated_at.cmp(&current.updated_at) {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => {
+            current.transcript_path.is_empty() && !candidate.transcript_path.is_empty()
+        }
+    }
+}
+
+/// Migrate a legacy `sessions.json` index into per-session metadata files
+///
+/// Existing per-session files take precedence. The legacy file is renamed
+/// (not deleted) so the migration is reversible.
+fn migrate_legacy_index(
+    storage: &dyn Storage,
+    clock: &dyn Clock,
+    storage_dir: &Path,
+) -> Result<(), String> {
+    let legacy_file = storage_dir.join(LEGACY_INDEX_FILE);
+    if !storage.exists(&legacy_file) {
+        return Ok(());
+    }
+
+    let content = storage
+        .read_to_string(&legacy_file)
+        .map_err(|e| format!("Failed to read sessions file: {}", e))?;
+    let index: SessionIndex = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse sessions file: {}", e))?;
+
+    let sessions_dir = storage_dir.join(SESSIONS_DIR);
+    for session in &index.sessions {
+        if !storage.exists(&session_record_path(&sessions_dir, &session.id)) {
+            write_session_record_to(storage, clock, &sessions_dir, session)?;
+        }
+    }
+    invalidate_session_cache();
+
+    storage
+        .rename(
+            &legacy_file,
+            &storage_dir.join(format!("{}.migrated", LEGACY_INDEX_FILE)),
+        )
+        .map_err(|e| format!("Failed to archive legacy sessions file: {}", e))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::recording::file_store::MemoryStorage;
+    use crate::recording::fixtures;
+    use crate::recording::timing::ManualClock;
 
     fn create_test_session(id: &str, duration: f64) -> Session {
         Session {
-            id: id.to_string(),
-            timestamp: "2024-11-02T15:30:00Z".to_string(),
-            audio_path: format!("audio/{}.wav", id),
             duration,
             preview: format!("Preview for {}", id),
-            transcript_path: format!("text/{}.txt", id),
-            clipboard_copied: false,
-            transcription_time_seconds: None,
-            model_path: None,
+            ..fixtures::session(id)
         }
     }
 
@@ -212,6 +631,7 @@ mod tests {
             clipboard_copied: true,
             transcription_time_seconds: Some(18.5),
             model_path: Some("/path/to/model.bin".to_string()),
+            ..Session::default()
         };
 
         let json = serde_json::to_string(&session).unwrap();
@@ -225,4 +645,203 @@ mod tests {
         assert_eq!(deserialized.transcript_path, "text/full-session.txt");
         assert_eq!(deserialized.clipboard_copied, true);
     }
+
+    #[test]
+    fn test_merge_prefers_most_recent_write() {
+        let mut older = create_test_session("dup", 30.0);
+        older.updated_at = Some("2024-11-02T15:31:00+00:00".to_string());
+        older.preview = "old".to_string();
+
+        let mut newer = create_test_session("dup", 30.0);
+        newer.updated_at = Some("2024-11-02T15:35:00+00:00".to_string());
+        newer.preview = "new".to_string();
+
+        let merged = merge_session_records(vec![newer.clone(), older.clone()]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].preview, "new");
+
+        let merged = merge_session_records(vec![older, newer]);
+        assert_eq!(merged[0].preview, "new");
+    }
+
+    #[test]
+    fn test_merge_prefers_transcribed_record_on_tie() {
+        let mut processing = create_test_session("dup", 30.0);
+        processing.transcript_path = String::new();
+
+        let transcribed = create_test_session("dup", 30.0);
+
+        let merged = merge_session_records(vec![transcribed, processing]);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].transcript_path, "text/dup.txt");
+    }
+
+    #[test]
+    fn test_merge_orders_newest_first() {
+        let mut first = create_test_session("a", 10.0);
+        first.timestamp = "2024-11-01T10:00:00+00:00".to_string();
+        let mut second = create_test_session("b", 10.0);
+        second.timestamp = "2024-11-03T10:00:00+00:00".to_string();
+
+        let merged = merge_session_records(vec![first, second]);
+        assert_eq!(merged[0].id, "b");
+        assert_eq!(merged[1].id, "a");
+    }
+
+    #[test]
+    fn test_sort_by_duration_and_title() {
+        let mut sessions = vec![
+            create_test_session("c", 30.0),
+            create_test_session("b", 10.0),
+            create_test_session("a", 20.0),
+        ];
+        sessions[0].title = Some("banana".to_string());
+        sessions[2].title = Some("Apple".to_string());
+        let ids = |sessions: &[Session]| -> Vec<String> {
+            sessions.iter().map(|s| s.id.clone()).collect()
+        };
+
+        let by_duration = SessionSort {
+            field: SessionSortField::Duration,
+            ascending: true,
+        };
+        sort_sessions(&mut sessions, by_duration).unwrap();
+        assert_eq!(ids(&sessions), ["b", "a", "c"]);
+
+        let by_title = SessionSort {
+            field: SessionSortField::Title,
+            ascending: false,
+        };
+        sort_sessions(&mut sessions, by_title).unwrap();
+        assert_eq!(ids(&sessions), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_conflict_copies_are_collapsed() {
+        let storage = MemoryStorage::new();
+        let clock = ManualClock::new();
+        let dir = Path::new("/storage");
+        let sessions_dir = dir.join(SESSIONS_DIR);
+
+        let mut original = create_test_session("s1", 30.0);
+        original.updated_at = Some("2024-11-02T15:31:00+00:00".to_string());
+        let mut conflict = original.clone();
+        conflict.updated_at = Some("2024-11-02T15:40:00+00:00".to_string());
+        conflict.preview = "edited elsewhere".to_string();
+
+        storage
+            .write(
+                &sessions_dir.join("s1.json"),
+                &serde_json::to_string(&original).unwrap(),
+            )
+            .unwrap();
+        storage
+            .write(
+                &sessions_dir.join("s1 (conflicted copy).json"),
+                &serde_json::to_string(&conflict).unwrap(),
+            )
+            .unwrap();
+
+        let index = load_sessions_in(&storage, &clock, dir).unwrap();
+        assert_eq!(index.sessions.len(), 1);
+        assert_eq!(index.sessions[0].preview, "edited elsewhere");
+        assert!(!storage.exists(&sessions_dir.join("s1 (conflicted copy).json")));
+
+        let reloaded = load_sessions_in(&storage, &clock, dir).unwrap();
+        assert_eq!(reloaded.sessions[0].preview, "edited elsewhere");
+    }
+
+    #[test]
+    fn test_corrupt_files_are_skipped() {
+        let storage = MemoryStorage::new();
+        let clock = ManualClock::new();
+        let dir = Path::new("/storage");
+        let sessions_dir = dir.join(SESSIONS_DIR);
+
+        let good = create_test_session("good", 10.0);
+        write_session_record_to(&storage, &clock, &sessions_dir, &good).unwrap();
+        storage
+            .write(&sessions_dir.join("bad.json"), "{ not json")
+            .unwrap();
+
+        let index = load_sessions_in(&storage, &clock, dir).unwrap();
+        assert_eq!(index.sessions.len(), 1);
+        assert_eq!(index.sessions[0].id, "good");
+    }
+
+    #[test]
+    fn test_unchanged_record_is_not_rewritten() {
+        let storage = MemoryStorage::new();
+        let clock = ManualClock::new();
+        let sessions_dir = Path::new("/storage").join(SESSIONS_DIR);
+        let session = create_test_session("stable", 10.0);
+
+        write_session_record_to(&storage, &clock, &sessions_dir, &session).unwrap();
+        let first = storage
+            .read_to_string(&sessions_dir.join("stable.json"))
+            .unwrap();
+
+        clock.advance(60);
+        write_session_record_to(&storage, &clock, &sessions_dir, &session).unwrap();
+        let second = storage
+            .read_to_string(&sessions_dir.join("stable.json"))
+            .unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_changed_record_is_stamped_by_clock() {
+        let storage = MemoryStorage::new();
+        let clock = ManualClock::new();
+        let sessions_dir = Path::new("/storage").join(SESSIONS_DIR);
+        let mut session = create_test_session("s1", 10.0);
+
+        assert!(write_session_record_to(&storage, &clock, &sessions_dir, &session).unwrap());
+        clock.advance(60);
+        assert!(!write_session_record_to(&storage, &clock, &sessions_dir, &session).unwrap());
+
+        session.preview = "edited".to_string();
+        assert!(write_session_record_to(&storage, &clock, &sessions_dir, &session).unwrap());
+
+        let index = load_sessions_in(&storage, &clock, Path::new("/storage")).unwrap();
+        assert_eq!(index.sessions[0].preview, "edited");
+        assert_eq!(index.sessions[0].updated_at, Some(clock.now().to_rfc3339()));
+        // No temp file is left behind
+        assert_eq!(storage.files(), vec![sessions_dir.join("s1.json")]);
+    }
+
+    #[test]
+    fn test_legacy_index_is_migrated() {
+        let storage = MemoryStorage::new();
+        let clock = ManualClock::new();
+        let storage_dir = Path::new("/storage");
+        let sessions_dir = storage_dir.join(SESSIONS_DIR);
+
+        let mut existing = create_test_session("s1", 10.0);
+        existing.preview = "per-session file".to_string();
+        write_session_record_to(&storage, &clock, &sessions_dir, &existing).unwrap();
+
+        let legacy = SessionIndex {
+            sessions: vec![
+                create_test_session("s1", 10.0),
+                create_test_session("s2", 20.0),
+            ],
+        };
+        storage
+            .write(
+                &storage_dir.join(LEGACY_INDEX_FILE),
+                &serde_json::to_string(&legacy).unwrap(),
+            )
+            .unwrap();
+
+        migrate_legacy_index(&storage, &clock, storage_dir).unwrap();
+
+        assert!(!storage.exists(&storage_dir.join(LEGACY_INDEX_FILE)));
+        assert!(storage.exists(&storage_dir.join("sessions.json.migrated")));
+        let index = load_sessions_in(&storage, &clock, storage_dir).unwrap();
+        assert_eq!(index.sessions.len(), 2);
+        let s1 = index.sessions.iter().find(|s| s.id == "s1").unwrap();
+        assert_eq!(s1.preview, "per-session file");
+    }
 }
@@ -0,0 +1,171 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often `wait_for_transcriptions` checks for finished tasks
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Most transcription tasks running at once; sessions stopped beyond that
+/// wait in the transcription queue (see `transcription_backlog`)
+pub(crate) const MAX_TRANSCRIPTION_TASKS: usize = 3;
+
+/// Transcriptions started after a recording stops, joined on shutdown
+static TRANSCRIPTION_TASKS: Mutex<Vec<TranscriptionTask>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Cancellation flag of the transcription task running on this thread
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+struct TranscriptionTask {
+    session_id: String,
+    handle: JoinHandle<()>,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Run a session's transcription on its own named thread, keeping its join
+/// handle so shutdown can wait for it
+///
+/// The task can be stopped with `cancel_transcription`, which ends its
+/// Whisper.cpp process (see `transcription_cancelled`).
+pub(crate) fn spawn_transcription_task<F>(session_id: &str, task: F) -> Result<(), String>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task_cancelled = Arc::clone(&cancelled);
+    let handle = thread::Builder::new()
+        .name(format!("transcribe-{}", session_id))
+        .spawn(move || {
+            CANCELLED.with(|flag| *flag.borrow_mut() = Some(task_cancelled));
+            task();
+        })
+        .map_err(|e| format!("Failed to start transcription of {}: {}", session_id, e))?;
+
+    let mut tasks = TRANSCRIPTION_TASKS.lock().unwrap();
+    join_finished(&mut tasks);
+    tasks.push(TranscriptionTask {
+        session_id: session_id.to_string(),
+        handle,
+        cancelled,
+    });
+    Ok(())
+}
+
+/// Why a new transcription task can't start now, if it can't
+///
+/// Whisper.cpp runs are CPU-heavy, so only `MAX_TRANSCRIPTION_TASKS` run at
+/// once; others are left to the transcription queue.
+pub(crate) fn transcription_backlog() -> Option<String> {
+    let running = running_transcriptions().len();
+    (running >= MAX_TRANSCRIPTION_TASKS)
+        .then(|| format!("{} transcriptions are already running", running))
+}
+
+/// Stop a session's running transcription
+///
+/// Its Whisper.cpp process is ended and the session keeps the transcript
+/// it had (none for a new recording).
+pub fn cancel_transcription(session_id: &str) -> Result<(), String> {
+    let mut tasks = TRANSCRIPTION_TASKS.lock().unwrap();
+    join_finished(&mut tasks);
+
+    let task = tasks
+        .iter()
+        .find(|task| task.session_id == session_id)
+        .ok_or_else(|| format!("Session {} is not being transcribed", session_id))?;
+    task.cancelled.store(true, Ordering::SeqCst);
+    log::info!("Cancelling transcription of {}", session_id);
+    Ok(())
+}
+
+/// Whether the transcription task running on this thread was cancelled
+///
+/// Always false outside transcription tasks.
+pub(crate) fn transcription_cancelled() -> bool {
+    CANCELLED.with(|flag| {
+        flag.borrow()
+            .as_ref()
+            .is_some_and(|cancelled| cancelled.load(Ordering::SeqCst))
+    })
+}
+
+/// Sessions whose transcription task is still running
+pub fn running_transcriptions() -> Vec<String> {
+    let mut tasks = TRANSCRIPTION_TASKS.lock().unwrap();
+    join_finished(&mut tasks);
+    tasks.iter().map(|task| task.session_id.clone()).collect()
+}
+
+/// Wait up to `timeout` for running transcriptions to finish
+///
+/// Returns the sessions still being transcribed when the time runs out;
+/// they stay marked pending and are transcribed again on the next launch.
+pub fn wait_for_transcriptions(timeout: Duration) -> Vec<String> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let running = running_transcriptions();
+        if running.is_empty() || Instant::now() >= deadline {
+            return running;
+        }
+        thread::sleep(WAIT_POLL_INTERVAL);
+    }
+}
+
+/// Join and drop the tasks that have finished, logging any that panicked
+fn join_finished(tasks: &mut Vec<TranscriptionTask>) {
+    let (finished, running) = tasks
+        .drain(..)
+        .partition(|task: &TranscriptionTask| task.handle.is_finished());
+    *tasks = running;
+
+    for task in finished {
+        if task.handle.join().is_err() {
+            log::error!("Transcription of {} panicked", task.session_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_waits_for_running_transcriptions() {
+        let (release, released) = mpsc::channel::<()>();
+        spawn_transcription_task("task-test-slow", move || {
+            let _ = released.recv();
+        })
+        .unwrap();
+        spawn_transcription_task("task-test-quick", || {}).unwrap();
+
+        let running = wait_for_transcriptions(Duration::from_millis(100));
+        assert!(running.contains(&"task-test-slow".to_string()));
+        assert!(!running.contains(&"task-test-quick".to_string()));
+
+        release.send(()).unwrap();
+        let running = wait_for_transcriptions(Duration::from_secs(2));
+        assert!(!running.contains(&"task-test-slow".to_string()));
+    }
+
+    #[test]
+    fn test_cancels_running_transcription() {
+        let (report, reported) = mpsc::channel();
+        spawn_transcription_task("task-test-cancel", move || {
+            while !transcription_cancelled() {
+                thread::sleep(Duration::from_millis(10));
+            }
+            let _ = report.send(());
+        })
+        .unwrap();
+
+        assert!(!transcription_cancelled());
+        assert!(cancel_transcription("task-test-missing").is_err());
+        cancel_transcription("task-test-cancel").unwrap();
+        reported.recv_timeout(Duration::from_secs(2)).unwrap();
+    }
+}
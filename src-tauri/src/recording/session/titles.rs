@@ -0,0 +1,204 @@
+use crate::recording::config::load_config;
+use crate::recording::models::{Session, TitleConfig, TitleSource};
+use crate::recording::session::storage::{load_sessions, load_transcript, update_session};
+use crate::recording::transcription::text_processor::extract_summary;
+use crate::recording::utils::http_request;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Longest automatic title, in characters
+const MAX_TITLE_CHARS: usize = 60;
+
+/// Longest wait for the language model to answer
+const LLM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Transcript characters sent to the language model
+const LLM_TRANSCRIPT_CHARS: usize = 4000;
+
+const LLM_PROMPT: &str = "Write a title of at most eight words for this voice note. \
+Reply with the title only, without quotes.";
+
+/// Title a newly transcribed session (see `TitleConfig`)
+pub fn auto_title_session(session: &Session) {
+    if !has_automatic_title(session) {
+        return;
+    }
+    let Ok(config) = load_config().map(|config| config.integrations.titles) else {
+        return;
+    };
+    if !config.enabled {
+        return;
+    }
+
+    if let Err(e) = title_session(&config, &session.id) {
+        log::warn!("Failed to title session {}: {}", session.id, e);
+    }
+}
+
+/// Generate titles again for every transcribed session without a meeting
+/// or phone-given title, e.g. after setting up a language model
+///
+/// Returns the number of sessions whose title changed.
+pub fn regenerate_titles() -> Result<usize, String> {
+    let config = load_config()?.integrations.titles;
+    let mut updated = 0;
+
+    for session in load_sessions()?.sessions {
+        if session.transcript_path.is_empty() || !has_automatic_title(&session) {
+            continue;
+        }
+
+        match title_session(&config, &session.id) {
+            Ok(true) => updated += 1,
+            Ok(false) => {}
+            Err(e) => log::warn!("Skipping title for {}: {}", session.id, e),
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Whether a session's title is missing or was generated, so it can be
+/// (re)generated
+fn has_automatic_title(session: &Session) -> bool {
+    session.title.is_none() || session.title_source.is_some()
+}
+
+/// Store a title generated from the session's transcript; returns whether
+/// it changed
+fn title_session(config: &TitleConfig, session_id: &str) -> Result<bool, String> {
+    let transcript = load_transcript(session_id)?;
+    let Some((title, source)) = generate_title(config, &transcript) else {
+        return Ok(false);
+    };
+
+    let mut changed = false;
+    update_session(session_id, |session| {
+        if has_automatic_title(session) && session.title.as_deref() != Some(&title) {
+            session.title = Some(title.clone());
+            session.title_source = Some(source);
+            changed = true;
+        }
+    })?;
+
+    Ok(changed)
+}
+
+/// A title from the language model if configured, otherwise (or when it
+/// fails) the transcript's first sentence
+fn generate_title(config: &TitleConfig, transcript: &str) -> Option<(String, TitleSource)> {
+    if transcript.trim().is_empty() {
+        return None;
+    }
+
+    if let Some(url) = config
+        .llm_url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+    {
+        match llm_title(config, url, transcript) {
+            Ok(title) => return Some((title, TitleSource::Llm)),
+            Err(e) => log::warn!("Falling back to first-sentence title: {}", e),
+        }
+    }
+
+    first_sentence_title(transcript).map(|title| (title, TitleSource::FirstSentence))
+}
+
+/// The transcript's first sentence, without its final punctuation and cut
+/// at a word boundary
+fn first_sentence_title(transcript: &str) -> Option<String> {
+    let sentence = extract_summary(transcript, 1);
+    clean_title(sentence.trim_end_matches(['.', '!', '?']))
+}
+
+/// Ask an OpenAI-compatible chat completions endpoint for a title
+fn llm_title(config: &TitleConfig, url: &str, transcript: &str) -> Result<String, String> {
+    let excerpt: String = transcript.chars().take(LLM_TRANSCRIPT_CHARS).collect();
+    let body = json!({
+        "model": config.llm_model.as_deref().unwrap_or_default(),
+        "messages": [
+            { "role": "system", "content": LLM_PROMPT },
+            { "role": "user", "content": excerpt },
+        ],
+    });
+
+    let mut request = http_request("Language model titles", "POST", url)?.timeout(LLM_TIMEOUT);
+    if let Some(key) = config.llm_api_key.as_deref() {
+        request = request.set("Authorization", &format!("Bearer {}", key));
+    }
+
+    let response: Value = match request.send_json(body) {
+        Ok(response) => response
+            .into_json()
+            .map_err(|e| format!("Failed to read language model response: {}", e))?,
+        Err(ureq::Error::Status(status, _)) => {
+            return Err(format!("Language model request failed ({})", status))
+        }
+        Err(e) => return Err(format!("Failed to reach language model: {}", e)),
+    };
+
+    response["choices"][0]["message"]["content"]
+        .as_str()
+        .and_then(|content| content.lines().find(|line| !line.trim().is_empty()))
+        .and_then(|line| clean_title(line.trim().trim_matches(['"', '\'', '*'])))
+        .ok_or_else(|| "Language model returned no title".to_string())
+}
+
+/// Collapse whitespace and shorten to `MAX_TITLE_CHARS`, cutting between
+/// words; None when nothing is left
+fn clean_title(text: &str) -> Option<String> {
+    let mut title = String::new();
+
+    for word in text.split_whitespace() {
+        let length = title.chars().count() + word.chars().count() + 1;
+        if !title.is_empty() && length > MAX_TITLE_CHARS {
+            title.push_str("...");
+            break;
+        }
+        if !title.is_empty() {
+            title.push(' ');
+        }
+        title.extend(word.chars().take(MAX_TITLE_CHARS));
+    }
+
+    (!title.is_empty()).then_some(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_titles_with_first_sentence() {
+        assert_eq!(
+            first_sentence_title("Pick up the groceries.  Then call mom!"),
+            Some("Pick up the groceries".to_string())
+        );
+        assert_eq!(first_sentence_title("   "), None);
+
+        let long = "word ".repeat(30);
+        let title = first_sentence_title(&long).unwrap();
+        assert!(title.ends_with("word..."));
+        assert!(title.chars().count() <= MAX_TITLE_CHARS + 3);
+    }
+
+    #[test]
+    fn test_keeps_given_titles() {
+        let mut session: Session = serde_json::from_value(json!({
+            "id": "2024-11-02_15-30-00",
+            "timestamp": "2024-11-02T15:30:00Z",
+            "audio_path": "audio/2024-11-02_15-30-00.wav",
+            "duration": 12.0,
+            "preview": "Team standup",
+        }))
+        .unwrap();
+        assert!(has_automatic_title(&session));
+
+        session.title = Some("Team standup".to_string());
+        assert!(!has_automatic_title(&session));
+
+        session.title_source = Some(TitleSource::FirstSentence);
+        assert!(has_automatic_title(&session));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::recording::models::{DiffChange, DiffChunk, TranscriptDiff};
+use crate::recording::session::storage::{get_session, load_transcript};
+use crate::recording::session::transcript_versions::load_transcript_version;
+
+/// Compare two transcripts of a session word by word
+///
+/// `version_a` and `version_b` are saved version numbers (see
+/// `list_transcript_versions`); None stands for the current transcript.
+pub fn diff_transcripts(
+    session_id: &str,
+    version_a: Option<u32>,
+    version_b: Option<u32>,
+) -> Result<TranscriptDiff, String> {
+    let (text_a, model_a) = transcript_text(session_id, version_a)?;
+    let (text_b, model_b) = transcript_text(session_id, version_b)?;
+
+    Ok(diff_texts(&text_a, &text_b, model_a, model_b))
+}
+
+/// Word-level diff from `text_a` to `text_b`, produced by the given models
+pub(crate) fn diff_texts(
+    text_a: &str,
+    text_b: &str,
+    model_a: Option<String>,
+    model_b: Option<String>,
+) -> TranscriptDiff {
+    let old: Vec<&str> = text_a.split_whitespace().collect();
+    let new: Vec<&str> = text_b.split_whitespace().collect();
+    let edits = word_diff(&old, &new);
+    let count = |change: DiffChange| edits.iter().filter(|(c, _)| *c == change).count();
+
+    TranscriptDiff {
+        model_a,
+        model_b,
+        words_removed: count(DiffChange::Removed),
+        words_added: count(DiffChange::Added),
+        chunks: chunks(&edits),
+    }
+}
+
+/// Text of a saved version, or of the current transcript, with its model
+fn transcript_text(
+    session_id: &str,
+    version: Option<u32>,
+) -> Result<(String, Option<String>), String> {
+    match version {
+        Some(version) => load_transcript_version(session_id, version),
+        None => Ok((
+            load_transcript(session_id)?,
+            get_session(session_id)?.model_path,
+        )),
+    }
+}
+
+/// Join consecutive words with the same change into one chunk
+fn chunks(edits: &[(DiffChange, &str)]) -> Vec<DiffChunk> {
+    let mut chunks: Vec<DiffChunk> = Vec::new();
+
+    for &(change, word) in edits {
+        match chunks.last_mut() {
+            Some(chunk) if chunk.change == change => {
+                chunk.text.push(' ');
+                chunk.text.push_str(word);
+            }
+            _ => chunks.push(DiffChunk {
+                change,
+                text: word.to_string(),
+            }),
+        }
+    }
+
+    chunks
+}
+
+/// Shortest edit turning `old` into `new`, one entry per word
+///
+/// The words both share at the start and end are set aside first, since
+/// transcripts of the same audio mostly agree.
+fn word_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffChange, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut edits: Vec<(DiffChange, &str)> = old[..prefix]
+        .iter()
+        .map(|word| (DiffChange::Equal, *word))
+        .collect();
+    edits.extend(myers_diff(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    edits.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|word| (DiffChange::Equal, *word)),
+    );
+
+    edits
+}
+
+/// Myers' O((N+M)D) diff algorithm
+fn myers_diff<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<(DiffChange, &'a str)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let offset = n + m + 1;
+    // Furthest x reached on each diagonal k = x - y, indexed by k + offset
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    // `v` for diagonals -(d + 1)..=(d + 1) before each round d
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=n + m {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+
+        for k in (-d..=d).step_by(2) {
+            let i = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk back from the end, one edit per round
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, before) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let furthest = |k: isize| before[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = furthest(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push((DiffChange::Equal, a[x as usize]));
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                edits.push((DiffChange::Added, b[y as usize]));
+            } else {
+                x -= 1;
+                edits.push((DiffChange::Removed, a[x as usize]));
+            }
+        }
+    }
+
+    edits.reverse();
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: &str, new: &str) -> Vec<DiffChunk> {
+        let old: Vec<&str> = old.split_whitespace().collect();
+        let new: Vec<&str> = new.split_whitespace().collect();
+        chunks(&word_diff(&old, &new))
+    }
+
+    fn chunk(change: DiffChange, text: &str) -> DiffChunk {
+        DiffChunk {
+            change,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diffs_words() {
+        assert_eq!(
+            diff(
+                "the quick brown fox jumped",
+                "the quick red fox jumped high"
+            ),
+            vec![
+                chunk(DiffChange::Equal, "the quick"),
+                chunk(DiffChange::Removed, "brown"),
+                chunk(DiffChange::Added, "red"),
+                chunk(DiffChange::Equal, "fox jumped"),
+                chunk(DiffChange::Added, "high"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diffs_identical_and_empty_transcripts() {
+        assert_eq!(
+            diff("same words", "same  words"),
+            vec![chunk(DiffChange::Equal, "same words")]
+        );
+        assert_eq!(
+            diff("", "all new"),
+            vec![chunk(DiffChange::Added, "all new")]
+        );
+        assert!(diff("", "").is_empty());
+    }
+
+    #[test]
+    fn test_finds_shortest_edit() {
+        let old: Vec<&str> = "a b c a b b a".split(' ').collect();
+        let new: Vec<&str> = "c b a b a c".split(' ').collect();
+        let edits = myers_diff(&old, &new);
+
+        let changed = edits
+            .iter()
+            .filter(|(change, _)| *change != DiffChange::Equal)
+            .count();
+        assert_eq!(changed, 5);
+
+        let rebuilt: Vec<&str> = edits
+            .iter()
+            .filter(|(change, _)| *change != DiffChange::Removed)
+            .map(|(_, word)| *word)
+            .collect();
+        assert_eq!(rebuilt, new);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::recording::models::{SavedTranscriptVersion, Session};
+use crate::recording::session::search::index_transcript;
+use crate::recording::session::storage::{get_session, update_session};
+use crate::recording::session::versioning::commit_transcripts;
+use crate::recording::transcription::generate_preview;
+use crate::recording::transcription::text_processor::save_transcript;
+use crate::recording::utils::{
+    get_storage_dir, romanized_transcript_relative_path, structured_transcript_relative_path,
+    transcript_versions_relative_dir,
+};
+use chrono::Utc;
+use std::fs;
+use std::path::Path;
+
+/// Index of a session's saved versions, inside its versions folder
+const VERSIONS_FILE: &str = "versions.json";
+
+/// Keep a session's current transcript (and its segments) as the next
+/// numbered version before it is replaced
+///
+/// Returns the version number, or None when the session has no transcript.
+pub(crate) fn save_transcript_version(session: &Session) -> Result<Option<u32>, String> {
+    let storage_dir = get_storage_dir()?;
+    let transcript = storage_dir.join(&session.transcript_path);
+    if session.transcript_path.is_empty() || !transcript.is_file() {
+        return Ok(None);
+    }
+
+    let versions_dir = storage_dir.join(transcript_versions_relative_dir(&session.id));
+    fs::create_dir_all(&versions_dir)
+        .map_err(|e| format!("Failed to create versions folder: {}", e))?;
+
+    let mut versions = read_versions(&versions_dir);
+    let version = versions.last().map_or(1, |saved| saved.version + 1);
+    fs::copy(&transcript, versions_dir.join(version_file(version, "txt")))
+        .map_err(|e| format!("Failed to save transcript version: {}", e))?;
+
+    let segments = storage_dir.join(structured_transcript_relative_path(&session.id));
+    if segments.is_file() {
+        fs::copy(&segments, versions_dir.join(version_file(version, "json")))
+            .map_err(|e| format!("Failed to save transcript version: {}", e))?;
+    }
+
+    versions.push(SavedTranscriptVersion {
+        version,
+        saved_at: Utc::now().to_rfc3339(),
+        model_path: session.model_path.clone(),
+        language: session.language.clone(),
+        preview: session.preview.clone(),
+    });
+    write_versions(&versions_dir, &versions)?;
+
+    log::info!("Saved transcript of {} as version {}", session.id, version);
+    Ok(Some(version))
+}
+
+/// Forget a version saved for a transcription that then failed, leaving
+/// the transcript unchanged
+pub(crate) fn discard_transcript_version(session_id: &str, version: u32) {
+    let Ok(storage_dir) = get_storage_dir() else {
+        return;
+    };
+    let versions_dir = storage_dir.join(transcript_versions_relative_dir(session_id));
+
+    let mut versions = read_versions(&versions_dir);
+    versions.retain(|saved| saved.version != version);
+    if let Err(e) = write_versions(&versions_dir, &versions) {
+        log::warn!("Failed to discard transcript version: {}", e);
+    }
+    for extension in ["txt", "json"] {
+        let _ = fs::remove_file(versions_dir.join(version_file(version, extension)));
+    }
+}
+
+/// Earlier transcripts of a session, newest first
+pub fn list_transcript_versions(session_id: &str) -> Result<Vec<SavedTranscriptVersion>, String> {
+    let versions_dir = get_storage_dir()?.join(transcript_versions_relative_dir(session_id));
+    let mut versions = read_versions(&versions_dir);
+    versions.reverse();
+
+    Ok(versions)
+}
+
+/// Text of a saved version and the model that produced it
+pub(crate) fn load_transcript_version(
+    session_id: &str,
+    version: u32,
+) -> Result<(String, Option<String>), String> {
+    let versions_dir = get_storage_dir()?.join(transcript_versions_relative_dir(session_id));
+    let saved = read_versions(&versions_dir)
+        .into_iter()
+        .find(|saved| saved.version == version)
+        .ok_or_else(|| format!("Transcript version {} not found", version))?;
+    let text = fs::read_to_string(versions_dir.join(version_file(version, "txt")))
+        .map_err(|e| format!("Failed to read transcript version: {}", e))?;
+
+    Ok((text, saved.model_path))
+}
+
+/// Make a saved version the session's transcript again
+///
+/// The transcript it replaces is saved as a new version first, so restoring
+/// is never destructive. Returns the updated session.
+pub fn restore_transcript_version(session_id: &str, version: u32) -> Result<Session, String> {
+    let session = get_session(session_id)?;
+    let storage_dir = get_storage_dir()?;
+    let versions_dir = storage_dir.join(transcript_versions_relative_dir(session_id));
+
+    let saved = read_versions(&versions_dir)
+        .into_iter()
+        .find(|saved| saved.version == version)
+        .ok_or_else(|| format!("Transcript version {} not found", version))?;
+    let (text, _) = load_transcript_version(session_id, version)?;
+
+    save_transcript_version(&session)?;
+    let transcript_path = save_transcript(session_id, &text)?;
+    index_transcript(session_id, &transcript_path, &text);
+
+    // Segments and the romanized copy belong to the replaced transcript
+    let segments = storage_dir.join(structured_transcript_relative_path(session_id));
+    let saved_segments = versions_dir.join(version_file(version, "json"));
+    if saved_segments.is_file() {
+        fs::copy(&saved_segments, &segments)
+            .map_err(|e| format!("Failed to restore transcript segments: {}", e))?;
+    } else {
+        let _ = fs::remove_file(&segments);
+    }
+    let _ = fs::remove_file(storage_dir.join(romanized_transcript_relative_path(session_id)));
+
+    update_session(session_id, |session| {
+        session.transcript_path = transcript_path;
+        session.preview = generate_preview(&text);
+        session.model_path = saved.model_path;
+        session.language = saved.language;
+    })?;
+
+    if let Err(e) = commit_transcripts("Restore") {
+        log::warn!("Failed to commit transcript: {}", e);
+    }
+
+    log::info!(
+        "Restored version {} of {}'s transcript",
+        version,
+        session_id
+    );
+    get_session(session_id)
+}
+
+/// Paths (relative to the storage root) of every saved version file of a
+/// session
+pub(crate) fn transcript_version_files(session_id: &str) -> Vec<String> {
+    let relative_dir = transcript_versions_relative_dir(session_id);
+    let Ok(entries) = get_storage_dir()
+        .and_then(|dir| fs::read_dir(dir.join(&relative_dir)).map_err(|e| e.to_string()))
+    else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .map(|entry| format!("{}/{}", relative_dir, entry.file_name().to_string_lossy()))
+        .collect()
+}
+
+fn version_file(version: u32, extension: &str) -> String {
+    format!("v{}.{}", version, extension)
+}
+
+/// Saved versions listed in a versions folder, oldest first
+fn read_versions(versions_dir: &Path) -> Vec<SavedTranscriptVersion> {
+    fs::read_to_string(versions_dir.join(VERSIONS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_versions(versions_dir: &Path, versions: &[SavedTranscriptVersion]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(versions)
+        .map_err(|e| format!("Failed to serialize transcript versions: {}", e))?;
+    fs::write(versions_dir.join(VERSIONS_FILE), content)
+        .map_err(|e| format!("Failed to write transcript versions: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    fn saved(version: u32) -> SavedTranscriptVersion {
+        SavedTranscriptVersion {
+            version,
+            saved_at: "2024-11-02T15:30:00Z".to_string(),
+            model_path: Some("ggml-base.en.bin".to_string()),
+            language: None,
+            preview: format!("Version {}", version),
+        }
+    }
+
+    #[test]
+    fn test_versions_index_round_trips() {
+        let dir = fixtures::temp_dir("versions");
+        assert!(read_versions(&dir).is_empty());
+
+        write_versions(&dir, &[saved(1), saved(2)]).unwrap();
+        let versions = read_versions(&dir);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[1].version, 2);
+        assert_eq!(version_file(2, "txt"), "v2.txt");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::recording::config::load_config;
+use crate::recording::models::TranscriptVersion;
+use crate::recording::session::storage::session_transcript_relative_path;
+use crate::recording::utils::get_storage_dir;
+use chrono::DateTime;
+use git2::{
+    Commit, DiffFormat, DiffOptions, ErrorCode, IndexAddOption, Oid, Repository, Signature,
+    StatusOptions,
+};
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Files tracked in the transcript repository: the plain-text transcripts,
+/// not the segment files or Whisper.cpp logs next to them
+const TRACKED_FILES: &str = "*.txt";
+
+/// How often transcripts edited outside the app are committed
+const EDIT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Sessions named individually in a commit message before it just counts them
+const MAX_NAMED_SESSIONS: usize = 3;
+
+/// Serializes commits from transcription workers and the edit poller
+static REPO_LOCK: Mutex<()> = Mutex::new(());
+
+/// Commit changed transcripts if `storage.versionTranscripts` is on
+///
+/// `action` starts the commit message, followed by the names of the changed
+/// transcripts, i.e. the session ids unless a filename pattern is set
+/// (e.g. "Transcribe 2024-11-02_15-30-00"). Creates the repository on first
+/// use; does nothing when no transcript changed.
+pub fn commit_transcripts(action: &str) -> Result<(), String> {
+    if !load_config()?.storage.version_transcripts {
+        return Ok(());
+    }
+
+    let _guard = REPO_LOCK.lock().unwrap();
+    let repo = open_repository()?;
+
+    let changed = changed_session_ids(&repo)?;
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    let mut index = repo.index().map_err(git_error)?;
+    index
+        .add_all([TRACKED_FILES], IndexAddOption::DEFAULT, None)
+        .map_err(git_error)?;
+    // Stage deleted transcripts too
+    index.update_all([TRACKED_FILES], None).map_err(git_error)?;
+    index.write().map_err(git_error)?;
+    let tree = repo
+        .find_tree(index.write_tree().map_err(git_error)?)
+        .map_err(git_error)?;
+
+    let parent = head_commit(&repo)?;
+    let message = match parent {
+        Some(_) => commit_message(action, &changed),
+        None => format!("Start transcript history ({} transcripts)", changed.len()),
+    };
+    let signature = Signature::now("ThoughtCast", "thoughtcast@localhost").map_err(git_error)?;
+    let parents: Vec<&Commit> = parent.iter().collect();
+
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &parents,
+    )
+    .map_err(git_error)?;
+    log::info!("Committed transcripts: {}", message);
+
+    Ok(())
+}
+
+/// Commit transcripts edited outside the app (e.g. in a text editor) while
+/// versioning is on, checking every minute
+pub fn start_transcript_versioning() {
+    thread::spawn(|| loop {
+        if let Err(e) = commit_transcripts("Edit") {
+            log::warn!("Failed to commit transcript edits: {}", e);
+        }
+        thread::sleep(EDIT_POLL_INTERVAL);
+    });
+}
+
+/// Versions of a session's transcript, newest first
+///
+/// Empty when versioning was never enabled.
+pub fn get_transcript_history(session_id: &str) -> Result<Vec<TranscriptVersion>, String> {
+    let _guard = REPO_LOCK.lock().unwrap();
+    let Some(repo) = existing_repository()? else {
+        return Ok(Vec::new());
+    };
+    if head_commit(&repo)?.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let file = transcript_file(session_id);
+    let mut revwalk = repo.revwalk().map_err(git_error)?;
+    revwalk.push_head().map_err(git_error)?;
+
+    let mut versions = Vec::new();
+    for oid in revwalk {
+        let commit = repo
+            .find_commit(oid.map_err(git_error)?)
+            .map_err(git_error)?;
+        let current = blob_id(&commit, &file);
+        let previous = commit
+            .parent(0)
+            .ok()
+            .and_then(|parent| blob_id(&parent, &file));
+
+        if current != previous {
+            versions.push(TranscriptVersion {
+                commit_id: commit.id().to_string(),
+                message: commit.summary().unwrap_or_default().to_string(),
+                timestamp: DateTime::from_timestamp(commit.time().seconds(), 0)
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    Ok(versions)
+}
+
+/// Unified diff of a session's transcript introduced by the version
+/// `commit_id`
+pub fn get_transcript_diff(session_id: &str, commit_id: &str) -> Result<String, String> {
+    let _guard = REPO_LOCK.lock().unwrap();
+    let repo = existing_repository()?.ok_or("Transcript versioning has not been enabled")?;
+
+    let oid = Oid::from_str(commit_id).map_err(|e| format!("Invalid version id: {}", e))?;
+    let commit = repo.find_commit(oid).map_err(git_error)?;
+    let tree = commit.tree().map_err(git_error)?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(git_error)?),
+        Err(_) => None,
+    };
+
+    let mut options = DiffOptions::new();
+    options.pathspec(transcript_file(session_id));
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+        .map_err(git_error)?;
+
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .map_err(git_error)?;
+
+    Ok(patch)
+}
+
+/// Commit message naming the changed sessions, or counting them when many
+/// changed at once
+fn commit_message(action: &str, session_ids: &[String]) -> String {
+    if session_ids.len() <= MAX_NAMED_SESSIONS {
+        format!("{} {}", action, session_ids.join(", "))
+    } else {
+        format!("{} {} transcripts", action, session_ids.len())
+    }
+}
+
+/// Sessions whose transcript was added, edited or deleted since the last
+/// commit
+fn changed_session_ids(repo: &Repository) -> Result<Vec<String>, String> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).pathspec(TRACKED_FILES);
+    let statuses = repo.statuses(Some(&mut options)).map_err(git_error)?;
+
+    let mut ids: Vec<String> = statuses
+        .iter()
+        .filter_map(|entry| {
+            let id = entry.path()?.strip_suffix(".txt")?;
+            Some(id.trim_end_matches(".romanized").to_string())
+        })
+        .collect();
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+fn head_commit(repo: &Repository) -> Result<Option<Commit<'_>>, String> {
+    match repo.head() {
+        Ok(head) => head.peel_to_commit().map(Some).map_err(git_error),
+        Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => Ok(None),
+        Err(e) => Err(git_error(e)),
+    }
+}
+
+/// Id of `file`'s content as of `commit`, or None if it didn't exist
+fn blob_id(commit: &Commit, file: &str) -> Option<Oid> {
+    let tree = commit.tree().ok()?;
+    let entry = tree.get_path(Path::new(file)).ok()?;
+    Some(entry.id())
+}
+
+/// Transcript file name within the `text/` repository
+fn transcript_file(session_id: &str) -> String {
+    let relative_path = session_transcript_relative_path(session_id);
+    relative_path
+        .strip_prefix("text/")
+        .unwrap_or(&relative_path)
+        .to_string()
+}
+
+fn open_repository() -> Result<Repository, String> {
+    match existing_repository()? {
+        Some(repo) => Ok(repo),
+        None => Repository::init(get_storage_dir()?.join("text"))
+            .map_err(|e| format!("Failed to create transcript repository: {}", e)),
+    }
+}
+
+fn existing_repository() -> Result<Option<Repository>, String> {
+    match Repository::open(get_storage_dir()?.join("text")) {
+        Ok(repo) => Ok(Some(repo)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(git_error(e)),
+    }
+}
+
+fn git_error(e: git2::Error) -> String {
+    format!("Transcript repository error: {}", e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_message_names_few_sessions() {
+        let ids = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(commit_message("Edit", &ids), "Edit a, b");
+
+        let many: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+        assert_eq!(
+            commit_message("Transcribe", &many),
+            "Transcribe 5 transcripts"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::recording::audio::read_wav_samples;
+use crate::recording::models::{WaveformFormat, WaveformStyle};
+use crate::recording::session::storage::get_session;
+use crate::recording::utils::{export_relative_path, get_storage_dir};
+use std::fs;
+
+/// Bounds on the image size in pixels
+const MIN_IMAGE_SIZE: u32 = 16;
+const MAX_IMAGE_SIZE: u32 = 4096;
+
+/// Share of each bar's slot that is drawn; the rest is the gap between bars
+const BAR_FILL: f64 = 0.6;
+
+/// Render a session's waveform as an image for sharing or embedding
+///
+/// Draws the whole recording, or the snippet between `start_seconds` and
+/// `end_seconds`, and writes it to `exports/` in the storage directory.
+/// Returns the path of the image.
+pub fn export_waveform_image(session_id: &str, style: &WaveformStyle) -> Result<String, String> {
+    let session = get_session(session_id)?;
+    let storage_dir = get_storage_dir()?;
+
+    let (samples, samples_per_second) = read_wav_samples(&storage_dir.join(&session.audio_path))?;
+    let snippet = snippet(
+        &samples,
+        samples_per_second,
+        style.start_seconds,
+        style.end_seconds,
+    );
+    if snippet.is_empty() {
+        return Err("The selected range contains no audio".to_string());
+    }
+
+    let width = style.width.clamp(MIN_IMAGE_SIZE, MAX_IMAGE_SIZE);
+    let height = style.height.clamp(MIN_IMAGE_SIZE, MAX_IMAGE_SIZE);
+    let bars = style.bars.clamp(1, width / 2) as usize;
+    let color = parse_hex_color(&style.color)?;
+    let background = style
+        .background
+        .as_deref()
+        .map(parse_hex_color)
+        .transpose()?;
+
+    let peaks = waveform_peaks(snippet, bars);
+    let (contents, extension) = match style.format {
+        WaveformFormat::Png => (render_png(&peaks, width, height, color, background)?, "png"),
+        WaveformFormat::Svg => (
+            render_svg(&peaks, width, height, color, background).into_bytes(),
+            "svg",
+        ),
+    };
+
+    let path = storage_dir.join(export_relative_path(&format!(
+        "{}-waveform.{}",
+        session.id, extension
+    )));
+    if let Some(export_dir) = path.parent() {
+        fs::create_dir_all(export_dir)
+            .map_err(|e| format!("Failed to create export folder: {}", e))?;
+    }
+    fs::write(&path, contents).map_err(|e| format!("Failed to write waveform image: {}", e))?;
+
+    log::info!("Exported waveform of {} to {}", session.id, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Samples between the start and end times, clamped to the recording
+fn snippet(
+    samples: &[f32],
+    samples_per_second: usize,
+    start_seconds: Option<f64>,
+    end_seconds: Option<f64>,
+) -> &[f32] {
+    let index =
+        |seconds: f64| ((seconds.max(0.0) * samples_per_second as f64) as usize).min(samples.len());
+    let start = start_seconds.map_or(0, index);
+    let end = end_seconds.map_or(samples.len(), index);
+
+    samples.get(start..end).unwrap_or_default()
+}
+
+/// Peak level of each of `bars` equal slices of the audio, scaled so the
+/// loudest bar is 1
+fn waveform_peaks(samples: &[f32], bars: usize) -> Vec<f32> {
+    let slice_len = samples.len().div_ceil(bars).max(1);
+    let mut peaks: Vec<f32> = (0..bars)
+        .map(|bar| {
+            samples
+                .iter()
+                .skip(bar * slice_len)
+                .take(slice_len)
+                .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+        })
+        .collect();
+
+    let loudest = peaks.iter().cloned().fold(0.0f32, f32::max);
+    if loudest > 0.0 {
+        for peak in peaks.iter_mut() {
+            *peak /= loudest;
+        }
+    }
+
+    peaks
+}
+
+/// Position and size (x, y, width, height) of each bar, centred vertically
+///
+/// Silent stretches still get a thin line so the waveform reads as one shape.
+fn bar_rects(peaks: &[f32], width: u32, height: u32) -> Vec<(f64, f64, f64, f64)> {
+    let slot = width as f64 / peaks.len() as f64;
+    let bar_width = (slot * BAR_FILL).max(1.0);
+
+    peaks
+        .iter()
+        .enumerate()
+        .map(|(i, &peak)| {
+            let bar_height = (peak as f64 * height as f64).max(2.0);
+            let x = i as f64 * slot + (slot - bar_width) / 2.0;
+            let y = (height as f64 - bar_height) / 2.0;
+            (x, y, bar_width, bar_height)
+        })
+        .collect()
+}
+
+fn render_svg(
+    peaks: &[f32],
+    width: u32,
+    height: u32,
+    color: [u8; 3],
+    background: Option<[u8; 3]>,
+) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = width,
+        h = height
+    );
+    if let Some(background) = background {
+        svg.push_str(&format!(
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            hex_color(background)
+        ));
+    }
+
+    svg.push_str(&format!("<g fill=\"{}\">\n", hex_color(color)));
+    for (x, y, w, h) in bar_rects(peaks, width, height) {
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{:.1}\"/>\n",
+            x,
+            y,
+            w,
+            h,
+            w / 2.0
+        ));
+    }
+    svg.push_str("</g>\n</svg>\n");
+
+    svg
+}
+
+fn render_png(
+    peaks: &[f32],
+    width: u32,
+    height: u32,
+    color: [u8; 3],
+    background: Option<[u8; 3]>,
+) -> Result<Vec<u8>, String> {
+    let fill = |[r, g, b]: [u8; 3]| [r, g, b, 255];
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+    if let Some(background) = background {
+        for pixel in pixels.chunks_mut(4) {
+            pixel.copy_from_slice(&fill(background));
+        }
+    }
+
+    for (x, y, w, h) in bar_rects(peaks, width, height) {
+        let columns = x.round() as usize..((x + w).round() as usize).min(width as usize);
+        let rows = y.round() as usize..((y + h).round() as usize).min(height as usize);
+        for row in rows {
+            for column in columns.clone() {
+                let offset = (row * width as usize + column) * 4;
+                pixels[offset..offset + 4].copy_from_slice(&fill(color));
+            }
+        }
+    }
+
+    let mut image = Vec::new();
+    let mut encoder = png::Encoder::new(&mut image, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let encode_error = |e: png::EncodingError| format!("Failed to encode waveform image: {}", e);
+    let mut writer = encoder.write_header().map_err(encode_error)?;
+    writer.write_image_data(&pixels).map_err(encode_error)?;
+    writer.finish().map_err(encode_error)?;
+
+    Ok(image)
+}
+
+/// Parse a `#rrggbb` color
+fn parse_hex_color(color: &str) -> Result<[u8; 3], String> {
+    let invalid = || format!("Invalid color '{}', expected #rrggbb", color);
+    let hex = color.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn hex_color([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peaks_are_scaled_to_loudest_bar() {
+        let samples = [0.1, -0.2, 0.0, 0.0, -0.4, 0.1];
+
+        assert_eq!(waveform_peaks(&samples, 3), vec![0.5, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_snippet_is_clamped_to_recording() {
+        let samples = [0.0; 10];
+
+        assert_eq!(snippet(&samples, 2, Some(1.0), Some(2.0)).len(), 2);
+        assert_eq!(snippet(&samples, 2, Some(4.0), Some(60.0)).len(), 2);
+        assert!(snippet(&samples, 2, Some(3.0), Some(1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_renders_svg_and_png() {
+        let peaks = [0.5, 1.0];
+
+        let svg = render_svg(&peaks, 100, 50, [255, 0, 0], Some([0, 0, 0]));
+        assert_eq!(svg.matches("<rect ").count(), 3);
+        assert!(svg.contains("fill=\"#ff0000\""));
+
+        let png = render_png(&peaks, 100, 50, [255, 0, 0], None).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_parses_hex_colors() {
+        assert_eq!(parse_hex_color("#6366F1"), Ok([0x63, 0x66, 0xf1]));
+        assert!(parse_hex_color("red").is_err());
+        assert!(parse_hex_color("#fff").is_err());
+        assert!(parse_hex_color("#ggg000").is_err());
+    }
+}
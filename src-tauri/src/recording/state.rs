@@ -1,6 +1,14 @@
-use chrono::{DateTime, Utc};
+use crate::recording::audio::pre_roll::PreRollBuffer;
+use crate::recording::audio::AudioFormat;
+use crate::recording::models::{CalendarEvent, InputDeviceFallbackEvent};
+use crate::recording::session::auto_copy::format_duration;
+use crate::recording::timing::{Clock, RecordingTimer, SystemClock};
 use serde::Serialize;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
 
 /// Recording status representing the current state of the recording session
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -12,26 +20,231 @@ pub enum RecordingStatus {
     Processing,
 }
 
+impl RecordingStatus {
+    /// Whether the recording state machine allows moving to `next`
+    ///
+    /// Idle → Recording ⇄ Paused, then Processing once stopped (or straight
+    /// back to Idle when cancelled or discarded), and Idle again once the
+    /// recording is saved. A new recording can't start while Processing.
+    pub fn can_transition_to(self, next: RecordingStatus) -> bool {
+        use RecordingStatus::*;
+
+        matches!(
+            (self, next),
+            (Idle, Recording)
+                | (Recording, Paused)
+                | (Paused, Recording)
+                | (Recording | Paused, Processing)
+                | (Recording | Paused | Processing, Idle)
+        )
+    }
+}
+
+/// A status change the recording state machine does not allow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: RecordingStatus,
+    pub to: RecordingStatus,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.from, self.to) {
+            (RecordingStatus::Processing, RecordingStatus::Recording) => {
+                write!(f, "The previous recording is still being processed.")
+            }
+            (from, to) => write!(f, "Recording can't go from {:?} to {:?}.", from, to),
+        }
+    }
+}
+
+impl From<InvalidTransition> for String {
+    fn from(error: InvalidTransition) -> String {
+        error.to_string()
+    }
+}
+
+/// Event payload for a change of recording status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RecordingStatusChangedEvent {
+    pub status: RecordingStatus,
+    pub previous: RecordingStatus,
+}
+
+/// Response to starting a recording while one is already active
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AlreadyRecording {
+    /// Recording or Paused
+    pub status: RecordingStatus,
+    /// Recorded length so far, excluding pauses
+    pub elapsed_seconds: f64,
+}
+
+impl fmt::Display for AlreadyRecording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Recording is already in progress ({} recorded).",
+            format_duration(self.elapsed_seconds)
+        )
+    }
+}
+
+impl From<AlreadyRecording> for String {
+    fn from(error: AlreadyRecording) -> String {
+        error.to_string()
+    }
+}
+
+/// Called on every status change
+pub type StatusListener = Box<dyn Fn(RecordingStatusChangedEvent) + Send>;
+
+/// Called when a recording falls back from a busy input device
+pub type InputFallbackListener = Box<dyn Fn(InputDeviceFallbackEvent) + Send>;
+
+/// Runs a listener on its own thread, one event at a time in the order they
+/// were reported
+///
+/// Events are reported with the state locked; handing them over means the
+/// listener runs once the lock is released, so it may lock the state itself.
+struct Listener<E> {
+    events: Sender<E>,
+}
+
+impl<E: Send + 'static> Listener<E> {
+    fn new(listener: Box<dyn Fn(E) + Send>) -> Self {
+        let (events, received) = mpsc::channel();
+        // Ends once the listener is replaced and its sender dropped
+        thread::spawn(move || {
+            for event in received {
+                listener(event);
+            }
+        });
+        Listener { events }
+    }
+
+    fn notify(&self, event: E) {
+        let _ = self.events.send(event);
+    }
+}
+
 /// The state of an active recording session
 ///
 /// Manages the recording status, audio samples buffer, and timing information
 /// including support for pause/resume functionality
 pub struct RecordingState {
-    pub status: RecordingStatus,
+    /// Changed only through `transition`, so every change is checked and reported
+    status: RecordingStatus,
+    status_listener: Option<Listener<RecordingStatusChangedEvent>>,
+    input_fallback_listener: Option<Listener<InputDeviceFallbackEvent>>,
     pub samples: Arc<Mutex<Vec<f32>>>,
-    pub start_time: Option<DateTime<Utc>>,
-    pub pause_start_time: Option<DateTime<Utc>>,
-    pub total_paused_duration_ms: i64,
+    /// Sample rate and channels of `samples`, as delivered by the input stream
+    pub audio_format: AudioFormat,
+    /// Recorded length of the active recording, excluding pauses
+    pub timer: RecordingTimer,
+    /// Rolling buffer of idle microphone audio (present only while pre-roll is enabled)
+    pub pre_roll: Option<Arc<Mutex<PreRollBuffer>>>,
+    /// Calendar event the active recording was started for
+    pub meeting: Option<CalendarEvent>,
+    /// Last saved segment of an auto-segmented recording
+    pub previous_segment_id: Option<String>,
+    /// Input device the active recording is captured from
+    pub input_device: Option<String>,
+    /// When the input stream last delivered audio
+    pub last_input_at: Option<Instant>,
+    /// Start of the current run of digital silence (exact zeros) on the input
+    pub input_silent_since: Option<Instant>,
+    /// Whether the current pause was started by `auto_pause`
+    pub auto_paused: bool,
+    /// Signalled by the capture thread once its stream is closed and no more
+    /// samples will be added
+    pub capture_stopped: Option<Receiver<()>>,
+    /// Number of samples already written to the checkpoint file while paused
+    pub checkpointed_samples: usize,
 }
 
 impl RecordingState {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Recording state timed by the given clock (a manual one in tests)
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         RecordingState {
             status: RecordingStatus::Idle,
+            status_listener: None,
+            input_fallback_listener: None,
             samples: Arc::new(Mutex::new(Vec::new())),
-            start_time: None,
-            pause_start_time: None,
-            total_paused_duration_ms: 0,
+            audio_format: AudioFormat::default(),
+            timer: RecordingTimer::new(clock),
+            pre_roll: None,
+            meeting: None,
+            previous_segment_id: None,
+            input_device: None,
+            last_input_at: None,
+            input_silent_since: None,
+            auto_paused: false,
+            capture_stopped: None,
+            checkpointed_samples: 0,
+        }
+    }
+
+    pub fn status(&self) -> RecordingStatus {
+        self.status
+    }
+
+    /// Move to a new status, notifying the status listener
+    ///
+    /// Moving to the current status does nothing; any other change must be
+    /// allowed by `RecordingStatus::can_transition_to`.
+    pub fn transition(&mut self, status: RecordingStatus) -> Result<(), InvalidTransition> {
+        let previous = self.status;
+        if previous == status {
+            return Ok(());
+        }
+        if !previous.can_transition_to(status) {
+            return Err(InvalidTransition {
+                from: previous,
+                to: status,
+            });
+        }
+
+        self.status = status;
+        log::debug!("Recording status {:?} -> {:?}", previous, status);
+        if let Some(listener) = &self.status_listener {
+            listener.notify(RecordingStatusChangedEvent { status, previous });
+        }
+        Ok(())
+    }
+
+    /// Return to Idle once a stopped recording is done with, leaving any
+    /// other status alone
+    pub fn finish_processing(&mut self) {
+        if self.status == RecordingStatus::Processing {
+            let _ = self.transition(RecordingStatus::Idle);
+        }
+    }
+
+    /// Report every status change to `listener`
+    ///
+    /// The listener runs on its own thread, after the change, so it may lock
+    /// the state.
+    pub fn set_status_listener(&mut self, listener: StatusListener) {
+        self.status_listener = Some(Listener::new(listener));
+    }
+
+    /// Report recordings moved off a busy input device to `listener`
+    ///
+    /// Like the status listener, it runs on its own thread.
+    pub fn set_input_fallback_listener(&mut self, listener: InputFallbackListener) {
+        self.input_fallback_listener = Some(Listener::new(listener));
+    }
+
+    /// Notify the listener that the default input device is used instead of
+    /// the selected one
+    pub fn report_input_fallback(&self, event: InputDeviceFallbackEvent) {
+        if let Some(listener) = &self.input_fallback_listener {
+            listener.notify(event);
         }
     }
 
@@ -40,6 +253,33 @@ impl RecordingState {
         self.status == RecordingStatus::Recording
     }
 
+    /// Note a buffer delivered by the input stream, for input health checks
+    pub fn record_input(&mut self, data: &[f32]) {
+        let now = Instant::now();
+        self.last_input_at = Some(now);
+
+        if data.iter().all(|&sample| sample == 0.0) {
+            self.input_silent_since.get_or_insert(now);
+        } else {
+            self.input_silent_since = None;
+        }
+    }
+
+    /// Start input health checks afresh (e.g. when recording starts or resumes)
+    pub fn reset_input_health(&mut self) {
+        self.last_input_at = Some(Instant::now());
+        self.input_silent_since = None;
+        self.auto_paused = false;
+    }
+
+    /// The active recording, which a new one would have to take over from
+    pub fn already_recording(&self) -> Option<AlreadyRecording> {
+        self.is_active().then(|| AlreadyRecording {
+            status: self.status,
+            elapsed_seconds: self.timer.elapsed_seconds(),
+        })
+    }
+
     /// Check if recording session is active (recording or paused, but not idle or processing)
     pub fn is_active(&self) -> bool {
         matches!(
@@ -57,3 +297,111 @@ impl Default for RecordingState {
 
 /// Type alias for thread-safe shared recording state
 pub type SharedRecordingState = Arc<Mutex<RecordingState>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::timing::ManualClock;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reports_each_status_change_once() {
+        let (sender, events) = mpsc::channel();
+        let mut state = RecordingState::new();
+        state.set_status_listener(Box::new(move |event| {
+            let _ = sender.send(event);
+        }));
+
+        for status in [
+            RecordingStatus::Recording,
+            RecordingStatus::Paused,
+            RecordingStatus::Paused,
+            RecordingStatus::Processing,
+            RecordingStatus::Idle,
+        ] {
+            state.transition(status).unwrap();
+        }
+
+        let changes: Vec<(RecordingStatus, RecordingStatus)> = (0..4)
+            .map(|_| events.recv_timeout(Duration::from_secs(1)).unwrap())
+            .map(|event| (event.previous, event.status))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (RecordingStatus::Idle, RecordingStatus::Recording),
+                (RecordingStatus::Recording, RecordingStatus::Paused),
+                (RecordingStatus::Paused, RecordingStatus::Processing),
+                (RecordingStatus::Processing, RecordingStatus::Idle),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_listener_can_lock_the_state() {
+        let state: SharedRecordingState = Arc::new(Mutex::new(RecordingState::new()));
+        let (sender, statuses) = mpsc::channel();
+        let listened = Arc::clone(&state);
+        state
+            .lock()
+            .unwrap()
+            .set_status_listener(Box::new(move |_| {
+                let _ = sender.send(listened.lock().unwrap().status());
+            }));
+
+        state
+            .lock()
+            .unwrap()
+            .transition(RecordingStatus::Recording)
+            .unwrap();
+
+        let status = statuses.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(status, RecordingStatus::Recording);
+    }
+
+    #[test]
+    fn test_rejects_invalid_transitions() {
+        let mut state = RecordingState::new();
+        assert!(state.transition(RecordingStatus::Paused).is_err());
+        assert!(state.transition(RecordingStatus::Processing).is_err());
+
+        state.transition(RecordingStatus::Recording).unwrap();
+        state.transition(RecordingStatus::Processing).unwrap();
+        let error = state.transition(RecordingStatus::Recording).unwrap_err();
+        assert_eq!(
+            error,
+            InvalidTransition {
+                from: RecordingStatus::Processing,
+                to: RecordingStatus::Recording,
+            }
+        );
+        assert_eq!(state.status(), RecordingStatus::Processing);
+
+        state.finish_processing();
+        assert_eq!(state.status(), RecordingStatus::Idle);
+        state.transition(RecordingStatus::Recording).unwrap();
+        state.finish_processing();
+        assert!(state.is_recording());
+    }
+
+    #[test]
+    fn test_reports_the_recording_in_progress() {
+        let clock = Arc::new(ManualClock::new());
+        let mut state = RecordingState::with_clock(clock.clone());
+        assert!(state.already_recording().is_none());
+
+        state.transition(RecordingStatus::Recording).unwrap();
+        state.timer.start(chrono::Duration::zero());
+        clock.advance(83);
+        state.transition(RecordingStatus::Paused).unwrap();
+        state.timer.pause();
+
+        let active = state.already_recording().unwrap();
+        assert_eq!(active.status, RecordingStatus::Paused);
+        assert_eq!(active.elapsed_seconds, 83.0);
+        assert_eq!(
+            String::from(active),
+            "Recording is already in progress (1:23 recorded)."
+        );
+    }
+}
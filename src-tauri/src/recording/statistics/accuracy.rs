@@ -0,0 +1,172 @@
+use super::models::{ConfidenceThresholds, EstimateAccuracy, EstimateSample};
+use crate::recording::utils::get_storage_dir;
+use chrono::Utc;
+use std::fs;
+use std::sync::Mutex;
+
+/// File (in the storage dir) holding recent estimated vs. actual times
+const ESTIMATE_ACCURACY_FILE: &str = "estimate_accuracy.json";
+
+/// Number of samples kept on disk
+const MAX_ESTIMATE_SAMPLES: usize = 100;
+
+/// Samples needed before the observed error adjusts the confidence thresholds
+const MIN_SAMPLES_FOR_ADJUSTMENT: usize = 5;
+
+/// Error (in percent) at which the default thresholds are kept
+const TARGET_ERROR_PERCENT: f64 = 20.0;
+
+/// Bounds on how far the thresholds are scaled
+const MIN_THRESHOLD_SCALE: f64 = 0.5;
+const MAX_THRESHOLD_SCALE: f64 = 2.0;
+
+/// Serializes updates from parallel batch workers
+static ESTIMATE_ACCURACY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Load recorded estimate samples, oldest first
+///
+/// Returns an empty list if no estimated transcription has finished yet
+pub fn load_estimate_samples() -> Result<Vec<EstimateSample>, String> {
+    let path = get_storage_dir()?.join(ESTIMATE_ACCURACY_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read estimate accuracy file: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse estimate accuracy file: {}", e))
+}
+
+/// Record how long a transcription took against its estimate, keeping only
+/// the most recent samples
+pub fn record_estimate_accuracy(
+    audio_duration_seconds: f64,
+    estimated_seconds: f64,
+    actual_seconds: f64,
+) -> Result<(), String> {
+    let _guard = ESTIMATE_ACCURACY_LOCK.lock().unwrap();
+    let mut samples = load_estimate_samples().unwrap_or_default();
+    samples.push(EstimateSample {
+        timestamp: Utc::now().to_rfc3339(),
+        audio_duration_seconds,
+        estimated_seconds,
+        actual_seconds,
+    });
+
+    if samples.len() > MAX_ESTIMATE_SAMPLES {
+        let excess = samples.len() - MAX_ESTIMATE_SAMPLES;
+        samples.drain(..excess);
+    }
+
+    let content = serde_json::to_string_pretty(&samples)
+        .map_err(|e| format!("Failed to serialize estimate accuracy: {}", e))?;
+
+    fs::write(get_storage_dir()?.join(ESTIMATE_ACCURACY_FILE), content)
+        .map_err(|e| format!("Failed to write estimate accuracy file: {}", e))
+}
+
+/// Observed estimate error and the confidence thresholds it results in
+pub fn get_estimate_accuracy() -> Result<EstimateAccuracy, String> {
+    let samples = load_estimate_samples()?;
+    let thresholds = confidence_thresholds(&samples);
+
+    Ok(EstimateAccuracy {
+        samples: samples.len(),
+        mean_absolute_percentage_error: mean_absolute_percentage_error(&samples),
+        medium_confidence_after: thresholds.medium,
+        high_confidence_after: thresholds.high,
+    })
+}
+
+/// Confidence thresholds scaled by the observed error
+///
+/// Accurate estimates reach higher confidence with fewer data points;
+/// inaccurate ones need more. The defaults apply until enough samples exist.
+pub fn confidence_thresholds(samples: &[EstimateSample]) -> ConfidenceThresholds {
+    let defaults = ConfidenceThresholds::default();
+    if samples.len() < MIN_SAMPLES_FOR_ADJUSTMENT {
+        return defaults;
+    }
+    let Some(error) = mean_absolute_percentage_error(samples) else {
+        return defaults;
+    };
+
+    let scale = (error / TARGET_ERROR_PERCENT).clamp(MIN_THRESHOLD_SCALE, MAX_THRESHOLD_SCALE);
+    ConfidenceThresholds {
+        medium: (defaults.medium as f64 * scale).round() as usize,
+        high: (defaults.high as f64 * scale).round() as usize,
+    }
+}
+
+/// Mean of |actual - estimated| / actual, in percent
+fn mean_absolute_percentage_error(samples: &[EstimateSample]) -> Option<f64> {
+    let errors: Vec<f64> = samples
+        .iter()
+        .filter(|s| s.actual_seconds > 0.0)
+        .map(|s| (s.actual_seconds - s.estimated_seconds).abs() / s.actual_seconds * 100.0)
+        .collect();
+
+    if errors.is_empty() {
+        return None;
+    }
+
+    Some(errors.iter().sum::<f64>() / errors.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(count: usize, estimated: f64, actual: f64) -> Vec<EstimateSample> {
+        (0..count)
+            .map(|i| EstimateSample {
+                timestamp: format!("2024-11-08T15:{:02}:00Z", i),
+                audio_duration_seconds: 60.0,
+                estimated_seconds: estimated,
+                actual_seconds: actual,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mean_absolute_percentage_error() {
+        let mut recorded = samples(2, 9.0, 10.0);
+        recorded.push(EstimateSample {
+            estimated_seconds: 13.0,
+            ..recorded[0].clone()
+        });
+
+        // 10%, 10% and 30% off
+        let error = mean_absolute_percentage_error(&recorded).unwrap();
+        assert!((error - 50.0 / 3.0).abs() < 1e-9);
+        assert_eq!(mean_absolute_percentage_error(&[]), None);
+    }
+
+    #[test]
+    fn test_thresholds_follow_observed_error() {
+        let defaults = ConfidenceThresholds::default();
+        assert_eq!(confidence_thresholds(&samples(3, 1.0, 10.0)), defaults);
+
+        // 5% off: tightened to the lower bound
+        let tight = confidence_thresholds(&samples(10, 9.5, 10.0));
+        assert_eq!(
+            tight,
+            ConfidenceThresholds {
+                medium: 10,
+                high: 25
+            }
+        );
+
+        // 30% off: widened by 1.5x
+        let wide = confidence_thresholds(&samples(10, 13.0, 10.0));
+        assert_eq!(
+            wide,
+            ConfidenceThresholds {
+                medium: 30,
+                high: 75
+            }
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use super::models::BenchmarkResult;
+use crate::recording::utils::get_storage_dir;
+use std::fs;
+
+/// File (in the storage dir) holding recent benchmark results
+const BENCHMARKS_FILE: &str = "benchmarks.json";
+
+/// Number of benchmark results kept on disk
+const MAX_BENCHMARKS: usize = 50;
+
+/// Load recorded benchmark results, oldest first
+///
+/// Returns an empty list if no benchmark has been run yet
+pub fn load_benchmarks() -> Result<Vec<BenchmarkResult>, String> {
+    let path = get_storage_dir()?.join(BENCHMARKS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read benchmarks file: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse benchmarks file: {}", e))
+}
+
+/// Most recent benchmark result for the given model, if any
+pub fn latest_benchmark(model_path: &str) -> Option<BenchmarkResult> {
+    load_benchmarks()
+        .ok()?
+        .into_iter()
+        .rev()
+        .find(|b| b.model_path == model_path)
+}
+
+/// Append a benchmark result, keeping only the most recent entries
+pub fn record_benchmark(result: &BenchmarkResult) -> Result<(), String> {
+    let mut benchmarks = load_benchmarks().unwrap_or_default();
+    benchmarks.push(result.clone());
+
+    if benchmarks.len() > MAX_BENCHMARKS {
+        let excess = benchmarks.len() - MAX_BENCHMARKS;
+        benchmarks.drain(..excess);
+    }
+
+    let content = serde_json::to_string_pretty(&benchmarks)
+        .map_err(|e| format!("Failed to serialize benchmarks: {}", e))?;
+
+    fs::write(get_storage_dir()?.join(BENCHMARKS_FILE), content)
+        .map_err(|e| format!("Failed to write benchmarks file: {}", e))
+}
@@ -1,24 +1,38 @@
-use super::models::{EstimateConfidence, TranscriptionEstimate, TranscriptionStats};
+use super::models::{
+    BenchmarkResult, ConfidenceThresholds, EstimateConfidence, TranscriptionEstimate,
+    TranscriptionStats,
+};
 
 const MIN_STATS_FOR_ESTIMATE: usize = 10;
-const LOW_CONFIDENCE_THRESHOLD: usize = 20;
-const MEDIUM_CONFIDENCE_THRESHOLD: usize = 50;
+
+/// Most recent stats the overhead model is fitted to (the fit compares every
+/// pair of them)
+const MAX_STATS_FOR_FIT: usize = 200;
+
+/// Whether there is enough historical data for a history-based estimate
+pub fn has_sufficient_history(stats: &TranscriptionStats) -> bool {
+    stats.stats.len() >= MIN_STATS_FOR_ESTIMATE
+}
 
 /// Calculate transcription time estimate based on historical data
 ///
 /// Returns None if insufficient data is available (< 10 data points)
 ///
 /// Algorithm:
-/// 1. Calculate ratio (transcription_time / audio_duration) for each historical stat
-/// 2. Compute median ratio to avoid outlier influence
-/// 3. Estimate = audio_duration * median_ratio
-/// 4. Confidence level based on number of data points
+/// 1. Fit transcription_time = overhead + rate * audio_duration, since
+///    Whisper.cpp's startup makes short clips proportionally slower (see
+///    `fit_overhead_model`)
+/// 2. Without a usable fit (e.g. all clips about as long), use the median
+///    ratio (transcription_time / audio_duration) to avoid outlier influence:
+///    estimate = audio_duration * median_ratio
+/// 3. Confidence level based on number of data points, per `thresholds`
 pub fn estimate_transcription_time(
     stats: &TranscriptionStats,
     audio_duration_seconds: f64,
+    thresholds: &ConfidenceThresholds,
 ) -> Option<TranscriptionEstimate> {
     // Not enough data for reliable estimate
-    if stats.stats.len() < MIN_STATS_FOR_ESTIMATE {
+    if !has_sufficient_history(stats) {
         return None;
     }
 
@@ -35,22 +49,19 @@ pub fn estimate_transcription_time(
     }
 
     // Calculate median ratio (more robust than mean against outliers)
-    ratios.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let median_ratio = if ratios.len() % 2 == 0 {
-        let mid = ratios.len() / 2;
-        (ratios[mid - 1] + ratios[mid]) / 2.0
-    } else {
-        ratios[ratios.len() / 2]
-    };
+    let median_ratio = median(&mut ratios);
 
     // Calculate estimate
-    let estimated_seconds = audio_duration_seconds * median_ratio;
+    let estimated_seconds = match fit_overhead_model(stats) {
+        Some((overhead, rate)) => overhead + rate * audio_duration_seconds,
+        None => audio_duration_seconds * median_ratio,
+    };
 
     // Determine confidence based on data point count
     let confidence = match stats.stats.len() {
         n if n < MIN_STATS_FOR_ESTIMATE => EstimateConfidence::None,
-        n if n < LOW_CONFIDENCE_THRESHOLD => EstimateConfidence::Low,
-        n if n < MEDIUM_CONFIDENCE_THRESHOLD => EstimateConfidence::Medium,
+        n if n < thresholds.medium => EstimateConfidence::Low,
+        n if n < thresholds.high => EstimateConfidence::Medium,
         _ => EstimateConfidence::High,
     };
 
@@ -60,6 +71,82 @@ pub fn estimate_transcription_time(
     })
 }
 
+/// Startup overhead (seconds) and rate (seconds per audio second) of the
+/// recent transcriptions
+///
+/// Theil-Sen fit: the rate is the median slope between pairs of
+/// transcriptions of different length, the overhead the median remainder.
+/// Medians keep single slow runs from skewing it. Returns None when the
+/// clips are too alike in length to tell overhead from rate, or the fit
+/// makes no sense (no positive rate, negative overhead).
+fn fit_overhead_model(stats: &TranscriptionStats) -> Option<(f64, f64)> {
+    let mut points: Vec<(&str, f64, f64)> = stats
+        .stats
+        .iter()
+        .filter(|s| s.audio_duration_seconds > 0.0)
+        .map(|s| {
+            (
+                s.timestamp.as_str(),
+                s.audio_duration_seconds,
+                s.transcription_time_seconds,
+            )
+        })
+        .collect();
+    points.sort_by(|a, b| b.0.cmp(a.0));
+    points.truncate(MAX_STATS_FOR_FIT);
+
+    // Pairs less than 10% apart in length say little about the rate
+    let mut slopes: Vec<f64> = Vec::new();
+    for (i, &(_, d1, t1)) in points.iter().enumerate() {
+        for &(_, d2, t2) in &points[i + 1..] {
+            if (d2 - d1).abs() > 0.1 * d1.max(d2) {
+                slopes.push((t2 - t1) / (d2 - d1));
+            }
+        }
+    }
+    if slopes.len() < MIN_STATS_FOR_ESTIMATE {
+        return None;
+    }
+    let rate = median(&mut slopes);
+
+    let mut remainders: Vec<f64> = points.iter().map(|&(_, d, t)| t - rate * d).collect();
+    let overhead = median(&mut remainders);
+
+    (rate > 0.0 && overhead >= 0.0).then_some((overhead, rate))
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    let remainder = values.len() % 2;
+    if remainder == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Calculate transcription time estimate, falling back to a benchmark baseline
+///
+/// History-based estimates take precedence. Until enough transcriptions have
+/// accumulated (e.g. on a fresh install), the benchmark's realtime factor is
+/// used instead, reported with low confidence.
+pub fn estimate_with_baseline(
+    stats: &TranscriptionStats,
+    baseline: Option<&BenchmarkResult>,
+    audio_duration_seconds: f64,
+    thresholds: &ConfidenceThresholds,
+) -> Option<TranscriptionEstimate> {
+    estimate_transcription_time(stats, audio_duration_seconds, thresholds).or_else(|| {
+        baseline
+            .filter(|b| b.realtime_factor > 0.0)
+            .map(|b| TranscriptionEstimate {
+                estimated_seconds: audio_duration_seconds * b.realtime_factor,
+                confidence: EstimateConfidence::Low,
+            })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,7 +169,7 @@ mod tests {
     #[test]
     fn test_insufficient_data_returns_none() {
         let stats = create_test_stats(5, 0.15); // Only 5 data points
-        let estimate = estimate_transcription_time(&stats, 300.0);
+        let estimate = estimate_transcription_time(&stats, 300.0, &ConfidenceThresholds::default());
 
         assert!(estimate.is_none());
     }
@@ -90,7 +177,8 @@ mod tests {
     #[test]
     fn test_estimate_with_low_confidence() {
         let stats = create_test_stats(15, 0.15); // 15 data points
-        let estimate = estimate_transcription_time(&stats, 300.0).unwrap();
+        let estimate =
+            estimate_transcription_time(&stats, 300.0, &ConfidenceThresholds::default()).unwrap();
 
         // 300s * 0.15 = 45s expected
         assert!((estimate.estimated_seconds - 45.0).abs() < 0.1);
@@ -102,7 +190,8 @@ mod tests {
     #[test]
     fn test_estimate_with_medium_confidence() {
         let stats = create_test_stats(30, 0.2); // 30 data points
-        let estimate = estimate_transcription_time(&stats, 180.0).unwrap();
+        let estimate =
+            estimate_transcription_time(&stats, 180.0, &ConfidenceThresholds::default()).unwrap();
 
         // 180s * 0.2 = 36s expected
         assert!((estimate.estimated_seconds - 36.0).abs() < 0.1);
@@ -114,7 +203,8 @@ mod tests {
     #[test]
     fn test_estimate_with_high_confidence() {
         let stats = create_test_stats(60, 0.1); // 60 data points
-        let estimate = estimate_transcription_time(&stats, 600.0).unwrap();
+        let estimate =
+            estimate_transcription_time(&stats, 600.0, &ConfidenceThresholds::default()).unwrap();
 
         // 600s * 0.1 = 60s expected
         assert!((estimate.estimated_seconds - 60.0).abs() < 0.1);
@@ -123,6 +213,24 @@ mod tests {
         matches!(estimate.confidence, EstimateConfidence::High);
     }
 
+    #[test]
+    fn test_confidence_follows_thresholds() {
+        let stats = create_test_stats(30, 0.2);
+        let tight = ConfidenceThresholds {
+            medium: 10,
+            high: 25,
+        };
+        let estimate = estimate_transcription_time(&stats, 180.0, &tight).unwrap();
+        assert!(matches!(estimate.confidence, EstimateConfidence::High));
+
+        let wide = ConfidenceThresholds {
+            medium: 40,
+            high: 100,
+        };
+        let estimate = estimate_transcription_time(&stats, 180.0, &wide).unwrap();
+        assert!(matches!(estimate.confidence, EstimateConfidence::Low));
+    }
+
     #[test]
     fn test_median_handles_outliers() {
         let mut stats = TranscriptionStats::default();
@@ -145,7 +253,8 @@ mod tests {
             model_path: "/test/model.bin".to_string(),
         });
 
-        let estimate = estimate_transcription_time(&stats, 100.0).unwrap();
+        let estimate =
+            estimate_transcription_time(&stats, 100.0, &ConfidenceThresholds::default()).unwrap();
 
         // Should be close to 15s (median), not affected much by the outlier
         assert!((estimate.estimated_seconds - 15.0).abs() < 2.0);
@@ -173,10 +282,86 @@ mod tests {
             model_path: "/test/model.bin".to_string(),
         });
 
-        let estimate = estimate_transcription_time(&stats, 120.0).unwrap();
+        let estimate =
+            estimate_transcription_time(&stats, 120.0, &ConfidenceThresholds::default()).unwrap();
 
         // Should estimate based on ratio 9/60 = 0.15
         // 120 * 0.15 = 18s
         assert!((estimate.estimated_seconds - 18.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_startup_overhead_is_fitted() {
+        let mut stats = TranscriptionStats::default();
+
+        // 2s of startup plus 0.1s per audio second
+        for i in 0..20 {
+            let audio_duration = 10.0 * (i + 1) as f64;
+            stats.stats.push(TranscriptionStat {
+                audio_duration_seconds: audio_duration,
+                transcription_time_seconds: 2.0 + 0.1 * audio_duration,
+                timestamp: format!("2024-11-08T15:{:02}:00Z", i),
+                model_path: "/test/model.bin".to_string(),
+            });
+        }
+
+        let short = estimate_transcription_time(&stats, 5.0, &ConfidenceThresholds::default());
+        let long = estimate_transcription_time(&stats, 1000.0, &ConfidenceThresholds::default());
+
+        assert!((short.unwrap().estimated_seconds - 2.5).abs() < 0.01);
+        assert!((long.unwrap().estimated_seconds - 102.0).abs() < 0.01);
+    }
+
+    fn create_test_benchmark(realtime_factor: f64) -> BenchmarkResult {
+        BenchmarkResult {
+            timestamp: "2024-11-08T15:00:00Z".to_string(),
+            model_path: "/test/model.bin".to_string(),
+            sample_seconds: 30.0,
+            elapsed_seconds: 30.0 * realtime_factor,
+            realtime_factor,
+            backend: Some("cpu".to_string()),
+            args: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_baseline_used_without_history() {
+        let stats = create_test_stats(3, 0.15);
+        let benchmark = create_test_benchmark(0.25);
+        let estimate = estimate_with_baseline(
+            &stats,
+            Some(&benchmark),
+            120.0,
+            &ConfidenceThresholds::default(),
+        )
+        .unwrap();
+
+        // 120s * 0.25 = 30s expected
+        assert!((estimate.estimated_seconds - 30.0).abs() < 0.1);
+        assert!(matches!(estimate.confidence, EstimateConfidence::Low));
+    }
+
+    #[test]
+    fn test_history_takes_precedence_over_baseline() {
+        let stats = create_test_stats(15, 0.15);
+        let benchmark = create_test_benchmark(0.5);
+        let estimate = estimate_with_baseline(
+            &stats,
+            Some(&benchmark),
+            100.0,
+            &ConfidenceThresholds::default(),
+        )
+        .unwrap();
+
+        // Uses the historical 0.15 ratio, not the benchmark's 0.5
+        assert!((estimate.estimated_seconds - 15.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_no_history_and_no_baseline_returns_none() {
+        let stats = create_test_stats(3, 0.15);
+        assert!(
+            estimate_with_baseline(&stats, None, 100.0, &ConfidenceThresholds::default()).is_none()
+        );
+    }
 }
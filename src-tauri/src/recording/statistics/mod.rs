@@ -1,10 +1,26 @@
+mod accuracy;
+mod benchmarks;
 mod estimator;
+mod model_runs;
 mod models;
+mod throughput;
 
-pub use estimator::estimate_transcription_time;
-pub use models::{TranscriptionEstimate, TranscriptionStat, TranscriptionStats};
+pub use accuracy::{
+    confidence_thresholds, get_estimate_accuracy, load_estimate_samples, record_estimate_accuracy,
+};
+pub use benchmarks::{latest_benchmark, record_benchmark};
+pub use estimator::{estimate_with_baseline, has_sufficient_history};
+pub use model_runs::{get_model_stats, record_model_run};
+pub use models::{
+    BenchmarkResult, EstimateAccuracy, ModelStats, ThroughputSample, TranscriptionEstimate,
+    TranscriptionStat, TranscriptionStats,
+};
+pub use throughput::{get_throughput_history, record_throughput};
 
+use crate::recording::config::load_config;
 use crate::recording::models::Session;
+use crate::recording::session::load_sessions;
+use crate::recording::transcription::select_model_path;
 
 /// Extract transcription timing statistics from sessions
 ///
@@ -25,9 +41,10 @@ pub fn extract_transcription_stats(sessions: &[Session]) -> TranscriptionStats {
                 session.transcription_time_seconds,
                 &session.model_path,
             ) {
+                // Transcription time follows the speech, not the silence
                 (Some(transcription_time), Some(model_path)) => {
                     Some(TranscriptionStat {
-                        audio_duration_seconds: session.duration,
+                        audio_duration_seconds: session.speech_seconds.unwrap_or(session.duration),
                         transcription_time_seconds: transcription_time,
                         timestamp: session.timestamp.clone(),
                         model_path: model_path.clone(),
@@ -40,3 +57,35 @@ pub fn extract_transcription_stats(sessions: &[Session]) -> TranscriptionStats {
 
     TranscriptionStats { version: 1, stats }
 }
+
+/// Timing of every transcription with complete metadata, newest recording
+/// first, for plotting ratio trends
+pub fn get_transcription_stats() -> Result<TranscriptionStats, String> {
+    Ok(extract_transcription_stats(&load_sessions()?.sessions))
+}
+
+/// Estimated transcription time for a recording of the given length
+///
+/// The model is picked by the recording's length, as for transcription,
+/// while the time is estimated from its speech (when measured), since the
+/// history is fitted on speech. Based on past transcriptions, falling back
+/// to the selected model's benchmark until enough history exists.
+/// Confidence thresholds adapt to how accurate past estimates were.
+pub fn estimate_for_duration(
+    audio_duration_seconds: f64,
+    speech_seconds: Option<f64>,
+) -> Result<Option<TranscriptionEstimate>, String> {
+    let stats = extract_transcription_stats(&load_sessions()?.sessions);
+    let baseline = load_config().ok().and_then(|config| {
+        latest_benchmark(select_model_path(&config.transcription, audio_duration_seconds))
+    });
+
+    let thresholds = confidence_thresholds(&load_estimate_samples().unwrap_or_default());
+
+    Ok(estimate_with_baseline(
+        &stats,
+        baseline.as_ref(),
+        speech_seconds.unwrap_or(audio_duration_seconds),
+        &thresholds,
+    ))
+}
@@ -0,0 +1,126 @@
+use super::models::{ModelRunCounts, ModelStats};
+use crate::recording::utils::get_storage_dir;
+use chrono::Utc;
+use std::fs;
+use std::sync::Mutex;
+
+/// File (in the storage dir) holding per-model transcription counts
+const MODEL_RUNS_FILE: &str = "model_runs.json";
+
+/// Serializes updates from parallel batch workers
+static MODEL_RUNS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Load the per-model transcription counts
+///
+/// Returns an empty list if nothing has been transcribed yet
+pub fn load_model_runs() -> Result<Vec<ModelRunCounts>, String> {
+    let path = get_storage_dir()?.join(MODEL_RUNS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read model runs file: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse model runs file: {}", e))
+}
+
+/// Count a transcription with the given model
+///
+/// `outcome` is the transcript's mean segment confidence (if Whisper
+/// reported one) or the error the transcription failed with.
+pub fn record_model_run(
+    model_path: &str,
+    outcome: Result<Option<f64>, &str>,
+) -> Result<(), String> {
+    let _guard = MODEL_RUNS_LOCK.lock().unwrap();
+    let mut runs = load_model_runs().unwrap_or_default();
+
+    let index = match runs
+        .iter()
+        .position(|counts| counts.model_path == model_path)
+    {
+        Some(index) => index,
+        None => {
+            runs.push(ModelRunCounts {
+                model_path: model_path.to_string(),
+                ..Default::default()
+            });
+            runs.len() - 1
+        }
+    };
+    count_run(&mut runs[index], outcome);
+
+    let content = serde_json::to_string_pretty(&runs)
+        .map_err(|e| format!("Failed to serialize model runs: {}", e))?;
+
+    fs::write(get_storage_dir()?.join(MODEL_RUNS_FILE), content)
+        .map_err(|e| format!("Failed to write model runs file: {}", e))
+}
+
+/// Failure rate and average confidence of every model used, most used first
+pub fn get_model_stats() -> Result<Vec<ModelStats>, String> {
+    let mut stats: Vec<ModelStats> = load_model_runs()?.iter().map(model_stats).collect();
+    stats.sort_by_key(|stats| std::cmp::Reverse(stats.transcriptions));
+
+    Ok(stats)
+}
+
+fn count_run(counts: &mut ModelRunCounts, outcome: Result<Option<f64>, &str>) {
+    counts.transcriptions += 1;
+    match outcome {
+        Ok(Some(confidence)) => {
+            counts.confidence_total += confidence;
+            counts.confidence_samples += 1;
+        }
+        Ok(None) => {}
+        Err(error) => {
+            counts.failures += 1;
+            counts.last_error = Some(error.to_string());
+            counts.last_failure_at = Some(Utc::now().to_rfc3339());
+        }
+    }
+}
+
+fn model_stats(counts: &ModelRunCounts) -> ModelStats {
+    ModelStats {
+        model_path: counts.model_path.clone(),
+        transcriptions: counts.transcriptions,
+        failures: counts.failures,
+        failure_rate: if counts.transcriptions == 0 {
+            0.0
+        } else {
+            counts.failures as f64 / counts.transcriptions as f64
+        },
+        average_confidence: (counts.confidence_samples > 0)
+            .then(|| counts.confidence_total / counts.confidence_samples as f64),
+        last_error: counts.last_error.clone(),
+        last_failure_at: counts.last_failure_at.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_failures_and_confidence() {
+        let mut counts = ModelRunCounts::default();
+        count_run(&mut counts, Ok(Some(0.9)));
+        count_run(&mut counts, Ok(Some(0.5)));
+        count_run(&mut counts, Ok(None));
+        count_run(&mut counts, Err("Whisper.cpp failed"));
+
+        let stats = model_stats(&counts);
+        assert_eq!(stats.transcriptions, 4);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.failure_rate, 0.25);
+        assert!((stats.average_confidence.unwrap() - 0.7).abs() < 1e-9);
+        assert_eq!(stats.last_error.as_deref(), Some("Whisper.cpp failed"));
+        assert!(stats.last_failure_at.is_some());
+
+        let unused = model_stats(&ModelRunCounts::default());
+        assert_eq!(unused.failure_rate, 0.0);
+        assert_eq!(unused.average_confidence, None);
+    }
+}
@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 /// A single transcription timing measurement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionStat {
-    /// Duration of the audio file in seconds
+    /// Seconds of speech in the audio file (its duration for sessions
+    /// recorded before speech was measured)
     pub audio_duration_seconds: f64,
     /// Time taken to transcribe in seconds
     pub transcription_time_seconds: f64,
@@ -31,6 +32,126 @@ impl Default for TranscriptionStats {
     }
 }
 
+/// Result of running a synthetic sample through the configured model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// ISO 8601 timestamp of when the benchmark ran
+    pub timestamp: String,
+    /// Path to the Whisper model benchmarked
+    pub model_path: String,
+    /// Length of the synthetic sample in seconds
+    pub sample_seconds: f64,
+    /// Wall-clock time Whisper.cpp took in seconds
+    pub elapsed_seconds: f64,
+    /// Processing time / audio duration (same ratio the estimator uses)
+    pub realtime_factor: f64,
+    /// Compute backend reported by Whisper.cpp (e.g. "cuda", "metal", "cpu")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Acceleration flags passed to Whisper.cpp
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// A transcription's estimated time next to the time it actually took
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateSample {
+    /// ISO 8601 timestamp of when the transcription finished
+    pub timestamp: String,
+    /// Duration of the audio file in seconds
+    pub audio_duration_seconds: f64,
+    /// Time the estimator predicted in seconds
+    pub estimated_seconds: f64,
+    /// Time the transcription took in seconds
+    pub actual_seconds: f64,
+}
+
+/// How fast a transcription ran on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputSample {
+    /// ISO 8601 timestamp of when the transcription finished
+    pub timestamp: String,
+    /// Path to the Whisper model used
+    pub model_path: String,
+    /// Duration of the audio file in seconds
+    pub audio_duration_seconds: f64,
+    /// Time the transcription took in seconds
+    pub transcription_seconds: f64,
+    /// Processing time / audio duration (lower is faster)
+    pub realtime_factor: f64,
+}
+
+/// How far estimates have been off, and the confidence thresholds in use
+#[derive(Debug, Clone, Serialize)]
+pub struct EstimateAccuracy {
+    /// Number of recent transcriptions compared against their estimate
+    pub samples: usize,
+    /// Mean absolute percentage error of those estimates (e.g. 12.5 = 12.5%)
+    pub mean_absolute_percentage_error: Option<f64>,
+    /// Data points needed for medium confidence
+    pub medium_confidence_after: usize,
+    /// Data points needed for high confidence
+    pub high_confidence_after: usize,
+}
+
+/// Number of data points at which an estimate's confidence goes up a level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceThresholds {
+    /// Data points needed for medium confidence
+    pub medium: usize,
+    /// Data points needed for high confidence
+    pub high: usize,
+}
+
+impl Default for ConfidenceThresholds {
+    fn default() -> Self {
+        Self {
+            medium: 20,
+            high: 50,
+        }
+    }
+}
+
+/// Running counts of one model's transcriptions, as stored on disk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelRunCounts {
+    /// Path to the Whisper model
+    pub model_path: String,
+    /// Transcriptions attempted with the model, failed ones included
+    pub transcriptions: u32,
+    /// Transcriptions that failed
+    pub failures: u32,
+    /// Sum of the mean segment confidence of each successful transcription
+    pub confidence_total: f64,
+    /// Successful transcriptions that reported a confidence
+    pub confidence_samples: u32,
+    /// Error of the most recent failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// ISO 8601 timestamp of the most recent failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<String>,
+}
+
+/// How reliably a model transcribes, for comparing models
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelStats {
+    /// Path to the Whisper model
+    pub model_path: String,
+    /// Transcriptions attempted with the model, failed ones included
+    pub transcriptions: u32,
+    /// Transcriptions that failed
+    pub failures: u32,
+    /// Failures / transcriptions (0 to 1)
+    pub failure_rate: f64,
+    /// Mean segment confidence across successful transcriptions
+    pub average_confidence: Option<f64>,
+    /// Error of the most recent failure
+    pub last_error: Option<String>,
+    /// ISO 8601 timestamp of the most recent failure
+    pub last_failure_at: Option<String>,
+}
+
 /// Estimation result from historical data
 #[derive(Debug, Clone, Serialize)]
 pub struct TranscriptionEstimate {
@@ -79,6 +200,26 @@ mod tests {
         assert_eq!(stats.stats.len(), 0);
     }
 
+    #[test]
+    fn test_benchmark_result_round_trip() {
+        let result = BenchmarkResult {
+            timestamp: "2024-11-08T15:30:00Z".to_string(),
+            model_path: "/path/to/model.bin".to_string(),
+            sample_seconds: 10.0,
+            elapsed_seconds: 2.0,
+            realtime_factor: 0.2,
+            backend: Some("metal".to_string()),
+            args: vec!["-t".to_string(), "8".to_string()],
+        };
+
+        let json = serde_json::to_string(&result).unwrap();
+        let deserialized: BenchmarkResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.realtime_factor, 0.2);
+        assert_eq!(deserialized.backend, Some("metal".to_string()));
+        assert_eq!(deserialized.args, vec!["-t", "8"]);
+    }
+
     #[test]
     fn test_estimate_serialization() {
         let estimate = TranscriptionEstimate {
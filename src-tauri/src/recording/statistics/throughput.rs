@@ -0,0 +1,67 @@
+use super::models::ThroughputSample;
+use crate::recording::utils::get_local_state_dir;
+use chrono::Utc;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// File (in the local state dir, since speed depends on this machine's
+/// hardware) holding the speed of recent transcriptions
+const THROUGHPUT_FILE: &str = "throughput_history.json";
+
+/// Number of samples kept on disk
+const MAX_THROUGHPUT_SAMPLES: usize = 1000;
+
+/// Serializes updates from parallel batch workers
+static THROUGHPUT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Load the speed of recent transcriptions on this machine, oldest first
+///
+/// Returns an empty list if nothing has been transcribed yet
+pub fn get_throughput_history() -> Result<Vec<ThroughputSample>, String> {
+    let path = throughput_file_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read throughput history: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse throughput history: {}", e))
+}
+
+/// Record how fast a transcription ran, keeping only the most recent samples
+pub fn record_throughput(
+    model_path: &str,
+    audio_duration_seconds: f64,
+    transcription_seconds: f64,
+) -> Result<(), String> {
+    if audio_duration_seconds <= 0.0 {
+        return Ok(());
+    }
+
+    let _guard = THROUGHPUT_LOCK.lock().unwrap();
+    let mut samples = get_throughput_history().unwrap_or_default();
+    samples.push(ThroughputSample {
+        timestamp: Utc::now().to_rfc3339(),
+        model_path: model_path.to_string(),
+        audio_duration_seconds,
+        transcription_seconds,
+        realtime_factor: transcription_seconds / audio_duration_seconds,
+    });
+
+    if samples.len() > MAX_THROUGHPUT_SAMPLES {
+        let excess = samples.len() - MAX_THROUGHPUT_SAMPLES;
+        samples.drain(..excess);
+    }
+
+    let content = serde_json::to_string_pretty(&samples)
+        .map_err(|e| format!("Failed to serialize throughput history: {}", e))?;
+
+    fs::write(throughput_file_path()?, content)
+        .map_err(|e| format!("Failed to write throughput history: {}", e))
+}
+
+fn throughput_file_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(THROUGHPUT_FILE))
+}
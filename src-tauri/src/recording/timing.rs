@@ -0,0 +1,179 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::Mutex;
+
+/// Source of the current time, replaceable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system's wall clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to, for deterministic tests
+#[cfg(test)]
+pub struct ManualClock(Mutex<DateTime<Utc>>);
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock(Mutex::new(Utc::now()))
+    }
+
+    pub fn advance(&self, seconds: i64) {
+        *self.0.lock().unwrap() += Duration::seconds(seconds);
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// Pause-aware stopwatch for the active recording
+///
+/// Time spent paused, including a pause still in progress, doesn't count
+/// towards the recorded length.
+pub struct RecordingTimer {
+    clock: Arc<dyn Clock>,
+    started_at: Option<DateTime<Utc>>,
+    paused_at: Option<DateTime<Utc>>,
+    paused: Duration,
+}
+
+impl RecordingTimer {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        RecordingTimer {
+            clock,
+            started_at: None,
+            paused_at: None,
+            paused: Duration::zero(),
+        }
+    }
+
+    /// Start timing from zero, or from `head_start` for audio that was
+    /// captured before the recording started (pre-roll)
+    pub fn start(&mut self, head_start: Duration) {
+        self.started_at = Some(self.clock.now() - head_start);
+        self.paused_at = None;
+        self.paused = Duration::zero();
+    }
+
+    /// Stop counting time until `resume`
+    pub fn pause(&mut self) {
+        if self.started_at.is_some() && self.paused_at.is_none() {
+            self.paused_at = Some(self.clock.now());
+        }
+    }
+
+    /// Count time again after a pause
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused += self.clock.now() - paused_at;
+        }
+    }
+
+    /// Stop timing; the elapsed time goes back to zero
+    pub fn reset(&mut self) {
+        self.started_at = None;
+        self.paused_at = None;
+        self.paused = Duration::zero();
+    }
+
+    /// When timing started, including any head start (None if not started)
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.started_at
+    }
+
+    /// Length in seconds of the pause in progress, if paused
+    pub fn current_pause_seconds(&self) -> Option<f64> {
+        self.paused_at.map(|paused_at| {
+            (self.clock.now() - paused_at).num_milliseconds().max(0) as f64 / 1000.0
+        })
+    }
+
+    /// Recorded time in seconds, excluding pauses (0 if not started)
+    pub fn elapsed_seconds(&self) -> f64 {
+        let Some(started_at) = self.started_at else {
+            return 0.0;
+        };
+
+        let now = self.clock.now();
+        let current_pause = self
+            .paused_at
+            .map_or(Duration::zero(), |paused_at| now - paused_at);
+        let active = now - started_at - self.paused - current_pause;
+
+        active.num_milliseconds().max(0) as f64 / 1000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timer() -> (Arc<ManualClock>, RecordingTimer) {
+        let clock = Arc::new(ManualClock::new());
+        let timer = RecordingTimer::new(clock.clone());
+        (clock, timer)
+    }
+
+    #[test]
+    fn test_excludes_paused_time() {
+        let (clock, mut timer) = timer();
+        assert_eq!(timer.elapsed_seconds(), 0.0);
+
+        timer.start(Duration::zero());
+        clock.advance(10);
+        timer.pause();
+        clock.advance(5);
+        // The pause in progress doesn't count
+        assert_eq!(timer.elapsed_seconds(), 10.0);
+        assert_eq!(timer.current_pause_seconds(), Some(5.0));
+
+        timer.resume();
+        clock.advance(3);
+        assert_eq!(timer.elapsed_seconds(), 13.0);
+
+        // Pausing twice or resuming while running changes nothing
+        timer.pause();
+        timer.pause();
+        clock.advance(2);
+        timer.resume();
+        timer.resume();
+        assert_eq!(timer.elapsed_seconds(), 13.0);
+    }
+
+    #[test]
+    fn test_head_start_and_reset() {
+        let (clock, mut timer) = timer();
+
+        timer.start(Duration::seconds(2));
+        clock.advance(4);
+        assert_eq!(timer.elapsed_seconds(), 6.0);
+
+        // Restarting (e.g. for the next segment) forgets earlier pauses
+        timer.pause();
+        clock.advance(4);
+        timer.start(Duration::zero());
+        clock.advance(1);
+        assert_eq!(timer.elapsed_seconds(), 1.0);
+
+        timer.reset();
+        clock.advance(1);
+        assert_eq!(timer.elapsed_seconds(), 0.0);
+
+        // Pausing before starting doesn't start the timer
+        timer.pause();
+        assert_eq!(timer.elapsed_seconds(), 0.0);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::recording::audio::{write_wav_file, AudioFormat, WAV_SAMPLE_RATE};
+use crate::recording::config::load_config;
+use crate::recording::session::load_sessions;
+use crate::recording::session::recovery::is_transcribing;
+use crate::recording::statistics::{
+    extract_transcription_stats, has_sufficient_history, latest_benchmark, record_benchmark,
+    BenchmarkResult,
+};
+use crate::recording::transcription::download::is_downloading;
+use crate::recording::transcription::engine::{
+    execute_whisper, validate_whisper_setup, whisper_args,
+};
+use chrono::Utc;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Default length of the synthetic benchmark sample in seconds
+///
+/// Whisper processes audio in 30 second windows, so shorter samples cost a
+/// full window and overstate the realtime factor used for estimates
+pub const DEFAULT_BENCHMARK_SECONDS: f64 = 30.0;
+
+/// Allowed benchmark sample length range in seconds
+const MIN_BENCHMARK_SECONDS: f64 = 1.0;
+const MAX_BENCHMARK_SECONDS: f64 = 120.0;
+
+/// Held while a benchmark runs, so two runs don't skew each other's timing
+static BENCHMARK_LOCK: Mutex<()> = Mutex::new(());
+
+/// Numbers the sample files, so no two runs share one
+static SAMPLE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Benchmark the configured Whisper model on a synthetic sample
+///
+/// Generates a speech-like test signal, transcribes it with the current
+/// configuration (including acceleration flags), records the result and
+/// returns the achieved speed together with the compute backend Whisper.cpp
+/// reported, so users can verify GPU acceleration is active.
+pub fn benchmark_model(sample_seconds: f64) -> Result<BenchmarkResult, String> {
+    let Ok(_running) = BENCHMARK_LOCK.try_lock() else {
+        return Err("A benchmark is already running.".to_string());
+    };
+
+    let config = load_config()?.transcription;
+    validate_whisper_setup(&config)?;
+
+    let sample_seconds = sample_seconds.clamp(MIN_BENCHMARK_SECONDS, MAX_BENCHMARK_SECONDS);
+    let sample_path = std::env::temp_dir().join(format!(
+        "thoughtcast-benchmark-{}-{}.wav",
+        std::process::id(),
+        SAMPLE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let sample = synthetic_sample(sample_seconds);
+    write_wav_file(&sample, AudioFormat::default(), &sample_path)?;
+
+    let start = Instant::now();
+    let output = execute_whisper(&sample_path, &config);
+    let elapsed_seconds = start.elapsed().as_secs_f64();
+
+    let _ = fs::remove_file(&sample_path);
+    let _ = fs::remove_file(sample_path.with_extension("wav.txt"));
+    let _ = fs::remove_file(sample_path.with_extension("wav.json"));
+
+    let output = output?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(format!("Whisper benchmark failed: {}", stderr));
+    }
+
+    let result = BenchmarkResult {
+        timestamp: Utc::now().to_rfc3339(),
+        model_path: config.model_path.clone(),
+        sample_seconds,
+        elapsed_seconds,
+        realtime_factor: elapsed_seconds / sample_seconds,
+        backend: detect_backend(&stderr),
+        args: whisper_args(&config),
+    };
+
+    if let Err(e) = record_benchmark(&result) {
+        log::warn!("Failed to record benchmark: {}", e);
+    }
+
+    Ok(result)
+}
+
+/// Run a calibration benchmark if the configured model has no baseline yet
+///
+/// On a fresh install there is no transcription history to estimate from, so
+/// the benchmark result seeds the estimator until enough sessions accumulate.
+/// Only runs when asked (e.g. by the setup wizard once the user agrees), and
+/// not while a transcription or model download would compete with it.
+/// Returns None when a baseline or sufficient history already exists.
+pub fn ensure_benchmark_baseline() -> Result<Option<BenchmarkResult>, String> {
+    if is_transcribing() || is_downloading() {
+        return Err("Transcription is busy; calibrate again once it finishes.".to_string());
+    }
+
+    let config = load_config()?;
+    if latest_benchmark(&config.transcription.model_path).is_some() {
+        return Ok(None);
+    }
+
+    let session_index = load_sessions()?;
+    if has_sufficient_history(&extract_transcription_stats(&session_index.sessions)) {
+        return Ok(None);
+    }
+
+    benchmark_model(DEFAULT_BENCHMARK_SECONDS).map(Some)
+}
+
+/// Generate a speech-like test signal
+///
+/// Harmonics of a varying fundamental, amplitude-modulated at syllable rate,
+/// so Whisper runs its full encoder/decoder path rather than skipping silence
+fn synthetic_sample(seconds: f64) -> Vec<f32> {
+    let sample_rate = WAV_SAMPLE_RATE as f32;
+    let total_samples = (seconds * WAV_SAMPLE_RATE as f64) as usize;
+
+    (0..total_samples)
+        .map(|i| {
+            let t = i as f32 / sample_rate;
+            let fundamental = 140.0 + 30.0 * (t * 0.7 * std::f32::consts::TAU).sin();
+            let envelope = 0.5 + 0.5 * (t * 4.0 * std::f32::consts::TAU).sin();
+            let voice: f32 = (1..=4)
+                .map(|harmonic| {
+                    let h = harmonic as f32;
+                    (t * fundamental * h * std::f32::consts::TAU).sin() / h
+                })
+                .sum();
+            0.2 * envelope * voice
+        })
+        .collect()
+}
+
+/// Detect the compute backend from Whisper.cpp's log output
+fn detect_backend(stderr: &str) -> Option<String> {
+    let log = stderr.to_lowercase();
+
+    if log.contains("no gpu found") {
+        return Some("cpu".to_string());
+    }
+
+    ["cuda", "metal", "vulkan", "openvino", "coreml", "sycl", "hipblas"]
+        .iter()
+        .find(|backend| log.contains(*backend))
+        .map(|backend| backend.to_string())
+        .or_else(|| log.contains("system_info").then(|| "cpu".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_sample_length_and_range() {
+        let samples = synthetic_sample(2.0);
+        assert_eq!(samples.len(), 2 * WAV_SAMPLE_RATE as usize);
+        assert!(samples.iter().all(|s| s.abs() <= 1.0));
+        assert!(samples.iter().any(|s| s.abs() > 0.05));
+    }
+
+    #[test]
+    fn test_detect_gpu_backends() {
+        assert_eq!(
+            detect_backend("whisper_backend_init_gpu: using CUDA backend"),
+            Some("cuda".to_string())
+        );
+        assert_eq!(
+            detect_backend("ggml_metal_init: found device: Apple M2"),
+            Some("metal".to_string())
+        );
+    }
+
+    #[test]
+    fn test_detect_cpu_backend() {
+        assert_eq!(
+            detect_backend("whisper_backend_init_gpu: no GPU found"),
+            Some("cpu".to_string())
+        );
+        assert_eq!(
+            detect_backend("system_info: n_threads = 4 | AVX = 1"),
+            Some("cpu".to_string())
+        );
+        assert_eq!(detect_backend(""), None);
+    }
+}
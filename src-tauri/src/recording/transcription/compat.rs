@@ -0,0 +1,178 @@
+use crate::recording::models::{TranscriptionConfig, WhisperBinaryInfo};
+use crate::recording::transcription::engine::whisper_args;
+use crate::recording::utils::get_local_state_dir;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// File in the local state directory recording the last binary check
+const BINARY_INFO_FILE: &str = "whisper-binary.json";
+
+/// Flags passed to Whisper.cpp on every run (see `execute_whisper`)
+const ALWAYS_PASSED_FLAGS: &[&str] = &["-m", "-f", "-otxt", "-ojf"];
+
+/// Detect the Whisper.cpp binary's version and check that it accepts the
+/// flags the config passes to it
+///
+/// The result is saved (see `load_whisper_binary_info`) and unsupported
+/// flags are logged, so upgrading Whisper.cpp can't silently break runs.
+pub fn check_whisper_compatibility(
+    config: &TranscriptionConfig,
+) -> Result<WhisperBinaryInfo, String> {
+    let help = Command::new(&config.whisper_path)
+        .arg("--help")
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", config.whisper_path, e))?;
+    let version = Command::new(&config.whisper_path)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_version(&output_text(&output)));
+
+    let help = output_text(&help);
+    let supported = supported_flags(&help);
+    let unsupported_flags = if supported.is_empty() {
+        log::warn!(
+            "{} --help listed no flags; skipping the compatibility check",
+            config.whisper_path
+        );
+        Vec::new()
+    } else {
+        configured_flags(config)
+            .into_iter()
+            .filter(|flag| !supported.contains(flag.as_str()))
+            .collect()
+    };
+
+    if !unsupported_flags.is_empty() {
+        log::warn!(
+            "Whisper.cpp at {} doesn't support {}; transcriptions may fail",
+            config.whisper_path,
+            unsupported_flags.join(", ")
+        );
+    }
+
+    let info = WhisperBinaryInfo {
+        whisper_path: config.whisper_path.clone(),
+        version,
+        unsupported_flags,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = save_whisper_binary_info(&info) {
+        log::warn!("{}", e);
+    }
+
+    Ok(info)
+}
+
+/// Result of the last binary check, if any
+pub fn load_whisper_binary_info() -> Option<WhisperBinaryInfo> {
+    let content = fs::read_to_string(binary_info_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_whisper_binary_info(info: &WhisperBinaryInfo) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(info)
+        .map_err(|e| format!("Failed to serialize Whisper.cpp info: {}", e))?;
+    fs::write(binary_info_path()?, content)
+        .map_err(|e| format!("Failed to save Whisper.cpp info: {}", e))
+}
+
+fn binary_info_path() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(BINARY_INFO_FILE))
+}
+
+/// Whisper.cpp prints its help to stderr, some builds to stdout
+fn output_text(output: &Output) -> String {
+    format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
+/// Flags the config makes Whisper.cpp run with, values left out
+fn configured_flags(config: &TranscriptionConfig) -> Vec<String> {
+    let mut flags: Vec<String> = ALWAYS_PASSED_FLAGS.iter().map(|f| f.to_string()).collect();
+    for arg in whisper_args(config) {
+        let is_flag = arg.starts_with('-') && !arg[1..].starts_with(|c: char| c.is_ascii_digit());
+        if is_flag && !flags.contains(&arg) {
+            flags.push(arg);
+        }
+    }
+    flags
+}
+
+/// Flags listed in `--help` output, e.g. `-fa` and `--flash-attn` from
+/// `  -fa,       --flash-attn      [false  ] flash attention`
+fn supported_flags(help: &str) -> HashSet<&str> {
+    help.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| token.starts_with('-') && token.len() > 1)
+        .map(|token| token.split('=').next().unwrap_or(token))
+        .collect()
+}
+
+/// First `x.y.z` version number in the text, e.g. `1.7.4` from
+/// `whisper.cpp version: v1.7.4`
+fn parse_version(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| {
+            let parts: Vec<&str> = token.split('.').collect();
+            parts.len() >= 3
+                && parts
+                    .iter()
+                    .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELP: &str = "\
+usage: whisper-cli [options] file0 file1 ...
+
+options:
+  -h,        --help              [default] show this help message and exit
+  -t N,      --threads N         [4      ] number of threads to use during computation
+  -l LANG,   --language LANG     [en     ] spoken language ('auto' for auto-detect)
+  -m FNAME,  --model FNAME       [models/ggml-base.en.bin] model path
+  -f FNAME,  --file FNAME        [       ] input audio file path
+  -otxt,     --output-txt        [false  ] output result in a text file
+  -ojf,      --output-json-full  [false  ] include more information in the JSON file
+  -ng,       --no-gpu            [false  ] disable GPU
+";
+
+    #[test]
+    fn test_finds_unsupported_flags() {
+        let mut config: TranscriptionConfig = serde_json::from_value(serde_json::json!({
+            "whisperPath": "/usr/bin/whisper",
+            "modelPath": "/models/base.bin",
+            "language": "de",
+        }))
+        .unwrap();
+        config.acceleration.flash_attention = true;
+        config.acceleration.threads = Some(2);
+
+        let supported = supported_flags(HELP);
+        let unsupported: Vec<String> = configured_flags(&config)
+            .into_iter()
+            .filter(|flag| !supported.contains(flag.as_str()))
+            .collect();
+
+        assert_eq!(unsupported, vec!["-fa"]);
+    }
+
+    #[test]
+    fn test_parses_version() {
+        assert_eq!(
+            parse_version("whisper.cpp version: v1.7.4 (abc123)"),
+            Some("1.7.4".to_string())
+        );
+        assert_eq!(parse_version("usage: whisper-cli [0.20] 1.5"), None);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::recording::config::{load_base_config, save_local_overrides};
+use crate::recording::models::TranscriptionConfig;
+use crate::recording::transcription::integrity::verify_model_file;
+use crate::recording::utils::{get_local_state_dir, http_request};
+use serde_json::{json, Map, Value};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Where Whisper.cpp's ggml models are published
+const MODEL_DOWNLOAD_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// Folder in the local state directory holding downloaded models
+const MODELS_DIR: &str = "models";
+
+/// Number of model downloads in progress
+static ACTIVE_DOWNLOADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a download as active until dropped
+struct ActiveDownload;
+
+impl ActiveDownload {
+    fn start() -> Self {
+        ACTIVE_DOWNLOADS.fetch_add(1, Ordering::SeqCst);
+        ActiveDownload
+    }
+}
+
+impl Drop for ActiveDownload {
+    fn drop(&mut self) {
+        ACTIVE_DOWNLOADS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether a model is being downloaded
+pub(crate) fn is_downloading() -> bool {
+    ACTIVE_DOWNLOADS.load(Ordering::SeqCst) > 0
+}
+
+/// Folder for downloaded models, outside the (possibly synced) storage
+/// folder since models are large and their paths machine-specific
+pub fn models_dir() -> Result<PathBuf, String> {
+    Ok(get_local_state_dir()?.join(MODELS_DIR))
+}
+
+/// Download the model the config points to if it is missing and
+/// `autoDownloadModels` is on, returning the config to transcribe with
+pub fn ensure_model(config: TranscriptionConfig) -> Result<TranscriptionConfig, String> {
+    if !config.auto_download_models || Path::new(&config.model_path).exists() {
+        return Ok(config);
+    }
+
+    let model_path = download_model(&config.model_path)?;
+    Ok(TranscriptionConfig {
+        model_path: model_path.to_string_lossy().to_string(),
+        ..config
+    })
+}
+
+/// Download a missing model into `models_dir` and point the config at it
+///
+/// `model_path` is the configured path; its file name (e.g.
+/// `ggml-base.en.bin`) selects the model. Every setting using that path is
+/// updated in this machine's config.local.json. Returns the new path.
+pub fn download_model(model_path: &str) -> Result<PathBuf, String> {
+    let target = download_named_model(&model_file_name(model_path)?)?;
+
+    let new_path = target.to_string_lossy().to_string();
+    if let Some(overrides) = repointed_settings(model_path, &new_path)? {
+        save_local_overrides(overrides)?;
+    }
+
+    Ok(target)
+}
+
+/// Download a model by file name (e.g. `ggml-tiny.en.bin`) into
+/// `models_dir`, unless already there, returning its path
+pub(crate) fn download_named_model(name: &str) -> Result<PathBuf, String> {
+    let target = models_dir()?.join(name);
+
+    if !target.exists() {
+        log::info!("Downloading Whisper model {}", name);
+        fetch_model(name, &target)?;
+        log::info!("Downloaded Whisper model to {}", target.display());
+    }
+
+    Ok(target)
+}
+
+/// File name of a model that can be downloaded, e.g. `ggml-base.en.bin`
+fn model_file_name(model_path: &str) -> Result<String, String> {
+    let name = Path::new(model_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if name.starts_with("ggml-") && name.ends_with(".bin") {
+        Ok(name)
+    } else {
+        Err(format!(
+            "Can't download '{}': only Whisper.cpp's ggml-*.bin models are available",
+            model_path
+        ))
+    }
+}
+
+/// Stream a model to a partial file, renamed once complete and verified
+fn fetch_model(name: &str, target: &Path) -> Result<(), String> {
+    let _active = ActiveDownload::start();
+    let url = format!("{}/{}", MODEL_DOWNLOAD_URL, name);
+    let response = match http_request("Model download", "GET", &url)?.call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(404, _)) => return Err(format!("Unknown Whisper model: {}", name)),
+        Err(ureq::Error::Status(status, _)) => {
+            return Err(format!("Model download failed ({})", status))
+        }
+        Err(e) => return Err(format!("Failed to download model: {}", e)),
+    };
+
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create models folder: {}", e))?;
+    }
+    let partial = target.with_extension("bin.part");
+    let written = File::create(&partial)
+        .and_then(|mut file| io::copy(&mut response.into_reader(), &mut file));
+    let verified = written
+        .map_err(|e| format!("Failed to download model: {}", e))
+        .and_then(|_| verify_model_file(&partial, name));
+    if let Err(e) = verified {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+
+    fs::rename(&partial, target).map_err(|e| format!("Failed to save model: {}", e))
+}
+
+/// Local config overrides replacing `old_path` with `new_path` in the model
+/// settings (default, per-length rules, draft and profiles); None when no
+/// setting uses it
+fn repointed_settings(old_path: &str, new_path: &str) -> Result<Option<Value>, String> {
+    let config = load_base_config()?;
+    let transcription = &config.transcription;
+    let mut overrides = Map::new();
+
+    if transcription.model_path == old_path {
+        overrides.insert("modelPath".to_string(), json!(new_path));
+    }
+    if transcription
+        .model_rules
+        .iter()
+        .any(|rule| rule.model_path == old_path)
+    {
+        // Arrays in a local layer replace the shared value, so all rules go in
+        let mut rules = transcription.model_rules.clone();
+        for rule in rules.iter_mut().filter(|rule| rule.model_path == old_path) {
+            rule.model_path = new_path.to_string();
+        }
+        overrides.insert("modelRules".to_string(), json!(rules));
+    }
+    if transcription.draft_model_path.as_deref() == Some(old_path) {
+        overrides.insert("draftModelPath".to_string(), json!(new_path));
+    }
+
+    let profiles: Map<String, Value> = config
+        .profiles
+        .iter()
+        .filter(|(_, profile)| profile.model_path.as_deref() == Some(old_path))
+        .map(|(name, _)| (name.clone(), json!({ "modelPath": new_path })))
+        .collect();
+
+    let mut settings = Map::new();
+    if !overrides.is_empty() {
+        settings.insert("transcription".to_string(), Value::Object(overrides));
+    }
+    if !profiles.is_empty() {
+        settings.insert("profiles".to_string(), Value::Object(profiles));
+    }
+
+    Ok((!settings.is_empty()).then_some(Value::Object(settings)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_file_name() {
+        assert_eq!(
+            model_file_name("/missing/models/ggml-base.en.bin"),
+            Ok("ggml-base.en.bin".to_string())
+        );
+        assert!(model_file_name("/models/custom-finetune.gguf").is_err());
+        assert!(model_file_name("").is_err());
+    }
+}
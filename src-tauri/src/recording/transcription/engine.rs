@@ -1,52 +1,267 @@
+use crate::recording::audio::wav_duration;
 use crate::recording::config::load_config;
-use crate::recording::transcription::text_processor::{clean_transcript, save_transcript};
+use crate::recording::models::{
+    AccelerationConfig, DecodingConfig, PostprocessStep, StructuredTranscript, TranscriptionConfig,
+};
+use crate::recording::postprocess::{pipeline, process_text};
+use crate::recording::session::tasks::transcription_cancelled;
+use crate::recording::statistics::record_model_run;
+use crate::recording::transcription::download::ensure_model;
+use crate::recording::transcription::integrity::verify_model;
+use crate::recording::transcription::priority;
+use crate::recording::transcription::run_log::save_transcription_log;
+use crate::recording::transcription::segments::{
+    load_structured_transcript, mean_confidence, parse_whisper_json, save_structured_transcript,
+};
+use crate::recording::transcription::text_processor::{save_romanized_transcript, save_transcript};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
-use std::thread;
-use std::time::Duration;
+use std::process::{Child, Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Folder in the temp directory where standalone files are transcribed
+const STANDALONE_TEMP_DIR: &str = "thoughtcast-transcribe";
+
+/// How often a running Whisper.cpp process is checked for cancellation and
+/// its time limit
+const WHISPER_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Transcribe audio using Whisper.cpp
 ///
-/// Orchestrates the full transcription workflow:
-/// 1. Load and validate Whisper configuration
-/// 2. Execute Whisper.cpp subprocess
-/// 3. Read raw transcript output
-/// 4. Clean transcript text
-/// 5. Save to storage
+/// Selects the model for the recording length (see `select_model_path`)
+/// and runs the transcription workflow. `decoding` replaces the configured
+/// decoding settings when given.
 ///
-/// Returns (transcript_path, transcript_text)
+/// Returns (transcript_path, transcript_text, model_path, language)
 pub fn transcribe_with_whisper(
     audio_path: &Path,
     session_id: &str,
+    audio_duration_seconds: f64,
+    decoding: Option<DecodingConfig>,
+) -> Result<(String, String, String, Option<String>), String> {
+    let config = load_config()?.transcription;
+    let model_path = select_model_path(&config, audio_duration_seconds).to_string();
+    run_transcription(
+        audio_path,
+        session_id,
+        TranscriptionConfig {
+            model_path,
+            decoding: decoding.unwrap_or(config.decoding),
+            ..config
+        },
+    )
+}
+
+/// Transcribe audio with a specific model, bypassing model rules
+///
+/// Returns (transcript_path, transcript_text, model_path, language)
+pub fn transcribe_with_model(
+    audio_path: &Path,
+    session_id: &str,
+    model_path: &str,
+) -> Result<(String, String, String, Option<String>), String> {
+    let config = load_config()?.transcription;
+    let model_path = model_path.to_string();
+    run_transcription(audio_path, session_id, TranscriptionConfig { model_path, ..config })
+}
+
+/// Transcribe an audio file without storing the transcript
+///
+/// Used for audio that doesn't belong to a session (e.g. jobs sent to the
+/// transcription server). The model is selected for the audio length as
+/// usual. Whisper's output files are removed.
+///
+/// Returns (transcript_text, model_path)
+pub fn transcribe_file(audio_path: &Path) -> Result<(String, String), String> {
+    let config = load_config()?.transcription;
+    let model_path = select_model_path(&config, wav_duration(audio_path)?).to_string();
+    transcribe_file_with(audio_path, TranscriptionConfig { model_path, ..config })
+}
+
+/// Transcribe any audio file and return its text, without creating a
+/// session or storing anything
+///
+/// Whisper runs on a temporary copy, so its output files never land next to
+/// the original.
+pub fn transcribe_standalone_file(path: &Path) -> Result<String, String> {
+    if !path.is_file() {
+        return Err(format!("Audio file not found: {}", path.display()));
+    }
+
+    let temp_dir = std::env::temp_dir().join(STANDALONE_TEMP_DIR);
+    fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("Failed to create temporary folder: {}", e))?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let copy = temp_dir.join(format!("{}-{}.wav", std::process::id(), stamp));
+    fs::copy(path, &copy).map_err(|e| format!("Failed to read audio file: {}", e))?;
+
+    let result = transcribe_file(&copy);
+    let _ = fs::remove_file(&copy);
+    result.map(|(text, _)| text)
+}
+
+/// Transcribe an audio file with a specific model without storing the
+/// transcript, bypassing model rules
+///
+/// Returns (transcript_text, model_path)
+pub fn transcribe_file_with_model(
+    audio_path: &Path,
+    model_path: &str,
 ) -> Result<(String, String), String> {
-    // Load and validate config
-    let config = load_config()?;
+    let config = load_config()?.transcription;
+    let model_path = model_path.to_string();
+    transcribe_file_with(audio_path, TranscriptionConfig { model_path, ..config })
+}
+
+fn transcribe_file_with(
+    audio_path: &Path,
+    config: TranscriptionConfig,
+) -> Result<(String, String), String> {
+    let config = ensure_model(config)?;
+    validate_whisper_setup(&config)?;
+
+    let output = execute_whisper(audio_path, &config);
+
+    let text_path = audio_path.with_extension("wav.txt");
+    let raw_transcript = fs::read_to_string(&text_path);
+    let _ = fs::remove_file(&text_path);
+    let language = read_segments(audio_path)
+        .ok()
+        .and_then(|transcript| transcript.language);
+
+    let output = output?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Whisper transcription failed: {}", stderr));
+    }
+
+    let raw_transcript =
+        raw_transcript.map_err(|e| format!("Failed to read transcript file: {}", e))?;
+    let transcript = process_text(
+        &pipeline(&config),
+        &raw_transcript,
+        language.as_deref(),
+        &config,
+    );
+    Ok((transcript, config.model_path))
+}
+
+/// Transcribe and save, counting the run towards the model's failure rate
+/// and average confidence
+fn run_transcription(
+    audio_path: &Path,
+    session_id: &str,
+    config: TranscriptionConfig,
+) -> Result<(String, String, String, Option<String>), String> {
+    let model_path = config.model_path.clone();
+    let result = transcribe_and_save(audio_path, session_id, config);
+
+    let outcome = match &result {
+        Ok(_) => Ok(load_structured_transcript(session_id)
+            .ok()
+            .as_ref()
+            .and_then(mean_confidence)),
+        Err(e) => Err(e.as_str()),
+    };
+    if let Err(e) = record_model_run(&model_path, outcome) {
+        log::warn!("Failed to record model run: {}", e);
+    }
+
+    result
+}
+
+/// Orchestrates the full transcription workflow:
+/// 1. Validate Whisper configuration, downloading a missing model if allowed
+/// 2. Execute Whisper.cpp subprocess
+/// 3. Read raw transcript output
+/// 4. Run the text steps of the post-processing pipeline
+/// 5. Save to storage (plain text plus per-segment confidence data)
+fn transcribe_and_save(
+    audio_path: &Path,
+    session_id: &str,
+    config: TranscriptionConfig,
+) -> Result<(String, String, String, Option<String>), String> {
+    let config = ensure_model(config)?;
     validate_whisper_setup(&config)?;
 
     // Run Whisper.cpp to generate transcript
-    let whisper_output_path = run_whisper_process(audio_path, &config)?;
+    let whisper_output_path = run_whisper_process(audio_path, session_id, &config)?;
 
     // Read raw transcript
     let raw_transcript = fs::read_to_string(&whisper_output_path)
         .map_err(|e| format!("Failed to read transcript file: {}", e))?;
 
-    // Clean transcript
-    let cleaned_transcript = clean_transcript(&raw_transcript);
+    // Delete temporary Whisper output file
+    let _ = fs::remove_file(whisper_output_path);
+
+    // Segment data (which also names the language) is best-effort; the plain
+    // transcript is what matters
+    let segments = read_segments(audio_path);
+    let language = segments
+        .as_ref()
+        .ok()
+        .and_then(|transcript| transcript.language.clone());
+
+    // Clean, correct and format as configured
+    let steps = pipeline(&config);
+    let cleaned_transcript = process_text(&steps, &raw_transcript, language.as_deref(), &config);
 
     // Save to storage
     let transcript_path = save_transcript(session_id, &cleaned_transcript)?;
 
-    // Delete temporary Whisper output file
-    let _ = fs::remove_file(whisper_output_path);
+    if steps.contains(&PostprocessStep::Romanize) {
+        if let Err(e) = save_romanized_transcript(session_id, &cleaned_transcript) {
+            log::warn!("{}", e);
+        }
+    }
 
-    Ok((transcript_path, cleaned_transcript))
+    if let Err(e) =
+        segments.and_then(|transcript| save_structured_transcript(session_id, &transcript))
+    {
+        log::warn!("Failed to save transcript segments: {}", e);
+    }
+
+    Ok((
+        transcript_path,
+        cleaned_transcript,
+        config.model_path,
+        language,
+    ))
+}
+
+/// Parse Whisper's JSON output ({audio_path}.json) into a structured
+/// transcript, removing the file
+fn read_segments(audio_path: &Path) -> Result<StructuredTranscript, String> {
+    let json_path = audio_path.with_extension("wav.json");
+    let json = fs::read_to_string(&json_path)
+        .map_err(|e| format!("Failed to read Whisper JSON output: {}", e));
+    let _ = fs::remove_file(&json_path);
+
+    parse_whisper_json(&json?)
 }
 
-/// Validate that Whisper.cpp and model files exist
-fn validate_whisper_setup(
-    config: &crate::recording::models::WhisperConfig,
-) -> Result<(), String> {
+/// Select the model for a recording of the given length
+///
+/// The matching rule with the smallest threshold wins, so rule order in the
+/// config doesn't matter. Falls back to the default model.
+pub fn select_model_path(config: &TranscriptionConfig, audio_duration_seconds: f64) -> &str {
+    config
+        .model_rules
+        .iter()
+        .filter(|rule| audio_duration_seconds < rule.max_duration_seconds)
+        .min_by(|a, b| a.max_duration_seconds.total_cmp(&b.max_duration_seconds))
+        .map(|rule| rule.model_path.as_str())
+        .unwrap_or(&config.model_path)
+}
+
+/// Validate that Whisper.cpp and model files exist, and that the model
+/// isn't damaged (checked once per run, see `verify_model`)
+pub fn validate_whisper_setup(config: &TranscriptionConfig) -> Result<(), String> {
     let whisper_path = Path::new(&config.whisper_path);
     if !whisper_path.exists() {
         return Err(
@@ -57,52 +272,68 @@ fn validate_whisper_setup(
     let model_path = Path::new(&config.model_path);
     if !model_path.exists() {
         return Err(
-            "Whisper model file is missing. Please download a model - see README.".to_string(),
+            "Whisper model file is missing. Please download a model - see README - or turn on \
+             autoDownloadModels to fetch it automatically."
+                .to_string(),
         );
     }
 
-    Ok(())
+    verify_model(model_path)?;
+    check_model_language(&config.model_path, config.language.as_deref())
+}
+
+/// Check that an English-only model (`*.en.bin`) isn't used for another
+/// language, which silently produces garbage transcripts
+pub fn check_model_language(model_path: &str, language: Option<&str>) -> Result<(), String> {
+    let Some(language) = language.filter(|language| !language.eq_ignore_ascii_case("en")) else {
+        return Ok(());
+    };
+    if !is_english_only_model(model_path) {
+        return Ok(());
+    }
+
+    let model = Path::new(model_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| model_path.to_string());
+    let problem = if language == "auto" {
+        "can't detect the spoken language".to_string()
+    } else {
+        format!("can't transcribe the configured language \"{}\"", language)
+    };
+    Err(format!(
+        "{} is an English-only model and {}. Choose a multilingual model (without \".en\") \
+         or set the language to English.",
+        model, problem
+    ))
+}
+
+/// Whether a model file is one of Whisper's English-only models, e.g.
+/// `ggml-base.en.bin` or `ggml-base.en-q5_0.bin`
+fn is_english_only_model(model_path: &str) -> bool {
+    Path::new(model_path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .is_some_and(|stem| stem.ends_with(".en") || stem.contains(".en-"))
 }
 
 /// Execute Whisper.cpp process and return the output file path
 ///
-/// On Windows, hides the console window to prevent popups
+/// The process output is kept in the session's transcription log
 fn run_whisper_process(
     audio_path: &Path,
-    config: &crate::recording::models::WhisperConfig,
+    session_id: &str,
+    config: &TranscriptionConfig,
 ) -> Result<std::path::PathBuf, String> {
-    // Run Whisper.cpp with -otxt flag to generate transcript file
-    // Whisper will create a file named {audio_path}.txt
-    #[cfg(target_os = "windows")]
-    let output = {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let start = Instant::now();
+    let output = execute_whisper(audio_path, config);
 
-        Command::new(&config.whisper_path)
-            .arg("-m")
-            .arg(&config.model_path)
-            .arg("-f")
-            .arg(audio_path)
-            .arg("-otxt")
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()
-            .map_err(|_| {
-                "Transcription service couldn't start. Check your Whisper.cpp installation."
-                    .to_string()
-            })?
-    };
+    if let Err(e) = save_transcription_log(session_id, audio_path, config, &output, start.elapsed())
+    {
+        log::warn!("{}", e);
+    }
 
-    #[cfg(not(target_os = "windows"))]
-    let output = Command::new(&config.whisper_path)
-        .arg("-m")
-        .arg(&config.model_path)
-        .arg("-f")
-        .arg(audio_path)
-        .arg("-otxt")
-        .output()
-        .map_err(|_| {
-            "Transcription service couldn't start. Check your Whisper.cpp installation.".to_string()
-        })?;
+    let output = output?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -124,3 +355,344 @@ fn run_whisper_process(
 
     Ok(whisper_output_path)
 }
+
+/// Run the Whisper.cpp process to completion and return its raw output
+///
+/// Runs Whisper.cpp with the -otxt and -ojf flags, so it creates a transcript
+/// file named {audio_path}.txt and full JSON output (segments with token
+/// probabilities) named {audio_path}.json, at the configured process
+/// priority. On Windows, hides the console window to prevent popups.
+///
+/// The process is ended when it runs past `timeoutMinutes` or the
+/// transcription task is cancelled (see `cancel_transcription`).
+pub fn execute_whisper(audio_path: &Path, config: &TranscriptionConfig) -> Result<Output, String> {
+    let mut command = Command::new(&config.whisper_path);
+    command
+        .arg("-m")
+        .arg(&config.model_path)
+        .arg("-f")
+        .arg(audio_path)
+        .arg("-otxt")
+        .arg("-ojf")
+        .args(whisper_args(config));
+
+    #[cfg(unix)]
+    priority::apply_priority(&mut command, config.priority);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW | priority::priority_class(config.priority));
+    }
+
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| {
+            "Transcription service couldn't start. Check your Whisper.cpp installation.".to_string()
+        })?;
+
+    let time_limit = (config.timeout_minutes > 0)
+        .then(|| Duration::from_secs(config.timeout_minutes as u64 * 60));
+    wait_for_whisper(child, time_limit)
+}
+
+/// Wait for a Whisper.cpp process, collecting its output
+///
+/// The process is killed when `time_limit` passes or the transcription is
+/// cancelled.
+fn wait_for_whisper(mut child: Child, time_limit: Option<Duration>) -> Result<Output, String> {
+    // Drain the pipes meanwhile, so a chatty process can't block on them
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let started = Instant::now();
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Ok(status),
+            Ok(None) => {}
+            Err(e) => break Err(format!("Failed to wait for Whisper.cpp: {}", e)),
+        }
+        if transcription_cancelled() {
+            break Err("Transcription was cancelled".to_string());
+        }
+        if let Some(limit) = time_limit.filter(|limit| started.elapsed() >= *limit) {
+            break Err(format!(
+                "Whisper.cpp didn't finish within {} minutes and was stopped",
+                limit.as_secs() / 60
+            ));
+        }
+        thread::sleep(WHISPER_POLL_INTERVAL);
+    };
+
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(e);
+        }
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Read a process pipe to its end on another thread
+fn read_in_background<R>(pipe: Option<R>) -> JoinHandle<Vec<u8>>
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut output);
+        }
+        output
+    })
+}
+
+/// Whisper.cpp's default thread count: min(4, available cores)
+const WHISPER_DEFAULT_MAX_THREADS: u32 = 4;
+
+/// Build Whisper.cpp command-line flags for the language, decoding and
+/// acceleration settings, with the thread count capped at `maxThreads`
+pub fn whisper_args(config: &TranscriptionConfig) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(language) = &config.language {
+        args.push("-l".to_string());
+        args.push(language.clone());
+    }
+    args.extend(decoding_args(&config.decoding));
+
+    let mut acceleration = config.acceleration.clone();
+
+    if let Some(max_threads) = config.max_threads {
+        let threads = acceleration.threads.unwrap_or_else(default_thread_count);
+        acceleration.threads = Some(threads.min(max_threads).max(1));
+    }
+
+    args.extend(acceleration_args(&acceleration));
+    args
+}
+
+fn default_thread_count() -> u32 {
+    thread::available_parallelism()
+        .map(|cores| cores.get() as u32)
+        .unwrap_or(1)
+        .min(WHISPER_DEFAULT_MAX_THREADS)
+}
+
+/// Build Whisper.cpp command-line flags for the decoding settings
+fn decoding_args(decoding: &DecodingConfig) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(temperature) = decoding.temperature {
+        args.push("--temperature".to_string());
+        args.push(temperature.to_string());
+    }
+    if let Some(best_of) = decoding.best_of {
+        args.push("--best-of".to_string());
+        args.push(best_of.to_string());
+    }
+    if let Some(beam_size) = decoding.beam_size {
+        args.push("--beam-size".to_string());
+        args.push(beam_size.to_string());
+    }
+
+    args
+}
+
+/// Build Whisper.cpp command-line flags for the acceleration settings
+pub fn acceleration_args(acceleration: &AccelerationConfig) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if acceleration.disable_gpu {
+        args.push("--no-gpu".to_string());
+    } else if let Some(device) = acceleration.gpu_device {
+        args.push("--device".to_string());
+        args.push(device.to_string());
+    }
+
+    if let Some(threads) = acceleration.threads {
+        args.push("-t".to_string());
+        args.push(threads.to_string());
+    }
+
+    if acceleration.flash_attention {
+        args.push("-fa".to_string());
+    }
+
+    if let Some(device) = &acceleration.openvino_device {
+        args.push("-oved".to_string());
+        args.push(device.clone());
+    }
+
+    args.extend(acceleration.extra_args.iter().cloned());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::models::{ModelRule, ProcessPriority};
+
+    fn config_with_rules(rules: Vec<(f64, &str)>) -> TranscriptionConfig {
+        TranscriptionConfig {
+            whisper_path: "/path/to/whisper".to_string(),
+            model_path: "medium.bin".to_string(),
+            acceleration: AccelerationConfig::default(),
+            draft_model_path: None,
+            priority: ProcessPriority::default(),
+            max_threads: None,
+            batch_workers: 1,
+            timeout_minutes: 120,
+            language: None,
+            corrections: Vec::new(),
+            romanize: false,
+            postprocess: None,
+            decoding: DecodingConfig::default(),
+            auto_download_models: false,
+            model_rules: rules
+                .into_iter()
+                .map(|(max_duration_seconds, model_path)| ModelRule {
+                    max_duration_seconds,
+                    model_path: model_path.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_select_model_without_rules_uses_default() {
+        let config = config_with_rules(vec![]);
+        assert_eq!(select_model_path(&config, 10.0), "medium.bin");
+    }
+
+    #[test]
+    fn test_select_model_by_duration() {
+        let config = config_with_rules(vec![(60.0, "tiny.en.bin")]);
+        assert_eq!(select_model_path(&config, 59.9), "tiny.en.bin");
+        assert_eq!(select_model_path(&config, 60.0), "medium.bin");
+        assert_eq!(select_model_path(&config, 600.0), "medium.bin");
+    }
+
+    #[test]
+    fn test_select_model_prefers_smallest_matching_threshold() {
+        let config = config_with_rules(vec![(300.0, "small.bin"), (30.0, "tiny.en.bin")]);
+        assert_eq!(select_model_path(&config, 20.0), "tiny.en.bin");
+        assert_eq!(select_model_path(&config, 120.0), "small.bin");
+        assert_eq!(select_model_path(&config, 400.0), "medium.bin");
+    }
+
+    #[test]
+    fn test_default_acceleration_adds_no_flags() {
+        assert!(acceleration_args(&AccelerationConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_acceleration_flags() {
+        let acceleration = AccelerationConfig {
+            disable_gpu: false,
+            gpu_device: Some(1),
+            threads: Some(4),
+            flash_attention: true,
+            openvino_device: Some("GPU".to_string()),
+            extra_args: vec!["-ngl".to_string(), "99".to_string()],
+        };
+
+        assert_eq!(
+            acceleration_args(&acceleration),
+            vec!["--device", "1", "-t", "4", "-fa", "-oved", "GPU", "-ngl", "99"]
+        );
+    }
+
+    #[test]
+    fn test_disable_gpu_overrides_device() {
+        let acceleration = AccelerationConfig {
+            disable_gpu: true,
+            gpu_device: Some(1),
+            ..AccelerationConfig::default()
+        };
+
+        assert_eq!(acceleration_args(&acceleration), vec!["--no-gpu"]);
+    }
+
+    #[test]
+    fn test_max_threads_caps_thread_count() {
+        let mut config = config_with_rules(Vec::new());
+        config.acceleration.threads = Some(8);
+        config.max_threads = Some(2);
+        assert_eq!(whisper_args(&config), vec!["-t", "2"]);
+
+        config.acceleration.threads = Some(1);
+        assert_eq!(whisper_args(&config), vec!["-t", "1"]);
+
+        config.max_threads = None;
+        assert_eq!(whisper_args(&config), vec!["-t", "1"]);
+    }
+
+    #[test]
+    fn test_language_flag() {
+        let mut config = config_with_rules(Vec::new());
+        config.language = Some("auto".to_string());
+
+        assert_eq!(whisper_args(&config), vec!["-l", "auto"]);
+    }
+
+    #[test]
+    fn test_english_only_model_language_mismatch() {
+        assert!(check_model_language("/models/ggml-base.en.bin", None).is_ok());
+        assert!(check_model_language("/models/ggml-base.en.bin", Some("en")).is_ok());
+        assert!(check_model_language("/models/ggml-base.bin", Some("de")).is_ok());
+
+        let error = check_model_language("/models/ggml-base.en-q5_0.bin", Some("de")).unwrap_err();
+        assert!(error.starts_with("ggml-base.en-q5_0.bin is an English-only model"));
+        assert!(error.contains("\"de\""));
+        assert!(check_model_language("/models/ggml-small.en.bin", Some("auto")).is_err());
+    }
+
+    #[test]
+    fn test_decoding_flags() {
+        let mut config = config_with_rules(Vec::new());
+        config.decoding = DecodingConfig {
+            temperature: Some(0.2),
+            best_of: None,
+            beam_size: Some(8),
+        };
+
+        assert_eq!(
+            whisper_args(&config),
+            vec!["--temperature", "0.2", "--beam-size", "8"]
+        );
+    }
+
+    #[test]
+    fn test_standalone_file_must_exist() {
+        let missing = std::env::temp_dir().join("thoughtcast-missing-standalone.wav");
+        let error = transcribe_standalone_file(&missing).unwrap_err();
+        assert!(error.starts_with("Audio file not found"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_stops_process_past_time_limit() {
+        let child = Command::new("sleep")
+            .arg("5")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let started = Instant::now();
+        let error = wait_for_whisper(child, Some(Duration::from_millis(200))).unwrap_err();
+        assert!(error.contains("didn't finish"));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::recording::models::TranscriptionConfig;
+use sha1::{Digest, Sha1};
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// SHA-1 checksums of the models published for Whisper.cpp (see its
+/// `models/README.md`)
+const KNOWN_MODELS: &[(&str, &str)] = &[
+    ("ggml-tiny.bin", "bd577a113a864445d4c299885e0cb97d4ba92b5f"),
+    (
+        "ggml-tiny.en.bin",
+        "c78c86eb1a8faa21b369bcd33207cc90d64ae9df",
+    ),
+    ("ggml-base.bin", "465707469ff3a37a2b9b8d8f89f2f99de7299dac"),
+    (
+        "ggml-base.en.bin",
+        "137c40403d78fd54d454da0f9bd998f78703390c",
+    ),
+    ("ggml-small.bin", "55356645c2b361a969dfd0ef2c5a50d530afd8d5"),
+    (
+        "ggml-small.en.bin",
+        "db8a495a91d927739e50b3fc1cc4c6b8f6c2d022",
+    ),
+    (
+        "ggml-medium.bin",
+        "fd9727b6e1217c2f614f9b698455c4ffd82463b4",
+    ),
+    (
+        "ggml-medium.en.bin",
+        "8c30f0e44ce9560643ebd10bbe50cd20eafd3723",
+    ),
+    (
+        "ggml-large-v1.bin",
+        "b1caaf735c4cc1429223d5a74f0f4d0b9b59a299",
+    ),
+    (
+        "ggml-large-v2.bin",
+        "0f4c8e34f21cf1a914c59d8b3ce882345ad349d6",
+    ),
+    (
+        "ggml-large-v3.bin",
+        "ad82bf6a9043ceed055076d0fd39f5f186ff8062",
+    ),
+    (
+        "ggml-large-v3-turbo.bin",
+        "4af2b29d7ec73d781377bfd1758ca957a807e941",
+    ),
+];
+
+/// Model files verified during this run, with the size and modification
+/// time they had, so each is hashed once unless it changes
+static VERIFIED_MODELS: Mutex<Vec<(PathBuf, u64, SystemTime)>> = Mutex::new(Vec::new());
+
+/// Check every model in use (default, per-length rules and draft) against
+/// its known checksum; missing models are reported elsewhere
+pub fn verify_models(config: &TranscriptionConfig) -> Result<(), String> {
+    std::iter::once(&config.model_path)
+        .chain(config.model_rules.iter().map(|rule| &rule.model_path))
+        .chain(&config.draft_model_path)
+        .map(Path::new)
+        .filter(|path| path.exists())
+        .try_for_each(verify_model)
+}
+
+/// Check a model file against the known checksum for its name
+///
+/// Models with other names (custom or quantized) aren't checked. A file
+/// that passed is not hashed again until its size or modification time
+/// changes.
+pub fn verify_model(path: &Path) -> Result<(), String> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if known_checksum(&name).is_none() {
+        return Ok(());
+    }
+
+    let stamp = std::fs::metadata(path)
+        .and_then(|metadata| Ok((metadata.len(), metadata.modified()?)))
+        .map_err(|e| format!("Failed to read model {}: {}", path.display(), e))?;
+    let verified = |(verified_path, size, modified): &(PathBuf, u64, SystemTime)| {
+        verified_path == path && (*size, *modified) == stamp
+    };
+    if VERIFIED_MODELS.lock().unwrap().iter().any(verified) {
+        return Ok(());
+    }
+
+    verify_model_file(path, &name)?;
+    VERIFIED_MODELS
+        .lock()
+        .unwrap()
+        .push((path.to_path_buf(), stamp.0, stamp.1));
+    Ok(())
+}
+
+/// Check a file (e.g. a download in progress) against the checksum known
+/// for the model `name`
+pub fn verify_model_file(path: &Path, name: &str) -> Result<(), String> {
+    let Some(expected) = known_checksum(name) else {
+        return Ok(());
+    };
+
+    let checksum =
+        sha1_file(path).map_err(|e| format!("Failed to read model {}: {}", path.display(), e))?;
+    if checksum != expected {
+        return Err(format!(
+            "Model {} is damaged or incompletely downloaded (checksum mismatch). \
+             Delete it and download it again.",
+            name
+        ));
+    }
+    Ok(())
+}
+
+fn known_checksum(name: &str) -> Option<&'static str> {
+    KNOWN_MODELS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, checksum)| *checksum)
+}
+
+/// Hex SHA-1 of a file's contents
+fn sha1_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha1::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::fixtures;
+
+    #[test]
+    fn test_rejects_damaged_known_model() {
+        let dir = fixtures::temp_dir("model-integrity");
+        let partial = dir.join("ggml-base.en.bin");
+        let custom = dir.join("ggml-custom.bin");
+        std::fs::write(&partial, b"lmgg").unwrap();
+        std::fs::write(&custom, b"lmgg").unwrap();
+
+        let error = verify_model(&partial).unwrap_err();
+        assert!(error.contains("incompletely downloaded"));
+        assert!(verify_model(&custom).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,25 @@
-pub mod text_processor;
+pub mod benchmark;
+pub mod compat;
+pub mod download;
 pub mod engine;
+pub mod integrity;
+pub mod priority;
+pub mod romanize;
+pub mod run_log;
+pub mod segments;
+pub mod text_processor;
 
-pub use engine::transcribe_with_whisper;
+pub use benchmark::{benchmark_model, ensure_benchmark_baseline, DEFAULT_BENCHMARK_SECONDS};
+pub use compat::{check_whisper_compatibility, load_whisper_binary_info};
+pub use download::download_model;
+pub use engine::{
+    select_model_path, transcribe_file, transcribe_file_with_model, transcribe_standalone_file,
+    transcribe_with_model, transcribe_with_whisper,
+};
+pub use integrity::verify_models;
+pub use run_log::{load_transcription_log, transcription_log_path};
+pub use segments::{
+    load_structured_transcript, low_confidence_segments, mean_confidence,
+    DEFAULT_LOW_CONFIDENCE_THRESHOLD,
+};
+pub use text_processor::generate_preview;
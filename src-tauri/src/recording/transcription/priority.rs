@@ -0,0 +1,64 @@
+use crate::recording::models::ProcessPriority;
+use std::process::Command;
+
+/// Unix niceness added to the Whisper.cpp process
+#[cfg(unix)]
+fn nice_increment(priority: ProcessPriority) -> i32 {
+    match priority {
+        ProcessPriority::Normal => 0,
+        ProcessPriority::BelowNormal => 10,
+        ProcessPriority::Idle => 19,
+    }
+}
+
+/// Run the spawned process at the configured scheduling priority
+#[cfg(unix)]
+pub fn apply_priority(command: &mut Command, priority: ProcessPriority) {
+    use std::os::raw::c_int;
+    use std::os::unix::process::CommandExt;
+
+    extern "C" {
+        fn nice(increment: c_int) -> c_int;
+    }
+
+    let increment = nice_increment(priority);
+    if increment == 0 {
+        return;
+    }
+
+    // SAFETY: nice() is a plain syscall wrapper, safe to call between fork
+    // and exec. Failing to lower the priority is not an error.
+    unsafe {
+        command.pre_exec(move || {
+            nice(increment);
+            Ok(())
+        });
+    }
+}
+
+/// Windows process creation flag for the configured priority
+#[cfg(windows)]
+pub fn priority_class(priority: ProcessPriority) -> u32 {
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x00004000;
+    const IDLE_PRIORITY_CLASS: u32 = 0x00000040;
+
+    match priority {
+        ProcessPriority::Normal => 0,
+        ProcessPriority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+        ProcessPriority::Idle => IDLE_PRIORITY_CLASS,
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_increment_lowers_priority() {
+        assert_eq!(nice_increment(ProcessPriority::Normal), 0);
+        assert!(nice_increment(ProcessPriority::BelowNormal) > 0);
+        assert!(
+            nice_increment(ProcessPriority::Idle) > nice_increment(ProcessPriority::BelowNormal)
+        );
+    }
+}
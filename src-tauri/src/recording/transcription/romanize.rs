@@ -0,0 +1,293 @@
+/// Whether a text contains Cyrillic, Greek or Japanese kana, which
+/// `romanize` writes in Latin letters
+pub fn needs_romanization(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c, '\u{0370}'..='\u{03ff}' | '\u{0400}'..='\u{04ff}' | '\u{3040}'..='\u{30ff}')
+    })
+}
+
+/// Write Cyrillic, Greek and Japanese kana in Latin letters
+///
+/// Cyrillic and Greek are transliterated letter by letter, kana with
+/// Hepburn romanization. Kanji, which can't be read without a dictionary,
+/// and all other characters are kept as they are.
+pub fn romanize(text: &str) -> String {
+    let chars: Vec<char> = text.chars().map(katakana_to_hiragana).collect();
+    let mut romanized = String::with_capacity(text.len());
+    // A small っ doubles the next consonant
+    let mut geminate = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        if c == 'っ' {
+            geminate = true;
+            continue;
+        }
+        // The long vowel mark repeats the previous vowel
+        if c == 'ー' {
+            if let Some(vowel) = romanized.chars().last().filter(|c| is_vowel(*c)) {
+                romanized.push(vowel);
+            }
+            continue;
+        }
+
+        let syllable = match kana(c) {
+            Some(base) => match chars.get(i).and_then(|&next| combine_small_kana(base, next)) {
+                Some(combined) => {
+                    i += 1;
+                    combined
+                }
+                None => base.to_string(),
+            },
+            None => transliterate(c),
+        };
+
+        if std::mem::take(&mut geminate) {
+            match syllable.chars().next() {
+                Some(_) if syllable.starts_with("ch") => romanized.push('t'),
+                Some(first) if first.is_ascii_lowercase() && !is_vowel(first) => {
+                    romanized.push(first)
+                }
+                _ => {}
+            }
+        }
+        romanized.push_str(&syllable);
+    }
+
+    romanized
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        '\u{30a1}'..='\u{30f6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// A kana followed by a small kana read as one syllable ("きゃ" → "kya",
+/// "ふぁ" → "fa")
+fn combine_small_kana(base: &str, small: char) -> Option<String> {
+    if let Some(glide) = match small {
+        'ゃ' => Some('a'),
+        'ゅ' => Some('u'),
+        'ょ' => Some('o'),
+        _ => None,
+    } {
+        let stem = base.strip_suffix('i').filter(|stem| !stem.is_empty())?;
+        return Some(match stem {
+            "sh" | "ch" | "j" => format!("{}{}", stem, glide),
+            _ => format!("{}y{}", stem, glide),
+        });
+    }
+
+    let vowel = match small {
+        'ぁ' => 'a',
+        'ぃ' => 'i',
+        'ぅ' => 'u',
+        'ぇ' => 'e',
+        'ぉ' => 'o',
+        _ => return None,
+    };
+    let stem = base.strip_suffix(['a', 'i', 'u', 'e', 'o'])?;
+    Some(format!("{}{}", if stem.is_empty() { "w" } else { stem }, vowel))
+}
+
+/// Hepburn romanization of a hiragana
+fn kana(c: char) -> Option<&'static str> {
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' | 'ゐ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' | 'ゑ' => "e",
+        'お' | 'ぉ' | 'を' => "o",
+        'か' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
+
+/// Latin spelling of a Cyrillic or Greek letter or Japanese punctuation
+/// mark, keeping the letter's case; other characters are returned as is
+fn transliterate(c: char) -> String {
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let Some(latin) = latin_letters(lower) else {
+        return c.to_string();
+    };
+
+    if lower == c {
+        return latin.to_string();
+    }
+    let mut letters = latin.chars();
+    letters
+        .next()
+        .map(|first| first.to_uppercase().chain(letters).collect())
+        .unwrap_or_default()
+}
+
+fn latin_letters(c: char) -> Option<&'static str> {
+    Some(match c {
+        // Cyrillic (Russian, plus the Ukrainian letters)
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ё' => "yo",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        // Greek
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' | 'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'θ' => "th",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' | 'ω' | 'ώ' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        // Japanese punctuation
+        '。' => ". ",
+        '、' => ", ",
+        '！' => "! ",
+        '？' => "? ",
+        '「' | '」' | '『' | '』' => "\"",
+        '・' | '\u{3000}' => " ",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_romanizes_cyrillic_and_greek() {
+        assert_eq!(romanize("Привет, мир! Щука"), "Privet, mir! Shchuka");
+        assert_eq!(romanize("Καλημέρα"), "Kalimera");
+    }
+
+    #[test]
+    fn test_romanizes_kana() {
+        assert_eq!(romanize("ありがとう"), "arigatou");
+        assert_eq!(romanize("キャッチ"), "kyatchi");
+        assert_eq!(romanize("コーヒー"), "koohii");
+        assert_eq!(romanize("ファイル"), "fairu");
+        // Kanji are kept
+        assert_eq!(romanize("日本です。"), "日本desu. ");
+    }
+
+    #[test]
+    fn test_needs_romanization() {
+        assert!(needs_romanization("Hello мир"));
+        assert!(needs_romanization("すし"));
+        assert!(!needs_romanization("Zażółć gęślą jaźń"));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::recording::models::TranscriptionConfig;
+use crate::recording::transcription::engine::acceleration_args;
+use crate::recording::utils::{get_storage_dir, transcription_log_relative_path};
+use chrono::Local;
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+use std::time::Duration;
+
+/// Save the full Whisper.cpp output for a session's transcription run
+///
+/// Written for successful and failed runs alike, replacing the log of any
+/// previous run. Returns the log's path relative to the storage root.
+pub fn save_transcription_log(
+    session_id: &str,
+    audio_path: &Path,
+    config: &TranscriptionConfig,
+    output: &Result<Output, String>,
+    elapsed: Duration,
+) -> Result<String, String> {
+    let relative_path = transcription_log_relative_path(session_id);
+    let log = format_transcription_log(audio_path, config, output, elapsed);
+
+    fs::write(get_storage_dir()?.join(&relative_path), log)
+        .map_err(|e| format!("Failed to write transcription log: {}", e))?;
+
+    Ok(relative_path)
+}
+
+/// Relative path of the session's transcription log, if one was written
+pub fn transcription_log_path(session_id: &str) -> Option<String> {
+    let relative_path = transcription_log_relative_path(session_id);
+    let storage_dir = get_storage_dir().ok()?;
+
+    storage_dir
+        .join(&relative_path)
+        .exists()
+        .then_some(relative_path)
+}
+
+/// Load the log of a session's most recent transcription run
+pub fn load_transcription_log(session_id: &str) -> Result<String, String> {
+    let path = get_storage_dir()?.join(transcription_log_relative_path(session_id));
+
+    if !path.exists() {
+        return Err(format!("No transcription log for session: {}", session_id));
+    }
+
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read transcription log: {}", e))
+}
+
+fn format_transcription_log(
+    audio_path: &Path,
+    config: &TranscriptionConfig,
+    output: &Result<Output, String>,
+    elapsed: Duration,
+) -> String {
+    let mut log = format!(
+        "Started: {}\n\
+         Whisper: {}\n\
+         Model: {}\n\
+         Audio: {}\n\
+         Extra args: {}\n\
+         Elapsed: {:.2}s\n",
+        (Local::now() - elapsed).to_rfc3339(),
+        config.whisper_path,
+        config.model_path,
+        audio_path.display(),
+        acceleration_args(&config.acceleration).join(" "),
+        elapsed.as_secs_f64(),
+    );
+
+    match output {
+        Ok(output) => {
+            log.push_str(&format!("Exit status: {}\n", output.status));
+            log.push_str("\n--- stdout ---\n");
+            log.push_str(&String::from_utf8_lossy(&output.stdout));
+            log.push_str("\n--- stderr ---\n");
+            log.push_str(&String::from_utf8_lossy(&output.stderr));
+        }
+        Err(e) => log.push_str(&format!("Failed to start: {}\n", e)),
+    }
+
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::models::{AccelerationConfig, DecodingConfig, ProcessPriority};
+
+    fn config() -> TranscriptionConfig {
+        TranscriptionConfig {
+            whisper_path: "/bin/whisper-cli".to_string(),
+            model_path: "/models/ggml-base.bin".to_string(),
+            model_rules: Vec::new(),
+            draft_model_path: None,
+            acceleration: AccelerationConfig {
+                threads: Some(4),
+                ..Default::default()
+            },
+            priority: ProcessPriority::default(),
+            max_threads: None,
+            batch_workers: 1,
+            timeout_minutes: 120,
+            language: None,
+            corrections: Vec::new(),
+            romanize: false,
+            postprocess: None,
+            decoding: DecodingConfig::default(),
+            auto_download_models: false,
+        }
+    }
+
+    #[test]
+    fn test_log_for_process_that_failed_to_start() {
+        let log = format_transcription_log(
+            Path::new("/audio/a.wav"),
+            &config(),
+            &Err("Transcription service couldn't start.".to_string()),
+            Duration::from_millis(1500),
+        );
+
+        assert!(log.contains("Model: /models/ggml-base.bin\n"));
+        assert!(log.contains("Extra args: -t 4\n"));
+        assert!(log.contains("Elapsed: 1.50s\n"));
+        assert!(log.contains("Failed to start: Transcription service couldn't start."));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::recording::models::{StructuredTranscript, TranscriptSegment};
+use crate::recording::utils::{get_storage_dir, structured_transcript_relative_path};
+use serde::Deserialize;
+use std::fs;
+
+/// Default confidence below which a segment should be proofread
+pub const DEFAULT_LOW_CONFIDENCE_THRESHOLD: f64 = 0.6;
+
+/// No-speech probability above which a segment is likely hallucinated
+const NO_SPEECH_THRESHOLD: f64 = 0.6;
+
+/// Whisper.cpp full JSON output (`-ojf`)
+#[derive(Deserialize)]
+struct WhisperJson {
+    transcription: Vec<WhisperJsonSegment>,
+    #[serde(default)]
+    result: Option<WhisperJsonResult>,
+}
+
+#[derive(Deserialize)]
+struct WhisperJsonResult {
+    /// Language transcribed in (detected when run with `-l auto`)
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WhisperJsonSegment {
+    offsets: WhisperJsonOffsets,
+    text: String,
+    #[serde(default)]
+    tokens: Vec<WhisperJsonToken>,
+    /// Only present in some whisper.cpp versions
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    #[serde(default)]
+    no_speech_prob: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct WhisperJsonOffsets {
+    from: i64,
+    to: i64,
+}
+
+#[derive(Deserialize)]
+struct WhisperJsonToken {
+    text: String,
+    #[serde(default)]
+    p: Option<f64>,
+}
+
+/// Parse Whisper.cpp full JSON output into transcript segments
+///
+/// Uses the segment's average log probability when whisper.cpp reports it,
+/// otherwise derives it from the token probabilities (special tokens such
+/// as `[_BEG_]` excluded)
+pub fn parse_whisper_json(json: &str) -> Result<StructuredTranscript, String> {
+    let output: WhisperJson = serde_json::from_str(json)
+        .map_err(|e| format!("Failed to parse Whisper JSON output: {}", e))?;
+
+    let segments = output
+        .transcription
+        .into_iter()
+        .map(|segment| {
+            let avg_logprob = segment
+                .avg_logprob
+                .or_else(|| average_token_logprob(&segment.tokens));
+
+            TranscriptSegment {
+                start_ms: segment.offsets.from,
+                end_ms: segment.offsets.to,
+                text: segment.text.trim().to_string(),
+                avg_logprob,
+                no_speech_prob: segment.no_speech_prob,
+                confidence: avg_logprob.map(f64::exp),
+            }
+        })
+        .collect();
+
+    Ok(StructuredTranscript {
+        version: 1,
+        segments,
+        language: output.result.and_then(|result| result.language),
+    })
+}
+
+/// Mean log probability of a segment's text tokens
+fn average_token_logprob(tokens: &[WhisperJsonToken]) -> Option<f64> {
+    let logprobs: Vec<f64> = tokens
+        .iter()
+        .filter(|token| !token.text.starts_with("[_"))
+        .filter_map(|token| token.p)
+        .filter(|p| *p > 0.0)
+        .map(f64::ln)
+        .collect();
+
+    if logprobs.is_empty() {
+        return None;
+    }
+
+    Some(logprobs.iter().sum::<f64>() / logprobs.len() as f64)
+}
+
+/// Mean confidence over the segments that have one
+pub fn mean_confidence(transcript: &StructuredTranscript) -> Option<f64> {
+    let confidences: Vec<f64> = transcript
+        .segments
+        .iter()
+        .filter_map(|segment| segment.confidence)
+        .collect();
+
+    if confidences.is_empty() {
+        return None;
+    }
+
+    Some(confidences.iter().sum::<f64>() / confidences.len() as f64)
+}
+
+/// Segments the user should proofread
+///
+/// A segment is low confidence if its confidence is below `threshold` or
+/// Whisper considered it likely to contain no speech
+pub fn low_confidence_segments(
+    transcript: &StructuredTranscript,
+    threshold: f64,
+) -> Vec<TranscriptSegment> {
+    transcript
+        .segments
+        .iter()
+        .filter(|segment| {
+            segment.confidence.is_some_and(|c| c < threshold)
+                || segment.no_speech_prob.is_some_and(|p| p > NO_SPEECH_THRESHOLD)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Save a structured transcript next to the plain-text transcript
+pub fn save_structured_transcript(
+    session_id: &str,
+    transcript: &StructuredTranscript,
+) -> Result<(), String> {
+    let path = get_storage_dir()?.join(structured_transcript_relative_path(session_id));
+    let content = serde_json::to_string_pretty(transcript)
+        .map_err(|e| format!("Failed to serialize structured transcript: {}", e))?;
+
+    fs::write(&path, content)
+        .map_err(|e| format!("Failed to write structured transcript: {}", e))
+}
+
+/// Load a session's structured transcript
+pub fn load_structured_transcript(session_id: &str) -> Result<StructuredTranscript, String> {
+    let path = get_storage_dir()?.join(structured_transcript_relative_path(session_id));
+
+    if !path.exists() {
+        return Err(format!(
+            "No segment data for session {} (transcribe it again to generate it)",
+            session_id
+        ));
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read structured transcript: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse structured transcript: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHISPER_JSON: &str = r#"{
+        "result": { "language": "en" },
+        "transcription": [
+            {
+                "timestamps": { "from": "00:00:00,000", "to": "00:00:02,000" },
+                "offsets": { "from": 0, "to": 2000 },
+                "text": " Hello world",
+                "tokens": [
+                    { "text": "[_BEG_]", "id": 50364, "p": 0.1 },
+                    { "text": " Hello", "id": 2425, "p": 0.9 },
+                    { "text": " world", "id": 1002, "p": 0.9 }
+                ]
+            },
+            {
+                "timestamps": { "from": "00:00:02,000", "to": "00:00:04,000" },
+                "offsets": { "from": 2000, "to": 4000 },
+                "text": " mumbled words",
+                "tokens": [
+                    { "text": " mumbled", "id": 1, "p": 0.3 },
+                    { "text": " words", "id": 2, "p": 0.4 }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_whisper_json_segments() {
+        let transcript = parse_whisper_json(WHISPER_JSON).unwrap();
+
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[0].text, "Hello world");
+        assert_eq!(transcript.segments[0].start_ms, 0);
+        assert_eq!(transcript.segments[1].end_ms, 4000);
+        assert_eq!(transcript.language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_confidence_ignores_special_tokens() {
+        let transcript = parse_whisper_json(WHISPER_JSON).unwrap();
+
+        // Only " Hello" and " world" (p = 0.9) count
+        let confidence = transcript.segments[0].confidence.unwrap();
+        assert!((confidence - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_segment_level_logprob_preferred() {
+        let json = r#"{ "transcription": [{
+            "offsets": { "from": 0, "to": 1000 },
+            "text": " Hi",
+            "tokens": [{ "text": " Hi", "p": 0.99 }],
+            "avg_logprob": -1.0,
+            "no_speech_prob": 0.05
+        }] }"#;
+        let transcript = parse_whisper_json(json).unwrap();
+
+        assert_eq!(transcript.segments[0].avg_logprob, Some(-1.0));
+        assert_eq!(transcript.segments[0].no_speech_prob, Some(0.05));
+        assert_eq!(transcript.language, None);
+    }
+
+    #[test]
+    fn test_low_confidence_segments() {
+        let transcript = parse_whisper_json(WHISPER_JSON).unwrap();
+        let low = low_confidence_segments(&transcript, DEFAULT_LOW_CONFIDENCE_THRESHOLD);
+
+        assert_eq!(low.len(), 1);
+        assert_eq!(low[0].text, "mumbled words");
+    }
+
+    #[test]
+    fn test_mean_confidence() {
+        let mut transcript = parse_whisper_json(WHISPER_JSON).unwrap();
+        transcript.segments[0].confidence = Some(0.9);
+        transcript.segments[1].confidence = Some(0.3);
+        assert!((mean_confidence(&transcript).unwrap() - 0.6).abs() < 1e-9);
+
+        transcript.segments[1].confidence = None;
+        assert!((mean_confidence(&transcript).unwrap() - 0.9).abs() < 1e-9);
+
+        transcript.segments.clear();
+        assert_eq!(mean_confidence(&transcript), None);
+    }
+
+    #[test]
+    fn test_high_no_speech_prob_is_low_confidence() {
+        let mut transcript = parse_whisper_json(WHISPER_JSON).unwrap();
+        transcript.segments[0].no_speech_prob = Some(0.8);
+
+        let low = low_confidence_segments(&transcript, 0.1);
+        assert_eq!(low.len(), 1);
+        assert_eq!(low[0].text, "Hello world");
+    }
+
+    #[test]
+    fn test_invalid_json_is_error() {
+        assert!(parse_whisper_json("not json").is_err());
+    }
+}
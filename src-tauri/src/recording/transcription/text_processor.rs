@@ -1,4 +1,7 @@
-use crate::recording::utils::get_storage_dir;
+use crate::recording::models::CorrectionRule;
+use crate::recording::session::storage::session_transcript_relative_path;
+use crate::recording::transcription::romanize::{needs_romanization, romanize};
+use crate::recording::utils::{get_storage_dir, romanized_transcript_relative_path};
 use std::fs;
 
 /// Clean raw Whisper transcript output
@@ -19,24 +22,195 @@ pub fn clean_transcript(raw_transcript: &str) -> String {
         .to_string()
 }
 
+/// Apply the corrections and punctuation conventions for a transcript's
+/// language
+///
+/// Corrections without a language apply to every transcript; the others
+/// only to transcripts in their language.
+pub fn apply_language_rules(
+    text: &str,
+    language: Option<&str>,
+    corrections: &[CorrectionRule],
+) -> String {
+    let language = language.map(normalize_language);
+
+    let corrected = corrections
+        .iter()
+        .filter(|rule| {
+            rule.language.is_none() || rule.language.as_deref().map(normalize_language) == language
+        })
+        .fold(text.to_string(), |text, rule| {
+            replace_word(&text, &rule.find, &rule.replace)
+        });
+
+    match language.as_deref() {
+        Some("fr") => french_punctuation_spacing(&corrected),
+        _ => corrected,
+    }
+}
+
+/// Language code without region, lowercase ("en-US" → "en")
+pub fn normalize_language(code: &str) -> String {
+    code.split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// Replace whole-word occurrences of `find` (ignoring ASCII case)
+fn replace_word(text: &str, find: &str, replace: &str) -> String {
+    if find.is_empty() {
+        return text.to_string();
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = rest.chars().next() {
+        let matched = !previous.is_some_and(char::is_alphanumeric)
+            && rest
+                .get(..find.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(find))
+            && !rest[find.len()..].starts_with(|c: char| c.is_alphanumeric());
+
+        if matched {
+            result.push_str(replace);
+            previous = find.chars().last();
+            rest = &rest[find.len()..];
+        } else {
+            result.push(c);
+            previous = Some(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    result
+}
+
+/// French puts a narrow no-break space before `;`, `:`, `!` and `?`
+fn french_punctuation_spacing(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if matches!(c, ';' | ':' | '!' | '?') {
+            let word_end = result.trim_end_matches(' ').len();
+            let spaced = word_end < result.len();
+            // Times ("10:30") and URLs keep their colon as is
+            let attached = c != ':' && result.ends_with(char::is_alphabetic);
+            if word_end > 0 && (spaced || attached) {
+                result.truncate(word_end);
+                result.push('\u{202f}');
+            }
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+/// Length of session previews, in characters
+const PREVIEW_CHARS: usize = 100;
+
+/// Generate a session preview from transcript text
+///
+/// Truncates on a character boundary, so multi-byte UTF-8 text is never split
+pub fn generate_preview(text: &str) -> String {
+    if text.is_empty() {
+        return "No transcript".to_string();
+    }
+
+    match text.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Extract a short summary made of the transcript's leading sentences
+///
+/// Sentences end at `.`, `!`, `?` or a line break
+pub fn extract_summary(transcript: &str, max_sentences: usize) -> String {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+
+    for c in transcript.chars() {
+        if c == '\n' {
+            if !current.trim().is_empty() {
+                sentences.push(current.trim().to_string());
+            }
+            current.clear();
+        } else {
+            current.push(c);
+            if matches!(c, '.' | '!' | '?') {
+                sentences.push(current.trim().to_string());
+                current.clear();
+            }
+        }
+
+        if sentences.len() >= max_sentences {
+            break;
+        }
+    }
+
+    if sentences.len() < max_sentences && !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+
+    sentences.join(" ")
+}
+
 /// Save cleaned transcript to the text directory
 ///
 /// Returns the relative path to the saved transcript file
 pub fn save_transcript(session_id: &str, transcript_text: &str) -> Result<String, String> {
     let storage_dir = get_storage_dir()?;
-    let transcript_filename = format!("{}.txt", session_id);
-    let transcript_path = storage_dir.join("text").join(&transcript_filename);
+    let relative_path = session_transcript_relative_path(session_id);
+    let transcript_path = storage_dir.join(&relative_path);
 
     fs::write(&transcript_path, transcript_text)
         .map_err(|e| format!("Failed to write cleaned transcript: {}", e))?;
 
-    Ok(format!("text/{}", transcript_filename))
+    Ok(relative_path)
+}
+
+/// Save a romanized copy of the transcript next to it
+///
+/// Transcripts with nothing to romanize get no copy, and an outdated one
+/// from an earlier transcription is removed.
+pub fn save_romanized_transcript(session_id: &str, transcript_text: &str) -> Result<(), String> {
+    let path = get_storage_dir()?.join(romanized_transcript_relative_path(session_id));
+
+    if !needs_romanization(transcript_text) {
+        let _ = fs::remove_file(&path);
+        return Ok(());
+    }
+
+    fs::write(&path, romanize(transcript_text))
+        .map_err(|e| format!("Failed to write romanized transcript: {}", e))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_generate_preview_truncates_long_text() {
+        let text = "a".repeat(150);
+        assert_eq!(generate_preview(&text), format!("{}...", "a".repeat(100)));
+        assert_eq!(generate_preview("Short note"), "Short note");
+        assert_eq!(generate_preview(""), "No transcript");
+    }
+
+    #[test]
+    fn test_generate_preview_keeps_multibyte_characters_whole() {
+        let text = format!("{}ł{}", "a".repeat(99), "ż".repeat(10));
+        assert_eq!(generate_preview(&text), format!("{}ł...", "a".repeat(99)));
+
+        let emoji = "🎙".repeat(120);
+        assert_eq!(generate_preview(&emoji).chars().count(), 103);
+    }
+
     #[test]
     fn test_clean_transcript_removes_timestamps() {
         let raw = "[00:00:00.000 --> 00:00:02.000]\nHello world\n[00:00:02.000 --> 00:00:04.000]\nThis is a test";
@@ -93,4 +267,56 @@ mod tests {
         let cleaned = clean_transcript(raw);
         assert_eq!(cleaned, "Line 1\nLine 2\nLine 3\nLine 4");
     }
+
+    #[test]
+    fn test_extract_summary_takes_leading_sentences() {
+        let text = "First point. Second point! Third point?";
+        assert_eq!(extract_summary(text, 2), "First point. Second point!");
+    }
+
+    #[test]
+    fn test_extract_summary_splits_on_line_breaks() {
+        let text = "Shopping list\nmilk and eggs\nbread";
+        assert_eq!(extract_summary(text, 2), "Shopping list milk and eggs");
+    }
+
+    #[test]
+    fn test_extract_summary_short_text() {
+        assert_eq!(extract_summary("no punctuation here", 2), "no punctuation here");
+        assert_eq!(extract_summary("", 2), "");
+    }
+
+    fn rule(find: &str, replace: &str, language: Option<&str>) -> CorrectionRule {
+        CorrectionRule {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            language: language.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_corrections_match_whole_words_in_their_language() {
+        let corrections = vec![
+            rule("thought cast", "ThoughtCast", None),
+            rule("jarek", "Jarek", Some("pl")),
+        ];
+
+        assert_eq!(
+            apply_language_rules("Thought Cast by jarek", Some("en"), &corrections),
+            "ThoughtCast by jarek"
+        );
+        assert_eq!(
+            apply_language_rules("jarek, jareks", Some("pl-PL"), &corrections),
+            "Jarek, jareks"
+        );
+    }
+
+    #[test]
+    fn test_french_punctuation_spacing() {
+        assert_eq!(
+            apply_language_rules("Vraiment ? Oui! Rendez-vous à 10:30 : parfait", Some("fr"), &[]),
+            "Vraiment\u{202f}? Oui\u{202f}! Rendez-vous à 10:30\u{202f}: parfait"
+        );
+        assert_eq!(apply_language_rules("Really ?", Some("en"), &[]), "Really ?");
+    }
 }
@@ -1,16 +1,285 @@
 use arboard::Clipboard;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Mechanism that successfully placed content on the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipboardMechanism {
+    /// In-process clipboard via arboard (macOS/Windows)
+    Native,
+    /// arboard on Linux, kept alive by a background thread that owns the
+    /// selection until another app takes it
+    PersistentNative,
+    /// `wl-copy` helper (Wayland)
+    WlCopy,
+    /// `xclip` helper (X11)
+    Xclip,
+    /// `xsel` helper (X11)
+    Xsel,
+}
+
+/// Content placed on the clipboard
+#[derive(Debug, Clone)]
+enum ClipboardPayload {
+    Text(String),
+    /// File references (pasted as attachments in chat/mail apps)
+    Files(Vec<PathBuf>),
+}
+
+impl ClipboardPayload {
+    fn write_to(&self, set: arboard::Set<'_>) -> Result<(), arboard::Error> {
+        match self {
+            ClipboardPayload::Text(text) => set.text(text.as_str()),
+            ClipboardPayload::Files(paths) => set.file_list(paths),
+        }
+    }
+}
 
 /// Copy text to the system clipboard
 ///
-/// This provides a simple wrapper around the arboard clipboard functionality,
-/// making it easy to mock for testing and isolating the system dependency
-pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
+/// On Linux, clipboard contents are owned by the writing process and vanish
+/// when that process stops serving them (and arboard writes can fail
+/// outright under Wayland). Persistent helpers are therefore tried first,
+/// falling back to an arboard owner thread.
+///
+/// Returns the mechanism that succeeded
+pub fn copy_to_clipboard(text: &str) -> Result<ClipboardMechanism, String> {
+    copy_payload(ClipboardPayload::Text(text.to_string()))
+}
+
+/// Copy file references (e.g. a session's audio file) to the clipboard
+///
+/// Replaces any text on the clipboard: pasting into Slack, Mail or a file
+/// manager attaches/copies the file itself
+pub fn copy_files_to_clipboard(paths: &[PathBuf]) -> Result<ClipboardMechanism, String> {
+    copy_payload(ClipboardPayload::Files(paths.to_vec()))
+}
+
+/// Check that the system clipboard can be opened
+pub fn check_clipboard_access() -> Result<(), String> {
+    Clipboard::new()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to access clipboard: {}", e))
+}
+
+fn copy_payload(payload: ClipboardPayload) -> Result<ClipboardMechanism, String> {
+    let mut errors = Vec::new();
+
+    for mechanism in clipboard_strategies() {
+        match copy_with(mechanism, &payload) {
+            Ok(()) => return Ok(mechanism),
+            Err(e) => errors.push(format!("{:?}: {}", mechanism, e)),
+        }
+    }
+
+    Err(format!("Failed to copy to clipboard ({})", errors.join("; ")))
+}
+
+/// Clipboard mechanisms to try, in order, for the current platform
+fn clipboard_strategies() -> Vec<ClipboardMechanism> {
+    if cfg!(target_os = "linux") {
+        let is_wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+        linux_strategies(is_wayland)
+    } else {
+        vec![ClipboardMechanism::Native]
+    }
+}
+
+/// Linux strategy order depends on the display server
+fn linux_strategies(is_wayland: bool) -> Vec<ClipboardMechanism> {
+    if is_wayland {
+        vec![ClipboardMechanism::WlCopy, ClipboardMechanism::PersistentNative]
+    } else {
+        vec![
+            ClipboardMechanism::Xclip,
+            ClipboardMechanism::Xsel,
+            ClipboardMechanism::PersistentNative,
+        ]
+    }
+}
+
+fn copy_with(mechanism: ClipboardMechanism, payload: &ClipboardPayload) -> Result<(), String> {
+    match (mechanism, payload) {
+        (ClipboardMechanism::Native, _) => copy_native(payload),
+        (ClipboardMechanism::PersistentNative, _) => copy_persistent_native(payload),
+        (ClipboardMechanism::WlCopy, ClipboardPayload::Text(text)) => {
+            copy_with_helper("wl-copy", &[], text)
+        }
+        (ClipboardMechanism::WlCopy, ClipboardPayload::Files(paths)) => {
+            copy_with_helper("wl-copy", &["--type", "text/uri-list"], &uri_list(paths))
+        }
+        (ClipboardMechanism::Xclip, ClipboardPayload::Text(text)) => {
+            copy_with_helper("xclip", &["-selection", "clipboard"], text)
+        }
+        (ClipboardMechanism::Xclip, ClipboardPayload::Files(paths)) => copy_with_helper(
+            "xclip",
+            &["-selection", "clipboard", "-t", "text/uri-list"],
+            &uri_list(paths),
+        ),
+        (ClipboardMechanism::Xsel, ClipboardPayload::Text(text)) => {
+            copy_with_helper("xsel", &["--clipboard", "--input"], text)
+        }
+        (ClipboardMechanism::Xsel, ClipboardPayload::Files(_)) => {
+            Err("xsel cannot copy file references".to_string())
+        }
+    }
+}
+
+/// Copy using arboard in-process (clipboard managers on macOS/Windows keep it)
+fn copy_native(payload: &ClipboardPayload) -> Result<(), String> {
     let mut clipboard = Clipboard::new()
         .map_err(|e| format!("Failed to access clipboard: {}", e))?;
 
-    clipboard
-        .set_text(text)
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    payload
+        .write_to(clipboard.set())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+/// Copy using arboard from a thread that keeps serving the selection
+///
+/// The thread blocks until another application takes clipboard ownership,
+/// so the content survives the app losing focus
+#[cfg(target_os = "linux")]
+fn copy_persistent_native(payload: &ClipboardPayload) -> Result<(), String> {
+    use arboard::SetExtLinux;
+    use std::sync::mpsc;
+
+    let payload = payload.clone();
+    let (ready_tx, ready_rx) = mpsc::channel();
+
+    std::thread::spawn(move || match Clipboard::new() {
+        Ok(mut clipboard) => {
+            let _ = ready_tx.send(Ok(()));
+            if let Err(e) = payload.write_to(clipboard.set().wait()) {
+                log::error!("Persistent clipboard owner failed: {}", e);
+            }
+        }
+        Err(e) => {
+            let _ = ready_tx.send(Err(format!("Failed to access clipboard: {}", e)));
+        }
+    });
+
+    ready_rx
+        .recv()
+        .map_err(|_| "Clipboard thread exited unexpectedly".to_string())?
+}
+
+#[cfg(not(target_os = "linux"))]
+fn copy_persistent_native(payload: &ClipboardPayload) -> Result<(), String> {
+    copy_native(payload)
+}
+
+/// Format paths as a `text/uri-list` clipboard payload
+fn uri_list(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| file_uri(path))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+/// Convert an absolute path to a percent-encoded `file://` URI
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// Copy by piping text into an external helper that forks to serve the
+/// selection after the app's own handle is gone
+fn copy_with_helper(program: &str, args: &[&str], text: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{} unavailable: {}", program, e))?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| format!("Failed to open {} input", program))?
+        .write_all(text.as_bytes())
+        .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for {}: {}", program, e))?;
+
+    if !status.success() {
+        return Err(format!("{} exited with {}", program, status));
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wayland_prefers_wl_copy() {
+        assert_eq!(
+            linux_strategies(true),
+            vec![ClipboardMechanism::WlCopy, ClipboardMechanism::PersistentNative]
+        );
+    }
+
+    #[test]
+    fn test_x11_prefers_xclip_then_xsel() {
+        assert_eq!(
+            linux_strategies(false),
+            vec![
+                ClipboardMechanism::Xclip,
+                ClipboardMechanism::Xsel,
+                ClipboardMechanism::PersistentNative
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mechanism_serialization() {
+        let json = serde_json::to_string(&ClipboardMechanism::WlCopy).unwrap();
+        assert_eq!(json, "\"wl-copy\"");
+    }
+
+    #[test]
+    fn test_file_uri_encoding() {
+        assert_eq!(
+            file_uri(Path::new("/Users/me/Thought Cast/a.wav")),
+            "file:///Users/me/Thought%20Cast/a.wav"
+        );
+        assert_eq!(
+            file_uri(Path::new("C:\\Users\\me\\a.wav")),
+            "file:///C:/Users/me/a.wav"
+        );
+    }
+
+    #[test]
+    fn test_uri_list_joins_with_crlf() {
+        let paths = vec![PathBuf::from("/a.wav"), PathBuf::from("/b.txt")];
+        assert_eq!(uri_list(&paths), "file:///a.wav\r\nfile:///b.txt");
+    }
+
+    #[test]
+    fn test_missing_helper_reports_error() {
+        let result = copy_with_helper("thoughtcast-nonexistent-helper", &[], "text");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+/// Reveal a file in the platform file manager (Finder/Explorer)
+///
+/// Selects the file where the platform supports it; on Linux the containing
+/// folder is opened since there is no portable "select" option
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg("-R").arg(path);
+        command
+    };
+
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        use std::os::windows::process::CommandExt;
+        // explorer requires the quoted path glued to the /select, switch
+        let mut command = Command::new("explorer");
+        command.raw_arg(format!("/select,\"{}\"", path.display()));
+        command
+    };
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = {
+        let folder = path.parent().unwrap_or(path);
+        let mut command = Command::new("xdg-open");
+        command.arg(folder);
+        command
+    };
+
+    spawn_detached(&mut command, "file manager")
+}
+
+/// Open a file with the platform's default application for its type
+///
+/// For transcripts this is the user's default text editor
+pub fn open_with_default_app(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(path);
+        command
+    };
+
+    // `cmd /C start` would interpret `&` and `^` in the path, so use the
+    // same protocol handler as for URLs, which opens files by type too
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler").arg(path);
+        command
+    };
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(path);
+        command
+    };
+
+    spawn_detached(&mut command, "default application")
+}
+
+/// Open a URL (e.g. `mailto:`) with its registered handler
+pub fn open_url(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    };
+
+    // `cmd /C start` would interpret `&` in the URL, so use the URL protocol handler
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler").arg(url);
+        command
+    };
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+
+    spawn_detached(&mut command, "link")
+}
+
+/// Spawn a launcher process without waiting for it
+///
+/// The launcher is reaped in the background once it exits (they hand off
+/// to the opened app and exit right away), so none are left as zombies. On
+/// Windows, hides the console window to prevent popups.
+fn spawn_detached(command: &mut Command, target: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to open {}: {}", target, e))?;
+
+    thread::spawn(move || match child.wait() {
+        // Not an error in itself: `explorer /select` exits with 1 on success
+        Ok(status) if !status.success() => log::debug!("Launcher exited with {}", status),
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to wait for launcher: {}", e),
+    });
+    Ok(())
+}
@@ -1,5 +1,17 @@
 pub mod clipboard;
+pub mod file_opener;
+pub mod http;
+pub mod network;
+pub mod secrets;
 pub mod storage;
 
-pub use clipboard::copy_to_clipboard;
-pub use storage::get_storage_dir;
+pub use clipboard::{
+    check_clipboard_access, copy_files_to_clipboard, copy_to_clipboard, ClipboardMechanism,
+};
+pub use file_opener::{open_url, open_with_default_app, reveal_in_file_manager};
+pub use network::{http_request, require_network, require_online};
+pub use storage::{
+    audio_relative_path, export_relative_path, get_local_state_dir, get_storage_dir,
+    romanized_transcript_relative_path, structured_transcript_relative_path,
+    transcript_relative_path, transcript_versions_relative_dir, transcription_log_relative_path,
+};
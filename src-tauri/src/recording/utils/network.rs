@@ -0,0 +1,99 @@
+use crate::recording::config::load_config;
+use crate::recording::config::loader::{config_file_path, local_config_path};
+use std::net::IpAddr;
+
+/// Whether privacy mode (`privacy.offline`) is on
+///
+/// Fails closed: a config file that exists but can't be loaded (e.g. after a
+/// bad edit) counts as privacy mode on. Only without any config file (first
+/// run) is privacy mode off.
+pub fn is_offline() -> bool {
+    match load_config() {
+        Ok(config) => config.privacy.offline,
+        Err(_) => [config_file_path(), local_config_path()]
+            .into_iter()
+            .flatten()
+            .any(|path| path.exists()),
+    }
+}
+
+/// Refuse handing a transcript to another app or service in privacy mode
+///
+/// For exits that don't connect to a host themselves (e.g. composing an
+/// e-mail in the mail client, or a note in an app that syncs it).
+pub fn require_online(feature: &str) -> Result<(), String> {
+    if !is_offline() {
+        return Ok(());
+    }
+    log::info!("Privacy mode: refused {}", feature);
+    Err(format!(
+        "{} is unavailable in privacy mode, which keeps audio and transcripts on this machine",
+        feature
+    ))
+}
+
+/// Refuse a network feature in privacy mode, unless it only talks to this
+/// machine
+///
+/// `feature` names what is refused (e.g. "Notion publishing"); `host` is the
+/// host it connects to.
+pub fn require_network(feature: &str, host: &str) -> Result<(), String> {
+    if is_loopback_host(host) {
+        return Ok(());
+    }
+    require_online(&format!("{} ({})", feature, host))
+}
+
+/// Outgoing HTTP request; every integration goes through this, so privacy
+/// mode is enforced in one place
+pub fn http_request(feature: &str, method: &str, url: &str) -> Result<ureq::Request, String> {
+    require_network(feature, url_host(url))?;
+    Ok(ureq::request(method, url))
+}
+
+/// Host of an `http(s)://` URL, without port or credentials
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+
+    match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    }
+}
+
+/// Whether a host (name or address) is this machine
+fn is_loopback_host(host: &str) -> bool {
+    match host.parse::<IpAddr>() {
+        Ok(address) => address.is_loopback(),
+        Err(_) => host.eq_ignore_ascii_case("localhost"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(
+            url_host("https://api.notion.com/v1/pages"),
+            "api.notion.com"
+        );
+        assert_eq!(url_host("http://localhost:11434/v1/chat"), "localhost");
+        assert_eq!(url_host("http://user:pw@[::1]:8080/x"), "::1");
+        assert_eq!(url_host("http://127.0.0.1?q=1"), "127.0.0.1");
+    }
+
+    #[test]
+    fn test_loopback_hosts_stay_available() {
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("127.0.0.1"));
+        assert!(is_loopback_host("::1"));
+        assert!(!is_loopback_host("0.0.0.0"));
+        assert!(!is_loopback_host("huggingface.co"));
+    }
+}
@@ -0,0 +1,33 @@
+use keyring::Entry;
+
+/// Keychain service under which integration credentials are stored
+const KEYCHAIN_SERVICE: &str = "ThoughtCast";
+
+/// Store a credential in the system keychain (macOS Keychain, Windows
+/// Credential Manager or the Secret Service on Linux)
+pub fn store_secret(name: &str, value: &str) -> Result<(), String> {
+    entry(name)?
+        .set_password(value)
+        .map_err(|e| format!("Failed to store {} credential: {}", name, e))
+}
+
+/// Credential stored under `name`, if any
+pub fn load_secret(name: &str) -> Result<Option<String>, String> {
+    match entry(name)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read {} credential: {}", name, e)),
+    }
+}
+
+/// Remove the credential stored under `name`; a missing one is not an error
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    match entry(name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove {} credential: {}", name, e)),
+    }
+}
+
+fn entry(name: &str) -> Result<Entry, String> {
+    Entry::new(KEYCHAIN_SERVICE, name).map_err(|e| format!("Failed to open keychain: {}", e))
+}
@@ -1,5 +1,14 @@
 use std::fs;
 use std::path::PathBuf;
+#[cfg(test)]
+use std::path::Path;
+#[cfg(test)]
+use std::sync::Mutex;
+
+/// Folder replacing the user's storage, local state and local config while a
+/// test runs a whole workflow (see `use_test_storage_root`)
+#[cfg(test)]
+static TEST_STORAGE_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
 
 /// Get the main storage directory for ThoughtCast recordings
 /// Creates the directory structure if it doesn't exist
@@ -7,10 +16,12 @@ use std::path::PathBuf;
 /// Uses Documents/ThoughtCast/ to follow voice memo app patterns
 /// and make recordings easily accessible to users
 pub fn get_storage_dir() -> Result<PathBuf, String> {
-    let documents_dir = dirs::document_dir()
-        .ok_or("Could not find documents directory")?;
-
-    let storage_dir = documents_dir.join("ThoughtCast");
+    let storage_dir = match test_storage_root() {
+        Some(root) => root,
+        None => dirs::document_dir()
+            .ok_or("Could not find documents directory")?
+            .join("ThoughtCast"),
+    };
 
     // Create directories if they don't exist
     fs::create_dir_all(&storage_dir)
@@ -22,5 +33,100 @@ pub fn get_storage_dir() -> Result<PathBuf, String> {
     fs::create_dir_all(storage_dir.join("text"))
         .map_err(|e| format!("Failed to create text directory: {}", e))?;
 
+    fs::create_dir_all(storage_dir.join("sessions"))
+        .map_err(|e| format!("Failed to create sessions directory: {}", e))?;
+
     Ok(storage_dir)
 }
+
+/// Get the machine-local app state directory (not synced with the storage folder)
+///
+/// Holds bookkeeping that only makes sense on this machine, such as
+/// transcriptions interrupted by quitting the app
+pub fn get_local_state_dir() -> Result<PathBuf, String> {
+    let state_dir = match test_storage_root() {
+        Some(root) => root.join("local"),
+        None => dirs::data_local_dir()
+            .ok_or("Could not find local data directory")?
+            .join("ThoughtCast"),
+    };
+
+    fs::create_dir_all(&state_dir)
+        .map_err(|e| format!("Failed to create local state directory: {}", e))?;
+
+    Ok(state_dir)
+}
+
+/// The test storage root in use, if any; always None outside tests
+#[cfg(test)]
+pub(crate) fn test_storage_root() -> Option<PathBuf> {
+    TEST_STORAGE_ROOT.lock().unwrap().clone()
+}
+
+#[cfg(not(test))]
+pub(crate) fn test_storage_root() -> Option<PathBuf> {
+    None
+}
+
+/// Keep the storage folder, local state and local config in `root` until
+/// the returned guard is dropped
+///
+/// Only one test may use it at a time, since the app's workflows read
+/// their folders from every thread.
+#[cfg(test)]
+pub(crate) fn use_test_storage_root(root: &Path) -> TestStorageRoot {
+    *TEST_STORAGE_ROOT.lock().unwrap() = Some(root.to_path_buf());
+    TestStorageRoot
+}
+
+/// Restores the user's folders when dropped (see `use_test_storage_root`)
+#[cfg(test)]
+pub(crate) struct TestStorageRoot;
+
+#[cfg(test)]
+impl Drop for TestStorageRoot {
+    fn drop(&mut self) {
+        *TEST_STORAGE_ROOT.lock().unwrap() = None;
+    }
+}
+
+/// Relative path (from the storage root) of a session's audio file
+///
+/// All session id → filename mapping goes through these helpers. Audio and
+/// transcript files are named by their file stem: the session id, or the
+/// name given by `storage.filenamePattern` (see `session_file_stem`).
+pub fn audio_relative_path(file_stem: &str) -> String {
+    format!("audio/{}.wav", file_stem)
+}
+
+/// Relative path (from the storage root) of a session's transcript file
+pub fn transcript_relative_path(file_stem: &str) -> String {
+    format!("text/{}.txt", file_stem)
+}
+
+/// Relative path (from the storage root) of a session's romanized transcript
+pub fn romanized_transcript_relative_path(session_id: &str) -> String {
+    format!("text/{}.romanized.txt", session_id)
+}
+
+/// Relative path (from the storage root) of a session's structured transcript
+pub fn structured_transcript_relative_path(session_id: &str) -> String {
+    format!("text/{}.json", session_id)
+}
+
+/// Relative path (from the storage root) of a session's Whisper.cpp run log
+pub fn transcription_log_relative_path(session_id: &str) -> String {
+    format!("text/{}.log", session_id)
+}
+
+/// Relative path (from the storage root) of the folder keeping earlier
+/// versions of a session's transcript
+pub fn transcript_versions_relative_dir(session_id: &str) -> String {
+    format!("versions/{}", session_id)
+}
+
+/// Relative path (from the storage root) of a file exported for sharing,
+/// such as a waveform image or clip
+pub fn export_relative_path(file_name: &str) -> String {
+    format!("exports/{}", file_name)
+}